@@ -0,0 +1,9 @@
+-- Migration: Store payment amount as integer minor units with currency
+-- Created: 2024-03-12
+
+-- Сумма платежа переводится из DOUBLE PRECISION (рубли) в BIGINT (копейки),
+-- чтобы исключить расхождения в копейках при сверке с банком
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS currency VARCHAR(3) NOT NULL DEFAULT 'RUB' CHECK (currency IN ('RUB'));
+
+ALTER TABLE payments ALTER COLUMN amount TYPE BIGINT USING round(amount * 100)::BIGINT;
+ALTER TABLE payments RENAME COLUMN amount TO amount_minor;
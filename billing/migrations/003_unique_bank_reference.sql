@@ -0,0 +1,8 @@
+-- Migration: Make bank reference unique across payments
+-- Created: 2024-03-19
+
+-- Один идентификатор операции банка соответствует ровно одному платежу;
+-- повторные webhook-уведомления с тем же bank_reference обрабатываются идемпотентно
+CREATE UNIQUE INDEX IF NOT EXISTS idx_payments_bank_reference
+    ON payments(bank_reference)
+    WHERE bank_reference IS NOT NULL;
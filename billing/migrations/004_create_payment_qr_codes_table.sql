@@ -0,0 +1,12 @@
+-- Migration: Store locally rendered payment QR codes
+-- Created: 2024-03-26
+
+-- QR-коды рендерятся в billing и отдаются через GET /payments/:id/qr.png,
+-- поэтому id платежа и сумма больше не уходят во внешний сервис генерации
+CREATE TABLE IF NOT EXISTS payment_qr_codes (
+    payment_id UUID PRIMARY KEY REFERENCES payments(id) ON DELETE CASCADE,
+    payload TEXT NOT NULL,
+    png BYTEA NOT NULL,
+    svg TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,14 @@
+-- Migration: Add admin overrides for debt-based trip blocking
+-- Created: 2024-04-09
+
+-- Пользователь с разрешением может начинать поездки, даже если его долг выше порога
+CREATE TABLE IF NOT EXISTS debt_overrides (
+    user_id UUID PRIMARY KEY,
+    reason TEXT NOT NULL,
+    granted_by UUID NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMP WITH TIME ZONE
+);
+
+-- Задолженность считается по неоплаченным платежам пользователя
+CREATE INDEX IF NOT EXISTS idx_payments_user_id_status ON payments(user_id, status);
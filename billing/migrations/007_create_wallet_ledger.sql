@@ -0,0 +1,98 @@
+-- Migration: Add prepaid wallets with an append-only double-entry ledger
+-- Created: 2024-04-16
+
+-- Платеж теперь либо оплачивает поездку, либо пополняет кошелек;
+-- поездку можно оплатить через СБП или списанием с кошелька
+ALTER TABLE payments ALTER COLUMN trip_id DROP NOT NULL;
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS kind VARCHAR(20) NOT NULL DEFAULT 'trip'
+    CHECK (kind IN ('trip', 'wallet_top_up'));
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS method VARCHAR(20) NOT NULL DEFAULT 'sbp'
+    CHECK (method IN ('sbp', 'wallet'));
+ALTER TABLE payments ADD CONSTRAINT payments_kind_trip_check
+    CHECK ((kind = 'trip') = (trip_id IS NOT NULL));
+ALTER TABLE payments ADD CONSTRAINT payments_top_up_method_check
+    CHECK (kind <> 'wallet_top_up' OR method = 'sbp');
+
+-- Движения по кошелькам
+CREATE TABLE IF NOT EXISTS wallet_transactions (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('top_up', 'trip_payment', 'refund')),
+    amount_minor BIGINT NOT NULL CHECK (amount_minor > 0),
+    currency VARCHAR(3) NOT NULL DEFAULT 'RUB' CHECK (currency IN ('RUB')),
+    payment_id UUID NOT NULL REFERENCES payments(id),
+    refund_id UUID REFERENCES refunds(id),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CHECK ((kind = 'refund') = (refund_id IS NOT NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_wallet_transactions_user_id ON wallet_transactions(user_id, created_at);
+-- Пополнение и оплата проводятся по платежу не больше одного раза
+CREATE UNIQUE INDEX IF NOT EXISTS idx_wallet_transactions_payment_once
+    ON wallet_transactions(payment_id) WHERE kind IN ('top_up', 'trip_payment');
+CREATE UNIQUE INDEX IF NOT EXISTS idx_wallet_transactions_refund_id
+    ON wallet_transactions(refund_id) WHERE refund_id IS NOT NULL;
+
+-- Проводки главной книги: счета wallet:{user_id}, bank_clearing, trip_revenue
+CREATE TABLE IF NOT EXISTS ledger_entries (
+    id BIGSERIAL PRIMARY KEY,
+    transaction_id UUID NOT NULL REFERENCES wallet_transactions(id),
+    account VARCHAR(64) NOT NULL,
+    amount_minor BIGINT NOT NULL CHECK (amount_minor <> 0),
+    currency VARCHAR(3) NOT NULL DEFAULT 'RUB' CHECK (currency IN ('RUB')),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_entries_account ON ledger_entries(account);
+CREATE INDEX IF NOT EXISTS idx_ledger_entries_transaction_id ON ledger_entries(transaction_id);
+
+-- Книга только дополняется: исправления делаются новыми операциями
+CREATE OR REPLACE FUNCTION reject_ledger_modification() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION '% is append-only', TG_TABLE_NAME;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER wallet_transactions_append_only
+    BEFORE UPDATE OR DELETE ON wallet_transactions
+    FOR EACH ROW EXECUTE FUNCTION reject_ledger_modification();
+
+CREATE TRIGGER ledger_entries_append_only
+    BEFORE UPDATE OR DELETE ON ledger_entries
+    FOR EACH ROW EXECUTE FUNCTION reject_ledger_modification();
+
+-- К концу транзакции у каждой операции должно быть не меньше двух проводок с нулевой суммой
+CREATE OR REPLACE FUNCTION check_ledger_transaction_balanced() RETURNS trigger AS $$
+DECLARE
+    checked_id UUID;
+    entries_count BIGINT;
+    entries_sum BIGINT;
+BEGIN
+    IF TG_TABLE_NAME = 'wallet_transactions' THEN
+        checked_id := NEW.id;
+    ELSE
+        checked_id := NEW.transaction_id;
+    END IF;
+
+    SELECT COUNT(*), COALESCE(SUM(amount_minor), 0)
+    INTO entries_count, entries_sum
+    FROM ledger_entries
+    WHERE transaction_id = checked_id;
+
+    IF entries_count < 2 OR entries_sum <> 0 THEN
+        RAISE EXCEPTION 'ledger transaction % is unbalanced: % entries, sum %',
+            checked_id, entries_count, entries_sum;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE CONSTRAINT TRIGGER wallet_transactions_balanced
+    AFTER INSERT ON wallet_transactions
+    DEFERRABLE INITIALLY DEFERRED
+    FOR EACH ROW EXECUTE FUNCTION check_ledger_transaction_balanced();
+
+CREATE CONSTRAINT TRIGGER ledger_entries_balanced
+    AFTER INSERT ON ledger_entries
+    DEFERRABLE INITIALLY DEFERRED
+    FOR EACH ROW EXECUTE FUNCTION check_ledger_transaction_balanced();
@@ -0,0 +1,55 @@
+-- Migration: Add promo codes and record applied discounts on trip payments
+-- Created: 2024-04-23
+
+CREATE TABLE IF NOT EXISTS promo_codes (
+    id UUID PRIMARY KEY,
+    code VARCHAR(64) NOT NULL,
+    campaign VARCHAR(128) NOT NULL,
+    discount_type VARCHAR(20) NOT NULL CHECK (discount_type IN ('percentage', 'fixed')),
+    discount_percent SMALLINT CHECK (discount_percent BETWEEN 1 AND 100),
+    discount_minor BIGINT CHECK (discount_minor > 0),
+    currency VARCHAR(3) NOT NULL DEFAULT 'RUB' CHECK (currency IN ('RUB')),
+    per_user_limit INTEGER NOT NULL CHECK (per_user_limit > 0),
+    -- Пустой массив - код действует на всех тарифах
+    tariff_ids UUID[] NOT NULL DEFAULT '{}',
+    expires_at TIMESTAMP WITH TIME ZONE,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CHECK ((discount_type = 'percentage') = (discount_percent IS NOT NULL)),
+    CHECK ((discount_type = 'fixed') = (discount_minor IS NOT NULL))
+);
+
+-- Коды хранятся в верхнем регистре
+CREATE UNIQUE INDEX IF NOT EXISTS idx_promo_codes_code ON promo_codes(code);
+CREATE INDEX IF NOT EXISTS idx_promo_codes_campaign ON promo_codes(campaign);
+
+-- Код, который пользователь указал до или во время поездки
+CREATE TABLE IF NOT EXISTS user_promo_codes (
+    user_id UUID PRIMARY KEY,
+    promo_code_id UUID NOT NULL REFERENCES promo_codes(id),
+    attached_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Скидка фиксируется на платеже: amount_minor - уже сумма к оплате,
+-- промокод копируется, чтобы история не зависела от последующих изменений кода
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS promo_code_id UUID REFERENCES promo_codes(id);
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS promo_code VARCHAR(64);
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS discount_minor BIGINT CHECK (discount_minor > 0);
+ALTER TABLE payments ADD CONSTRAINT payments_discount_check
+    CHECK ((promo_code_id IS NULL) = (discount_minor IS NULL) AND (promo_code_id IS NULL) = (promo_code IS NULL));
+ALTER TABLE payments ADD CONSTRAINT payments_discount_trip_check
+    CHECK (promo_code_id IS NULL OR kind = 'trip');
+
+-- Поездка, полностью покрытая скидкой, оплачена сразу и имеет нулевую сумму
+ALTER TABLE payments DROP CONSTRAINT IF EXISTS payments_method_check;
+ALTER TABLE payments ADD CONSTRAINT payments_method_check
+    CHECK (method IN ('sbp', 'wallet', 'promo'));
+ALTER TABLE payments DROP CONSTRAINT IF EXISTS payments_amount_check;
+ALTER TABLE payments ADD CONSTRAINT payments_amount_check
+    CHECK (amount_minor > 0 OR (amount_minor = 0 AND method = 'promo'));
+ALTER TABLE payments ADD CONSTRAINT payments_promo_method_check
+    CHECK (method <> 'promo' OR promo_code_id IS NOT NULL);
+
+-- Лимит использований считается по платежам пользователя с кодом
+CREATE INDEX IF NOT EXISTS idx_payments_promo_code_id ON payments(promo_code_id, user_id)
+    WHERE promo_code_id IS NOT NULL;
@@ -0,0 +1,7 @@
+-- Migration: Allow only one payment per trip
+-- Created: 2024-05-14
+
+-- Проверка в коде не защищает от параллельных запросов на оплату одной поездки
+CREATE UNIQUE INDEX IF NOT EXISTS idx_payments_trip_id_unique
+    ON payments(trip_id)
+    WHERE kind = 'trip';
@@ -0,0 +1,44 @@
+use uuid::Uuid;
+use chrono::Utc;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::PromoCodeRepository,
+    models::{AttachedPromoCode, PromoCode},
+};
+
+pub struct AttachPromoCodeUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    repository: P,
+}
+
+impl<P> AttachPromoCodeUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    pub fn new(repository: P) -> Self {
+        Self { repository }
+    }
+
+    // Тариф здесь не проверяется: он известен только при расчете стоимости поездки
+    pub async fn execute(&self, user_id: Uuid, code: &str) -> Result<AttachedPromoCode, PaymentError> {
+        let promo_code = self.repository.find_by_code(&PromoCode::normalize_code(code)).await?
+            .ok_or(PaymentError::PromoCodeNotFound)?;
+
+        let now = Utc::now();
+        let redemptions = self.repository.count_redemptions(promo_code.id, user_id).await?;
+        if let Some(reason) = promo_code.unavailable_reason(redemptions, now) {
+            return Err(PaymentError::PromoCodeNotApplicable(reason.to_string()));
+        }
+
+        self.repository.attach(user_id, promo_code.id, now).await?;
+
+        Ok(AttachedPromoCode {
+            user_id,
+            promo_code,
+            redemptions,
+            attached_at: now,
+        })
+    }
+}
@@ -2,58 +2,596 @@ use uuid::Uuid;
 use chrono::Utc;
 use crate::domain::{
     errors::PaymentError,
-    interfaces::{PaymentRepository, QRCodeGenerator},
-    models::{Payment, PaymentStatus, CreatePaymentRequest},
+    interfaces::{PaymentRepository, PromoCodeRepository, QRCodeGenerator},
+    models::{AppliedDiscount, Money, Payment, PaymentKind, PaymentMethod, PaymentStatus, CreatePaymentRequest, WalletTransaction},
 };
 
-pub struct CreatePaymentUseCase<R, Q> 
+pub struct CreatePaymentUseCase<R, Q, P>
 where
     R: PaymentRepository,
     Q: QRCodeGenerator,
+    P: PromoCodeRepository,
 {
     repository: R,
     qr_generator: Q,
+    promo_code_repository: P,
 }
 
-impl<R, Q> CreatePaymentUseCase<R, Q>
+struct PromoCodeDiscount {
+    discount: AppliedDiscount,
+    exhausted: bool,
+}
+
+impl<R, Q, P> CreatePaymentUseCase<R, Q, P>
 where
     R: PaymentRepository,
     Q: QRCodeGenerator,
+    P: PromoCodeRepository,
 {
-    pub fn new(repository: R, qr_generator: Q) -> Self {
-        Self { repository, qr_generator }
+    pub fn new(repository: R, qr_generator: Q, promo_code_repository: P) -> Self {
+        Self { repository, qr_generator, promo_code_repository }
     }
 
     pub async fn execute(&self, request: CreatePaymentRequest) -> Result<Uuid, PaymentError> {
         // Валидация суммы
-        if request.amount <= 0.0 {
+        if !request.amount.is_positive() {
             return Err(PaymentError::InvalidAmount { amount: request.amount });
         }
 
-        // Проверяем, нет ли уже платежа для этой поездки
-        if let Some(_) = self.repository.find_by_trip_id(request.trip_id).await? {
+        // Проверяем, нет ли уже платежа для этой поездки; параллельный запрос отсекает уникальный индекс
+        if self.repository.find_by_trip_id(request.trip_id).await?.is_some() {
             return Err(PaymentError::PaymentAlreadyProcessed);
         }
 
-        // Создаем платеж
+        // Сумма поездки уменьшается на скидку по промокоду, который указал пользователь
+        let promo = self.apply_promo_code(&request).await?;
+        let discount = promo.as_ref().map(|p| p.discount.clone());
+        let amount = match &discount {
+            Some(discount) => request.amount
+                .checked_sub(discount.amount)
+                .ok_or(PaymentError::InvalidAmount { amount: request.amount })?,
+            None => request.amount,
+        };
+
+        let payment_id = if amount.is_zero() {
+            // Скидка покрыла всю сумму - платить нечего
+            self.create_covered_by_promo(&request, amount, discount).await?
+        } else if let Some(payment_id) = self.try_pay_from_wallet(&request, amount, discount.clone()).await? {
+            // Если на кошельке хватает средств, поездка оплачивается с него сразу
+            payment_id
+        } else {
+            // Иначе выставляем счет с QR-кодом СБП
+            self.create_with_qr_code(&request, amount, discount).await?
+        };
+
+        // Исчерпанный код больше не применяется к следующим поездкам
+        if let Some(promo) = promo.filter(|p| p.exhausted) {
+            self.promo_code_repository
+                .detach_code(request.user_id, promo.discount.promo_code_id)
+                .await?;
+        }
+
+        Ok(payment_id)
+    }
+
+    async fn apply_promo_code(&self, request: &CreatePaymentRequest) -> Result<Option<PromoCodeDiscount>, PaymentError> {
+        // Скидка действует только на оплату поездки по тарифу
+        let Some(tariff_id) = request.tariff_id else {
+            return Ok(None);
+        };
+        let Some(attached) = self.promo_code_repository.find_attached(request.user_id).await? else {
+            return Ok(None);
+        };
+
+        let promo_code = attached.promo_code;
+        if promo_code.unavailable_reason(attached.redemptions, Utc::now()).is_some() {
+            // Код истек, выключен или исчерпан, пока пользователь был в поездке
+            self.promo_code_repository.detach_code(request.user_id, promo_code.id).await?;
+            return Ok(None);
+        }
+        if !promo_code.applies_to_tariff(tariff_id) {
+            // Код остается привязанным и применится к поездке по подходящему тарифу
+            return Ok(None);
+        }
+
+        let amount = promo_code.discount.amount_for(request.amount);
+        if !amount.is_positive() {
+            return Ok(None);
+        }
+
+        Ok(Some(PromoCodeDiscount {
+            discount: AppliedDiscount {
+                promo_code_id: promo_code.id,
+                code: promo_code.code,
+                amount,
+            },
+            exhausted: attached.redemptions + 1 >= promo_code.per_user_limit,
+        }))
+    }
+
+    async fn create_covered_by_promo(
+        &self,
+        request: &CreatePaymentRequest,
+        amount: Money,
+        discount: Option<AppliedDiscount>,
+    ) -> Result<Uuid, PaymentError> {
+        let now = Utc::now();
+        let payment = Payment {
+            id: Uuid::new_v4(),
+            trip_id: Some(request.trip_id),
+            user_id: request.user_id,
+            kind: PaymentKind::Trip,
+            method: PaymentMethod::Promo,
+            amount,
+            discount,
+            status: PaymentStatus::Paid,
+            bank_reference: None,
+            qr_code_url: None,
+            created_at: now,
+            paid_at: Some(now),
+        };
+
+        self.repository.create(&payment).await?;
+        Ok(payment.id)
+    }
+
+    async fn create_with_qr_code(
+        &self,
+        request: &CreatePaymentRequest,
+        amount: Money,
+        discount: Option<AppliedDiscount>,
+    ) -> Result<Uuid, PaymentError> {
         let payment_id = Uuid::new_v4();
-        let qr_code_url = self.qr_generator.generate_qr_code(payment_id, request.amount).await
+        let qr_code = self.qr_generator.generate_qr_code(payment_id, amount).await
             .map_err(|e| PaymentError::Internal(anyhow::anyhow!("Failed to generate QR code: {}", e)))?;
 
         let payment = Payment {
             id: payment_id,
-            trip_id: request.trip_id,
+            trip_id: Some(request.trip_id),
             user_id: request.user_id,
-            amount: request.amount,
+            kind: PaymentKind::Trip,
+            method: PaymentMethod::Sbp,
+            amount,
+            discount,
             status: PaymentStatus::Pending,
             bank_reference: None,
-            qr_code_url: Some(qr_code_url),
+            // Изображение отдает сам billing (через dispatcher для клиентов)
+            qr_code_url: Some(format!("/payments/{}/qr.png", payment_id)),
             created_at: Utc::now(),
             paid_at: None,
         };
 
-        self.repository.create(&payment).await?;
+        self.repository.create_with_qr_code(&payment, &qr_code).await?;
         Ok(payment_id)
     }
+
+    async fn try_pay_from_wallet(
+        &self,
+        request: &CreatePaymentRequest,
+        amount: Money,
+        discount: Option<AppliedDiscount>,
+    ) -> Result<Option<Uuid>, PaymentError> {
+        let Some(balance) = self.repository.wallet_balance(request.user_id).await? else {
+            return Ok(None);
+        };
+        if balance.currency != amount.currency || balance.minor_units < amount.minor_units {
+            return Ok(None);
+        }
+
+        let now = Utc::now();
+        let payment = Payment {
+            id: Uuid::new_v4(),
+            trip_id: Some(request.trip_id),
+            user_id: request.user_id,
+            kind: PaymentKind::Trip,
+            method: PaymentMethod::Wallet,
+            amount,
+            discount,
+            status: PaymentStatus::Paid,
+            bank_reference: None,
+            qr_code_url: None,
+            created_at: now,
+            paid_at: Some(now),
+        };
+
+        // Остаток проверяется повторно под блокировкой кошелька: параллельное списание
+        // могло его уменьшить, тогда выставляем обычный счет
+        if self.repository.create_paid_from_wallet(&payment, &WalletTransaction::trip_payment(&payment)).await? {
+            Ok(Some(payment.id))
+        } else {
+            Ok(None)
+        }
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::DateTime;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use crate::domain::models::{
+        AttachedPromoCode, CampaignReport, Currency, Discount, PaymentQrCode, PromoCode, Refund, WalletTransactionKind,
+    };
+
+    #[derive(Clone, Default)]
+    struct MockPaymentRepository {
+        payments: Arc<Mutex<HashMap<Uuid, Payment>>>,
+        wallet: Arc<Mutex<Option<Money>>>,
+        wallet_debits: Arc<Mutex<Vec<WalletTransaction>>>,
+        qr_codes: Arc<Mutex<Vec<Uuid>>>,
+        // Списание, которое параллельный запрос успеет сделать перед нашим
+        concurrent_debit: Arc<Mutex<Option<Money>>>,
+    }
+
+    impl MockPaymentRepository {
+        fn with_wallet(balance: i64) -> Self {
+            let repository = Self::default();
+            *repository.wallet.lock().unwrap() = Some(rub(balance));
+            repository
+        }
+
+        fn get(&self, id: Uuid) -> Payment {
+            self.payments.lock().unwrap()[&id].clone()
+        }
+
+        fn balance(&self) -> Option<Money> {
+            *self.wallet.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl PaymentRepository for MockPaymentRepository {
+        async fn create(&self, payment: &Payment) -> Result<(), PaymentError> {
+            self.payments.lock().unwrap().insert(payment.id, payment.clone());
+            Ok(())
+        }
+
+        async fn create_with_qr_code(&self, payment: &Payment, qr_code: &PaymentQrCode) -> Result<(), PaymentError> {
+            assert_eq!(qr_code.payment_id, payment.id);
+            self.qr_codes.lock().unwrap().push(payment.id);
+            self.payments.lock().unwrap().insert(payment.id, payment.clone());
+            Ok(())
+        }
+
+        async fn find_qr_code(&self, _payment_id: Uuid) -> Result<Option<PaymentQrCode>, PaymentError> { unimplemented!() }
+        async fn find_by_id(&self, _id: Uuid) -> Result<Option<Payment>, PaymentError> { unimplemented!() }
+
+        async fn find_by_trip_id(&self, trip_id: Uuid) -> Result<Option<Payment>, PaymentError> {
+            Ok(self.payments.lock().unwrap().values().find(|p| p.trip_id == Some(trip_id)).cloned())
+        }
+
+        async fn find_by_user_id(&self, _user_id: Uuid) -> Result<Vec<Payment>, PaymentError> { unimplemented!() }
+        async fn find_by_bank_reference(&self, _bank_reference: &str) -> Result<Option<Payment>, PaymentError> { unimplemented!() }
+        async fn update(&self, _id: Uuid, _payment: &Payment) -> Result<(), PaymentError> { unimplemented!() }
+
+        async fn complete_pending(
+            &self,
+            _id: Uuid,
+            _status: PaymentStatus,
+            _bank_reference: &str,
+            _paid_at: Option<DateTime<Utc>>,
+            _wallet_credit: Option<&WalletTransaction>,
+        ) -> Result<bool, PaymentError> {
+            unimplemented!()
+        }
+
+        async fn find_refunds_by_payment_id(&self, _payment_id: Uuid) -> Result<Vec<Refund>, PaymentError> { unimplemented!() }
+        async fn create_refund(&self, _refund: &Refund, _wallet_credit: Option<&WalletTransaction>) -> Result<bool, PaymentError> { unimplemented!() }
+
+        async fn create_paid_from_wallet(&self, payment: &Payment, debit: &WalletTransaction) -> Result<bool, PaymentError> {
+            let mut wallet = self.wallet.lock().unwrap();
+            let mut balance = wallet.expect("wallet must exist");
+            if let Some(concurrent) = self.concurrent_debit.lock().unwrap().take() {
+                balance = balance.checked_sub(concurrent).unwrap();
+            }
+            *wallet = Some(balance);
+
+            let Some(remaining) = balance.checked_add(debit.wallet_delta()).filter(|m| !m.is_negative()) else {
+                return Ok(false);
+            };
+            *wallet = Some(remaining);
+            self.wallet_debits.lock().unwrap().push(debit.clone());
+            self.payments.lock().unwrap().insert(payment.id, payment.clone());
+            Ok(true)
+        }
+
+        async fn wallet_balance(&self, _user_id: Uuid) -> Result<Option<Money>, PaymentError> {
+            Ok(self.balance())
+        }
+
+        async fn find_wallet_transactions_by_user_id(&self, _user_id: Uuid) -> Result<Vec<WalletTransaction>, PaymentError> { unimplemented!() }
+    }
+
+    struct MockQRCodeGenerator;
+
+    #[async_trait]
+    impl QRCodeGenerator for MockQRCodeGenerator {
+        async fn generate_qr_code(&self, payment_id: Uuid, amount: Money) -> Result<PaymentQrCode, anyhow::Error> {
+            Ok(PaymentQrCode {
+                payment_id,
+                payload: format!("https://qr.nspk.ru/test?sum={}", amount.minor_units),
+                png: vec![],
+                svg: String::new(),
+                created_at: Utc::now(),
+            })
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockPromoCodeRepository {
+        attached: Arc<Mutex<Option<AttachedPromoCode>>>,
+    }
+
+    #[async_trait]
+    impl PromoCodeRepository for MockPromoCodeRepository {
+        async fn create(&self, _promo_code: &PromoCode) -> Result<(), PaymentError> { unimplemented!() }
+        async fn find_by_id(&self, _id: Uuid) -> Result<Option<PromoCode>, PaymentError> { unimplemented!() }
+        async fn find_by_code(&self, _code: &str) -> Result<Option<PromoCode>, PaymentError> { unimplemented!() }
+        async fn find_all(&self) -> Result<Vec<PromoCode>, PaymentError> { unimplemented!() }
+        async fn deactivate(&self, _id: Uuid) -> Result<bool, PaymentError> { unimplemented!() }
+        async fn count_redemptions(&self, _promo_code_id: Uuid, _user_id: Uuid) -> Result<u32, PaymentError> { unimplemented!() }
+        async fn attach(&self, _user_id: Uuid, _promo_code_id: Uuid, _attached_at: DateTime<Utc>) -> Result<(), PaymentError> { unimplemented!() }
+
+        async fn find_attached(&self, _user_id: Uuid) -> Result<Option<AttachedPromoCode>, PaymentError> {
+            Ok(self.attached.lock().unwrap().clone())
+        }
+
+        async fn detach(&self, _user_id: Uuid) -> Result<bool, PaymentError> { unimplemented!() }
+
+        async fn detach_code(&self, _user_id: Uuid, promo_code_id: Uuid) -> Result<bool, PaymentError> {
+            let mut attached = self.attached.lock().unwrap();
+            if attached.as_ref().is_some_and(|a| a.promo_code.id == promo_code_id) {
+                *attached = None;
+                return Ok(true);
+            }
+            Ok(false)
+        }
+
+        async fn campaign_report(&self, _from: Option<DateTime<Utc>>, _to: Option<DateTime<Utc>>) -> Result<Vec<CampaignReport>, PaymentError> { unimplemented!() }
+    }
+
+    type TestUseCase = CreatePaymentUseCase<MockPaymentRepository, MockQRCodeGenerator, MockPromoCodeRepository>;
+
+    fn rub(minor_units: i64) -> Money {
+        Money::new(minor_units, Currency::Rub)
+    }
+
+    fn use_case(repository: &MockPaymentRepository, promo_codes: &MockPromoCodeRepository) -> TestUseCase {
+        CreatePaymentUseCase::new(repository.clone(), MockQRCodeGenerator, promo_codes.clone())
+    }
+
+    fn attached(request: &CreatePaymentRequest, discount: Discount, per_user_limit: u32, redemptions: u32) -> MockPromoCodeRepository {
+        let promo_codes = MockPromoCodeRepository::default();
+        *promo_codes.attached.lock().unwrap() = Some(AttachedPromoCode {
+            user_id: request.user_id,
+            promo_code: PromoCode {
+                id: Uuid::new_v4(),
+                code: "SPRING".to_string(),
+                campaign: "Spring".to_string(),
+                discount,
+                per_user_limit,
+                tariff_ids: vec![],
+                expires_at: None,
+                active: true,
+                created_at: Utc::now(),
+            },
+            redemptions,
+            attached_at: Utc::now(),
+        });
+        promo_codes
+    }
+
+    fn attached_code(promo_codes: &MockPromoCodeRepository) -> Option<PromoCode> {
+        promo_codes.attached.lock().unwrap().as_ref().map(|a| a.promo_code.clone())
+    }
+
+    fn request(amount: i64) -> CreatePaymentRequest {
+        CreatePaymentRequest {
+            trip_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            amount: rub(amount),
+            tariff_id: Some(Uuid::new_v4()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trip_is_paid_from_wallet_with_sufficient_balance() {
+        let repository = MockPaymentRepository::with_wallet(20_000);
+
+        let payment_id = use_case(&repository, &MockPromoCodeRepository::default())
+            .execute(request(15_050))
+            .await
+            .unwrap();
+
+        let payment = repository.get(payment_id);
+        assert_eq!(payment.method, PaymentMethod::Wallet);
+        assert_eq!(payment.status, PaymentStatus::Paid);
+        assert_eq!(payment.amount, rub(15_050));
+        assert_eq!(repository.balance(), Some(rub(4_950)));
+        let debits = repository.wallet_debits.lock().unwrap();
+        assert_eq!(debits.len(), 1);
+        assert_eq!(debits[0].kind, WalletTransactionKind::TripPayment);
+        assert_eq!(debits[0].payment_id, payment_id);
+        assert!(repository.qr_codes.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_exact_wallet_balance_pays_for_trip() {
+        let repository = MockPaymentRepository::with_wallet(15_050);
+
+        let payment_id = use_case(&repository, &MockPromoCodeRepository::default())
+            .execute(request(15_050))
+            .await
+            .unwrap();
+
+        assert_eq!(repository.get(payment_id).method, PaymentMethod::Wallet);
+        assert_eq!(repository.balance(), Some(rub(0)));
+    }
+
+    #[tokio::test]
+    async fn test_insufficient_wallet_balance_falls_back_to_qr_code() {
+        let repository = MockPaymentRepository::with_wallet(15_049);
+
+        let payment_id = use_case(&repository, &MockPromoCodeRepository::default())
+            .execute(request(15_050))
+            .await
+            .unwrap();
+
+        let payment = repository.get(payment_id);
+        assert_eq!(payment.method, PaymentMethod::Sbp);
+        assert_eq!(payment.status, PaymentStatus::Pending);
+        assert_eq!(payment.amount, rub(15_050));
+        assert_eq!(*repository.qr_codes.lock().unwrap(), vec![payment_id]);
+        assert_eq!(repository.balance(), Some(rub(15_049)));
+        assert!(repository.wallet_debits.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_user_without_wallet_gets_qr_code() {
+        let repository = MockPaymentRepository::default();
+
+        let payment_id = use_case(&repository, &MockPromoCodeRepository::default())
+            .execute(request(15_050))
+            .await
+            .unwrap();
+
+        assert_eq!(repository.get(payment_id).method, PaymentMethod::Sbp);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_debit_is_rechecked_on_write() {
+        let repository = MockPaymentRepository::with_wallet(20_000);
+        *repository.concurrent_debit.lock().unwrap() = Some(rub(10_000));
+
+        let payment_id = use_case(&repository, &MockPromoCodeRepository::default())
+            .execute(request(15_050))
+            .await
+            .unwrap();
+
+        assert_eq!(repository.get(payment_id).method, PaymentMethod::Sbp);
+        assert_eq!(repository.balance(), Some(rub(10_000)));
+        assert!(repository.wallet_debits.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_percentage_discount_reduces_qr_amount() {
+        let repository = MockPaymentRepository::default();
+        let request = request(15_050);
+        let promo_codes = attached(&request, Discount::Percentage { percent: 15 }, 3, 0);
+        let promo_code = attached_code(&promo_codes).unwrap();
+
+        let payment_id = use_case(&repository, &promo_codes).execute(request).await.unwrap();
+
+        let payment = repository.get(payment_id);
+        // 15% от 150.50 = 22.575 -> 22.57
+        let discount = payment.discount.unwrap();
+        assert_eq!(discount.amount, rub(2_257));
+        assert_eq!(discount.promo_code_id, promo_code.id);
+        assert_eq!(payment.amount, rub(12_793));
+        assert_eq!(payment.method, PaymentMethod::Sbp);
+        assert_eq!(*repository.qr_codes.lock().unwrap(), vec![payment_id]);
+        // Лимит не исчерпан - код остается привязанным
+        assert!(attached_code(&promo_codes).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_fixed_discount_is_capped_and_covers_trip() {
+        let repository = MockPaymentRepository::with_wallet(50_000);
+        let request = request(15_050);
+        let promo_codes = attached(&request, Discount::Fixed { amount: rub(50_000) }, 3, 0);
+
+        let payment_id = use_case(&repository, &promo_codes).execute(request).await.unwrap();
+
+        let payment = repository.get(payment_id);
+        assert_eq!(payment.discount.unwrap().amount, rub(15_050));
+        assert_eq!(payment.amount, rub(0));
+        assert_eq!(payment.method, PaymentMethod::Promo);
+        assert_eq!(payment.status, PaymentStatus::Paid);
+        assert!(repository.qr_codes.lock().unwrap().is_empty());
+        assert_eq!(repository.balance(), Some(rub(50_000)));
+    }
+
+    #[tokio::test]
+    async fn test_discounted_amount_is_paid_from_wallet() {
+        // Полной суммы на кошельке не хватает, суммы со скидкой - хватает
+        let repository = MockPaymentRepository::with_wallet(10_000);
+        let request = request(15_050);
+        let promo_codes = attached(&request, Discount::Fixed { amount: rub(5_050) }, 3, 0);
+
+        let payment_id = use_case(&repository, &promo_codes).execute(request).await.unwrap();
+
+        let payment = repository.get(payment_id);
+        assert_eq!(payment.method, PaymentMethod::Wallet);
+        assert_eq!(payment.amount, rub(10_000));
+        assert_eq!(payment.discount.unwrap().amount, rub(5_050));
+        assert_eq!(repository.balance(), Some(rub(0)));
+        assert_eq!(repository.wallet_debits.lock().unwrap()[0].amount, rub(10_000));
+    }
+
+    #[tokio::test]
+    async fn test_last_allowed_use_detaches_code() {
+        let repository = MockPaymentRepository::default();
+        let request = request(15_050);
+        let promo_codes = attached(&request, Discount::Percentage { percent: 10 }, 2, 1);
+
+        let payment_id = use_case(&repository, &promo_codes).execute(request).await.unwrap();
+
+        assert_eq!(repository.get(payment_id).discount.unwrap().amount, rub(1_505));
+        assert!(attached_code(&promo_codes).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expired_code_is_detached_without_discount() {
+        let repository = MockPaymentRepository::default();
+        let request = request(15_050);
+        let promo_codes = attached(&request, Discount::Percentage { percent: 10 }, 3, 0);
+        promo_codes.attached.lock().unwrap().as_mut().unwrap().promo_code.expires_at = Some(Utc::now());
+
+        let payment_id = use_case(&repository, &promo_codes).execute(request).await.unwrap();
+
+        let payment = repository.get(payment_id);
+        assert!(payment.discount.is_none());
+        assert_eq!(payment.amount, rub(15_050));
+        assert!(attached_code(&promo_codes).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_code_is_detached_without_discount() {
+        let repository = MockPaymentRepository::default();
+        let request = request(15_050);
+        let promo_codes = attached(&request, Discount::Percentage { percent: 10 }, 2, 2);
+
+        let payment_id = use_case(&repository, &promo_codes).execute(request).await.unwrap();
+
+        assert!(repository.get(payment_id).discount.is_none());
+        assert!(attached_code(&promo_codes).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_code_for_other_tariff_stays_attached() {
+        let repository = MockPaymentRepository::default();
+        let request = request(15_050);
+        let promo_codes = attached(&request, Discount::Percentage { percent: 10 }, 3, 0);
+        promo_codes.attached.lock().unwrap().as_mut().unwrap().promo_code.tariff_ids = vec![Uuid::new_v4()];
+
+        let payment_id = use_case(&repository, &promo_codes).execute(request).await.unwrap();
+
+        assert!(repository.get(payment_id).discount.is_none());
+        assert!(attached_code(&promo_codes).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_payment_without_tariff_gets_no_discount() {
+        let repository = MockPaymentRepository::default();
+        let mut request = request(15_050);
+        request.tariff_id = None;
+        let promo_codes = attached(&request, Discount::Percentage { percent: 10 }, 1, 0);
+
+        let payment_id = use_case(&repository, &promo_codes).execute(request).await.unwrap();
+
+        assert!(repository.get(payment_id).discount.is_none());
+        assert!(attached_code(&promo_codes).is_some());
+    }
+}
@@ -0,0 +1,80 @@
+use uuid::Uuid;
+use chrono::Utc;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::PromoCodeRepository,
+    models::{CreatePromoCodeRequest, Discount, PromoCode},
+};
+
+// Ограничения схемы
+const MAX_CODE_LENGTH: usize = 64;
+const MAX_CAMPAIGN_LENGTH: usize = 128;
+
+pub struct CreatePromoCodeUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    repository: P,
+}
+
+impl<P> CreatePromoCodeUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    pub fn new(repository: P) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, request: CreatePromoCodeRequest) -> Result<PromoCode, PaymentError> {
+        let code = PromoCode::normalize_code(&request.code);
+        if code.is_empty() || code.len() > MAX_CODE_LENGTH {
+            return Err(PaymentError::InvalidPromoCode(format!("code must be 1 to {} characters", MAX_CODE_LENGTH)));
+        }
+        if !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(PaymentError::InvalidPromoCode("code may contain only latin letters, digits, '-' and '_'".to_string()));
+        }
+
+        let campaign = request.campaign.trim();
+        if campaign.is_empty() || campaign.len() > MAX_CAMPAIGN_LENGTH {
+            return Err(PaymentError::InvalidPromoCode(format!("campaign must be 1 to {} characters", MAX_CAMPAIGN_LENGTH)));
+        }
+
+        match request.discount {
+            Discount::Percentage { percent } if !(1..=100).contains(&percent) => {
+                return Err(PaymentError::InvalidPromoCode("percent must be between 1 and 100".to_string()));
+            }
+            Discount::Fixed { amount } if !amount.is_positive() => {
+                return Err(PaymentError::InvalidPromoCode(format!("discount amount must be positive, got {}", amount)));
+            }
+            _ => {}
+        }
+
+        if request.per_user_limit == 0 || request.per_user_limit > i32::MAX as u32 {
+            return Err(PaymentError::InvalidPromoCode("per_user_limit must be positive".to_string()));
+        }
+
+        let now = Utc::now();
+        if request.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(PaymentError::InvalidPromoCode("expires_at must be in the future".to_string()));
+        }
+
+        let mut tariff_ids = request.tariff_ids;
+        tariff_ids.sort();
+        tariff_ids.dedup();
+
+        let promo_code = PromoCode {
+            id: Uuid::new_v4(),
+            code,
+            campaign: campaign.to_string(),
+            discount: request.discount,
+            per_user_limit: request.per_user_limit,
+            tariff_ids,
+            expires_at: request.expires_at,
+            active: true,
+            created_at: now,
+        };
+        self.repository.create(&promo_code).await?;
+
+        Ok(promo_code)
+    }
+}
@@ -0,0 +1,336 @@
+use uuid::Uuid;
+use chrono::Utc;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::PaymentRepository,
+    models::{CreateRefundRequest, Money, Payment, PaymentKind, PaymentMethod, Refund, WalletTransaction},
+};
+
+pub struct CreateRefundUseCase<R>
+where
+    R: PaymentRepository,
+{
+    repository: R,
+}
+
+impl<R> CreateRefundUseCase<R>
+where
+    R: PaymentRepository,
+{
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, payment_id: Uuid, request: CreateRefundRequest) -> Result<(Refund, Payment), PaymentError> {
+        if !request.amount.is_positive() {
+            return Err(PaymentError::InvalidAmount { amount: request.amount });
+        }
+        let reason = request.reason.trim();
+        if reason.is_empty() {
+            return Err(PaymentError::RefundReasonRequired);
+        }
+
+        let payment = self.repository.find_by_id(payment_id).await?
+            .ok_or(PaymentError::PaymentNotFound)?;
+        if !payment.status.is_refundable() {
+            return Err(PaymentError::PaymentNotRefundable {
+                status: payment.status.as_str().to_string(),
+            });
+        }
+        // Пополнение уже зачислено на кошелек: возврат через банк оставил бы деньги и там, и там
+        if payment.kind == PaymentKind::WalletTopUp {
+            return Err(PaymentError::TopUpNotRefundable);
+        }
+        if request.amount.currency != payment.amount.currency {
+            return Err(PaymentError::InvalidAmount { amount: request.amount });
+        }
+
+        let available = self.refundable_amount(&payment).await?;
+        if request.amount.minor_units > available.minor_units {
+            return Err(PaymentError::RefundExceedsPaidAmount {
+                requested: request.amount,
+                available,
+            });
+        }
+
+        let refund = Refund {
+            id: Uuid::new_v4(),
+            payment_id,
+            amount: request.amount,
+            reason: reason.to_string(),
+            created_at: Utc::now(),
+        };
+
+        // Поездка, оплаченная с кошелька, возвращается на кошелек, а не через банк
+        let wallet_credit = (payment.method == PaymentMethod::Wallet)
+            .then(|| WalletTransaction::refund(&payment, refund.id, refund.amount));
+
+        if !self.repository.create_refund(&refund, wallet_credit.as_ref()).await? {
+            // Параллельный возврат успел уменьшить доступную сумму
+            let payment = self.repository.find_by_id(payment_id).await?
+                .ok_or(PaymentError::PaymentNotFound)?;
+            if !payment.status.is_refundable() {
+                return Err(PaymentError::PaymentNotRefundable {
+                    status: payment.status.as_str().to_string(),
+                });
+            }
+            return Err(PaymentError::RefundExceedsPaidAmount {
+                requested: request.amount,
+                available: self.refundable_amount(&payment).await?,
+            });
+        }
+
+        let payment = self.repository.find_by_id(payment_id).await?
+            .ok_or(PaymentError::PaymentNotFound)?;
+        Ok((refund, payment))
+    }
+
+    async fn refundable_amount(&self, payment: &Payment) -> Result<Money, PaymentError> {
+        let refunded: i64 = self.repository.find_refunds_by_payment_id(payment.id).await?
+            .iter()
+            .map(|refund| refund.amount.minor_units)
+            .sum();
+        Ok(Money::new(payment.amount.minor_units - refunded, payment.amount.currency))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::DateTime;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use crate::domain::models::{Currency, PaymentQrCode, PaymentStatus};
+
+    #[derive(Clone, Default)]
+    struct MockPaymentRepository {
+        payments: Arc<Mutex<HashMap<Uuid, Payment>>>,
+        refunds: Arc<Mutex<Vec<Refund>>>,
+        wallet_credits: Arc<Mutex<Vec<WalletTransaction>>>,
+        // Возвраты, которые параллельный запрос успеет сделать перед нашим
+        concurrent_refunds: Arc<Mutex<Vec<Refund>>>,
+    }
+
+    impl MockPaymentRepository {
+        fn with_payment(payment: Payment) -> Self {
+            let repository = Self::default();
+            repository.payments.lock().unwrap().insert(payment.id, payment);
+            repository
+        }
+
+        fn refunded(&self, payment_id: Uuid) -> i64 {
+            self.refunds.lock().unwrap().iter()
+                .filter(|refund| refund.payment_id == payment_id)
+                .map(|refund| refund.amount.minor_units)
+                .sum()
+        }
+
+        fn apply_refund(&self, refund: &Refund) -> bool {
+            let mut payments = self.payments.lock().unwrap();
+            let payment = payments.get_mut(&refund.payment_id).unwrap();
+            let refunded = self.refunded(refund.payment_id) + refund.amount.minor_units;
+            if refunded > payment.amount.minor_units {
+                return false;
+            }
+            payment.status = if refunded == payment.amount.minor_units {
+                PaymentStatus::Refunded
+            } else {
+                PaymentStatus::PartiallyRefunded
+            };
+            self.refunds.lock().unwrap().push(refund.clone());
+            true
+        }
+    }
+
+    #[async_trait]
+    impl PaymentRepository for MockPaymentRepository {
+        async fn create(&self, _payment: &Payment) -> Result<(), PaymentError> { unimplemented!() }
+        async fn create_with_qr_code(&self, _payment: &Payment, _qr_code: &PaymentQrCode) -> Result<(), PaymentError> { unimplemented!() }
+        async fn find_qr_code(&self, _payment_id: Uuid) -> Result<Option<PaymentQrCode>, PaymentError> { unimplemented!() }
+
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Payment>, PaymentError> {
+            Ok(self.payments.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_by_trip_id(&self, _trip_id: Uuid) -> Result<Option<Payment>, PaymentError> { unimplemented!() }
+        async fn find_by_user_id(&self, _user_id: Uuid) -> Result<Vec<Payment>, PaymentError> { unimplemented!() }
+        async fn find_by_bank_reference(&self, _bank_reference: &str) -> Result<Option<Payment>, PaymentError> { unimplemented!() }
+        async fn update(&self, _id: Uuid, _payment: &Payment) -> Result<(), PaymentError> { unimplemented!() }
+
+        async fn complete_pending(
+            &self,
+            _id: Uuid,
+            _status: PaymentStatus,
+            _bank_reference: &str,
+            _paid_at: Option<DateTime<Utc>>,
+            _wallet_credit: Option<&WalletTransaction>,
+        ) -> Result<bool, PaymentError> {
+            unimplemented!()
+        }
+
+        async fn find_refunds_by_payment_id(&self, payment_id: Uuid) -> Result<Vec<Refund>, PaymentError> {
+            Ok(self.refunds.lock().unwrap().iter()
+                .filter(|refund| refund.payment_id == payment_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn create_refund(&self, refund: &Refund, wallet_credit: Option<&WalletTransaction>) -> Result<bool, PaymentError> {
+            let concurrent: Vec<Refund> = self.concurrent_refunds.lock().unwrap().drain(..).collect();
+            for other in &concurrent {
+                assert!(self.apply_refund(other));
+            }
+
+            if !self.apply_refund(refund) {
+                return Ok(false);
+            }
+            if let Some(credit) = wallet_credit {
+                self.wallet_credits.lock().unwrap().push(credit.clone());
+            }
+            Ok(true)
+        }
+
+        async fn create_paid_from_wallet(&self, _payment: &Payment, _debit: &WalletTransaction) -> Result<bool, PaymentError> { unimplemented!() }
+        async fn wallet_balance(&self, _user_id: Uuid) -> Result<Option<Money>, PaymentError> { unimplemented!() }
+        async fn find_wallet_transactions_by_user_id(&self, _user_id: Uuid) -> Result<Vec<WalletTransaction>, PaymentError> { unimplemented!() }
+    }
+
+    fn rub(minor_units: i64) -> Money {
+        Money::new(minor_units, Currency::Rub)
+    }
+
+    fn paid_trip_payment(method: PaymentMethod, amount: i64) -> Payment {
+        Payment {
+            id: Uuid::new_v4(),
+            trip_id: Some(Uuid::new_v4()),
+            user_id: Uuid::new_v4(),
+            kind: PaymentKind::Trip,
+            method,
+            amount: rub(amount),
+            discount: None,
+            status: PaymentStatus::Paid,
+            bank_reference: Some("bank-ref".to_string()),
+            qr_code_url: None,
+            created_at: Utc::now(),
+            paid_at: Some(Utc::now()),
+        }
+    }
+
+    fn refund_request(amount: i64) -> CreateRefundRequest {
+        CreateRefundRequest { amount: rub(amount), reason: "Trip dispute".to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_partial_refunds_up_to_paid_amount() {
+        let payment = paid_trip_payment(PaymentMethod::Sbp, 10_000);
+        let use_case = CreateRefundUseCase::new(MockPaymentRepository::with_payment(payment.clone()));
+
+        let (_, after_first) = use_case.execute(payment.id, refund_request(4_000)).await.unwrap();
+        assert_eq!(after_first.status, PaymentStatus::PartiallyRefunded);
+
+        let (_, after_second) = use_case.execute(payment.id, refund_request(6_000)).await.unwrap();
+        assert_eq!(after_second.status, PaymentStatus::Refunded);
+    }
+
+    #[tokio::test]
+    async fn test_refund_over_remaining_amount_is_rejected() {
+        let payment = paid_trip_payment(PaymentMethod::Sbp, 10_000);
+        let repository = MockPaymentRepository::with_payment(payment.clone());
+        let use_case = CreateRefundUseCase::new(repository.clone());
+        use_case.execute(payment.id, refund_request(7_000)).await.unwrap();
+
+        let result = use_case.execute(payment.id, refund_request(3_001)).await;
+
+        match result {
+            Err(PaymentError::RefundExceedsPaidAmount { requested, available }) => {
+                assert_eq!(requested, rub(3_001));
+                assert_eq!(available, rub(3_000));
+            }
+            other => panic!("expected RefundExceedsPaidAmount, got {:?}", other.map(|_| ())),
+        }
+        assert_eq!(repository.refunded(payment.id), 7_000);
+    }
+
+    #[tokio::test]
+    async fn test_refund_over_paid_amount_is_rejected() {
+        let payment = paid_trip_payment(PaymentMethod::Sbp, 10_000);
+        let use_case = CreateRefundUseCase::new(MockPaymentRepository::with_payment(payment.clone()));
+
+        let result = use_case.execute(payment.id, refund_request(10_001)).await;
+
+        assert!(matches!(result, Err(PaymentError::RefundExceedsPaidAmount { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_refund_is_rechecked_on_write() {
+        let payment = paid_trip_payment(PaymentMethod::Sbp, 10_000);
+        let repository = MockPaymentRepository::with_payment(payment.clone());
+        repository.concurrent_refunds.lock().unwrap().push(Refund {
+            id: Uuid::new_v4(),
+            payment_id: payment.id,
+            amount: rub(8_000),
+            reason: "Parallel refund".to_string(),
+            created_at: Utc::now(),
+        });
+        let use_case = CreateRefundUseCase::new(repository.clone());
+
+        let result = use_case.execute(payment.id, refund_request(5_000)).await;
+
+        match result {
+            Err(PaymentError::RefundExceedsPaidAmount { available, .. }) => assert_eq!(available, rub(2_000)),
+            other => panic!("expected RefundExceedsPaidAmount, got {:?}", other.map(|_| ())),
+        }
+        assert_eq!(repository.refunded(payment.id), 8_000);
+    }
+
+    #[tokio::test]
+    async fn test_fully_refunded_payment_is_not_refundable() {
+        let payment = paid_trip_payment(PaymentMethod::Sbp, 10_000);
+        let use_case = CreateRefundUseCase::new(MockPaymentRepository::with_payment(payment.clone()));
+        use_case.execute(payment.id, refund_request(10_000)).await.unwrap();
+
+        let result = use_case.execute(payment.id, refund_request(1)).await;
+
+        assert!(matches!(result, Err(PaymentError::PaymentNotRefundable { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_refund_of_unpaid_payment_is_rejected() {
+        let mut payment = paid_trip_payment(PaymentMethod::Sbp, 10_000);
+        payment.status = PaymentStatus::Pending;
+        let use_case = CreateRefundUseCase::new(MockPaymentRepository::with_payment(payment.clone()));
+
+        let result = use_case.execute(payment.id, refund_request(1_000)).await;
+
+        assert!(matches!(result, Err(PaymentError::PaymentNotRefundable { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_wallet_trip_refund_is_credited_to_wallet() {
+        let payment = paid_trip_payment(PaymentMethod::Wallet, 10_000);
+        let repository = MockPaymentRepository::with_payment(payment.clone());
+        let use_case = CreateRefundUseCase::new(repository.clone());
+
+        let (refund, _) = use_case.execute(payment.id, refund_request(2_500)).await.unwrap();
+
+        let credits = repository.wallet_credits.lock().unwrap();
+        assert_eq!(credits.len(), 1);
+        assert_eq!(credits[0].amount, rub(2_500));
+        assert_eq!(credits[0].refund_id, Some(refund.id));
+    }
+
+    #[tokio::test]
+    async fn test_wallet_top_up_is_not_refundable() {
+        let mut payment = paid_trip_payment(PaymentMethod::Sbp, 10_000);
+        payment.kind = PaymentKind::WalletTopUp;
+        payment.trip_id = None;
+        let repository = MockPaymentRepository::with_payment(payment.clone());
+        let use_case = CreateRefundUseCase::new(repository.clone());
+
+        let result = use_case.execute(payment.id, refund_request(10_000)).await;
+
+        assert!(matches!(result, Err(PaymentError::TopUpNotRefundable)));
+        assert_eq!(repository.refunded(payment.id), 0);
+    }
+}
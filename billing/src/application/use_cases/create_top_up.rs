@@ -0,0 +1,55 @@
+use uuid::Uuid;
+use chrono::Utc;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::{PaymentRepository, QRCodeGenerator},
+    models::{CreateTopUpRequest, Payment, PaymentKind, PaymentMethod, PaymentStatus},
+};
+
+pub struct CreateTopUpUseCase<R, Q>
+where
+    R: PaymentRepository,
+    Q: QRCodeGenerator,
+{
+    repository: R,
+    qr_generator: Q,
+}
+
+impl<R, Q> CreateTopUpUseCase<R, Q>
+where
+    R: PaymentRepository,
+    Q: QRCodeGenerator,
+{
+    pub fn new(repository: R, qr_generator: Q) -> Self {
+        Self { repository, qr_generator }
+    }
+
+    // Деньги зачисляются на кошелек, когда банк подтвердит оплату
+    pub async fn execute(&self, request: CreateTopUpRequest) -> Result<Payment, PaymentError> {
+        if !request.amount.is_positive() {
+            return Err(PaymentError::InvalidAmount { amount: request.amount });
+        }
+
+        let payment_id = Uuid::new_v4();
+        let qr_code = self.qr_generator.generate_qr_code(payment_id, request.amount).await
+            .map_err(|e| PaymentError::Internal(anyhow::anyhow!("Failed to generate QR code: {}", e)))?;
+
+        let payment = Payment {
+            id: payment_id,
+            trip_id: None,
+            user_id: request.user_id,
+            kind: PaymentKind::WalletTopUp,
+            method: PaymentMethod::Sbp,
+            amount: request.amount,
+            discount: None,
+            status: PaymentStatus::Pending,
+            bank_reference: None,
+            qr_code_url: Some(format!("/payments/{}/qr.png", payment_id)),
+            created_at: Utc::now(),
+            paid_at: None,
+        };
+
+        self.repository.create_with_qr_code(&payment, &qr_code).await?;
+        Ok(payment)
+    }
+}
@@ -0,0 +1,31 @@
+use uuid::Uuid;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::PromoCodeRepository,
+    models::PromoCode,
+};
+
+pub struct DeactivatePromoCodeUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    repository: P,
+}
+
+impl<P> DeactivatePromoCodeUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    pub fn new(repository: P) -> Self {
+        Self { repository }
+    }
+
+    // Код не удаляется: на него ссылаются платежи и отчеты по кампании
+    pub async fn execute(&self, id: Uuid) -> Result<PromoCode, PaymentError> {
+        if !self.repository.deactivate(id).await? {
+            return Err(PaymentError::PromoCodeNotFound);
+        }
+        self.repository.find_by_id(id).await?
+            .ok_or(PaymentError::PromoCodeNotFound)
+    }
+}
@@ -0,0 +1,28 @@
+use uuid::Uuid;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::PromoCodeRepository,
+};
+
+pub struct DetachPromoCodeUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    repository: P,
+}
+
+impl<P> DetachPromoCodeUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    pub fn new(repository: P) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, user_id: Uuid) -> Result<(), PaymentError> {
+        if !self.repository.detach(user_id).await? {
+            return Err(PaymentError::PromoCodeNotAttached);
+        }
+        Ok(())
+    }
+}
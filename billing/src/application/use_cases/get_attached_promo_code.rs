@@ -0,0 +1,27 @@
+use uuid::Uuid;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::PromoCodeRepository,
+    models::AttachedPromoCode,
+};
+
+pub struct GetAttachedPromoCodeUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    repository: P,
+}
+
+impl<P> GetAttachedPromoCodeUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    pub fn new(repository: P) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, user_id: Uuid) -> Result<AttachedPromoCode, PaymentError> {
+        self.repository.find_attached(user_id).await?
+            .ok_or(PaymentError::PromoCodeNotAttached)
+    }
+}
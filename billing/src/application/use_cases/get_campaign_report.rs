@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::PromoCodeRepository,
+    models::CampaignReport,
+};
+
+pub struct GetCampaignReportUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    repository: P,
+}
+
+impl<P> GetCampaignReportUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    pub fn new(repository: P) -> Self {
+        Self { repository }
+    }
+
+    // Период [from, to) по дате создания платежа
+    pub async fn execute(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CampaignReport>, PaymentError> {
+        if from.zip(to).is_some_and(|(from, to)| from >= to) {
+            return Err(PaymentError::InvalidReportPeriod);
+        }
+        self.repository.campaign_report(from, to).await
+    }
+}
@@ -0,0 +1,27 @@
+use uuid::Uuid;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::PaymentRepository,
+    models::PaymentQrCode,
+};
+
+pub struct GetPaymentQrCodeUseCase<R>
+where
+    R: PaymentRepository,
+{
+    repository: R,
+}
+
+impl<R> GetPaymentQrCodeUseCase<R>
+where
+    R: PaymentRepository,
+{
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, payment_id: Uuid) -> Result<PaymentQrCode, PaymentError> {
+        self.repository.find_qr_code(payment_id).await?
+            .ok_or(PaymentError::QrCodeNotFound)
+    }
+}
@@ -0,0 +1,29 @@
+use uuid::Uuid;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::PaymentRepository,
+    models::Refund,
+};
+
+pub struct GetPaymentRefundsUseCase<R>
+where
+    R: PaymentRepository,
+{
+    repository: R,
+}
+
+impl<R> GetPaymentRefundsUseCase<R>
+where
+    R: PaymentRepository,
+{
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, payment_id: Uuid) -> Result<Vec<Refund>, PaymentError> {
+        if self.repository.find_by_id(payment_id).await?.is_none() {
+            return Err(PaymentError::PaymentNotFound);
+        }
+        self.repository.find_refunds_by_payment_id(payment_id).await
+    }
+}
@@ -0,0 +1,25 @@
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::PromoCodeRepository,
+    models::PromoCode,
+};
+
+pub struct GetPromoCodesUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    repository: P,
+}
+
+impl<P> GetPromoCodesUseCase<P>
+where
+    P: PromoCodeRepository,
+{
+    pub fn new(repository: P) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<PromoCode>, PaymentError> {
+        self.repository.find_all().await
+    }
+}
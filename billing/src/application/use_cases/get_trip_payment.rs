@@ -0,0 +1,27 @@
+use uuid::Uuid;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::PaymentRepository,
+    models::Payment,
+};
+
+pub struct GetTripPaymentUseCase<R> 
+where
+    R: PaymentRepository,
+{
+    repository: R,
+}
+
+impl<R> GetTripPaymentUseCase<R>
+where
+    R: PaymentRepository,
+{
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, trip_id: Uuid) -> Result<Payment, PaymentError> {
+        self.repository.find_by_trip_id(trip_id).await?
+            .ok_or(PaymentError::PaymentNotFound)
+    }
+}
@@ -0,0 +1,51 @@
+use uuid::Uuid;
+use chrono::Utc;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::{DebtOverrideRepository, PaymentRepository},
+    models::{Money, PaymentKind, PaymentStatus, UserBalance},
+};
+
+pub struct GetUserBalanceUseCase<R, D>
+where
+    R: PaymentRepository,
+    D: DebtOverrideRepository,
+{
+    repository: R,
+    debt_override_repository: D,
+}
+
+impl<R, D> GetUserBalanceUseCase<R, D>
+where
+    R: PaymentRepository,
+    D: DebtOverrideRepository,
+{
+    pub fn new(repository: R, debt_override_repository: D) -> Self {
+        Self { repository, debt_override_repository }
+    }
+
+    // Долг - неоплаченные платежи за поездки; неоплаченные пополнения долгом не считаются
+    pub async fn execute(&self, user_id: Uuid) -> Result<UserBalance, PaymentError> {
+        let payments = self.repository.find_by_user_id(user_id).await?;
+
+        let mut outstanding = Money::default();
+        let mut unpaid_payments = 0;
+        for payment in payments.iter().filter(|p| p.kind == PaymentKind::Trip && p.status == PaymentStatus::Pending) {
+            outstanding = outstanding
+                .checked_add(payment.amount)
+                .ok_or(PaymentError::InvalidAmount { amount: payment.amount })?;
+            unpaid_payments += 1;
+        }
+
+        // Истекшее разрешение больше не действует
+        let debt_override = self.debt_override_repository.find_by_user_id(user_id).await?
+            .filter(|o| o.is_active(Utc::now()));
+
+        Ok(UserBalance {
+            user_id,
+            outstanding,
+            unpaid_payments,
+            debt_override,
+        })
+    }
+}
@@ -2,7 +2,7 @@ use uuid::Uuid;
 use crate::domain::{
     errors::PaymentError,
     interfaces::PaymentRepository,
-    models::Payment,
+    models::PaymentHistoryEntry,
 };
 
 pub struct GetUserPaymentsUseCase<R> 
@@ -20,8 +20,15 @@ where
         Self { repository }
     }
 
-    pub async fn execute(&self, user_id: Uuid) -> Result<Vec<Payment>, PaymentError> {
-        self.repository.find_by_user_id(user_id).await
+    pub async fn execute(&self, user_id: Uuid) -> Result<Vec<PaymentHistoryEntry>, PaymentError> {
+        let payments = self.repository.find_by_user_id(user_id).await?;
+        let transactions = self.repository.find_wallet_transactions_by_user_id(user_id).await?;
+
+        let mut history: Vec<PaymentHistoryEntry> = payments.into_iter()
+            .map(PaymentHistoryEntry::Payment)
+            .chain(transactions.into_iter().map(PaymentHistoryEntry::WalletTransaction))
+            .collect();
+        history.sort_by_key(|entry| std::cmp::Reverse(entry.created_at()));
+        Ok(history)
     }
 }
-
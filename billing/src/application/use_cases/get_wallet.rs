@@ -0,0 +1,31 @@
+use uuid::Uuid;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::PaymentRepository,
+    models::{Currency, Money, Wallet},
+};
+
+pub struct GetWalletUseCase<R>
+where
+    R: PaymentRepository,
+{
+    repository: R,
+}
+
+impl<R> GetWalletUseCase<R>
+where
+    R: PaymentRepository,
+{
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, user_id: Uuid) -> Result<Wallet, PaymentError> {
+        // Кошелек без движений показываем пустым в рублях: других валют у кошельков нет
+        let balance = self.repository
+            .wallet_balance(user_id)
+            .await?
+            .unwrap_or_else(|| Money::new(0, Currency::Rub));
+        Ok(Wallet { user_id, balance })
+    }
+}
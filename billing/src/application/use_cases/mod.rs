@@ -1,8 +1,43 @@
+mod attach_promo_code;
 mod create_payment;
+mod create_promo_code;
+mod create_refund;
+mod create_top_up;
+mod deactivate_promo_code;
+mod detach_promo_code;
+mod get_attached_promo_code;
+mod get_campaign_report;
 mod get_payment;
+mod get_payment_qr_code;
+mod get_payment_refunds;
+mod get_promo_codes;
+mod get_trip_payment;
+mod get_user_balance;
 mod get_user_payments;
+mod get_wallet;
+mod process_payment_callback;
+mod relay_outbox_events;
+mod remove_debt_override;
+mod set_debt_override;
 
+pub use attach_promo_code::*;
 pub use create_payment::*;
+pub use create_promo_code::*;
+pub use create_refund::*;
+pub use create_top_up::*;
+pub use deactivate_promo_code::*;
+pub use detach_promo_code::*;
+pub use get_attached_promo_code::*;
+pub use get_campaign_report::*;
 pub use get_payment::*;
+pub use get_payment_qr_code::*;
+pub use get_payment_refunds::*;
+pub use get_promo_codes::*;
+pub use get_trip_payment::*;
+pub use get_user_balance::*;
 pub use get_user_payments::*;
-
+pub use get_wallet::*;
+pub use process_payment_callback::*;
+pub use relay_outbox_events::*;
+pub use remove_debt_override::*;
+pub use set_debt_override::*;
@@ -0,0 +1,416 @@
+use chrono::Utc;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::{PaymentRepository, WebhookSigner},
+    models::{Payment, PaymentCallback, PaymentKind, PaymentStatus, WalletTransaction},
+};
+
+pub struct ProcessPaymentCallbackUseCase<R, S>
+where
+    R: PaymentRepository,
+    S: WebhookSigner,
+{
+    repository: R,
+    signer: S,
+}
+
+impl<R, S> ProcessPaymentCallbackUseCase<R, S>
+where
+    R: PaymentRepository,
+    S: WebhookSigner,
+{
+    pub fn new(repository: R, signer: S) -> Self {
+        Self { repository, signer }
+    }
+
+    // Возвращает актуальный платеж и признак повторной доставки того же уведомления
+    pub async fn execute(
+        &self,
+        payload: &[u8],
+        signature: Option<&str>,
+    ) -> Result<(Payment, bool), PaymentError> {
+        // Подпись проверяется по сырому телу запроса до его разбора
+        let signature = signature.ok_or(PaymentError::InvalidSignature)?;
+        if !self.signer.verify(payload, signature) {
+            return Err(PaymentError::InvalidSignature);
+        }
+
+        let callback: PaymentCallback = serde_json::from_slice(payload)
+            .map_err(|e| PaymentError::InvalidCallback(e.to_string()))?;
+        if callback.bank_reference.trim().is_empty() {
+            return Err(PaymentError::InvalidCallback("bank_reference must not be empty".to_string()));
+        }
+
+        let payment = self.repository.find_by_id(callback.payment_id).await?
+            .ok_or(PaymentError::PaymentNotFound)?;
+
+        // Банк повторно доставил уже примененное уведомление
+        if payment.bank_reference.as_deref() == Some(callback.bank_reference.as_str()) {
+            return Self::replayed(payment, &callback);
+        }
+
+        if self.repository.find_by_bank_reference(&callback.bank_reference).await?.is_some() {
+            return Err(PaymentError::BankReferenceInUse(callback.bank_reference));
+        }
+
+        if !payment.status.can_transition_to(callback.status) {
+            return Err(PaymentError::InvalidStatusTransition {
+                from: payment.status.as_str().to_string(),
+                to: callback.status.as_str().to_string(),
+            });
+        }
+
+        if callback.amount != payment.amount {
+            return Err(PaymentError::AmountMismatch {
+                expected: payment.amount,
+                actual: callback.amount,
+            });
+        }
+
+        let paid_at = (callback.status == PaymentStatus::Paid)
+            .then(|| callback.paid_at.unwrap_or_else(Utc::now));
+
+        // Оплаченное пополнение зачисляется на кошелек вместе со сменой статуса
+        let wallet_credit = (payment.kind == PaymentKind::WalletTopUp && callback.status == PaymentStatus::Paid)
+            .then(|| WalletTransaction::top_up(&payment));
+
+        let updated = self.repository
+            .complete_pending(payment.id, callback.status, &callback.bank_reference, paid_at, wallet_credit.as_ref())
+            .await?;
+        if !updated {
+            // Параллельное уведомление успело завершить платеж раньше
+            let current = self.repository.find_by_id(payment.id).await?
+                .ok_or(PaymentError::PaymentNotFound)?;
+            return Self::replayed(current, &callback);
+        }
+
+        Ok((
+            Payment {
+                status: callback.status,
+                bank_reference: Some(callback.bank_reference),
+                paid_at,
+                ..payment
+            },
+            false,
+        ))
+    }
+
+    fn replayed(payment: Payment, callback: &PaymentCallback) -> Result<(Payment, bool), PaymentError> {
+        // Оплаченный платеж мог быть после этого (частично) возвращен - это тоже уже примененное уведомление
+        let already_applied = payment.status == callback.status
+            || (callback.status == PaymentStatus::Paid
+                && matches!(payment.status, PaymentStatus::PartiallyRefunded | PaymentStatus::Refunded));
+        if already_applied
+            && payment.bank_reference.as_deref() == Some(callback.bank_reference.as_str())
+        {
+            Ok((payment, true))
+        } else {
+            Err(PaymentError::InvalidStatusTransition {
+                from: payment.status.as_str().to_string(),
+                to: callback.status.as_str().to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::DateTime;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+    use crate::domain::models::{Currency, Money, PaymentMethod, PaymentQrCode, Refund, WalletTransactionKind};
+
+    const SIGNATURE: &str = "valid-signature";
+
+    #[derive(Clone, Default)]
+    struct MockPaymentRepository {
+        payments: Arc<Mutex<HashMap<Uuid, Payment>>>,
+        wallet_credits: Arc<Mutex<Vec<WalletTransaction>>>,
+        // Уведомление, которое параллельный запрос применит перед нашим
+        concurrent_callback: Arc<Mutex<Option<(PaymentStatus, String)>>>,
+    }
+
+    impl MockPaymentRepository {
+        fn with_payments(payments: &[&Payment]) -> Self {
+            let repository = Self::default();
+            for payment in payments {
+                repository.payments.lock().unwrap().insert(payment.id, (*payment).clone());
+            }
+            repository
+        }
+
+        fn get(&self, id: Uuid) -> Payment {
+            self.payments.lock().unwrap()[&id].clone()
+        }
+
+        fn finish(&self, id: Uuid, status: PaymentStatus, bank_reference: &str, paid_at: Option<DateTime<Utc>>) -> bool {
+            let mut payments = self.payments.lock().unwrap();
+            let payment = payments.get_mut(&id).unwrap();
+            if payment.status != PaymentStatus::Pending {
+                return false;
+            }
+            payment.status = status;
+            payment.bank_reference = Some(bank_reference.to_string());
+            payment.paid_at = paid_at;
+            true
+        }
+    }
+
+    #[async_trait]
+    impl PaymentRepository for MockPaymentRepository {
+        async fn create(&self, _payment: &Payment) -> Result<(), PaymentError> { unimplemented!() }
+        async fn create_with_qr_code(&self, _payment: &Payment, _qr_code: &PaymentQrCode) -> Result<(), PaymentError> { unimplemented!() }
+        async fn find_qr_code(&self, _payment_id: Uuid) -> Result<Option<PaymentQrCode>, PaymentError> { unimplemented!() }
+
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Payment>, PaymentError> {
+            Ok(self.payments.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_by_trip_id(&self, _trip_id: Uuid) -> Result<Option<Payment>, PaymentError> { unimplemented!() }
+        async fn find_by_user_id(&self, _user_id: Uuid) -> Result<Vec<Payment>, PaymentError> { unimplemented!() }
+
+        async fn find_by_bank_reference(&self, bank_reference: &str) -> Result<Option<Payment>, PaymentError> {
+            Ok(self.payments.lock().unwrap().values()
+                .find(|payment| payment.bank_reference.as_deref() == Some(bank_reference))
+                .cloned())
+        }
+
+        async fn update(&self, _id: Uuid, _payment: &Payment) -> Result<(), PaymentError> { unimplemented!() }
+
+        async fn complete_pending(
+            &self,
+            id: Uuid,
+            status: PaymentStatus,
+            bank_reference: &str,
+            paid_at: Option<DateTime<Utc>>,
+            wallet_credit: Option<&WalletTransaction>,
+        ) -> Result<bool, PaymentError> {
+            if let Some((status, bank_reference)) = self.concurrent_callback.lock().unwrap().take() {
+                assert!(self.finish(id, status, &bank_reference, Some(Utc::now())));
+            }
+
+            if !self.finish(id, status, bank_reference, paid_at) {
+                return Ok(false);
+            }
+            if let Some(credit) = wallet_credit {
+                self.wallet_credits.lock().unwrap().push(credit.clone());
+            }
+            Ok(true)
+        }
+
+        async fn find_refunds_by_payment_id(&self, _payment_id: Uuid) -> Result<Vec<Refund>, PaymentError> { unimplemented!() }
+        async fn create_refund(&self, _refund: &Refund, _wallet_credit: Option<&WalletTransaction>) -> Result<bool, PaymentError> { unimplemented!() }
+        async fn create_paid_from_wallet(&self, _payment: &Payment, _debit: &WalletTransaction) -> Result<bool, PaymentError> { unimplemented!() }
+        async fn wallet_balance(&self, _user_id: Uuid) -> Result<Option<Money>, PaymentError> { unimplemented!() }
+        async fn find_wallet_transactions_by_user_id(&self, _user_id: Uuid) -> Result<Vec<WalletTransaction>, PaymentError> { unimplemented!() }
+    }
+
+    struct MockSigner;
+
+    impl WebhookSigner for MockSigner {
+        fn sign(&self, _payload: &[u8]) -> String {
+            SIGNATURE.to_string()
+        }
+
+        fn verify(&self, _payload: &[u8], signature: &str) -> bool {
+            signature == SIGNATURE
+        }
+    }
+
+    fn rub(minor_units: i64) -> Money {
+        Money::new(minor_units, Currency::Rub)
+    }
+
+    fn pending_payment(kind: PaymentKind) -> Payment {
+        Payment {
+            id: Uuid::new_v4(),
+            trip_id: (kind == PaymentKind::Trip).then(Uuid::new_v4),
+            user_id: Uuid::new_v4(),
+            kind,
+            method: PaymentMethod::Sbp,
+            amount: rub(15_050),
+            discount: None,
+            status: PaymentStatus::Pending,
+            bank_reference: None,
+            qr_code_url: None,
+            created_at: Utc::now(),
+            paid_at: None,
+        }
+    }
+
+    fn callback(payment: &Payment, status: PaymentStatus, bank_reference: &str) -> Vec<u8> {
+        serde_json::to_vec(&PaymentCallback {
+            payment_id: payment.id,
+            bank_reference: bank_reference.to_string(),
+            status,
+            amount: payment.amount,
+            paid_at: None,
+        })
+        .unwrap()
+    }
+
+    fn use_case(repository: &MockPaymentRepository) -> ProcessPaymentCallbackUseCase<MockPaymentRepository, MockSigner> {
+        ProcessPaymentCallbackUseCase::new(repository.clone(), MockSigner)
+    }
+
+    #[tokio::test]
+    async fn test_pending_payment_becomes_paid() {
+        let payment = pending_payment(PaymentKind::Trip);
+        let repository = MockPaymentRepository::with_payments(&[&payment]);
+
+        let (updated, duplicate) = use_case(&repository)
+            .execute(&callback(&payment, PaymentStatus::Paid, "ref-1"), Some(SIGNATURE))
+            .await
+            .unwrap();
+
+        assert!(!duplicate);
+        assert_eq!(updated.status, PaymentStatus::Paid);
+        assert!(updated.paid_at.is_some());
+        let stored = repository.get(payment.id);
+        assert_eq!(stored.status, PaymentStatus::Paid);
+        assert_eq!(stored.bank_reference.as_deref(), Some("ref-1"));
+        assert!(repository.wallet_credits.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pending_payment_becomes_failed() {
+        let payment = pending_payment(PaymentKind::Trip);
+        let repository = MockPaymentRepository::with_payments(&[&payment]);
+
+        let (updated, duplicate) = use_case(&repository)
+            .execute(&callback(&payment, PaymentStatus::Failed, "ref-1"), Some(SIGNATURE))
+            .await
+            .unwrap();
+
+        assert!(!duplicate);
+        assert_eq!(updated.status, PaymentStatus::Failed);
+        assert_eq!(updated.paid_at, None);
+        assert_eq!(repository.get(payment.id).status, PaymentStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_paid_top_up_is_credited_to_wallet() {
+        let payment = pending_payment(PaymentKind::WalletTopUp);
+        let repository = MockPaymentRepository::with_payments(&[&payment]);
+
+        use_case(&repository)
+            .execute(&callback(&payment, PaymentStatus::Paid, "ref-1"), Some(SIGNATURE))
+            .await
+            .unwrap();
+
+        let credits = repository.wallet_credits.lock().unwrap();
+        assert_eq!(credits.len(), 1);
+        assert_eq!(credits[0].kind, WalletTransactionKind::TopUp);
+        assert_eq!(credits[0].amount, payment.amount);
+    }
+
+    #[tokio::test]
+    async fn test_replayed_callback_is_idempotent() {
+        let payment = pending_payment(PaymentKind::WalletTopUp);
+        let repository = MockPaymentRepository::with_payments(&[&payment]);
+        let use_case = use_case(&repository);
+        let body = callback(&payment, PaymentStatus::Paid, "ref-1");
+        let (first, _) = use_case.execute(&body, Some(SIGNATURE)).await.unwrap();
+
+        let (replayed, duplicate) = use_case.execute(&body, Some(SIGNATURE)).await.unwrap();
+
+        assert!(duplicate);
+        assert_eq!(replayed.status, PaymentStatus::Paid);
+        assert_eq!(replayed.paid_at, first.paid_at);
+        assert_eq!(repository.wallet_credits.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_callback_applied_concurrently_is_reported_as_duplicate() {
+        let payment = pending_payment(PaymentKind::Trip);
+        let repository = MockPaymentRepository::with_payments(&[&payment]);
+        *repository.concurrent_callback.lock().unwrap() = Some((PaymentStatus::Paid, "ref-1".to_string()));
+
+        let (current, duplicate) = use_case(&repository)
+            .execute(&callback(&payment, PaymentStatus::Paid, "ref-1"), Some(SIGNATURE))
+            .await
+            .unwrap();
+
+        assert!(duplicate);
+        assert_eq!(current.status, PaymentStatus::Paid);
+    }
+
+    #[tokio::test]
+    async fn test_amount_mismatch_is_rejected() {
+        let payment = pending_payment(PaymentKind::Trip);
+        let repository = MockPaymentRepository::with_payments(&[&payment]);
+        let body = serde_json::to_vec(&PaymentCallback {
+            payment_id: payment.id,
+            bank_reference: "ref-1".to_string(),
+            status: PaymentStatus::Paid,
+            amount: rub(15_000),
+            paid_at: None,
+        })
+        .unwrap();
+
+        let result = use_case(&repository).execute(&body, Some(SIGNATURE)).await;
+
+        match result {
+            Err(PaymentError::AmountMismatch { expected, actual }) => {
+                assert_eq!(expected, rub(15_050));
+                assert_eq!(actual, rub(15_000));
+            }
+            other => panic!("expected AmountMismatch, got {:?}", other.map(|_| ())),
+        }
+        assert_eq!(repository.get(payment.id).status, PaymentStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_bank_reference_of_another_payment_is_rejected() {
+        let mut paid = pending_payment(PaymentKind::Trip);
+        paid.status = PaymentStatus::Paid;
+        paid.bank_reference = Some("ref-1".to_string());
+        let payment = pending_payment(PaymentKind::Trip);
+        let repository = MockPaymentRepository::with_payments(&[&paid, &payment]);
+
+        let result = use_case(&repository)
+            .execute(&callback(&payment, PaymentStatus::Paid, "ref-1"), Some(SIGNATURE))
+            .await;
+
+        assert!(matches!(result, Err(PaymentError::BankReferenceInUse(reference)) if reference == "ref-1"));
+        assert_eq!(repository.get(payment.id).status, PaymentStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_callback_on_final_payment_is_rejected() {
+        let payment = pending_payment(PaymentKind::Trip);
+        let repository = MockPaymentRepository::with_payments(&[&payment]);
+        let use_case = use_case(&repository);
+        use_case
+            .execute(&callback(&payment, PaymentStatus::Failed, "ref-1"), Some(SIGNATURE))
+            .await
+            .unwrap();
+
+        let new_reference = use_case
+            .execute(&callback(&payment, PaymentStatus::Paid, "ref-2"), Some(SIGNATURE))
+            .await;
+        let same_reference = use_case
+            .execute(&callback(&payment, PaymentStatus::Paid, "ref-1"), Some(SIGNATURE))
+            .await;
+
+        assert!(matches!(new_reference, Err(PaymentError::InvalidStatusTransition { .. })));
+        assert!(matches!(same_reference, Err(PaymentError::InvalidStatusTransition { .. })));
+        let stored = repository.get(payment.id);
+        assert_eq!(stored.status, PaymentStatus::Failed);
+        assert_eq!(stored.bank_reference.as_deref(), Some("ref-1"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_signature_is_rejected() {
+        let payment = pending_payment(PaymentKind::Trip);
+        let repository = MockPaymentRepository::with_payments(&[&payment]);
+        let body = callback(&payment, PaymentStatus::Paid, "ref-1");
+
+        assert!(matches!(use_case(&repository).execute(&body, Some("forged")).await, Err(PaymentError::InvalidSignature)));
+        assert!(matches!(use_case(&repository).execute(&body, None).await, Err(PaymentError::InvalidSignature)));
+        assert_eq!(repository.get(payment.id).status, PaymentStatus::Pending);
+    }
+}
@@ -0,0 +1,77 @@
+use std::time::Duration;
+use chrono::Utc;
+use tracing::{warn, error};
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::{OutboxRepository, EventPublisher},
+};
+
+const RELAY_BATCH_SIZE: i64 = 100;
+const RELAY_LEASE: Duration = Duration::from_secs(30);
+const RELAY_RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+const RELAY_MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+pub struct RelayOutboxEventsUseCase<R, P>
+where
+    R: OutboxRepository,
+    P: EventPublisher,
+{
+    repository: R,
+    publisher: P,
+    retention: Duration,
+}
+
+impl<R, P> RelayOutboxEventsUseCase<R, P>
+where
+    R: OutboxRepository,
+    P: EventPublisher,
+{
+    pub fn new(repository: R, publisher: P, retention: Duration) -> Self {
+        Self { repository, publisher, retention }
+    }
+
+    pub async fn execute(&self) -> Result<usize, PaymentError> {
+        let now = Utc::now();
+        let events = self.repository
+            .claim_pending(now, now + to_chrono(RELAY_LEASE), RELAY_BATCH_SIZE)
+            .await?;
+
+        let mut published = 0;
+        for event in events {
+            match self.publisher.publish(&event).await {
+                Ok(()) => {
+                    self.repository.mark_published(event.id, Utc::now()).await?;
+                    published += 1;
+                }
+                Err(e) => {
+                    let delay = RELAY_RETRY_BASE_DELAY
+                        .saturating_mul(1 << event.attempts.min(16))
+                        .min(RELAY_MAX_RETRY_DELAY);
+                    warn!(
+                        "Event {} ({}) publish attempt {} failed, retry in {:?}: {}",
+                        event.id, event.event_type, event.attempts + 1, delay, e
+                    );
+                    if let Err(mark_error) = self.repository
+                        .mark_failed(event.id, &e.to_string(), Utc::now() + to_chrono(delay))
+                        .await
+                    {
+                        error!("Failed to record publish failure of event {}: {:?}", event.id, mark_error);
+                    }
+                    break;
+                }
+            }
+        }
+
+        Ok(published)
+    }
+
+    pub async fn purge_published(&self) -> Result<u64, PaymentError> {
+        self.repository
+            .delete_published_before(Utc::now() - to_chrono(self.retention))
+            .await
+    }
+}
+
+fn to_chrono(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::days(365))
+}
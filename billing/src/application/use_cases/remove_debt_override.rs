@@ -0,0 +1,28 @@
+use uuid::Uuid;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::DebtOverrideRepository,
+};
+
+pub struct RemoveDebtOverrideUseCase<D>
+where
+    D: DebtOverrideRepository,
+{
+    repository: D,
+}
+
+impl<D> RemoveDebtOverrideUseCase<D>
+where
+    D: DebtOverrideRepository,
+{
+    pub fn new(repository: D) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, user_id: Uuid) -> Result<(), PaymentError> {
+        if !self.repository.delete(user_id).await? {
+            return Err(PaymentError::DebtOverrideNotFound);
+        }
+        Ok(())
+    }
+}
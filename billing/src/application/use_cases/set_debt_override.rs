@@ -0,0 +1,46 @@
+use uuid::Uuid;
+use chrono::Utc;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::DebtOverrideRepository,
+    models::{DebtOverride, SetDebtOverrideRequest},
+};
+
+pub struct SetDebtOverrideUseCase<D>
+where
+    D: DebtOverrideRepository,
+{
+    repository: D,
+}
+
+impl<D> SetDebtOverrideUseCase<D>
+where
+    D: DebtOverrideRepository,
+{
+    pub fn new(repository: D) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, request: SetDebtOverrideRequest) -> Result<DebtOverride, PaymentError> {
+        let reason = request.reason.trim();
+        if reason.is_empty() {
+            return Err(PaymentError::InvalidDebtOverride("reason must not be empty".to_string()));
+        }
+
+        let now = Utc::now();
+        if request.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(PaymentError::InvalidDebtOverride("expires_at must be in the future".to_string()));
+        }
+
+        let debt_override = DebtOverride {
+            user_id,
+            reason: reason.to_string(),
+            granted_by: request.granted_by,
+            created_at: now,
+            expires_at: request.expires_at,
+        };
+        self.repository.upsert(&debt_override).await?;
+
+        Ok(debt_override)
+    }
+}
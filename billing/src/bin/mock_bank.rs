@@ -0,0 +1,141 @@
+use billing::{
+    domain::{
+        interfaces::WebhookSigner,
+        models::{Money, PaymentCallback, PaymentStatus},
+    },
+    infrastructure::HmacWebhookSigner,
+    presentation::handlers::BANK_SIGNATURE_HEADER,
+};
+
+use chrono::Utc;
+use dotenv::dotenv;
+use serde::Deserialize;
+use tracing::{info, warn, error};
+use tracing_subscriber::{EnvFilter, FmtSubscriber};
+use uuid::Uuid;
+
+const USAGE: &str = "Usage: mock_bank <payment_id> [paid|failed] [--bank-reference <ref>] [--repeat <n>]";
+
+// Локальная имитация банка: подписывает уведомление о результате оплаты
+// и отправляет его в POST /payments/webhook billing сервиса
+
+#[derive(Deserialize)]
+struct PaymentInfo {
+    amount: Money,
+    status: String,
+}
+
+struct Args {
+    payment_id: Uuid,
+    status: PaymentStatus,
+    bank_reference: String,
+    repeat: u32,
+}
+
+fn parse_args() -> anyhow::Result<Args> {
+    let mut args = std::env::args().skip(1);
+
+    let payment_id = args
+        .next()
+        .ok_or_else(|| anyhow::anyhow!(USAGE))?
+        .parse::<Uuid>()
+        .map_err(|e| anyhow::anyhow!("Invalid payment_id: {}", e))?;
+
+    let mut status = PaymentStatus::Paid;
+    let mut bank_reference = None;
+    let mut repeat = 1;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bank-reference" => {
+                bank_reference = Some(args.next().ok_or_else(|| anyhow::anyhow!(USAGE))?);
+            }
+            "--repeat" => {
+                repeat = args
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!(USAGE))?
+                    .parse::<u32>()
+                    .map_err(|e| anyhow::anyhow!("Invalid --repeat value: {}", e))?
+                    .max(1);
+            }
+            "paid" => status = PaymentStatus::Paid,
+            "failed" => status = PaymentStatus::Failed,
+            other => return Err(anyhow::anyhow!("Unknown argument '{}'. {}", other, USAGE)),
+        }
+    }
+
+    // Идентификатор операции банка; повторные отправки используют тот же идентификатор
+    let bank_reference = bank_reference
+        .unwrap_or_else(|| format!("MOCK-{}", Uuid::new_v4().simple().to_string().to_uppercase()));
+
+    Ok(Args { payment_id, status, bank_reference, repeat })
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    // Инициализация логирования
+    let subscriber = FmtSubscriber::builder()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)
+        .expect("setting default subscriber failed");
+
+    dotenv().ok();
+
+    let args = parse_args()?;
+
+    let billing_url = std::env::var("BILLING_SERVICE_URL")
+        .unwrap_or_else(|_| "http://localhost:3004".to_string());
+    let bank_webhook_secret = std::env::var("BANK_WEBHOOK_SECRET")
+        .unwrap_or_else(|_| {
+            warn!("BANK_WEBHOOK_SECRET not set, using default (not recommended for production)");
+            "your-bank-webhook-secret".to_string()
+        });
+    let signer = HmacWebhookSigner::new(&bank_webhook_secret);
+    let client = reqwest::Client::new();
+
+    // Банк знает сумму выставленного счета - берем ее из самого платежа
+    let payment: PaymentInfo = client
+        .get(format!("{}/payments/{}", billing_url, args.payment_id))
+        .send()
+        .await?
+        .error_for_status()
+        .map_err(|e| {
+            error!("Failed to load payment {}: {}", args.payment_id, e);
+            e
+        })?
+        .json()
+        .await?;
+    info!(
+        "Payment {} is {} for {}",
+        args.payment_id, payment.status, payment.amount
+    );
+
+    let callback = PaymentCallback {
+        payment_id: args.payment_id,
+        bank_reference: args.bank_reference,
+        status: args.status,
+        amount: payment.amount,
+        paid_at: (args.status == PaymentStatus::Paid).then(Utc::now),
+    };
+    let body = serde_json::to_vec(&callback)?;
+    let signature = signer.sign(&body);
+
+    for attempt in 1..=args.repeat {
+        let response = client
+            .post(format!("{}/payments/webhook", billing_url))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(BANK_SIGNATURE_HEADER, &signature)
+            .body(body.clone())
+            .send()
+            .await?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        info!(
+            "Callback {}/{} ({}, {}): {} {}",
+            attempt, args.repeat, callback.status.as_str(), callback.bank_reference, status, text
+        );
+    }
+
+    Ok(())
+}
@@ -6,7 +6,10 @@ pub enum PaymentError {
     PaymentNotFound,
     
     #[error("invalid payment amount: {amount}")]
-    InvalidAmount { amount: f64 },
+    InvalidAmount { amount: crate::domain::models::Money },
+    
+    #[error("payment QR code not found")]
+    QrCodeNotFound,
     
     #[error("payment already processed")]
     PaymentAlreadyProcessed,
@@ -14,6 +17,57 @@ pub enum PaymentError {
     #[error("invalid payment status transition: from {from} to {to}")]
     InvalidStatusTransition { from: String, to: String },
     
+    #[error("invalid webhook signature")]
+    InvalidSignature,
+    
+    #[error("invalid bank callback: {0}")]
+    InvalidCallback(String),
+    
+    #[error("amount mismatch: expected {expected}, got {actual}")]
+    AmountMismatch { expected: crate::domain::models::Money, actual: crate::domain::models::Money },
+    
+    #[error("bank reference {0} is already used by another payment")]
+    BankReferenceInUse(String),
+    
+    #[error("payment in status {status} cannot be refunded")]
+    PaymentNotRefundable { status: String },
+    
+    #[error("wallet top-up cannot be refunded")]
+    TopUpNotRefundable,
+    
+    #[error("refund of {requested} exceeds refundable amount {available}")]
+    RefundExceedsPaidAmount { requested: crate::domain::models::Money, available: crate::domain::models::Money },
+    
+    #[error("refund reason is required")]
+    RefundReasonRequired,
+    
+    #[error("debt override not found")]
+    DebtOverrideNotFound,
+    
+    #[error("invalid debt override: {0}")]
+    InvalidDebtOverride(String),
+    
+    #[error("promo code not found")]
+    PromoCodeNotFound,
+    
+    #[error("promo code {0} already exists")]
+    PromoCodeAlreadyExists(String),
+    
+    #[error("invalid promo code: {0}")]
+    InvalidPromoCode(String),
+    
+    #[error("promo code cannot be applied: {0}")]
+    PromoCodeNotApplicable(String),
+    
+    #[error("no promo code attached")]
+    PromoCodeNotAttached,
+    
+    #[error("invalid report period: start must be before end")]
+    InvalidReportPeriod,
+    
+    #[error("event publish error: {0}")]
+    EventPublish(String),
+    
     #[error("database error: {0}")]
     Database(#[from] sqlx::Error),
     
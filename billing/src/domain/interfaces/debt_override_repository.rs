@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::domain::{
+    errors::PaymentError,
+    models::DebtOverride,
+};
+
+#[async_trait]
+pub trait DebtOverrideRepository {
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<DebtOverride>, PaymentError>;
+    // Новое разрешение заменяет предыдущее для того же пользователя
+    async fn upsert(&self, debt_override: &DebtOverride) -> Result<(), PaymentError>;
+    // Возвращает false, если разрешения не было
+    async fn delete(&self, user_id: Uuid) -> Result<bool, PaymentError>;
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use crate::domain::{
+    errors::PaymentError,
+    models::OutboxEvent,
+};
+
+#[async_trait]
+pub trait EventPublisher {
+    async fn publish(&self, event: &OutboxEvent) -> Result<(), PaymentError>;
+}
@@ -1,6 +1,16 @@
 mod payment_repository;
+mod debt_override_repository;
+mod promo_code_repository;
 mod qr_code_generator;
+mod webhook_signer;
+mod outbox_repository;
+mod event_publisher;
 
 pub use payment_repository::*;
+pub use debt_override_repository::*;
+pub use promo_code_repository::*;
 pub use qr_code_generator::*;
+pub use webhook_signer::*;
+pub use outbox_repository::*;
+pub use event_publisher::*;
 
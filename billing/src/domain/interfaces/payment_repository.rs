@@ -1,16 +1,41 @@
 use async_trait::async_trait;
 use uuid::Uuid;
+use chrono::{DateTime, Utc};
 use crate::domain::{
     errors::PaymentError,
-    models::Payment,
+    models::{Money, Payment, PaymentQrCode, PaymentStatus, Refund, WalletTransaction},
 };
 
 #[async_trait]
 pub trait PaymentRepository {
     async fn create(&self, payment: &Payment) -> Result<(), PaymentError>;
+    // Платеж и его QR-код сохраняются в одной транзакции
+    async fn create_with_qr_code(&self, payment: &Payment, qr_code: &PaymentQrCode) -> Result<(), PaymentError>;
+    async fn find_qr_code(&self, payment_id: Uuid) -> Result<Option<PaymentQrCode>, PaymentError>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Payment>, PaymentError>;
     async fn find_by_trip_id(&self, trip_id: Uuid) -> Result<Option<Payment>, PaymentError>;
     async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Payment>, PaymentError>;
+    async fn find_by_bank_reference(&self, bank_reference: &str) -> Result<Option<Payment>, PaymentError>;
     async fn update(&self, id: Uuid, payment: &Payment) -> Result<(), PaymentError>;
+    // Переводит платеж из pending в итоговый статус; возвращает false, если статус уже изменен.
+    // Зачисление на кошелек (для пополнения) проводится в той же транзакции
+    async fn complete_pending(
+        &self,
+        id: Uuid,
+        status: PaymentStatus,
+        bank_reference: &str,
+        paid_at: Option<DateTime<Utc>>,
+        wallet_credit: Option<&WalletTransaction>,
+    ) -> Result<bool, PaymentError>;
+    async fn find_refunds_by_payment_id(&self, payment_id: Uuid) -> Result<Vec<Refund>, PaymentError>;
+    // Сохраняет возврат и пересчитывает статус платежа; возвращает false,
+    // если вместе с уже сделанными возвратами сумма превысила бы оплаченную.
+    // Возврат по оплаченной с кошелька поездке зачисляется на кошелек в той же транзакции
+    async fn create_refund(&self, refund: &Refund, wallet_credit: Option<&WalletTransaction>) -> Result<bool, PaymentError>;
+    // Сохраняет оплаченный с кошелька платеж вместе со списанием; возвращает false,
+    // если на кошельке недостаточно средств
+    async fn create_paid_from_wallet(&self, payment: &Payment, debit: &WalletTransaction) -> Result<bool, PaymentError>;
+    // Валюта берется из проводок кошелька; None, если движений по нему еще не было
+    async fn wallet_balance(&self, user_id: Uuid) -> Result<Option<Money>, PaymentError>;
+    async fn find_wallet_transactions_by_user_id(&self, user_id: Uuid) -> Result<Vec<WalletTransaction>, PaymentError>;
 }
-
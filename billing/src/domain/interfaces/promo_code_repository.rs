@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::domain::{
+    errors::PaymentError,
+    models::{AttachedPromoCode, CampaignReport, PromoCode},
+};
+
+#[async_trait]
+pub trait PromoCodeRepository {
+    async fn create(&self, promo_code: &PromoCode) -> Result<(), PaymentError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PromoCode>, PaymentError>;
+    async fn find_by_code(&self, code: &str) -> Result<Option<PromoCode>, PaymentError>;
+    async fn find_all(&self) -> Result<Vec<PromoCode>, PaymentError>;
+    // Возвращает false, если промокода нет
+    async fn deactivate(&self, id: Uuid) -> Result<bool, PaymentError>;
+    // Сколько поездок пользователь оплатил со скидкой по коду (без неуспешных и отмененных платежей)
+    async fn count_redemptions(&self, promo_code_id: Uuid, user_id: Uuid) -> Result<u32, PaymentError>;
+    // Новый код заменяет ранее указанный пользователем
+    async fn attach(&self, user_id: Uuid, promo_code_id: Uuid, attached_at: DateTime<Utc>) -> Result<(), PaymentError>;
+    async fn find_attached(&self, user_id: Uuid) -> Result<Option<AttachedPromoCode>, PaymentError>;
+    // Возвращает false, если у пользователя не было указанного кода
+    async fn detach(&self, user_id: Uuid) -> Result<bool, PaymentError>;
+    // Отвязывает код, только если пользователь не успел указать другой
+    async fn detach_code(&self, user_id: Uuid, promo_code_id: Uuid) -> Result<bool, PaymentError>;
+    async fn campaign_report(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CampaignReport>, PaymentError>;
+}
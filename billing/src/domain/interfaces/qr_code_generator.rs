@@ -1,8 +1,8 @@
 use async_trait::async_trait;
 use uuid::Uuid;
+use crate::domain::models::{Money, PaymentQrCode};
 
 #[async_trait]
 pub trait QRCodeGenerator {
-    async fn generate_qr_code(&self, payment_id: Uuid, amount: f64) -> Result<String, anyhow::Error>;
+    async fn generate_qr_code(&self, payment_id: Uuid, amount: Money) -> Result<PaymentQrCode, anyhow::Error>;
 }
-
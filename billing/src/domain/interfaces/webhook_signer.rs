@@ -0,0 +1,5 @@
+// Секрет подписи общий с банком
+pub trait WebhookSigner {
+    fn sign(&self, payload: &[u8]) -> String;
+    fn verify(&self, payload: &[u8], signature: &str) -> bool;
+}
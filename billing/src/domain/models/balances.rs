@@ -0,0 +1,65 @@
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::domain::models::Money;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UserBalance {
+    pub user_id: Uuid,
+    pub outstanding: Money,
+    pub unpaid_payments: usize,
+    pub debt_override: Option<DebtOverride>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebtOverride {
+    pub user_id: Uuid,
+    pub reason: String,
+    pub granted_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl DebtOverride {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct SetDebtOverrideRequest {
+    pub reason: String,
+    pub granted_by: Uuid,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn debt_override(expires_at: Option<DateTime<Utc>>) -> DebtOverride {
+        DebtOverride {
+            user_id: Uuid::new_v4(),
+            reason: "Disputed trip".to_string(),
+            granted_by: Uuid::new_v4(),
+            created_at: Utc::now(),
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn test_override_without_expiry_is_active() {
+        assert!(debt_override(None).is_active(Utc::now()));
+    }
+
+    #[test]
+    fn test_override_is_active_until_expiry() {
+        let now = Utc::now();
+        let expiring = debt_override(Some(now + Duration::minutes(1)));
+        assert!(expiring.is_active(now));
+        // Момент истечения уже не покрывается разрешением
+        assert!(!expiring.is_active(now + Duration::minutes(1)));
+        assert!(!expiring.is_active(now + Duration::hours(1)));
+    }
+}
@@ -1,4 +1,17 @@
 mod payments;
+mod money;
+mod qr_codes;
+mod balances;
+mod wallets;
+mod promo_codes;
+mod outbox;
+mod payment_events;
 
 pub use payments::*;
-
+pub use money::*;
+pub use qr_codes::*;
+pub use balances::*;
+pub use wallets::*;
+pub use promo_codes::*;
+pub use outbox::*;
+pub use payment_events::*;
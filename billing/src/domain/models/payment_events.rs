@@ -0,0 +1,54 @@
+use serde::Serialize;
+use crate::domain::models::{OutboxEvent, Payment, PaymentStatus, Refund};
+
+pub const PAYMENT_AGGREGATE: &str = "payment";
+
+// Полный снимок платежа и прежний статус
+#[derive(Debug, Serialize)]
+pub struct PaymentStatusChanged<'a> {
+    #[serde(flatten)]
+    pub payment: &'a Payment,
+    pub previous_status: Option<PaymentStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RefundCreated<'a> {
+    #[serde(flatten)]
+    pub refund: &'a Refund,
+    pub payment_status: PaymentStatus,
+}
+
+// Платеж из кошелька создается сразу оплаченным - PaymentPaid
+pub fn payment_event_type(previous: Option<PaymentStatus>, current: PaymentStatus) -> &'static str {
+    match (previous, current) {
+        (None, PaymentStatus::Pending) => "PaymentCreated",
+        (_, PaymentStatus::Paid) => "PaymentPaid",
+        (_, PaymentStatus::Failed) => "PaymentFailed",
+        (_, PaymentStatus::Cancelled) => "PaymentCancelled",
+        (_, PaymentStatus::PartiallyRefunded | PaymentStatus::Refunded) => "PaymentRefunded",
+        _ => "PaymentStatusChanged",
+    }
+}
+
+impl Payment {
+    pub fn status_event(&self, previous: Option<PaymentStatus>) -> Result<OutboxEvent, serde_json::Error> {
+        OutboxEvent::new(
+            PAYMENT_AGGREGATE,
+            self.id,
+            payment_event_type(previous, self.status),
+            &PaymentStatusChanged { payment: self, previous_status: previous },
+        )
+    }
+}
+
+impl Refund {
+    // События возврата упорядочены вместе с событиями платежа
+    pub fn created_event(&self, payment_status: PaymentStatus) -> Result<OutboxEvent, serde_json::Error> {
+        OutboxEvent::new(
+            PAYMENT_AGGREGATE,
+            self.payment_id,
+            "RefundCreated",
+            &RefundCreated { refund: self, payment_status },
+        )
+    }
+}
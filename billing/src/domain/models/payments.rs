@@ -1,13 +1,19 @@
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::domain::models::{AppliedDiscount, Money};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Payment {
     pub id: Uuid,
-    pub trip_id: Uuid,
+    // Есть только у оплаты поездки
+    pub trip_id: Option<Uuid>,
     pub user_id: Uuid,
-    pub amount: f64,
+    pub kind: PaymentKind,
+    pub method: PaymentMethod,
+    // Сумма к оплате с учетом скидки
+    pub amount: Money,
+    pub discount: Option<AppliedDiscount>,
     pub status: PaymentStatus,
     pub bank_reference: Option<String>,
     pub qr_code_url: Option<String>,
@@ -16,12 +22,73 @@ pub struct Payment {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentKind {
+    Trip,           // Оплата поездки или штрафа по ней
+    WalletTopUp,    // Пополнение кошелька
+}
+
+impl PaymentKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentKind::Trip => "trip",
+            PaymentKind::WalletTopUp => "wallet_top_up",
+        }
+    }
+}
+
+impl std::str::FromStr for PaymentKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "trip" => Ok(PaymentKind::Trip),
+            "wallet_top_up" => Ok(PaymentKind::WalletTopUp),
+            _ => Err(format!("Invalid payment kind: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentMethod {
+    Sbp,    // QR-код СБП, результат приходит от банка
+    Wallet, // Списание с кошелька пользователя
+    Promo,  // Поездка полностью оплачена скидкой по промокоду
+}
+
+impl PaymentMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentMethod::Sbp => "sbp",
+            PaymentMethod::Wallet => "wallet",
+            PaymentMethod::Promo => "promo",
+        }
+    }
+}
+
+impl std::str::FromStr for PaymentMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sbp" => Ok(PaymentMethod::Sbp),
+            "wallet" => Ok(PaymentMethod::Wallet),
+            "promo" => Ok(PaymentMethod::Promo),
+            _ => Err(format!("Invalid payment method: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PaymentStatus {
-    Pending,    // Ожидает оплаты
-    Paid,       // Оплачено
-    Failed,     // Ошибка оплаты
-    Cancelled,  // Отменено
+    Pending,            // Ожидает оплаты
+    Paid,               // Оплачено
+    Failed,             // Ошибка оплаты
+    Cancelled,          // Отменено
+    PartiallyRefunded,  // Часть суммы возвращена
+    Refunded,           // Сумма возвращена полностью
 }
 
 impl PaymentStatus {
@@ -31,8 +98,23 @@ impl PaymentStatus {
             PaymentStatus::Paid => "paid",
             PaymentStatus::Failed => "failed",
             PaymentStatus::Cancelled => "cancelled",
+            PaymentStatus::PartiallyRefunded => "partially_refunded",
+            PaymentStatus::Refunded => "refunded",
         }
     }
+
+    // Банк может только завершить ожидающий платеж: Pending -> Paid/Failed
+    pub fn can_transition_to(&self, next: PaymentStatus) -> bool {
+        matches!(
+            (self, next),
+            (PaymentStatus::Pending, PaymentStatus::Paid) | (PaymentStatus::Pending, PaymentStatus::Failed)
+        )
+    }
+
+    // Возврат возможен только по оплаченному платежу, пока сумма не возвращена полностью
+    pub fn is_refundable(&self) -> bool {
+        matches!(self, PaymentStatus::Paid | PaymentStatus::PartiallyRefunded)
+    }
 }
 
 impl std::str::FromStr for PaymentStatus {
@@ -44,6 +126,8 @@ impl std::str::FromStr for PaymentStatus {
             "paid" => Ok(PaymentStatus::Paid),
             "failed" => Ok(PaymentStatus::Failed),
             "cancelled" => Ok(PaymentStatus::Cancelled),
+            "partially_refunded" => Ok(PaymentStatus::PartiallyRefunded),
+            "refunded" => Ok(PaymentStatus::Refunded),
             _ => Err(format!("Invalid payment status: {}", s)),
         }
     }
@@ -53,12 +137,38 @@ impl std::str::FromStr for PaymentStatus {
 pub struct CreatePaymentRequest {
     pub trip_id: Uuid,
     pub user_id: Uuid,
-    pub amount: f64,
+    pub amount: Money,
+    // Тариф поездки; промокод пользователя применяется только к оплате поездки по тарифу
+    pub tariff_id: Option<Uuid>,
 }
 
 #[derive(Deserialize)]
-pub struct UpdatePaymentRequest {
-    pub status: Option<String>,
-    pub bank_reference: Option<String>,
+pub struct CreateTopUpRequest {
+    pub user_id: Uuid,
+    pub amount: Money,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Refund {
+    pub id: Uuid,
+    pub payment_id: Uuid,
+    pub amount: Money,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateRefundRequest {
+    pub amount: Money,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentCallback {
+    pub payment_id: Uuid,
+    pub bank_reference: String,
+    pub status: PaymentStatus,
+    pub amount: Money,
+    pub paid_at: Option<DateTime<Utc>>,
 }
 
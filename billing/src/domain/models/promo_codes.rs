@@ -0,0 +1,178 @@
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::domain::models::Money;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Discount {
+    Percentage { percent: u8 },  // Процент от суммы поездки, 1..=100
+    Fixed { amount: Money },     // Фиксированная сумма
+}
+
+impl Discount {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Discount::Percentage { .. } => "percentage",
+            Discount::Fixed { .. } => "fixed",
+        }
+    }
+
+    // Процент округляется вниз до копейки, фиксированная скидка не превышает сумму
+    pub fn amount_for(&self, amount: Money) -> Money {
+        let minor_units = match self {
+            Discount::Percentage { percent } => amount.minor_units * i64::from(*percent) / 100,
+            Discount::Fixed { amount: discount } => discount.minor_units.min(amount.minor_units),
+        };
+        Money::new(minor_units.max(0), amount.currency)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromoCode {
+    pub id: Uuid,
+    pub code: String,
+    pub campaign: String,
+    pub discount: Discount,
+    // Сколько поездок один пользователь может оплатить со скидкой по коду
+    pub per_user_limit: u32,
+    // Пустой список - код действует на всех тарифах
+    pub tariff_ids: Vec<Uuid>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PromoCode {
+    pub fn normalize_code(code: &str) -> String {
+        code.trim().to_uppercase()
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    pub fn applies_to_tariff(&self, tariff_id: Uuid) -> bool {
+        self.tariff_ids.is_empty() || self.tariff_ids.contains(&tariff_id)
+    }
+
+    pub fn unavailable_reason(&self, redemptions: u32, now: DateTime<Utc>) -> Option<&'static str> {
+        if !self.active {
+            Some("promo code is no longer active")
+        } else if self.is_expired(now) {
+            Some("promo code has expired")
+        } else if redemptions >= self.per_user_limit {
+            Some("promo code usage limit reached")
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CreatePromoCodeRequest {
+    pub code: String,
+    pub campaign: String,
+    pub discount: Discount,
+    pub per_user_limit: u32,
+    pub tariff_ids: Vec<Uuid>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AttachedPromoCode {
+    pub user_id: Uuid,
+    pub promo_code: PromoCode,
+    // Сколько поездок пользователь уже оплатил со скидкой по этому коду
+    pub redemptions: u32,
+    pub attached_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppliedDiscount {
+    pub promo_code_id: Uuid,
+    pub code: String,
+    pub amount: Money,
+}
+
+// Учитываются платежи, кроме неуспешных и отмененных
+#[derive(Debug, Clone, Serialize)]
+pub struct CampaignReport {
+    pub campaign: String,
+    pub promo_codes: u32,
+    pub redemptions: u32,
+    pub discount_total: Money,
+    pub charged_total: Money,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::Currency;
+    use chrono::Duration;
+
+    fn rub(minor_units: i64) -> Money {
+        Money::new(minor_units, Currency::Rub)
+    }
+
+    fn promo_code(per_user_limit: u32) -> PromoCode {
+        PromoCode {
+            id: Uuid::new_v4(),
+            code: "SPRING".to_string(),
+            campaign: "Spring".to_string(),
+            discount: Discount::Percentage { percent: 10 },
+            per_user_limit,
+            tariff_ids: vec![],
+            expires_at: None,
+            active: true,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_percentage_is_rounded_down_to_minor_unit() {
+        let discount = Discount::Percentage { percent: 15 };
+        // 15% от 99.99 = 14.9985 -> 14.99
+        assert_eq!(discount.amount_for(rub(9999)), rub(1499));
+        assert_eq!(discount.amount_for(rub(6)), rub(0));
+    }
+
+    #[test]
+    fn test_full_percentage_covers_whole_amount() {
+        assert_eq!(Discount::Percentage { percent: 100 }.amount_for(rub(12345)), rub(12345));
+    }
+
+    #[test]
+    fn test_fixed_discount_is_capped_by_amount() {
+        let discount = Discount::Fixed { amount: rub(50000) };
+        assert_eq!(discount.amount_for(rub(80000)), rub(50000));
+        assert_eq!(discount.amount_for(rub(30000)), rub(30000));
+    }
+
+    #[test]
+    fn test_per_user_limit() {
+        let promo_code = promo_code(2);
+        let now = Utc::now();
+        assert_eq!(promo_code.unavailable_reason(0, now), None);
+        assert_eq!(promo_code.unavailable_reason(1, now), None);
+        assert_eq!(promo_code.unavailable_reason(2, now), Some("promo code usage limit reached"));
+    }
+
+    #[test]
+    fn test_inactive_and_expired_codes_are_unavailable() {
+        let now = Utc::now();
+        let mut inactive = promo_code(5);
+        inactive.active = false;
+        assert_eq!(inactive.unavailable_reason(0, now), Some("promo code is no longer active"));
+
+        let mut expired = promo_code(5);
+        expired.expires_at = Some(now);
+        assert_eq!(expired.unavailable_reason(0, now), Some("promo code has expired"));
+        assert_eq!(expired.unavailable_reason(0, now - Duration::seconds(1)), None);
+    }
+
+    #[test]
+    fn test_codes_are_normalized() {
+        assert_eq!(PromoCode::normalize_code("  spring2024 "), "SPRING2024");
+    }
+}
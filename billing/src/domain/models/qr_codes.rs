@@ -0,0 +1,12 @@
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+// QR-код СБП отрисовывается локально
+#[derive(Debug, Clone)]
+pub struct PaymentQrCode {
+    pub payment_id: Uuid,
+    pub payload: String,    // Ссылка в формате НСПК, закодированная в QR
+    pub png: Vec<u8>,
+    pub svg: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,212 @@
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::domain::models::{Money, Payment};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletTransactionKind {
+    TopUp,          // Пополнение через СБП
+    TripPayment,    // Оплата поездки с кошелька
+    Refund,         // Возврат по оплаченной с кошелька поездке
+}
+
+impl WalletTransactionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WalletTransactionKind::TopUp => "top_up",
+            WalletTransactionKind::TripPayment => "trip_payment",
+            WalletTransactionKind::Refund => "refund",
+        }
+    }
+}
+
+impl std::str::FromStr for WalletTransactionKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "top_up" => Ok(WalletTransactionKind::TopUp),
+            "trip_payment" => Ok(WalletTransactionKind::TripPayment),
+            "refund" => Ok(WalletTransactionKind::Refund),
+            _ => Err(format!("Invalid wallet transaction kind: {}", s)),
+        }
+    }
+}
+
+// Счет главной книги: каждая операция переносит сумму с одного счета на другой
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedgerAccount {
+    Wallet(Uuid),   // Кошелек пользователя
+    BankClearing,   // Поступления от банка по СБП
+    TripRevenue,    // Выручка за поездки
+}
+
+impl LedgerAccount {
+    pub fn code(&self) -> String {
+        match self {
+            LedgerAccount::Wallet(user_id) => format!("wallet:{}", user_id),
+            LedgerAccount::BankClearing => "bank_clearing".to_string(),
+            LedgerAccount::TripRevenue => "trip_revenue".to_string(),
+        }
+    }
+}
+
+// Проводка: положительная сумма увеличивает остаток счета, отрицательная - уменьшает
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LedgerEntry {
+    pub account: LedgerAccount,
+    pub amount: Money,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletTransaction {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: WalletTransactionKind,
+    // Всегда положительна; направление определяется типом
+    pub amount: Money,
+    pub payment_id: Uuid,
+    pub refund_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WalletTransaction {
+    pub fn top_up(payment: &Payment) -> Self {
+        Self::new(payment, WalletTransactionKind::TopUp, payment.amount, None)
+    }
+
+    pub fn trip_payment(payment: &Payment) -> Self {
+        Self::new(payment, WalletTransactionKind::TripPayment, payment.amount, None)
+    }
+
+    pub fn refund(payment: &Payment, refund_id: Uuid, amount: Money) -> Self {
+        Self::new(payment, WalletTransactionKind::Refund, amount, Some(refund_id))
+    }
+
+    fn new(payment: &Payment, kind: WalletTransactionKind, amount: Money, refund_id: Option<Uuid>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id: payment.user_id,
+            kind,
+            amount,
+            payment_id: payment.id,
+            refund_id,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn wallet_delta(&self) -> Money {
+        match self.kind {
+            WalletTransactionKind::TopUp | WalletTransactionKind::Refund => self.amount,
+            WalletTransactionKind::TripPayment => Money::new(-self.amount.minor_units, self.amount.currency),
+        }
+    }
+
+    // Пара проводок операции; их сумма всегда равна нулю
+    pub fn entries(&self) -> [LedgerEntry; 2] {
+        let wallet = LedgerAccount::Wallet(self.user_id);
+        let counterparty = match self.kind {
+            WalletTransactionKind::TopUp => LedgerAccount::BankClearing,
+            WalletTransactionKind::TripPayment | WalletTransactionKind::Refund => LedgerAccount::TripRevenue,
+        };
+        let delta = self.wallet_delta();
+        [
+            LedgerEntry { account: wallet, amount: delta },
+            LedgerEntry {
+                account: counterparty,
+                amount: Money::new(-delta.minor_units, delta.currency),
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Wallet {
+    pub user_id: Uuid,
+    pub balance: Money,
+}
+
+#[derive(Debug, Clone)]
+pub enum PaymentHistoryEntry {
+    Payment(Payment),
+    WalletTransaction(WalletTransaction),
+}
+
+impl PaymentHistoryEntry {
+    pub fn created_at(&self) -> DateTime<Utc> {
+        match self {
+            PaymentHistoryEntry::Payment(payment) => payment.created_at,
+            PaymentHistoryEntry::WalletTransaction(transaction) => transaction.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{Currency, PaymentKind, PaymentMethod, PaymentStatus};
+
+    fn rub(minor_units: i64) -> Money {
+        Money::new(minor_units, Currency::Rub)
+    }
+
+    fn payment(kind: PaymentKind, amount: i64) -> Payment {
+        Payment {
+            id: Uuid::new_v4(),
+            trip_id: (kind == PaymentKind::Trip).then(Uuid::new_v4),
+            user_id: Uuid::new_v4(),
+            kind,
+            method: PaymentMethod::Wallet,
+            amount: rub(amount),
+            discount: None,
+            status: PaymentStatus::Paid,
+            bank_reference: None,
+            qr_code_url: None,
+            created_at: Utc::now(),
+            paid_at: Some(Utc::now()),
+        }
+    }
+
+    fn assert_entries(transaction: &WalletTransaction, counterparty: LedgerAccount, wallet_delta: i64) {
+        let [wallet, other] = transaction.entries();
+
+        assert_eq!(transaction.wallet_delta(), rub(wallet_delta));
+        assert_eq!(wallet, LedgerEntry { account: LedgerAccount::Wallet(transaction.user_id), amount: rub(wallet_delta) });
+        assert_eq!(other, LedgerEntry { account: counterparty, amount: rub(-wallet_delta) });
+        assert_eq!(wallet.amount.minor_units + other.amount.minor_units, 0);
+    }
+
+    #[test]
+    fn test_top_up_moves_money_from_bank_clearing_to_wallet() {
+        let top_up = payment(PaymentKind::WalletTopUp, 50_000);
+        let transaction = WalletTransaction::top_up(&top_up);
+
+        assert_eq!(transaction.kind, WalletTransactionKind::TopUp);
+        assert_eq!(transaction.amount, rub(50_000));
+        assert_entries(&transaction, LedgerAccount::BankClearing, 50_000);
+    }
+
+    #[test]
+    fn test_trip_payment_moves_money_from_wallet_to_revenue() {
+        let trip = payment(PaymentKind::Trip, 15_050);
+        let transaction = WalletTransaction::trip_payment(&trip);
+
+        assert_eq!(transaction.kind, WalletTransactionKind::TripPayment);
+        // Сумма хранится положительной, знак дает тип операции
+        assert_eq!(transaction.amount, rub(15_050));
+        assert_entries(&transaction, LedgerAccount::TripRevenue, -15_050);
+    }
+
+    #[test]
+    fn test_refund_moves_money_from_revenue_back_to_wallet() {
+        let trip = payment(PaymentKind::Trip, 15_050);
+        let refund_id = Uuid::new_v4();
+        let transaction = WalletTransaction::refund(&trip, refund_id, rub(5_000));
+
+        assert_eq!(transaction.kind, WalletTransactionKind::Refund);
+        assert_eq!(transaction.refund_id, Some(refund_id));
+        assert_eq!(transaction.payment_id, trip.id);
+        assert_entries(&transaction, LedgerAccount::TripRevenue, 5_000);
+    }
+}
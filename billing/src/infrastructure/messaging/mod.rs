@@ -0,0 +1,3 @@
+mod rabbitmq_event_publisher;
+
+pub use rabbitmq_event_publisher::*;
@@ -1,6 +1,8 @@
 pub mod repositories;
 pub mod services;
+pub mod messaging;
 
 pub use repositories::*;
 pub use services::*;
+pub use messaging::*;
 
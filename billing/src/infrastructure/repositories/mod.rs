@@ -1,4 +1,10 @@
 mod postgres_payment_repository;
+mod postgres_debt_override_repository;
+mod postgres_promo_code_repository;
+mod postgres_outbox_repository;
 
 pub use postgres_payment_repository::*;
+pub use postgres_debt_override_repository::*;
+pub use postgres_promo_code_repository::*;
+pub use postgres_outbox_repository::*;
 
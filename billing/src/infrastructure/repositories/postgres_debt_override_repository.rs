@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::DebtOverrideRepository,
+    models::DebtOverride,
+};
+
+pub struct PostgresDebtOverrideRepository {
+    pool: PgPool,
+}
+
+impl PostgresDebtOverrideRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl Clone for PostgresDebtOverrideRepository {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl DebtOverrideRepository for PostgresDebtOverrideRepository {
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<DebtOverride>, PaymentError> {
+        let row = sqlx::query(
+            r#"
+            SELECT user_id, reason, granted_by, created_at, expires_at
+            FROM debt_overrides
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| DebtOverride {
+            user_id: r.get("user_id"),
+            reason: r.get("reason"),
+            granted_by: r.get("granted_by"),
+            created_at: r.get("created_at"),
+            expires_at: r.get("expires_at"),
+        }))
+    }
+
+    async fn upsert(&self, debt_override: &DebtOverride) -> Result<(), PaymentError> {
+        sqlx::query(
+            r#"
+            INSERT INTO debt_overrides (user_id, reason, granted_by, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (user_id) DO UPDATE
+            SET reason = EXCLUDED.reason,
+                granted_by = EXCLUDED.granted_by,
+                created_at = EXCLUDED.created_at,
+                expires_at = EXCLUDED.expires_at
+            "#,
+        )
+        .bind(debt_override.user_id)
+        .bind(&debt_override.reason)
+        .bind(debt_override.granted_by)
+        .bind(debt_override.created_at)
+        .bind(debt_override.expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: Uuid) -> Result<bool, PaymentError> {
+        let result = sqlx::query("DELETE FROM debt_overrides WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1,10 +1,12 @@
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{postgres::PgRow, PgConnection, PgPool, Row};
+use super::enqueue_event;
 use uuid::Uuid;
+use chrono::{DateTime, Utc};
 use crate::domain::{
     errors::PaymentError,
     interfaces::PaymentRepository,
-    models::{Payment, PaymentStatus},
+    models::{AppliedDiscount, Currency, LedgerAccount, Money, Payment, PaymentQrCode, PaymentStatus, Refund, WalletTransaction},
 };
 
 pub struct PostgresPaymentRepository {
@@ -25,44 +27,116 @@ impl Clone for PostgresPaymentRepository {
     }
 }
 
-fn payment_status_from_str(s: &str) -> PaymentStatus {
-    match s {
-        "pending" => PaymentStatus::Pending,
-        "paid" => PaymentStatus::Paid,
-        "failed" => PaymentStatus::Failed,
-        "cancelled" => PaymentStatus::Cancelled,
-        _ => PaymentStatus::Pending,
+// Неизвестное значение в базе - ошибка, а не значение по умолчанию: иначе оно попадет в расчеты
+fn parse_stored<T>(value: &str) -> Result<T, PaymentError>
+where
+    T: std::str::FromStr<Err = String>,
+{
+    value.parse().map_err(|e: String| PaymentError::Internal(anyhow::anyhow!(e)))
+}
+
+fn parse_column<T>(r: &PgRow, column: &str) -> Result<T, PaymentError>
+where
+    T: std::str::FromStr<Err = String>,
+{
+    parse_stored(r.get::<String, _>(column).as_str())
+}
+
+fn payment_from_row(r: &PgRow) -> Result<Payment, PaymentError> {
+    let currency: Currency = parse_column(r, "currency")?;
+    Ok(Payment {
+        id: r.get("id"),
+        trip_id: r.get("trip_id"),
+        user_id: r.get("user_id"),
+        kind: parse_column(r, "kind")?,
+        method: parse_column(r, "method")?,
+        amount: Money::new(r.get("amount_minor"), currency),
+        discount: applied_discount_from_row(r, currency),
+        status: parse_column(r, "status")?,
+        bank_reference: r.get("bank_reference"),
+        qr_code_url: r.get("qr_code_url"),
+        created_at: r.get("created_at"),
+        paid_at: r.get("paid_at"),
+    })
+}
+
+fn applied_discount_from_row(r: &PgRow, currency: Currency) -> Option<AppliedDiscount> {
+    let promo_code_id: Option<Uuid> = r.get("promo_code_id");
+    let code: Option<String> = r.get("promo_code");
+    let discount_minor: Option<i64> = r.get("discount_minor");
+    match (promo_code_id, code, discount_minor) {
+        (Some(promo_code_id), Some(code), Some(discount_minor)) => Some(AppliedDiscount {
+            promo_code_id,
+            code,
+            amount: Money::new(discount_minor, currency),
+        }),
+        _ => None,
     }
 }
 
 #[async_trait]
 impl PaymentRepository for PostgresPaymentRepository {
     async fn create(&self, payment: &Payment) -> Result<(), PaymentError> {
+        let event = payment.status_event(None).map_err(anyhow::Error::from)?;
+        let mut tx = self.pool.begin().await?;
+
+        insert_payment(&mut tx, payment).await?;
+
+        enqueue_event(&mut tx, &event).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn create_with_qr_code(&self, payment: &Payment, qr_code: &PaymentQrCode) -> Result<(), PaymentError> {
+        let event = payment.status_event(None).map_err(anyhow::Error::from)?;
+        let mut tx = self.pool.begin().await?;
+
+        insert_payment(&mut tx, payment).await?;
+
         sqlx::query(
             r#"
-            INSERT INTO payments (id, trip_id, user_id, amount, status, bank_reference, qr_code_url, created_at, paid_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            INSERT INTO payment_qr_codes (payment_id, payload, png, svg, created_at)
+            VALUES ($1, $2, $3, $4, $5)
             "#,
         )
-        .bind(payment.id)
-        .bind(payment.trip_id)
-        .bind(payment.user_id)
-        .bind(payment.amount)
-        .bind(payment.status.as_str())
-        .bind(&payment.bank_reference)
-        .bind(&payment.qr_code_url)
-        .bind(payment.created_at)
-        .bind(payment.paid_at)
-        .execute(&self.pool)
+        .bind(qr_code.payment_id)
+        .bind(&qr_code.payload)
+        .bind(&qr_code.png)
+        .bind(&qr_code.svg)
+        .bind(qr_code.created_at)
+        .execute(&mut *tx)
         .await?;
-        
+
+        enqueue_event(&mut tx, &event).await?;
+        tx.commit().await?;
         Ok(())
     }
 
+    async fn find_qr_code(&self, payment_id: Uuid) -> Result<Option<PaymentQrCode>, PaymentError> {
+        let row = sqlx::query(
+            r#"
+            SELECT payment_id, payload, png, svg, created_at
+            FROM payment_qr_codes
+            WHERE payment_id = $1
+            "#,
+        )
+        .bind(payment_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| PaymentQrCode {
+            payment_id: r.get("payment_id"),
+            payload: r.get("payload"),
+            png: r.get("png"),
+            svg: r.get("svg"),
+            created_at: r.get("created_at"),
+        }))
+    }
+
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Payment>, PaymentError> {
         let row = sqlx::query(
             r#"
-            SELECT id, trip_id, user_id, amount, status, bank_reference, qr_code_url, created_at, paid_at
+            SELECT id, trip_id, user_id, kind, method, amount_minor, currency, promo_code_id, promo_code, discount_minor, status, bank_reference, qr_code_url, created_at, paid_at
             FROM payments
             WHERE id = $1
             "#,
@@ -71,23 +145,13 @@ impl PaymentRepository for PostgresPaymentRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.map(|r| Payment {
-            id: r.get("id"),
-            trip_id: r.get("trip_id"),
-            user_id: r.get("user_id"),
-            amount: r.get("amount"),
-            status: payment_status_from_str(r.get::<String, _>("status").as_str()),
-            bank_reference: r.get("bank_reference"),
-            qr_code_url: r.get("qr_code_url"),
-            created_at: r.get("created_at"),
-            paid_at: r.get("paid_at"),
-        }))
+        row.as_ref().map(payment_from_row).transpose()
     }
 
     async fn find_by_trip_id(&self, trip_id: Uuid) -> Result<Option<Payment>, PaymentError> {
         let row = sqlx::query(
             r#"
-            SELECT id, trip_id, user_id, amount, status, bank_reference, qr_code_url, created_at, paid_at
+            SELECT id, trip_id, user_id, kind, method, amount_minor, currency, promo_code_id, promo_code, discount_minor, status, bank_reference, qr_code_url, created_at, paid_at
             FROM payments
             WHERE trip_id = $1
             LIMIT 1
@@ -97,23 +161,13 @@ impl PaymentRepository for PostgresPaymentRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.map(|r| Payment {
-            id: r.get("id"),
-            trip_id: r.get("trip_id"),
-            user_id: r.get("user_id"),
-            amount: r.get("amount"),
-            status: payment_status_from_str(r.get::<String, _>("status").as_str()),
-            bank_reference: r.get("bank_reference"),
-            qr_code_url: r.get("qr_code_url"),
-            created_at: r.get("created_at"),
-            paid_at: r.get("paid_at"),
-        }))
+        row.as_ref().map(payment_from_row).transpose()
     }
 
     async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Payment>, PaymentError> {
         let rows = sqlx::query(
             r#"
-            SELECT id, trip_id, user_id, amount, status, bank_reference, qr_code_url, created_at, paid_at
+            SELECT id, trip_id, user_id, kind, method, amount_minor, currency, promo_code_id, promo_code, discount_minor, status, bank_reference, qr_code_url, created_at, paid_at
             FROM payments
             WHERE user_id = $1
             ORDER BY created_at DESC
@@ -123,39 +177,373 @@ impl PaymentRepository for PostgresPaymentRepository {
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().map(|r| Payment {
-            id: r.get("id"),
-            trip_id: r.get("trip_id"),
-            user_id: r.get("user_id"),
-            amount: r.get("amount"),
-            status: payment_status_from_str(r.get::<String, _>("status").as_str()),
-            bank_reference: r.get("bank_reference"),
-            qr_code_url: r.get("qr_code_url"),
-            created_at: r.get("created_at"),
-            paid_at: r.get("paid_at"),
-        }).collect())
+        rows.iter().map(payment_from_row).collect()
+    }
+
+    async fn find_by_bank_reference(&self, bank_reference: &str) -> Result<Option<Payment>, PaymentError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, trip_id, user_id, kind, method, amount_minor, currency, promo_code_id, promo_code, discount_minor, status, bank_reference, qr_code_url, created_at, paid_at
+            FROM payments
+            WHERE bank_reference = $1
+            "#,
+        )
+        .bind(bank_reference)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(payment_from_row).transpose()
     }
 
     async fn update(&self, id: Uuid, payment: &Payment) -> Result<(), PaymentError> {
+        let mut tx = self.pool.begin().await?;
+
+        let previous_status: Option<String> = sqlx::query_scalar("SELECT status FROM payments WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
         sqlx::query(
             r#"
             UPDATE payments
-            SET trip_id = $2, user_id = $3, amount = $4, status = $5, bank_reference = $6, qr_code_url = $7, paid_at = $8
+            SET trip_id = $2, user_id = $3, amount_minor = $4, currency = $5, status = $6, bank_reference = $7, qr_code_url = $8, paid_at = $9
             WHERE id = $1
             "#,
         )
         .bind(id)
         .bind(payment.trip_id)
         .bind(payment.user_id)
-        .bind(payment.amount)
+        .bind(payment.amount.minor_units)
+        .bind(payment.amount.currency.as_str())
         .bind(payment.status.as_str())
         .bind(&payment.bank_reference)
         .bind(&payment.qr_code_url)
         .bind(payment.paid_at)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
-        
+
+        // Событие публикуется только при смене статуса
+        if let Some(previous_status) = previous_status.as_deref().map(parse_stored::<PaymentStatus>).transpose()?
+            && previous_status != payment.status
+        {
+            let event = Payment { id, ..payment.clone() }
+                .status_event(Some(previous_status))
+                .map_err(anyhow::Error::from)?;
+            enqueue_event(&mut tx, &event).await?;
+        }
+        tx.commit().await?;
         Ok(())
     }
+
+    async fn complete_pending(
+        &self,
+        id: Uuid,
+        status: PaymentStatus,
+        bank_reference: &str,
+        paid_at: Option<DateTime<Utc>>,
+        wallet_credit: Option<&WalletTransaction>,
+    ) -> Result<bool, PaymentError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Условие на статус защищает от гонки параллельных уведомлений банка
+        let row = sqlx::query(
+            r#"
+            UPDATE payments
+            SET status = $2, bank_reference = $3, paid_at = $4
+            WHERE id = $1 AND status = 'pending'
+            RETURNING id, trip_id, user_id, kind, method, amount_minor, currency, promo_code_id, promo_code, discount_minor, status, bank_reference, qr_code_url, created_at, paid_at
+            "#,
+        )
+        .bind(id)
+        .bind(status.as_str())
+        .bind(bank_reference)
+        .bind(paid_at)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| match e.as_database_error() {
+            Some(db_error) if db_error.is_unique_violation() => {
+                PaymentError::BankReferenceInUse(bank_reference.to_string())
+            }
+            _ => PaymentError::Database(e),
+        })?;
+
+        let Some(row) = row else {
+            return Ok(false);
+        };
+
+        if let Some(credit) = wallet_credit {
+            insert_wallet_transaction(&mut tx, credit).await?;
+        }
+
+        let event = payment_from_row(&row)?
+            .status_event(Some(PaymentStatus::Pending))
+            .map_err(anyhow::Error::from)?;
+        enqueue_event(&mut tx, &event).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn find_refunds_by_payment_id(&self, payment_id: Uuid) -> Result<Vec<Refund>, PaymentError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, payment_id, amount_minor, currency, reason, created_at
+            FROM refunds
+            WHERE payment_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(payment_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| {
+            Ok(Refund {
+                id: r.get("id"),
+                payment_id: r.get("payment_id"),
+                amount: Money::new(r.get("amount_minor"), parse_column(&r, "currency")?),
+                reason: r.get("reason"),
+                created_at: r.get("created_at"),
+            })
+        }).collect()
+    }
+
+    async fn create_refund(&self, refund: &Refund, wallet_credit: Option<&WalletTransaction>) -> Result<bool, PaymentError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Блокировка строки платежа сериализует параллельные возвраты по нему
+        let payment = sqlx::query(
+            r#"
+            SELECT amount_minor, status
+            FROM payments
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(refund.payment_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(PaymentError::PaymentNotFound)?;
+
+        let paid_minor: i64 = payment.get("amount_minor");
+        let status: PaymentStatus = parse_column(&payment, "status")?;
+        if !status.is_refundable() {
+            return Ok(false);
+        }
+
+        let refunded_minor: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(amount_minor), 0)::BIGINT FROM refunds WHERE payment_id = $1",
+        )
+        .bind(refund.payment_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let total_minor = refunded_minor + refund.amount.minor_units;
+        if total_minor > paid_minor {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO refunds (id, payment_id, amount_minor, currency, reason, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(refund.id)
+        .bind(refund.payment_id)
+        .bind(refund.amount.minor_units)
+        .bind(refund.amount.currency.as_str())
+        .bind(&refund.reason)
+        .bind(refund.created_at)
+        .execute(&mut *tx)
+        .await?;
+
+        let new_status = if total_minor == paid_minor {
+            PaymentStatus::Refunded
+        } else {
+            PaymentStatus::PartiallyRefunded
+        };
+        sqlx::query("UPDATE payments SET status = $2 WHERE id = $1")
+            .bind(refund.payment_id)
+            .bind(new_status.as_str())
+            .execute(&mut *tx)
+            .await?;
+
+        if let Some(credit) = wallet_credit {
+            insert_wallet_transaction(&mut tx, credit).await?;
+        }
+
+        let event = refund.created_event(new_status).map_err(anyhow::Error::from)?;
+        enqueue_event(&mut tx, &event).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn create_paid_from_wallet(&self, payment: &Payment, debit: &WalletTransaction) -> Result<bool, PaymentError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Блокировка кошелька сериализует параллельные списания, чтобы остаток не ушел в минус
+        let account = LedgerAccount::Wallet(payment.user_id).code();
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1, 0))")
+            .bind(&account)
+            .execute(&mut *tx)
+            .await?;
+
+        let balance_minor = account_balance_minor(&mut tx, &account, debit.amount.currency).await?;
+        if balance_minor < debit.amount.minor_units {
+            return Ok(false);
+        }
+
+        insert_payment(&mut tx, payment).await?;
+
+        insert_wallet_transaction(&mut tx, debit).await?;
+
+        let event = payment.status_event(None).map_err(anyhow::Error::from)?;
+        enqueue_event(&mut tx, &event).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn wallet_balance(&self, user_id: Uuid) -> Result<Option<Money>, PaymentError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT currency, SUM(amount_minor)::BIGINT AS balance_minor
+            FROM ledger_entries
+            WHERE account = $1
+            GROUP BY currency
+            "#,
+        )
+        .bind(LedgerAccount::Wallet(user_id).code())
+        .fetch_all(&self.pool)
+        .await?;
+
+        match rows.as_slice() {
+            [] => Ok(None),
+            [row] => Ok(Some(Money::new(row.get("balance_minor"), parse_column(row, "currency")?))),
+            _ => Err(PaymentError::Internal(anyhow::anyhow!(
+                "Wallet of user {} has entries in several currencies", user_id
+            ))),
+        }
+    }
+
+    async fn find_wallet_transactions_by_user_id(&self, user_id: Uuid) -> Result<Vec<WalletTransaction>, PaymentError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, user_id, kind, amount_minor, currency, payment_id, refund_id, created_at
+            FROM wallet_transactions
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| {
+            Ok(WalletTransaction {
+                id: r.get("id"),
+                user_id: r.get("user_id"),
+                kind: parse_column(&r, "kind")?,
+                amount: Money::new(r.get("amount_minor"), parse_column(&r, "currency")?),
+                payment_id: r.get("payment_id"),
+                refund_id: r.get("refund_id"),
+                created_at: r.get("created_at"),
+            })
+        }).collect()
+    }
 }
 
+async fn insert_payment(conn: &mut PgConnection, payment: &Payment) -> Result<(), PaymentError> {
+    sqlx::query(
+        r#"
+        INSERT INTO payments (id, trip_id, user_id, kind, method, amount_minor, currency, promo_code_id, promo_code, discount_minor, status, bank_reference, qr_code_url, created_at, paid_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+        "#,
+    )
+    .bind(payment.id)
+    .bind(payment.trip_id)
+    .bind(payment.user_id)
+    .bind(payment.kind.as_str())
+    .bind(payment.method.as_str())
+    .bind(payment.amount.minor_units)
+    .bind(payment.amount.currency.as_str())
+    .bind(payment.discount.as_ref().map(|d| d.promo_code_id))
+    .bind(payment.discount.as_ref().map(|d| d.code.as_str()))
+    .bind(payment.discount.as_ref().map(|d| d.amount.minor_units))
+    .bind(payment.status.as_str())
+    .bind(&payment.bank_reference)
+    .bind(&payment.qr_code_url)
+    .bind(payment.created_at)
+    .bind(payment.paid_at)
+    .execute(conn)
+    .await
+    .map_err(|e| match e.as_database_error() {
+        // Параллельный запрос успел создать платеж за эту поездку
+        Some(db_error) if db_error.constraint() == Some("idx_payments_trip_id_unique") => {
+            PaymentError::PaymentAlreadyProcessed
+        }
+        _ => PaymentError::Database(e),
+    })?;
+    Ok(())
+}
+
+async fn account_balance_minor(conn: &mut PgConnection, account: &str, currency: Currency) -> Result<i64, PaymentError> {
+    let balance: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(SUM(amount_minor), 0)::BIGINT FROM ledger_entries WHERE account = $1 AND currency = $2",
+    )
+    .bind(account)
+    .bind(currency.as_str())
+    .fetch_one(conn)
+    .await?;
+    Ok(balance)
+}
+
+// Вызывается внутри транзакции; в конце база проверяет, что сумма проводок равна нулю
+async fn insert_wallet_transaction(conn: &mut PgConnection, transaction: &WalletTransaction) -> Result<(), PaymentError> {
+    sqlx::query(
+        r#"
+        INSERT INTO wallet_transactions (id, user_id, kind, amount_minor, currency, payment_id, refund_id, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+    )
+    .bind(transaction.id)
+    .bind(transaction.user_id)
+    .bind(transaction.kind.as_str())
+    .bind(transaction.amount.minor_units)
+    .bind(transaction.amount.currency.as_str())
+    .bind(transaction.payment_id)
+    .bind(transaction.refund_id)
+    .bind(transaction.created_at)
+    .execute(&mut *conn)
+    .await?;
+
+    for entry in transaction.entries() {
+        sqlx::query(
+            r#"
+            INSERT INTO ledger_entries (transaction_id, account, amount_minor, currency, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(transaction.id)
+        .bind(entry.account.code())
+        .bind(entry.amount.minor_units)
+        .bind(entry.amount.currency.as_str())
+        .bind(transaction.created_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_stored_values_are_rejected() {
+        assert_eq!(parse_stored::<Currency>("RUB").unwrap(), Currency::Rub);
+        assert!(matches!(parse_stored::<Currency>("XXX"), Err(PaymentError::Internal(_))));
+        assert!(matches!(parse_stored::<Currency>(""), Err(PaymentError::Internal(_))));
+        assert!(matches!(parse_stored::<PaymentStatus>("settled"), Err(PaymentError::Internal(_))));
+    }
+}
@@ -0,0 +1,278 @@
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::domain::{
+    errors::PaymentError,
+    interfaces::PromoCodeRepository,
+    models::{AttachedPromoCode, CampaignReport, Currency, Discount, Money, PromoCode},
+};
+
+pub struct PostgresPromoCodeRepository {
+    pool: PgPool,
+}
+
+impl PostgresPromoCodeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl Clone for PostgresPromoCodeRepository {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+fn currency_from_row(r: &PgRow) -> Result<Currency, PaymentError> {
+    r.get::<String, _>("currency")
+        .parse()
+        .map_err(|e: String| PaymentError::Internal(anyhow::anyhow!(e)))
+}
+
+fn promo_code_from_row(r: &PgRow) -> Result<PromoCode, PaymentError> {
+    let discount = match r.get::<String, _>("discount_type").as_str() {
+        "percentage" => Discount::Percentage {
+            percent: r.get::<i16, _>("discount_percent") as u8,
+        },
+        "fixed" => Discount::Fixed {
+            amount: Money::new(r.get("discount_minor"), currency_from_row(r)?),
+        },
+        other => {
+            return Err(PaymentError::Internal(anyhow::anyhow!("Invalid discount type: {}", other)));
+        }
+    };
+
+    Ok(PromoCode {
+        id: r.get("id"),
+        code: r.get("code"),
+        campaign: r.get("campaign"),
+        discount,
+        per_user_limit: r.get::<i32, _>("per_user_limit") as u32,
+        tariff_ids: r.get("tariff_ids"),
+        expires_at: r.get("expires_at"),
+        active: r.get("active"),
+        created_at: r.get("created_at"),
+    })
+}
+
+#[async_trait]
+impl PromoCodeRepository for PostgresPromoCodeRepository {
+    async fn create(&self, promo_code: &PromoCode) -> Result<(), PaymentError> {
+        let (discount_percent, discount_minor, currency) = match promo_code.discount {
+            Discount::Percentage { percent } => (Some(i16::from(percent)), None, Currency::default()),
+            Discount::Fixed { amount } => (None, Some(amount.minor_units), amount.currency),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO promo_codes (id, code, campaign, discount_type, discount_percent, discount_minor, currency, per_user_limit, tariff_ids, expires_at, active, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            "#,
+        )
+        .bind(promo_code.id)
+        .bind(&promo_code.code)
+        .bind(&promo_code.campaign)
+        .bind(promo_code.discount.as_str())
+        .bind(discount_percent)
+        .bind(discount_minor)
+        .bind(currency.as_str())
+        .bind(promo_code.per_user_limit as i32)
+        .bind(&promo_code.tariff_ids)
+        .bind(promo_code.expires_at)
+        .bind(promo_code.active)
+        .bind(promo_code.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| match e.as_database_error() {
+            Some(db_error) if db_error.is_unique_violation() => {
+                PaymentError::PromoCodeAlreadyExists(promo_code.code.clone())
+            }
+            _ => PaymentError::Database(e),
+        })?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PromoCode>, PaymentError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, code, campaign, discount_type, discount_percent, discount_minor, currency, per_user_limit, tariff_ids, expires_at, active, created_at
+            FROM promo_codes
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(promo_code_from_row).transpose()
+    }
+
+    async fn find_by_code(&self, code: &str) -> Result<Option<PromoCode>, PaymentError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, code, campaign, discount_type, discount_percent, discount_minor, currency, per_user_limit, tariff_ids, expires_at, active, created_at
+            FROM promo_codes
+            WHERE code = $1
+            "#,
+        )
+        .bind(code)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(promo_code_from_row).transpose()
+    }
+
+    async fn find_all(&self) -> Result<Vec<PromoCode>, PaymentError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, code, campaign, discount_type, discount_percent, discount_minor, currency, per_user_limit, tariff_ids, expires_at, active, created_at
+            FROM promo_codes
+            ORDER BY created_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(promo_code_from_row).collect()
+    }
+
+    async fn deactivate(&self, id: Uuid) -> Result<bool, PaymentError> {
+        let result = sqlx::query("UPDATE promo_codes SET active = FALSE WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn count_redemptions(&self, promo_code_id: Uuid, user_id: Uuid) -> Result<u32, PaymentError> {
+        let count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM payments
+            WHERE promo_code_id = $1 AND user_id = $2 AND status NOT IN ('failed', 'cancelled')
+            "#,
+        )
+        .bind(promo_code_id)
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count as u32)
+    }
+
+    async fn attach(&self, user_id: Uuid, promo_code_id: Uuid, attached_at: DateTime<Utc>) -> Result<(), PaymentError> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_promo_codes (user_id, promo_code_id, attached_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id) DO UPDATE
+            SET promo_code_id = EXCLUDED.promo_code_id,
+                attached_at = EXCLUDED.attached_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(promo_code_id)
+        .bind(attached_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_attached(&self, user_id: Uuid) -> Result<Option<AttachedPromoCode>, PaymentError> {
+        let row = sqlx::query(
+            r#"
+            SELECT pc.id, pc.code, pc.campaign, pc.discount_type, pc.discount_percent, pc.discount_minor, pc.currency,
+                   pc.per_user_limit, pc.tariff_ids, pc.expires_at, pc.active, pc.created_at,
+                   upc.user_id, upc.attached_at,
+                   (
+                       SELECT COUNT(*)
+                       FROM payments p
+                       WHERE p.promo_code_id = pc.id AND p.user_id = upc.user_id
+                         AND p.status NOT IN ('failed', 'cancelled')
+                   ) AS redemptions
+            FROM user_promo_codes upc
+            JOIN promo_codes pc ON pc.id = upc.promo_code_id
+            WHERE upc.user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| {
+            Ok(AttachedPromoCode {
+                user_id: r.get("user_id"),
+                promo_code: promo_code_from_row(&r)?,
+                redemptions: r.get::<i64, _>("redemptions") as u32,
+                attached_at: r.get("attached_at"),
+            })
+        }).transpose()
+    }
+
+    async fn detach(&self, user_id: Uuid) -> Result<bool, PaymentError> {
+        let result = sqlx::query("DELETE FROM user_promo_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn detach_code(&self, user_id: Uuid, promo_code_id: Uuid) -> Result<bool, PaymentError> {
+        let result = sqlx::query("DELETE FROM user_promo_codes WHERE user_id = $1 AND promo_code_id = $2")
+            .bind(user_id)
+            .bind(promo_code_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn campaign_report(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CampaignReport>, PaymentError> {
+        // Кампании без использований тоже попадают в отчет с нулевыми суммами.
+        // Суммы в разных валютах не складываются: такая кампания дает строку на каждую валюту
+        let rows = sqlx::query(
+            r#"
+            SELECT pc.campaign,
+                   COALESCE(p.currency, pc.currency) AS currency,
+                   COUNT(DISTINCT pc.id)::BIGINT AS promo_codes,
+                   COUNT(p.id)::BIGINT AS redemptions,
+                   COALESCE(SUM(p.discount_minor), 0)::BIGINT AS discount_minor,
+                   COALESCE(SUM(p.amount_minor), 0)::BIGINT AS charged_minor
+            FROM promo_codes pc
+            LEFT JOIN payments p
+                ON p.promo_code_id = pc.id
+                AND p.status NOT IN ('failed', 'cancelled')
+                AND ($1::TIMESTAMPTZ IS NULL OR p.created_at >= $1)
+                AND ($2::TIMESTAMPTZ IS NULL OR p.created_at < $2)
+            GROUP BY pc.campaign, COALESCE(p.currency, pc.currency)
+            ORDER BY pc.campaign, currency
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| {
+            let currency = currency_from_row(&r)?;
+            Ok(CampaignReport {
+                campaign: r.get("campaign"),
+                promo_codes: r.get::<i64, _>("promo_codes") as u32,
+                redemptions: r.get::<i64, _>("redemptions") as u32,
+                discount_total: Money::new(r.get("discount_minor"), currency),
+                charged_total: Money::new(r.get("charged_minor"), currency),
+            })
+        }).collect()
+    }
+}
@@ -0,0 +1,99 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use crate::domain::interfaces::WebhookSigner;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// Формат заголовка подписи: sha256=<hex>
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+#[derive(Clone)]
+pub struct HmacWebhookSigner {
+    secret: Vec<u8>,
+}
+
+impl HmacWebhookSigner {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            secret: secret.as_bytes().to_vec(),
+        }
+    }
+
+    fn mac(&self, payload: &[u8]) -> HmacSha256 {
+        // HMAC принимает ключ любой длины, поэтому ошибка здесь невозможна
+        let mut mac = HmacSha256::new_from_slice(&self.secret)
+            .expect("HMAC accepts keys of any length");
+        mac.update(payload);
+        mac
+    }
+}
+
+impl WebhookSigner for HmacWebhookSigner {
+    fn sign(&self, payload: &[u8]) -> String {
+        format!("{}{}", SIGNATURE_PREFIX, hex::encode(self.mac(payload).finalize().into_bytes()))
+    }
+
+    fn verify(&self, payload: &[u8], signature: &str) -> bool {
+        let signature = signature.trim();
+        let signature = signature.strip_prefix(SIGNATURE_PREFIX).unwrap_or(signature);
+        match hex::decode(signature) {
+            // verify_slice сравнивает за постоянное время
+            Ok(expected) => self.mac(payload).verify_slice(&expected).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAYLOAD: &[u8] = br#"{"payment_id":"8a6e0804-2bd0-4672-b79d-d97027f9071a","status":"paid"}"#;
+
+    #[test]
+    fn test_sign_matches_rfc4231_vector() {
+        // RFC 4231, test case 2
+        let signer = HmacWebhookSigner::new("Jefe");
+        assert_eq!(
+            signer.sign(b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_verify_accepts_own_signature() {
+        let signer = HmacWebhookSigner::new("bank-secret");
+        let signature = signer.sign(PAYLOAD);
+
+        assert!(signer.verify(PAYLOAD, &signature));
+        // Без префикса и с пробелами вокруг подпись тоже принимается
+        assert!(signer.verify(PAYLOAD, signature.trim_start_matches(SIGNATURE_PREFIX)));
+        assert!(signer.verify(PAYLOAD, &format!(" {} ", signature)));
+    }
+
+    #[test]
+    fn test_verify_rejects_modified_payload() {
+        let signer = HmacWebhookSigner::new("bank-secret");
+        let signature = signer.sign(PAYLOAD);
+
+        let tampered = String::from_utf8(PAYLOAD.to_vec()).unwrap().replace("paid", "failed");
+        assert!(!signer.verify(tampered.as_bytes(), &signature));
+    }
+
+    #[test]
+    fn test_verify_rejects_signature_with_other_secret() {
+        let signature = HmacWebhookSigner::new("attacker-secret").sign(PAYLOAD);
+
+        assert!(!HmacWebhookSigner::new("bank-secret").verify(PAYLOAD, &signature));
+    }
+
+    #[test]
+    fn test_verify_rejects_malformed_signature() {
+        let signer = HmacWebhookSigner::new("bank-secret");
+        let signature = signer.sign(PAYLOAD);
+
+        assert!(!signer.verify(PAYLOAD, ""));
+        assert!(!signer.verify(PAYLOAD, "sha256=not-hex"));
+        assert!(!signer.verify(PAYLOAD, &signature[..signature.len() - 2]));
+    }
+}
@@ -1,4 +1,5 @@
-mod mock_qr_code_generator;
-
-pub use mock_qr_code_generator::*;
+mod hmac_webhook_signer;
+mod sbp_qr_code_generator;
 
+pub use hmac_webhook_signer::*;
+pub use sbp_qr_code_generator::*;
@@ -0,0 +1,125 @@
+use std::io::Cursor;
+use async_trait::async_trait;
+use chrono::Utc;
+use image::{DynamicImage, ImageFormat, Luma};
+use qrcode::{render::svg, EcLevel, QrCode};
+use uuid::Uuid;
+use crate::domain::{
+    interfaces::QRCodeGenerator,
+    models::{Money, PaymentQrCode},
+};
+
+const QR_MIN_DIMENSION: u32 = 300;
+
+// Тип платежной ссылки НСПК: 02 - динамическая ссылка с фиксированной суммой
+const SBP_DYNAMIC_LINK_TYPE: &str = "02";
+
+// QR-коды генерируются локально, без обращения к внешним сервисам
+#[derive(Clone)]
+pub struct SbpQrCodeGenerator {
+    bank_id: String,
+    base_url: String,
+}
+
+impl SbpQrCodeGenerator {
+    pub fn new(bank_id: &str, base_url: &str) -> Self {
+        Self {
+            bank_id: bank_id.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    // Формат НСПК: {base_url}/{QR-ID}?type=02&bank={bank_id}&sum={копейки}&cur=RUB&crc={CRC16}.
+    // QR-ID в боевой интеграции выдает НСПК через банк-участник; локально это 32 символа id платежа
+    pub fn build_payload(&self, payment_id: Uuid, amount: Money) -> String {
+        let qr_id = payment_id.simple().to_string().to_uppercase();
+        let link = format!(
+            "{}/{}?type={}&bank={}&sum={}&cur={}",
+            self.base_url, qr_id, SBP_DYNAMIC_LINK_TYPE, self.bank_id, amount.minor_units, amount.currency
+        );
+        // Контрольная сумма считается по всей ссылке до параметра crc
+        let crc = crc16_ccitt(link.as_bytes());
+        format!("{}&crc={:04X}", link, crc)
+    }
+}
+
+// CRC-16/CCITT-FALSE (полином 0x1021, начальное значение 0xFFFF)
+fn crc16_ccitt(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+#[async_trait]
+impl QRCodeGenerator for SbpQrCodeGenerator {
+    async fn generate_qr_code(&self, payment_id: Uuid, amount: Money) -> Result<PaymentQrCode, anyhow::Error> {
+        let payload = self.build_payload(payment_id, amount);
+        let code = QrCode::with_error_correction_level(payload.as_bytes(), EcLevel::M)?;
+
+        let image = code
+            .render::<Luma<u8>>()
+            .min_dimensions(QR_MIN_DIMENSION, QR_MIN_DIMENSION)
+            .build();
+        let mut png = Vec::new();
+        DynamicImage::ImageLuma8(image).write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+
+        let svg = code
+            .render::<svg::Color>()
+            .min_dimensions(QR_MIN_DIMENSION, QR_MIN_DIMENSION)
+            .build();
+
+        Ok(PaymentQrCode {
+            payment_id,
+            payload,
+            png,
+            svg,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::Currency;
+
+    #[test]
+    fn test_crc16_ccitt_check_value() {
+        // Контрольное значение CRC-16/CCITT-FALSE для "123456789"
+        assert_eq!(crc16_ccitt(b"123456789"), 0x29B1);
+        assert_eq!(crc16_ccitt(b""), 0xFFFF);
+    }
+
+    #[test]
+    fn test_build_payload_known_answer() {
+        let generator = SbpQrCodeGenerator::new("100000000111", "https://qr.nspk.ru/");
+        let payment_id = Uuid::parse_str("8a6e0804-2bd0-4672-b79d-d97027f9071a").unwrap();
+
+        let payload = generator.build_payload(payment_id, Money::new(15050, Currency::Rub));
+
+        assert_eq!(
+            payload,
+            "https://qr.nspk.ru/8A6E08042BD04672B79DD97027F9071A?type=02&bank=100000000111&sum=15050&cur=RUB&crc=444D"
+        );
+    }
+
+    #[test]
+    fn test_build_payload_crc_covers_amount() {
+        let generator = SbpQrCodeGenerator::new("100000000111", "https://qr.nspk.ru");
+        let payment_id = Uuid::new_v4();
+
+        let first = generator.build_payload(payment_id, Money::new(15050, Currency::Rub));
+        let second = generator.build_payload(payment_id, Money::new(15051, Currency::Rub));
+
+        assert_ne!(first.rsplit_once("&crc=").unwrap().1, second.rsplit_once("&crc=").unwrap().1);
+    }
+}
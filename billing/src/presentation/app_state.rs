@@ -1,31 +1,75 @@
 use std::sync::Arc;
 use crate::{
     application::use_cases::{
-        CreatePaymentUseCase, GetPaymentUseCase, GetUserPaymentsUseCase,
+        CreatePaymentUseCase, GetPaymentUseCase, GetTripPaymentUseCase, GetPaymentQrCodeUseCase, GetUserPaymentsUseCase,
+        ProcessPaymentCallbackUseCase, CreateRefundUseCase, GetPaymentRefundsUseCase, CreateTopUpUseCase, GetWalletUseCase,
+        GetUserBalanceUseCase, SetDebtOverrideUseCase, RemoveDebtOverrideUseCase,
+        CreatePromoCodeUseCase, GetPromoCodesUseCase, DeactivatePromoCodeUseCase, GetCampaignReportUseCase,
+        AttachPromoCodeUseCase, GetAttachedPromoCodeUseCase, DetachPromoCodeUseCase,
     },
-    domain::interfaces::{PaymentRepository, QRCodeGenerator},
+    domain::interfaces::{PaymentRepository, QRCodeGenerator, WebhookSigner, DebtOverrideRepository, PromoCodeRepository},
 };
 
-pub struct AppState<R, Q>
+pub struct AppState<R, Q, S, D, P>
 where
     R: PaymentRepository + Send + Sync + 'static,
     Q: QRCodeGenerator + Send + Sync + 'static,
+    S: WebhookSigner + Send + Sync + 'static,
+    D: DebtOverrideRepository + Send + Sync + 'static,
+    P: PromoCodeRepository + Send + Sync + 'static,
 {
-    pub create_payment_use_case: Arc<CreatePaymentUseCase<R, Q>>,
+    pub create_payment_use_case: Arc<CreatePaymentUseCase<R, Q, P>>,
     pub get_payment_use_case: Arc<GetPaymentUseCase<R>>,
+    pub get_trip_payment_use_case: Arc<GetTripPaymentUseCase<R>>,
+    pub get_payment_qr_code_use_case: Arc<GetPaymentQrCodeUseCase<R>>,
     pub get_user_payments_use_case: Arc<GetUserPaymentsUseCase<R>>,
+    pub process_payment_callback_use_case: Arc<ProcessPaymentCallbackUseCase<R, S>>,
+    pub create_refund_use_case: Arc<CreateRefundUseCase<R>>,
+    pub get_payment_refunds_use_case: Arc<GetPaymentRefundsUseCase<R>>,
+    pub create_top_up_use_case: Arc<CreateTopUpUseCase<R, Q>>,
+    pub get_wallet_use_case: Arc<GetWalletUseCase<R>>,
+    pub get_user_balance_use_case: Arc<GetUserBalanceUseCase<R, D>>,
+    pub set_debt_override_use_case: Arc<SetDebtOverrideUseCase<D>>,
+    pub remove_debt_override_use_case: Arc<RemoveDebtOverrideUseCase<D>>,
+    pub create_promo_code_use_case: Arc<CreatePromoCodeUseCase<P>>,
+    pub get_promo_codes_use_case: Arc<GetPromoCodesUseCase<P>>,
+    pub deactivate_promo_code_use_case: Arc<DeactivatePromoCodeUseCase<P>>,
+    pub get_campaign_report_use_case: Arc<GetCampaignReportUseCase<P>>,
+    pub attach_promo_code_use_case: Arc<AttachPromoCodeUseCase<P>>,
+    pub get_attached_promo_code_use_case: Arc<GetAttachedPromoCodeUseCase<P>>,
+    pub detach_promo_code_use_case: Arc<DetachPromoCodeUseCase<P>>,
 }
 
-impl<R, Q> Clone for AppState<R, Q>
+impl<R, Q, S, D, P> Clone for AppState<R, Q, S, D, P>
 where
     R: PaymentRepository + Send + Sync + 'static,
     Q: QRCodeGenerator + Send + Sync + 'static,
+    S: WebhookSigner + Send + Sync + 'static,
+    D: DebtOverrideRepository + Send + Sync + 'static,
+    P: PromoCodeRepository + Send + Sync + 'static,
 {
     fn clone(&self) -> Self {
         Self {
             create_payment_use_case: Arc::clone(&self.create_payment_use_case),
             get_payment_use_case: Arc::clone(&self.get_payment_use_case),
+            get_trip_payment_use_case: Arc::clone(&self.get_trip_payment_use_case),
+            get_payment_qr_code_use_case: Arc::clone(&self.get_payment_qr_code_use_case),
             get_user_payments_use_case: Arc::clone(&self.get_user_payments_use_case),
+            process_payment_callback_use_case: Arc::clone(&self.process_payment_callback_use_case),
+            create_refund_use_case: Arc::clone(&self.create_refund_use_case),
+            get_payment_refunds_use_case: Arc::clone(&self.get_payment_refunds_use_case),
+            create_top_up_use_case: Arc::clone(&self.create_top_up_use_case),
+            get_wallet_use_case: Arc::clone(&self.get_wallet_use_case),
+            get_user_balance_use_case: Arc::clone(&self.get_user_balance_use_case),
+            set_debt_override_use_case: Arc::clone(&self.set_debt_override_use_case),
+            remove_debt_override_use_case: Arc::clone(&self.remove_debt_override_use_case),
+            create_promo_code_use_case: Arc::clone(&self.create_promo_code_use_case),
+            get_promo_codes_use_case: Arc::clone(&self.get_promo_codes_use_case),
+            deactivate_promo_code_use_case: Arc::clone(&self.deactivate_promo_code_use_case),
+            get_campaign_report_use_case: Arc::clone(&self.get_campaign_report_use_case),
+            attach_promo_code_use_case: Arc::clone(&self.attach_promo_code_use_case),
+            get_attached_promo_code_use_case: Arc::clone(&self.get_attached_promo_code_use_case),
+            detach_promo_code_use_case: Arc::clone(&self.detach_promo_code_use_case),
         }
     }
 }
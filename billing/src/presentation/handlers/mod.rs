@@ -1,4 +1,5 @@
 mod payment_handlers;
+mod promo_code_handlers;
 
 pub use payment_handlers::*;
-
+pub use promo_code_handlers::*;
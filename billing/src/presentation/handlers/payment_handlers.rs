@@ -1,6 +1,7 @@
 use axum::{
+    body::Bytes,
     extract::{State, Path},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
     response::Json,
 };
 use serde::{Deserialize, Serialize};
@@ -8,26 +9,106 @@ use uuid::Uuid;
 use tracing::{info, warn, error};
 use crate::presentation::app_state::AppState;
 use crate::domain::errors::PaymentError;
+use crate::domain::models::Money;
 
 #[derive(Deserialize)]
 pub struct CreatePaymentRequest {
     pub trip_id: Uuid,
     pub user_id: Uuid,
-    pub amount: f64,
+    pub amount: Money,
+    // Без тарифа (например, штраф за неявку) промокод не применяется
+    pub tariff_id: Option<Uuid>,
 }
 
 #[derive(Serialize)]
 pub struct CreatePaymentResponse {
     pub payment_id: Uuid,
-    pub qr_code_url: String,
+    pub status: String,
+    pub method: String,
+    pub amount: Money,
+    pub discount: Option<AppliedDiscountResponse>,
+    // Нет, если поездка сразу оплачена с кошелька или скидкой
+    pub qr_code_url: Option<String>,
+}
+
+impl From<crate::domain::models::Payment> for CreatePaymentResponse {
+    fn from(payment: crate::domain::models::Payment) -> Self {
+        Self {
+            payment_id: payment.id,
+            status: payment.status.as_str().to_string(),
+            method: payment.method.as_str().to_string(),
+            amount: payment.amount,
+            discount: payment.discount.map(|d| d.into()),
+            qr_code_url: payment.qr_code_url,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CreateTopUpRequest {
+    pub amount: Money,
+}
+
+#[derive(Serialize)]
+pub struct WalletResponse {
+    pub user_id: Uuid,
+    pub balance: Money,
+}
+
+#[derive(Serialize)]
+pub struct WalletTransactionResponse {
+    pub id: Uuid,
+    pub kind: String,
+    pub amount: Money,
+    // Изменение остатка кошелька: отрицательное для оплаты поездки
+    pub balance_change: Money,
+    pub payment_id: Uuid,
+    pub refund_id: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<crate::domain::models::WalletTransaction> for WalletTransactionResponse {
+    fn from(transaction: crate::domain::models::WalletTransaction) -> Self {
+        Self {
+            id: transaction.id,
+            kind: transaction.kind.as_str().to_string(),
+            amount: transaction.amount,
+            balance_change: transaction.wallet_delta(),
+            payment_id: transaction.payment_id,
+            refund_id: transaction.refund_id,
+            created_at: transaction.created_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PaymentHistoryItemResponse {
+    Payment(PaymentResponse),
+    WalletTransaction(WalletTransactionResponse),
+}
+
+impl From<crate::domain::models::PaymentHistoryEntry> for PaymentHistoryItemResponse {
+    fn from(entry: crate::domain::models::PaymentHistoryEntry) -> Self {
+        match entry {
+            crate::domain::models::PaymentHistoryEntry::Payment(payment) => Self::Payment(payment.into()),
+            crate::domain::models::PaymentHistoryEntry::WalletTransaction(transaction) => {
+                Self::WalletTransaction(transaction.into())
+            }
+        }
+    }
 }
 
 #[derive(Serialize)]
 pub struct PaymentResponse {
     pub id: Uuid,
-    pub trip_id: Uuid,
+    pub trip_id: Option<Uuid>,
     pub user_id: Uuid,
-    pub amount: f64,
+    pub kind: String,
+    pub method: String,
+    // Сумма к оплате с учетом скидки
+    pub amount: Money,
+    pub discount: Option<AppliedDiscountResponse>,
     pub status: String,
     pub bank_reference: Option<String>,
     pub qr_code_url: Option<String>,
@@ -35,13 +116,125 @@ pub struct PaymentResponse {
     pub paid_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+// Сумма до скидки - amount + discount.amount
+#[derive(Serialize)]
+pub struct AppliedDiscountResponse {
+    pub promo_code_id: Uuid,
+    pub code: String,
+    pub amount: Money,
+}
+
+impl From<crate::domain::models::AppliedDiscount> for AppliedDiscountResponse {
+    fn from(discount: crate::domain::models::AppliedDiscount) -> Self {
+        Self {
+            promo_code_id: discount.promo_code_id,
+            code: discount.code,
+            amount: discount.amount,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CreateRefundRequest {
+    pub amount: Money,
+    pub reason: String,
+}
+
+#[derive(Serialize)]
+pub struct RefundResponse {
+    pub id: Uuid,
+    pub payment_id: Uuid,
+    pub amount: Money,
+    pub reason: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<crate::domain::models::Refund> for RefundResponse {
+    fn from(refund: crate::domain::models::Refund) -> Self {
+        Self {
+            id: refund.id,
+            payment_id: refund.payment_id,
+            amount: refund.amount,
+            reason: refund.reason,
+            created_at: refund.created_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct CreateRefundResponse {
+    pub refund: RefundResponse,
+    pub payment_status: String,
+}
+
+#[derive(Serialize)]
+pub struct UserBalanceResponse {
+    pub user_id: Uuid,
+    pub outstanding: Money,
+    pub unpaid_payments: usize,
+    pub debt_override: Option<DebtOverrideResponse>,
+}
+
+impl From<crate::domain::models::UserBalance> for UserBalanceResponse {
+    fn from(balance: crate::domain::models::UserBalance) -> Self {
+        Self {
+            user_id: balance.user_id,
+            outstanding: balance.outstanding,
+            unpaid_payments: balance.unpaid_payments,
+            debt_override: balance.debt_override.map(|o| o.into()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct SetDebtOverrideRequest {
+    pub reason: String,
+    pub granted_by: Uuid,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize)]
+pub struct DebtOverrideResponse {
+    pub user_id: Uuid,
+    pub reason: String,
+    pub granted_by: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<crate::domain::models::DebtOverride> for DebtOverrideResponse {
+    fn from(debt_override: crate::domain::models::DebtOverride) -> Self {
+        Self {
+            user_id: debt_override.user_id,
+            reason: debt_override.reason,
+            granted_by: debt_override.granted_by,
+            created_at: debt_override.created_at,
+            expires_at: debt_override.expires_at,
+        }
+    }
+}
+
+pub const BANK_SIGNATURE_HEADER: &str = "x-bank-signature";
+
+#[derive(Serialize)]
+pub struct PaymentWebhookResponse {
+    pub payment_id: Uuid,
+    pub status: String,
+    pub bank_reference: Option<String>,
+    pub paid_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub duplicate: bool,
+}
+
 impl From<crate::domain::models::Payment> for PaymentResponse {
     fn from(payment: crate::domain::models::Payment) -> Self {
         Self {
             id: payment.id,
             trip_id: payment.trip_id,
             user_id: payment.user_id,
+            kind: payment.kind.as_str().to_string(),
+            method: payment.method.as_str().to_string(),
             amount: payment.amount,
+            discount: payment.discount.map(|d| d.into()),
             status: payment.status.as_str().to_string(),
             bank_reference: payment.bank_reference,
             qr_code_url: payment.qr_code_url,
@@ -51,13 +244,16 @@ impl From<crate::domain::models::Payment> for PaymentResponse {
     }
 }
 
-pub async fn create_payment_handler<R, Q>(
-    State(state): State<AppState<R, Q>>,
+pub async fn create_payment_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
     Json(request): Json<CreatePaymentRequest>,
 ) -> Result<Json<CreatePaymentResponse>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
     Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
 {
     info!("Creating payment for trip {} by user {}", request.trip_id, request.user_id);
     
@@ -65,18 +261,22 @@ where
         trip_id: request.trip_id,
         user_id: request.user_id,
         amount: request.amount,
+        tariff_id: request.tariff_id,
     };
 
     match state.create_payment_use_case.execute(create_request).await {
         Ok(payment_id) => {
-            // Получаем созданный платеж для получения QR-кода
+            // Получаем созданный платеж: он либо ждет оплаты по QR-коду, либо уже оплачен с кошелька
             match state.get_payment_use_case.execute(payment_id).await {
                 Ok(payment) => {
-                    info!("Payment created successfully: {}", payment_id);
-                    Ok(Json(CreatePaymentResponse {
-                        payment_id,
-                        qr_code_url: payment.qr_code_url.unwrap_or_default(),
-                    }))
+                    info!(
+                        "Payment created successfully: {} ({}, {})",
+                        payment_id, payment.method.as_str(), payment.status.as_str()
+                    );
+                    if let Some(discount) = &payment.discount {
+                        info!("Promo code {} applied to payment {}: -{}", discount.code, payment_id, discount.amount);
+                    }
+                    Ok(Json(payment.into()))
                 }
                 Err(e) => {
                     error!("Error getting created payment: {:?}", e);
@@ -111,13 +311,16 @@ where
     }
 }
 
-pub async fn get_payment_handler<R, Q>(
-    State(state): State<AppState<R, Q>>,
+pub async fn get_payment_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
     Path(payment_id): Path<Uuid>,
 ) -> Result<Json<PaymentResponse>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
     Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
 {
     info!("Getting payment: {}", payment_id);
     match state.get_payment_use_case.execute(payment_id).await {
@@ -142,19 +345,221 @@ where
     }
 }
 
-pub async fn get_user_payments_handler<R, Q>(
-    State(state): State<AppState<R, Q>>,
+pub async fn get_trip_payment_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(trip_id): Path<Uuid>,
+) -> Result<Json<PaymentResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Getting payment for trip: {}", trip_id);
+    match state.get_trip_payment_use_case.execute(trip_id).await {
+        Ok(payment) => Ok(Json(payment.into())),
+        Err(PaymentError::PaymentNotFound) => {
+            warn!("Payment not found for trip: {}", trip_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Payment not found"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting payment for trip {}: {:?}", trip_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_payment_qr_png_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(payment_id): Path<Uuid>,
+) -> Result<([(header::HeaderName, &'static str); 1], Vec<u8>), (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Getting PNG QR code for payment: {}", payment_id);
+    match state.get_payment_qr_code_use_case.execute(payment_id).await {
+        Ok(qr_code) => Ok(([(header::CONTENT_TYPE, "image/png")], qr_code.png)),
+        Err(e) => Err(qr_code_error(payment_id, e)),
+    }
+}
+
+pub async fn get_payment_qr_svg_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(payment_id): Path<Uuid>,
+) -> Result<([(header::HeaderName, &'static str); 1], String), (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Getting SVG QR code for payment: {}", payment_id);
+    match state.get_payment_qr_code_use_case.execute(payment_id).await {
+        Ok(qr_code) => Ok(([(header::CONTENT_TYPE, "image/svg+xml")], qr_code.svg)),
+        Err(e) => Err(qr_code_error(payment_id, e)),
+    }
+}
+
+fn qr_code_error(payment_id: Uuid, error: PaymentError) -> (StatusCode, Json<serde_json::Value>) {
+    match error {
+        PaymentError::QrCodeNotFound => {
+            warn!("QR code not found for payment: {}", payment_id);
+            (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "QR code not found"})),
+            )
+        }
+        e => {
+            error!("Error getting QR code for payment {}: {:?}", payment_id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            )
+        }
+    }
+}
+
+pub async fn create_refund_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(payment_id): Path<Uuid>,
+    Json(request): Json<CreateRefundRequest>,
+) -> Result<Json<CreateRefundResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Creating refund of {} for payment {}", request.amount, payment_id);
+
+    let refund_request = crate::domain::models::CreateRefundRequest {
+        amount: request.amount,
+        reason: request.reason,
+    };
+
+    match state.create_refund_use_case.execute(payment_id, refund_request).await {
+        Ok((refund, payment)) => {
+            info!("Refund {} created, payment {} is now {}", refund.id, payment_id, payment.status.as_str());
+            Ok(Json(CreateRefundResponse {
+                refund: refund.into(),
+                payment_status: payment.status.as_str().to_string(),
+            }))
+        }
+        Err(PaymentError::InvalidAmount { amount }) => {
+            warn!("Invalid refund amount: {}", amount);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Invalid refund amount: {}", amount)})),
+            ))
+        }
+        Err(PaymentError::RefundReasonRequired) => {
+            warn!("Refund reason is missing for payment {}", payment_id);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": "Refund reason is required"})),
+            ))
+        }
+        Err(PaymentError::RefundExceedsPaidAmount { requested, available }) => {
+            warn!("Refund of {} exceeds refundable {} for payment {}", requested, available, payment_id);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error": format!("Refund of {} exceeds refundable amount {}", requested, available),
+                    "refundable_amount": available,
+                })),
+            ))
+        }
+        Err(PaymentError::PaymentNotFound) => {
+            warn!("Payment not found: {}", payment_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Payment not found"})),
+            ))
+        }
+        Err(PaymentError::PaymentNotRefundable { status }) => {
+            warn!("Payment {} in status {} cannot be refunded", payment_id, status);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": format!("Payment in status {} cannot be refunded", status)})),
+            ))
+        }
+        Err(PaymentError::TopUpNotRefundable) => {
+            warn!("Payment {} is a wallet top-up and cannot be refunded", payment_id);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": "Wallet top-up cannot be refunded"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error creating refund for payment {}: {:?}", payment_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_payment_refunds_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(payment_id): Path<Uuid>,
+) -> Result<Json<Vec<RefundResponse>>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Getting refunds for payment: {}", payment_id);
+    match state.get_payment_refunds_use_case.execute(payment_id).await {
+        Ok(refunds) => Ok(Json(refunds.into_iter().map(|r| r.into()).collect())),
+        Err(PaymentError::PaymentNotFound) => {
+            warn!("Payment not found: {}", payment_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Payment not found"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting refunds for payment {}: {:?}", payment_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_user_payments_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
     Path(user_id): Path<Uuid>,
-) -> Result<Json<Vec<PaymentResponse>>, (StatusCode, Json<serde_json::Value>)>
+) -> Result<Json<Vec<PaymentHistoryItemResponse>>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
     Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
 {
-    info!("Getting payments for user: {}", user_id);
+    info!("Getting payment history for user: {}", user_id);
     match state.get_user_payments_use_case.execute(user_id).await {
-        Ok(payments) => {
-            info!("Payments retrieved successfully: {} payments", payments.len());
-            Ok(Json(payments.into_iter().map(|p| p.into()).collect()))
+        Ok(history) => {
+            info!("Payment history retrieved successfully: {} entries", history.len());
+            Ok(Json(history.into_iter().map(|entry| entry.into()).collect()))
         }
         Err(e) => {
             error!("Error getting payments for user {}: {:?}", user_id, e);
@@ -166,3 +571,260 @@ where
     }
 }
 
+
+pub async fn get_wallet_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<WalletResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Getting wallet for user: {}", user_id);
+    match state.get_wallet_use_case.execute(user_id).await {
+        Ok(wallet) => Ok(Json(WalletResponse {
+            user_id: wallet.user_id,
+            balance: wallet.balance,
+        })),
+        Err(e) => {
+            error!("Error getting wallet for user {}: {:?}", user_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn create_top_up_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<CreateTopUpRequest>,
+) -> Result<Json<CreatePaymentResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Creating wallet top-up of {} for user {}", request.amount, user_id);
+
+    let top_up_request = crate::domain::models::CreateTopUpRequest {
+        user_id,
+        amount: request.amount,
+    };
+
+    match state.create_top_up_use_case.execute(top_up_request).await {
+        Ok(payment) => {
+            info!("Wallet top-up payment created: {}", payment.id);
+            Ok(Json(payment.into()))
+        }
+        Err(PaymentError::InvalidAmount { amount }) => {
+            warn!("Invalid top-up amount: {}", amount);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Invalid top-up amount: {}", amount)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error creating top-up for user {}: {:?}", user_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_user_balance_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<UserBalanceResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Getting balance for user: {}", user_id);
+    match state.get_user_balance_use_case.execute(user_id).await {
+        Ok(balance) => {
+            info!(
+                "User {} owes {} across {} unpaid payments",
+                user_id, balance.outstanding, balance.unpaid_payments
+            );
+            Ok(Json(balance.into()))
+        }
+        Err(e) => {
+            error!("Error getting balance for user {}: {:?}", user_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn set_debt_override_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<SetDebtOverrideRequest>,
+) -> Result<Json<DebtOverrideResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Granting debt override for user {} by {}", user_id, request.granted_by);
+
+    let override_request = crate::domain::models::SetDebtOverrideRequest {
+        reason: request.reason,
+        granted_by: request.granted_by,
+        expires_at: request.expires_at,
+    };
+
+    match state.set_debt_override_use_case.execute(user_id, override_request).await {
+        Ok(debt_override) => {
+            info!("Debt override granted for user {}", user_id);
+            Ok(Json(debt_override.into()))
+        }
+        Err(PaymentError::InvalidDebtOverride(reason)) => {
+            warn!("Invalid debt override for user {}: {}", user_id, reason);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Invalid debt override: {}", reason)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error granting debt override for user {}: {:?}", user_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn remove_debt_override_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(user_id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Removing debt override for user: {}", user_id);
+    match state.remove_debt_override_use_case.execute(user_id).await {
+        Ok(()) => {
+            info!("Debt override removed for user {}", user_id);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(PaymentError::DebtOverrideNotFound) => {
+            warn!("Debt override not found for user: {}", user_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Debt override not found"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error removing debt override for user {}: {:?}", user_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn payment_webhook_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<PaymentWebhookResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    let signature = headers
+        .get(BANK_SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok());
+
+    match state.process_payment_callback_use_case.execute(&body, signature).await {
+        Ok((payment, duplicate)) => {
+            if duplicate {
+                info!("Duplicate bank callback for payment {} ignored", payment.id);
+            } else {
+                info!("Payment {} marked as {} by bank", payment.id, payment.status.as_str());
+            }
+            Ok(Json(PaymentWebhookResponse {
+                payment_id: payment.id,
+                status: payment.status.as_str().to_string(),
+                bank_reference: payment.bank_reference,
+                paid_at: payment.paid_at,
+                duplicate,
+            }))
+        }
+        Err(PaymentError::InvalidSignature) => {
+            warn!("Rejected bank callback with invalid signature");
+            Err((
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Invalid signature"})),
+            ))
+        }
+        Err(PaymentError::InvalidCallback(reason)) => {
+            warn!("Invalid bank callback: {}", reason);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Invalid callback: {}", reason)})),
+            ))
+        }
+        Err(PaymentError::AmountMismatch { expected, actual }) => {
+            warn!("Bank callback amount mismatch: expected {}, got {}", expected, actual);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Amount mismatch: expected {}, got {}", expected, actual)})),
+            ))
+        }
+        Err(PaymentError::PaymentNotFound) => {
+            warn!("Bank callback for unknown payment");
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Payment not found"})),
+            ))
+        }
+        Err(PaymentError::InvalidStatusTransition { from, to }) => {
+            warn!("Rejected payment status transition from {} to {}", from, to);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": format!("Invalid status transition from {} to {}", from, to)})),
+            ))
+        }
+        Err(PaymentError::BankReferenceInUse(bank_reference)) => {
+            warn!("Bank reference {} is already used by another payment", bank_reference);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": "Bank reference is already used by another payment"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error processing bank callback: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use axum::{
+    extract::{State, Path, Query},
+    http::StatusCode,
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use tracing::{info, warn, error};
+use crate::presentation::app_state::AppState;
+use crate::domain::errors::PaymentError;
+use crate::domain::models::{Discount, Money};
+
+#[derive(Deserialize)]
+pub struct CreatePromoCodeRequest {
+    pub code: String,
+    pub campaign: String,
+    pub discount: Discount,
+    // По умолчанию код можно применить один раз
+    #[serde(default = "default_per_user_limit")]
+    pub per_user_limit: u32,
+    #[serde(default)]
+    pub tariff_ids: Vec<Uuid>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+fn default_per_user_limit() -> u32 {
+    1
+}
+
+#[derive(Serialize)]
+pub struct PromoCodeResponse {
+    pub id: Uuid,
+    pub code: String,
+    pub campaign: String,
+    pub discount: Discount,
+    pub per_user_limit: u32,
+    pub tariff_ids: Vec<Uuid>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub active: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<crate::domain::models::PromoCode> for PromoCodeResponse {
+    fn from(promo_code: crate::domain::models::PromoCode) -> Self {
+        Self {
+            id: promo_code.id,
+            code: promo_code.code,
+            campaign: promo_code.campaign,
+            discount: promo_code.discount,
+            per_user_limit: promo_code.per_user_limit,
+            tariff_ids: promo_code.tariff_ids,
+            expires_at: promo_code.expires_at,
+            active: promo_code.active,
+            created_at: promo_code.created_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct AttachPromoCodeRequest {
+    pub code: String,
+}
+
+#[derive(Serialize)]
+pub struct AttachedPromoCodeResponse {
+    pub user_id: Uuid,
+    pub promo_code: PromoCodeResponse,
+    pub redemptions: u32,
+    pub remaining_uses: u32,
+    pub attached_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<crate::domain::models::AttachedPromoCode> for AttachedPromoCodeResponse {
+    fn from(attached: crate::domain::models::AttachedPromoCode) -> Self {
+        Self {
+            user_id: attached.user_id,
+            remaining_uses: attached.promo_code.per_user_limit.saturating_sub(attached.redemptions),
+            promo_code: attached.promo_code.into(),
+            redemptions: attached.redemptions,
+            attached_at: attached.attached_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CampaignReportQuery {
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize)]
+pub struct CampaignReportResponse {
+    pub campaign: String,
+    pub promo_codes: u32,
+    pub redemptions: u32,
+    pub discount_total: Money,
+    // Сколько пользователи должны были заплатить по поездкам со скидкой
+    pub charged_total: Money,
+}
+
+impl From<crate::domain::models::CampaignReport> for CampaignReportResponse {
+    fn from(report: crate::domain::models::CampaignReport) -> Self {
+        Self {
+            campaign: report.campaign,
+            promo_codes: report.promo_codes,
+            redemptions: report.redemptions,
+            discount_total: report.discount_total,
+            charged_total: report.charged_total,
+        }
+    }
+}
+
+pub async fn create_promo_code_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Json(request): Json<CreatePromoCodeRequest>,
+) -> Result<Json<PromoCodeResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Creating promo code {} for campaign {}", request.code, request.campaign);
+
+    let create_request = crate::domain::models::CreatePromoCodeRequest {
+        code: request.code,
+        campaign: request.campaign,
+        discount: request.discount,
+        per_user_limit: request.per_user_limit,
+        tariff_ids: request.tariff_ids,
+        expires_at: request.expires_at,
+    };
+
+    match state.create_promo_code_use_case.execute(create_request).await {
+        Ok(promo_code) => {
+            info!("Promo code {} created: {}", promo_code.code, promo_code.id);
+            Ok(Json(promo_code.into()))
+        }
+        Err(PaymentError::InvalidPromoCode(reason)) => {
+            warn!("Invalid promo code: {}", reason);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Invalid promo code: {}", reason)})),
+            ))
+        }
+        Err(PaymentError::PromoCodeAlreadyExists(code)) => {
+            warn!("Promo code {} already exists", code);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": format!("Promo code {} already exists", code)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error creating promo code: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_promo_codes_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+) -> Result<Json<Vec<PromoCodeResponse>>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Getting promo codes");
+    match state.get_promo_codes_use_case.execute().await {
+        Ok(promo_codes) => Ok(Json(promo_codes.into_iter().map(|p| p.into()).collect())),
+        Err(e) => {
+            error!("Error getting promo codes: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn deactivate_promo_code_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(promo_code_id): Path<Uuid>,
+) -> Result<Json<PromoCodeResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Deactivating promo code: {}", promo_code_id);
+    match state.deactivate_promo_code_use_case.execute(promo_code_id).await {
+        Ok(promo_code) => {
+            info!("Promo code {} deactivated", promo_code.code);
+            Ok(Json(promo_code.into()))
+        }
+        Err(PaymentError::PromoCodeNotFound) => {
+            warn!("Promo code not found: {}", promo_code_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Promo code not found"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error deactivating promo code {}: {:?}", promo_code_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_campaign_report_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Query(query): Query<CampaignReportQuery>,
+) -> Result<Json<Vec<CampaignReportResponse>>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Getting promo campaign report");
+    match state.get_campaign_report_use_case.execute(query.from, query.to).await {
+        Ok(reports) => Ok(Json(reports.into_iter().map(|r| r.into()).collect())),
+        Err(PaymentError::InvalidReportPeriod) => {
+            warn!("Invalid campaign report period");
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": "Report period start must be before its end"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting campaign report: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn attach_promo_code_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<AttachPromoCodeRequest>,
+) -> Result<Json<AttachedPromoCodeResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Attaching promo code for user: {}", user_id);
+    match state.attach_promo_code_use_case.execute(user_id, &request.code).await {
+        Ok(attached) => {
+            info!("Promo code {} attached for user {}", attached.promo_code.code, user_id);
+            Ok(Json(attached.into()))
+        }
+        Err(PaymentError::PromoCodeNotFound) => {
+            warn!("Unknown promo code entered by user {}", user_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Promo code not found"})),
+            ))
+        }
+        Err(PaymentError::PromoCodeNotApplicable(reason)) => {
+            warn!("Promo code cannot be attached for user {}: {}", user_id, reason);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": format!("Promo code cannot be applied: {}", reason)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error attaching promo code for user {}: {:?}", user_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_attached_promo_code_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<AttachedPromoCodeResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Getting attached promo code for user: {}", user_id);
+    match state.get_attached_promo_code_use_case.execute(user_id).await {
+        Ok(attached) => Ok(Json(attached.into())),
+        Err(PaymentError::PromoCodeNotAttached) => {
+            warn!("No promo code attached for user: {}", user_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "No promo code attached"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting attached promo code for user {}: {:?}", user_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn detach_promo_code_handler<R, Q, S, D, P>(
+    State(state): State<AppState<R, Q, S, D, P>>,
+    Path(user_id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
+    Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
+{
+    info!("Detaching promo code for user: {}", user_id);
+    match state.detach_promo_code_use_case.execute(user_id).await {
+        Ok(()) => {
+            info!("Promo code detached for user {}", user_id);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(PaymentError::PromoCodeNotAttached) => {
+            warn!("No promo code attached for user: {}", user_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "No promo code attached"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error detaching promo code for user {}: {:?}", user_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
@@ -1,21 +1,37 @@
 use axum::{
     Router,
-    routing::{get, post},
+    routing::{get, post, put},
 };
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing::info;
 use crate::presentation::{handlers::*, app_state::AppState};
 
-pub fn create_router<R, Q>(app_state: AppState<R, Q>) -> Router
+pub fn create_router<R, Q, S, D, P>(app_state: AppState<R, Q, S, D, P>) -> Router
 where
     R: crate::domain::interfaces::PaymentRepository + Send + Sync + 'static,
     Q: crate::domain::interfaces::QRCodeGenerator + Send + Sync + 'static,
+    S: crate::domain::interfaces::WebhookSigner + Send + Sync + 'static,
+    D: crate::domain::interfaces::DebtOverrideRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::PromoCodeRepository + Send + Sync + 'static,
 {
     info!("Setting up routes...");
     Router::new()
         .route("/payments", post(create_payment_handler))
+        .route("/payments/webhook", post(payment_webhook_handler))
         .route("/payments/:id", get(get_payment_handler))
+        .route("/payments/:id/qr.png", get(get_payment_qr_png_handler))
+        .route("/payments/:id/qr.svg", get(get_payment_qr_svg_handler))
+        .route("/payments/:id/refunds", post(create_refund_handler).get(get_payment_refunds_handler))
+        .route("/trips/:trip_id/payment", get(get_trip_payment_handler))
         .route("/users/:user_id/payments", get(get_user_payments_handler))
+        .route("/users/:user_id/wallet", get(get_wallet_handler))
+        .route("/users/:user_id/wallet/top-ups", post(create_top_up_handler))
+        .route("/users/:user_id/balance", get(get_user_balance_handler))
+        .route("/users/:user_id/debt-override", put(set_debt_override_handler).delete(remove_debt_override_handler))
+        .route("/users/:user_id/promo-code", put(attach_promo_code_handler).get(get_attached_promo_code_handler).delete(detach_promo_code_handler))
+        .route("/promo-codes", post(create_promo_code_handler).get(get_promo_codes_handler))
+        .route("/promo-codes/campaigns", get(get_campaign_report_handler))
+        .route("/promo-codes/:id/deactivate", post(deactivate_promo_code_handler))
         .with_state(app_state)
         .layer(CorsLayer::permissive())
         .layer(TraceLayer::new_for_http())
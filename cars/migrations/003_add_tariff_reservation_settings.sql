@@ -0,0 +1,8 @@
+-- Migration: Add reservation expiry settings to tariffs
+-- Created: 2024-02-20
+
+-- Сколько минут резерв ждет активации, прежде чем сервис trips его закроет
+ALTER TABLE tariffs ADD COLUMN IF NOT EXISTS reservation_window_minutes INTEGER NOT NULL DEFAULT 15 CHECK (reservation_window_minutes > 0);
+
+-- Штраф за неявку по истекшему резерву (0 — без штрафа)
+ALTER TABLE tariffs ADD COLUMN IF NOT EXISTS no_show_fee DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (no_show_fee >= 0);
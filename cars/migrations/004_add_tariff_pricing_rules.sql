@@ -0,0 +1,18 @@
+-- Migration: Add pricing rules to tariffs
+-- Created: 2024-03-05
+
+-- Ставка за километр пробега
+ALTER TABLE tariffs ADD COLUMN IF NOT EXISTS price_per_km DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (price_per_km >= 0);
+
+-- Поминутная ставка, пока машина припаркована в рамках поездки
+ALTER TABLE tariffs ADD COLUMN IF NOT EXISTS parking_price_per_minute DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (parking_price_per_minute >= 0);
+
+-- Минимальная стоимость поездки (0 — без минимума)
+ALTER TABLE tariffs ADD COLUMN IF NOT EXISTS minimum_fare DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (minimum_fare >= 0);
+
+-- Предел поминутной части стоимости за каждые начатые сутки (0 — без лимита)
+ALTER TABLE tariffs ADD COLUMN IF NOT EXISTS daily_cap DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (daily_cap >= 0);
+
+-- Правило округления итоговой суммы
+ALTER TABLE tariffs ADD COLUMN IF NOT EXISTS rounding VARCHAR(20) NOT NULL DEFAULT 'kopeck'
+    CHECK (rounding IN ('kopeck', 'ruble_up', 'ruble_nearest'));
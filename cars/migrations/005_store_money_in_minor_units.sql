@@ -0,0 +1,45 @@
+-- Migration: Store money as integer minor units with currency
+-- Created: 2024-03-12
+
+-- Денежные суммы переводятся из DOUBLE PRECISION (рубли) в BIGINT (копейки),
+-- чтобы исключить накопление ошибки округления. Колонки переименовываются с суффиксом
+-- _minor, чтобы старые запросы, ожидающие рубли, падали явно, а не читали копейки.
+
+-- Тарифы: все суммы тарифа в одной валюте
+ALTER TABLE tariffs ADD COLUMN IF NOT EXISTS currency VARCHAR(3) NOT NULL DEFAULT 'RUB' CHECK (currency IN ('RUB'));
+
+ALTER TABLE tariffs ALTER COLUMN price_per_minute TYPE BIGINT USING round(price_per_minute * 100)::BIGINT;
+ALTER TABLE tariffs RENAME COLUMN price_per_minute TO price_per_minute_minor;
+
+ALTER TABLE tariffs ALTER COLUMN no_show_fee DROP DEFAULT;
+ALTER TABLE tariffs ALTER COLUMN no_show_fee TYPE BIGINT USING round(no_show_fee * 100)::BIGINT;
+ALTER TABLE tariffs ALTER COLUMN no_show_fee SET DEFAULT 0;
+ALTER TABLE tariffs RENAME COLUMN no_show_fee TO no_show_fee_minor;
+
+ALTER TABLE tariffs ALTER COLUMN price_per_km DROP DEFAULT;
+ALTER TABLE tariffs ALTER COLUMN price_per_km TYPE BIGINT USING round(price_per_km * 100)::BIGINT;
+ALTER TABLE tariffs ALTER COLUMN price_per_km SET DEFAULT 0;
+ALTER TABLE tariffs RENAME COLUMN price_per_km TO price_per_km_minor;
+
+ALTER TABLE tariffs ALTER COLUMN parking_price_per_minute DROP DEFAULT;
+ALTER TABLE tariffs ALTER COLUMN parking_price_per_minute TYPE BIGINT USING round(parking_price_per_minute * 100)::BIGINT;
+ALTER TABLE tariffs ALTER COLUMN parking_price_per_minute SET DEFAULT 0;
+ALTER TABLE tariffs RENAME COLUMN parking_price_per_minute TO parking_price_per_minute_minor;
+
+ALTER TABLE tariffs ALTER COLUMN minimum_fare DROP DEFAULT;
+ALTER TABLE tariffs ALTER COLUMN minimum_fare TYPE BIGINT USING round(minimum_fare * 100)::BIGINT;
+ALTER TABLE tariffs ALTER COLUMN minimum_fare SET DEFAULT 0;
+ALTER TABLE tariffs RENAME COLUMN minimum_fare TO minimum_fare_minor;
+
+ALTER TABLE tariffs ALTER COLUMN daily_cap DROP DEFAULT;
+ALTER TABLE tariffs ALTER COLUMN daily_cap TYPE BIGINT USING round(daily_cap * 100)::BIGINT;
+ALTER TABLE tariffs ALTER COLUMN daily_cap SET DEFAULT 0;
+ALTER TABLE tariffs RENAME COLUMN daily_cap TO daily_cap_minor;
+
+-- Машины: базовая цена в копейках
+ALTER TABLE cars ADD COLUMN IF NOT EXISTS currency VARCHAR(3) NOT NULL DEFAULT 'RUB' CHECK (currency IN ('RUB'));
+
+ALTER TABLE cars ALTER COLUMN base_price DROP DEFAULT;
+ALTER TABLE cars ALTER COLUMN base_price TYPE BIGINT USING round(base_price * 100)::BIGINT;
+ALTER TABLE cars ALTER COLUMN base_price SET DEFAULT 0;
+ALTER TABLE cars RENAME COLUMN base_price TO base_price_minor;
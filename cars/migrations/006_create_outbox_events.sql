@@ -0,0 +1,32 @@
+-- Migration: Create transactional outbox for domain events
+-- Created: 2024-05-13
+
+-- События пишутся в одной транзакции с изменением машины или тарифа, relay публикует их в RabbitMQ
+CREATE TABLE IF NOT EXISTS outbox_events (
+    id UUID PRIMARY KEY,
+    aggregate_type VARCHAR(50) NOT NULL,
+    aggregate_id UUID NOT NULL,
+    event_type VARCHAR(100) NOT NULL,
+    payload JSONB NOT NULL,
+    occurred_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- NULL, пока брокер не подтвердил публикацию
+    published_at TIMESTAMP WITH TIME ZONE,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- Аренда relay: пока не истекла, другой экземпляр сервиса событие не берет
+    locked_until TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_outbox_events_pending
+    ON outbox_events (next_attempt_at, occurred_at)
+    WHERE published_at IS NULL;
+
+-- Поиск более ранних неопубликованных событий агрегата: события агрегата публикуются по порядку
+CREATE INDEX IF NOT EXISTS idx_outbox_events_pending_aggregate
+    ON outbox_events (aggregate_id, occurred_at)
+    WHERE published_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_outbox_events_published_at
+    ON outbox_events (published_at)
+    WHERE published_at IS NOT NULL;
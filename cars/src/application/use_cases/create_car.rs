@@ -2,7 +2,7 @@ use uuid::Uuid;
 use crate::domain::{
     errors::CarError,
     interfaces::{CarRepository, TariffRepository},
-    models::{Car, CreateCarRequest},
+    models::{Car, CreateCarRequest, Tariff},
 };
 
 pub struct CreateCarUseCase<CR, TR> 
@@ -28,18 +28,18 @@ where
 
     pub async fn execute(&self, request: CreateCarRequest) -> Result<Uuid, CarError> {
         // Проверяем, существует ли тариф
-        self.tariff_repository.find_by_id(request.tariff_id).await?
+        let tariff = self.tariff_repository.find_by_id(request.tariff_id).await?
             .ok_or(CarError::TariffNotFound)?;
 
         // Проверяем, не занят ли номер
-        if let Some(_) = self.car_repository.find_by_license_plate(&request.license_plate).await? {
+        if self.car_repository.find_by_license_plate(&request.license_plate).await?.is_some() {
             return Err(CarError::CarAlreadyExists {
                 license_plate: request.license_plate,
             });
         }
 
         // Проверяем, не зарегистрирован ли уже IoT девайс
-        if let Some(_) = self.car_repository.find_by_iot_serial(&request.iot_serial_number).await? {
+        if self.car_repository.find_by_iot_serial(&request.iot_serial_number).await?.is_some() {
             return Err(CarError::IotDeviceAlreadyRegistered {
                 serial_number: request.iot_serial_number,
             });
@@ -55,9 +55,20 @@ where
             tariff_id: request.tariff_id,
             base_price: request.base_price,
         };
+        ensure_car_currency(&car, &tariff)?;
 
         self.car_repository.create(&car).await?;
         Ok(car.id)
     }
 }
 
+
+pub(crate) fn ensure_car_currency(car: &Car, tariff: &Tariff) -> Result<(), CarError> {
+    if car.base_price.currency != tariff.currency() {
+        return Err(CarError::CurrencyMismatch {
+            expected: tariff.currency().to_string(),
+            actual: car.base_price.currency.to_string(),
+        });
+    }
+    Ok(())
+}
@@ -26,8 +26,16 @@ where
             price_per_minute: request.price_per_minute,
             minimal_rating: request.minimal_rating,
             minimal_experience: request.minimal_experience,
+            reservation_window_minutes: request.reservation_window_minutes,
+            no_show_fee: request.no_show_fee,
+            price_per_km: request.price_per_km,
+            parking_price_per_minute: request.parking_price_per_minute,
+            minimum_fare: request.minimum_fare,
+            daily_cap: request.daily_cap,
+            rounding: request.rounding,
         };
 
+        tariff.ensure_single_currency()?;
         self.repository.create(&tariff).await?;
         Ok(tariff.id)
     }
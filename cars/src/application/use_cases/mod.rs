@@ -3,18 +3,24 @@ mod get_car;
 mod update_car;
 mod delete_car;
 mod list_cars;
+mod transition_car_state;
 mod create_tariff;
 mod get_tariff;
 mod update_tariff;
 mod list_tariffs;
+mod quote_trip;
+mod relay_outbox_events;
 
 pub use create_car::*;
 pub use get_car::*;
 pub use update_car::*;
 pub use delete_car::*;
 pub use list_cars::*;
+pub use transition_car_state::*;
 pub use create_tariff::*;
 pub use get_tariff::*;
 pub use update_tariff::*;
 pub use list_tariffs::*;
+pub use quote_trip::*;
+pub use relay_outbox_events::*;
 
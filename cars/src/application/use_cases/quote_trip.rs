@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::{
+    errors::CarError,
+    interfaces::{CarRepository, PricingPolicy, TariffRepository},
+    models::{FareQuote, TariffPricingPolicy, TripUsage},
+};
+
+pub struct QuoteTripUseCase<CR, TR>
+where
+    CR: CarRepository,
+    TR: TariffRepository,
+{
+    car_repository: CR,
+    tariff_repository: TR,
+}
+
+impl<CR, TR> QuoteTripUseCase<CR, TR>
+where
+    CR: CarRepository,
+    TR: TariffRepository,
+{
+    pub fn new(car_repository: CR, tariff_repository: TR) -> Self {
+        Self { car_repository, tariff_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        car_id: Uuid,
+        started_at: DateTime<Utc>,
+        ended_at: DateTime<Utc>,
+        parking_minutes: u64,
+        distance_km: f64,
+    ) -> Result<(Uuid, FareQuote), CarError> {
+        let usage = TripUsage::new(started_at, ended_at, parking_minutes, distance_km)
+            .map_err(CarError::InvalidQuoteRequest)?;
+
+        let car = self.car_repository.find_by_id(car_id).await?
+            .ok_or(CarError::CarNotFound)?;
+        let tariff = self.tariff_repository.find_by_id(car.tariff_id).await?
+            .ok_or(CarError::TariffNotFound)?;
+
+        // Базовая стоимость машины добавляется к расчету по тарифу
+        let policy = TariffPricingPolicy::new(&tariff, car.base_price)?;
+        Ok((tariff.id, policy.quote(&usage)))
+    }
+}
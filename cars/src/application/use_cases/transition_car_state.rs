@@ -0,0 +1,161 @@
+use uuid::Uuid;
+use crate::domain::{
+    errors::CarError,
+    interfaces::CarRepository,
+    models::CarState,
+};
+
+pub struct TransitionCarStateUseCase<R> 
+where
+    R: CarRepository,
+{
+    repository: R,
+}
+
+impl<R> TransitionCarStateUseCase<R>
+where
+    R: CarRepository,
+{
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, car_id: Uuid, from: CarState, to: CarState) -> Result<(), CarError> {
+        if !from.can_transition_to(to) {
+            return Err(CarError::InvalidStateTransition {
+                from: from.as_str().to_string(),
+                to: to.as_str().to_string(),
+            });
+        }
+
+        // Переход выполняется атомарно: при конкурентных запросах успешен только один
+        if self.repository.transition_state(car_id, from, to).await? {
+            return Ok(());
+        }
+
+        // Разбираемся, почему переход не удался: машины нет или она в другом состоянии
+        let car = self.repository.find_by_id(car_id).await?
+            .ok_or(CarError::CarNotFound)?;
+
+        Err(CarError::InvalidStateTransition {
+            from: car.state.as_str().to_string(),
+            to: to.as_str().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use crate::domain::models::{Car, Currency, Money};
+
+    // Переход условный, как UPDATE ... WHERE state = from в Postgres
+    #[derive(Clone, Default)]
+    struct MockCarRepository {
+        cars: Arc<Mutex<HashMap<Uuid, Car>>>,
+        transition_calls: Arc<Mutex<usize>>,
+    }
+
+    impl MockCarRepository {
+        fn with_car(state: CarState) -> (Self, Uuid) {
+            let repository = Self::default();
+            let car = Car {
+                id: Uuid::new_v4(),
+                model: "Kia Rio".to_string(),
+                license_plate: "A123BC77".to_string(),
+                iot_serial_number: "VIN0000000000001".to_string(),
+                state,
+                tariff_id: Uuid::new_v4(),
+                base_price: Money::new(0, Currency::Rub),
+            };
+            let id = car.id;
+            repository.cars.lock().unwrap().insert(id, car);
+            (repository, id)
+        }
+
+        fn state(&self, id: Uuid) -> CarState {
+            self.cars.lock().unwrap()[&id].state
+        }
+    }
+
+    #[async_trait]
+    impl CarRepository for MockCarRepository {
+        async fn create(&self, _car: &Car) -> Result<(), CarError> { unimplemented!() }
+
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Car>, CarError> {
+            Ok(self.cars.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_by_license_plate(&self, _license_plate: &str) -> Result<Option<Car>, CarError> { unimplemented!() }
+        async fn find_by_iot_serial(&self, _iot_serial: &str) -> Result<Option<Car>, CarError> { unimplemented!() }
+        async fn find_by_tariff_id(&self, _tariff_id: Uuid) -> Result<Vec<Car>, CarError> { unimplemented!() }
+        async fn find_all(&self) -> Result<Vec<Car>, CarError> { unimplemented!() }
+        async fn update(&self, _id: Uuid, _car: &Car) -> Result<(), CarError> { unimplemented!() }
+
+        async fn transition_state(&self, id: Uuid, from: CarState, to: CarState) -> Result<bool, CarError> {
+            *self.transition_calls.lock().unwrap() += 1;
+            match self.cars.lock().unwrap().get_mut(&id) {
+                Some(car) if car.state == from => {
+                    car.state = to;
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+
+        async fn delete(&self, _id: Uuid) -> Result<(), CarError> { unimplemented!() }
+    }
+
+    #[tokio::test]
+    async fn test_transition_from_expected_state() {
+        let (repository, car_id) = MockCarRepository::with_car(CarState::Available);
+        let use_case = TransitionCarStateUseCase::new(repository.clone());
+
+        use_case.execute(car_id, CarState::Available, CarState::Reserved).await.unwrap();
+
+        assert_eq!(repository.state(car_id), CarState::Reserved);
+    }
+
+    #[tokio::test]
+    async fn test_second_reservation_of_same_car_fails() {
+        let (repository, car_id) = MockCarRepository::with_car(CarState::Available);
+        let use_case = TransitionCarStateUseCase::new(repository.clone());
+
+        use_case.execute(car_id, CarState::Available, CarState::Reserved).await.unwrap();
+        let result = use_case.execute(car_id, CarState::Available, CarState::Reserved).await;
+
+        // В ошибке фактическое состояние машины
+        match result {
+            Err(CarError::InvalidStateTransition { from, to }) => {
+                assert_eq!(from, "reserved");
+                assert_eq!(to, "reserved");
+            }
+            other => panic!("expected InvalidStateTransition, got {:?}", other),
+        }
+        assert_eq!(repository.state(car_id), CarState::Reserved);
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_transition_is_rejected_before_repository() {
+        let (repository, car_id) = MockCarRepository::with_car(CarState::Reserved);
+        let use_case = TransitionCarStateUseCase::new(repository.clone());
+
+        let result = use_case.execute(car_id, CarState::Reserved, CarState::Maintenance).await;
+
+        assert!(matches!(result, Err(CarError::InvalidStateTransition { .. })));
+        assert_eq!(*repository.transition_calls.lock().unwrap(), 0);
+        assert_eq!(repository.state(car_id), CarState::Reserved);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_car_is_not_found() {
+        let use_case = TransitionCarStateUseCase::new(MockCarRepository::default());
+
+        let result = use_case.execute(Uuid::new_v4(), CarState::Reserved, CarState::Available).await;
+
+        assert!(matches!(result, Err(CarError::CarNotFound)));
+    }
+}
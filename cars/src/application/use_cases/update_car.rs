@@ -4,6 +4,7 @@ use crate::domain::{
     interfaces::{CarRepository, TariffRepository},
     models::UpdateCarRequest,
 };
+use super::create_car::ensure_car_currency;
 
 pub struct UpdateCarUseCase<CR, TR> 
 where
@@ -57,14 +58,17 @@ where
             car.state = state;
         }
         if let Some(tariff_id) = request.tariff_id {
-            // Проверяем, существует ли тариф
-            self.tariff_repository.find_by_id(tariff_id).await?
-                .ok_or(CarError::TariffNotFound)?;
             car.tariff_id = tariff_id;
         }
         if let Some(base_price) = request.base_price {
             car.base_price = base_price;
         }
+        if request.tariff_id.is_some() || request.base_price.is_some() {
+            // Проверяем, существует ли тариф и совпадает ли его валюта с базовой ценой
+            let tariff = self.tariff_repository.find_by_id(car.tariff_id).await?
+                .ok_or(CarError::TariffNotFound)?;
+            ensure_car_currency(&car, &tariff)?;
+        }
 
         self.car_repository.update(car_id, &car).await?;
         Ok(())
@@ -35,7 +35,29 @@ where
         if let Some(minimal_experience) = request.minimal_experience {
             tariff.minimal_experience = minimal_experience;
         }
+        if let Some(reservation_window_minutes) = request.reservation_window_minutes {
+            tariff.reservation_window_minutes = reservation_window_minutes;
+        }
+        if let Some(no_show_fee) = request.no_show_fee {
+            tariff.no_show_fee = no_show_fee;
+        }
+        if let Some(price_per_km) = request.price_per_km {
+            tariff.price_per_km = price_per_km;
+        }
+        if let Some(parking_price_per_minute) = request.parking_price_per_minute {
+            tariff.parking_price_per_minute = parking_price_per_minute;
+        }
+        if let Some(minimum_fare) = request.minimum_fare {
+            tariff.minimum_fare = minimum_fare;
+        }
+        if let Some(daily_cap) = request.daily_cap {
+            tariff.daily_cap = daily_cap;
+        }
+        if let Some(rounding) = request.rounding {
+            tariff.rounding = rounding;
+        }
 
+        tariff.ensure_single_currency()?;
         self.repository.update(tariff_id, &tariff).await?;
         Ok(())
     }
@@ -14,8 +14,17 @@ pub enum CarError {
     #[error("iot device already registered: {serial_number}")]
     IotDeviceAlreadyRegistered { serial_number: String },
     
-    #[error("invalid state transition")]
-    InvalidStateTransition,
+    #[error("invalid state transition: from {from} to {to}")]
+    InvalidStateTransition { from: String, to: String },
+    
+    #[error("currency mismatch: expected {expected}, got {actual}")]
+    CurrencyMismatch { expected: String, actual: String },
+    
+    #[error("invalid quote request: {0}")]
+    InvalidQuoteRequest(String),
+    
+    #[error("event publish error: {0}")]
+    EventPublish(String),
     
     #[error("database error: {0}")]
     Database(#[from] sqlx::Error),
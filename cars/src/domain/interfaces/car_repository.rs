@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use uuid::Uuid;
 use crate::domain::{
     errors::CarError,
-    models::Car,
+    models::{Car, CarState},
 };
 
 #[async_trait]
@@ -14,6 +14,8 @@ pub trait CarRepository {
     async fn find_by_tariff_id(&self, tariff_id: Uuid) -> Result<Vec<Car>, CarError>;
     async fn find_all(&self) -> Result<Vec<Car>, CarError>;
     async fn update(&self, id: Uuid, car: &Car) -> Result<(), CarError>;
+    // false, если машина не находилась в состоянии from
+    async fn transition_state(&self, id: Uuid, from: CarState, to: CarState) -> Result<bool, CarError>;
     async fn delete(&self, id: Uuid) -> Result<(), CarError>;
 }
 
@@ -1,6 +1,11 @@
 mod car_repository;
 mod tariff_repository;
+mod pricing_policy;
+mod outbox_repository;
+mod event_publisher;
 
 pub use car_repository::*;
 pub use tariff_repository::*;
-
+pub use pricing_policy::*;
+pub use outbox_repository::*;
+pub use event_publisher::*;
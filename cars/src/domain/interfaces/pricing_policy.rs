@@ -0,0 +1,6 @@
+use crate::domain::models::{FareQuote, TripUsage};
+
+// Реализации детерминированы: одинаковые параметры поездки всегда дают одинаковую сумму
+pub trait PricingPolicy {
+    fn quote(&self, usage: &TripUsage) -> FareQuote;
+}
@@ -0,0 +1,37 @@
+use serde::Serialize;
+use crate::domain::models::{Car, CarState, OutboxEvent, Tariff};
+
+pub const CAR_AGGREGATE: &str = "car";
+pub const TARIFF_AGGREGATE: &str = "tariff";
+
+// previous_state заполнен, если изменилось состояние
+#[derive(Debug, Serialize)]
+pub struct CarChanged<'a> {
+    #[serde(flatten)]
+    pub car: &'a Car,
+    pub previous_state: Option<CarState>,
+}
+
+impl Car {
+    pub fn created_event(&self) -> Result<OutboxEvent, serde_json::Error> {
+        OutboxEvent::new(CAR_AGGREGATE, self.id, "CarCreated", &CarChanged { car: self, previous_state: None })
+    }
+
+    pub fn updated_event(&self, previous_state: Option<CarState>) -> Result<OutboxEvent, serde_json::Error> {
+        OutboxEvent::new(CAR_AGGREGATE, self.id, "CarUpdated", &CarChanged { car: self, previous_state })
+    }
+
+    pub fn deleted_event(&self) -> Result<OutboxEvent, serde_json::Error> {
+        OutboxEvent::new(CAR_AGGREGATE, self.id, "CarDeleted", &CarChanged { car: self, previous_state: None })
+    }
+}
+
+impl Tariff {
+    pub fn created_event(&self) -> Result<OutboxEvent, serde_json::Error> {
+        OutboxEvent::new(TARIFF_AGGREGATE, self.id, "TariffCreated", self)
+    }
+
+    pub fn updated_event(&self) -> Result<OutboxEvent, serde_json::Error> {
+        OutboxEvent::new(TARIFF_AGGREGATE, self.id, "TariffUpdated", self)
+    }
+}
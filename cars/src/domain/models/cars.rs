@@ -1,5 +1,6 @@
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
+use crate::domain::models::Money;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Car {
@@ -9,7 +10,7 @@ pub struct Car {
     pub iot_serial_number: String,
     pub state: CarState,
     pub tariff_id: Uuid,
-    pub base_price: f64, // Базовая стоимость для машины (добавляется к тарифу)
+    pub base_price: Money, // Базовая стоимость для машины (добавляется к тарифу)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -30,6 +31,20 @@ impl CarState {
             CarState::Reserved => "reserved",
         }
     }
+
+    pub fn can_transition_to(&self, to: CarState) -> bool {
+        matches!(
+            (self, to),
+            (CarState::Available, CarState::Reserved)
+                | (CarState::Reserved, CarState::InUse)
+                | (CarState::Reserved, CarState::Available)
+                | (CarState::InUse, CarState::Available)
+                // Откат активации при компенсации
+                | (CarState::InUse, CarState::Reserved)
+                | (CarState::Available, CarState::Maintenance)
+                | (CarState::Maintenance, CarState::Available)
+        )
+    }
 }
 
 impl std::str::FromStr for CarState {
@@ -53,7 +68,7 @@ pub struct CreateCarRequest {
     pub iot_serial_number: String,
     pub state: CarState,
     pub tariff_id: Uuid,
-    pub base_price: f64,
+    pub base_price: Money,
 }
 
 #[derive(Deserialize)]
@@ -63,6 +78,44 @@ pub struct UpdateCarRequest {
     pub iot_serial_number: Option<String>,
     pub state: Option<CarState>,
     pub tariff_id: Option<Uuid>,
-    pub base_price: Option<f64>,
+    pub base_price: Option<Money>,
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_STATES: [CarState; 4] = [CarState::Available, CarState::InUse, CarState::Maintenance, CarState::Reserved];
+
+    #[test]
+    fn test_allowed_transitions() {
+        let allowed = [
+            (CarState::Available, CarState::Reserved),
+            (CarState::Reserved, CarState::InUse),
+            (CarState::Reserved, CarState::Available),
+            (CarState::InUse, CarState::Available),
+            (CarState::InUse, CarState::Reserved),
+            (CarState::Available, CarState::Maintenance),
+            (CarState::Maintenance, CarState::Available),
+        ];
+
+        for from in ALL_STATES {
+            for to in ALL_STATES {
+                assert_eq!(
+                    from.can_transition_to(to),
+                    allowed.contains(&(from, to)),
+                    "{} -> {}", from.as_str(), to.as_str()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_busy_car_cannot_skip_to_maintenance_or_in_use() {
+        assert!(!CarState::Reserved.can_transition_to(CarState::Maintenance));
+        assert!(!CarState::InUse.can_transition_to(CarState::Maintenance));
+        assert!(!CarState::Available.can_transition_to(CarState::InUse));
+        assert!(!CarState::Maintenance.can_transition_to(CarState::Reserved));
+    }
+}
@@ -1,6 +1,13 @@
 mod cars;
 mod tariffs;
+mod money;
+mod pricing;
+mod outbox;
+mod car_events;
 
 pub use cars::*;
 pub use tariffs::*;
-
+pub use money::*;
+pub use pricing::*;
+pub use outbox::*;
+pub use car_events::*;
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Currency {
+    #[default]
+    #[serde(rename = "RUB")]
+    Rub,
+}
+
+impl Currency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Currency::Rub => "RUB",
+        }
+    }
+
+    pub fn minor_units_per_major(&self) -> i64 {
+        match self {
+            Currency::Rub => 100,
+        }
+    }
+}
+
+impl std::str::FromStr for Currency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_str() {
+            "RUB" => Ok(Currency::Rub),
+            _ => Err(format!("Unsupported currency: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Money {
+    pub minor_units: i64,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub const fn new(minor_units: i64, currency: Currency) -> Self {
+        Self { minor_units, currency }
+    }
+
+    pub const fn zero(currency: Currency) -> Self {
+        Self::new(0, currency)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.minor_units == 0
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.minor_units > 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.minor_units < 0
+    }
+
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        if self.currency != other.currency {
+            return None;
+        }
+        self.minor_units
+            .checked_add(other.minor_units)
+            .map(|minor_units| Money::new(minor_units, self.currency))
+    }
+
+    pub fn checked_sub(self, other: Money) -> Option<Money> {
+        if self.currency != other.currency {
+            return None;
+        }
+        self.minor_units
+            .checked_sub(other.minor_units)
+            .map(|minor_units| Money::new(minor_units, self.currency))
+    }
+
+    pub fn times(self, quantity: u64) -> Money {
+        let quantity = i64::try_from(quantity).unwrap_or(i64::MAX);
+        Money::new(self.minor_units.saturating_mul(quantity), self.currency)
+    }
+
+    // Результат округляется до копейки
+    pub fn mul_rounded(self, factor: f64) -> Money {
+        Money::new((self.minor_units as f64 * factor).round() as i64, self.currency)
+    }
+
+    pub fn ceil_to_major(self) -> Money {
+        let unit = self.currency.minor_units_per_major();
+        let remainder = self.minor_units.rem_euclid(unit);
+        if remainder == 0 {
+            self
+        } else {
+            Money::new(self.minor_units - remainder + unit, self.currency)
+        }
+    }
+
+    // Половина рубля округляется вверх
+    pub fn round_to_major(self) -> Money {
+        let unit = self.currency.minor_units_per_major();
+        let remainder = self.minor_units.rem_euclid(unit);
+        if remainder * 2 >= unit {
+            Money::new(self.minor_units - remainder + unit, self.currency)
+        } else {
+            Money::new(self.minor_units - remainder, self.currency)
+        }
+    }
+}
+
+impl std::fmt::Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unit = self.currency.minor_units_per_major();
+        let sign = if self.minor_units < 0 { "-" } else { "" };
+        let abs = self.minor_units.unsigned_abs();
+        write!(f, "{}{}.{:02} {}", sign, abs / unit as u64, abs % unit as u64, self.currency)
+    }
+}
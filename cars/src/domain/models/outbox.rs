@@ -0,0 +1,33 @@
+use uuid::Uuid;
+use serde::Serialize;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone)]
+pub struct OutboxEvent {
+    pub id: Uuid,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+    pub attempts: u32,
+}
+
+impl OutboxEvent {
+    pub fn new<T: Serialize>(
+        aggregate_type: &str,
+        aggregate_id: Uuid,
+        event_type: &str,
+        payload: &T,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            id: Uuid::new_v4(),
+            aggregate_type: aggregate_type.to_string(),
+            aggregate_id,
+            event_type: event_type.to_string(),
+            payload: serde_json::to_value(payload)?,
+            occurred_at: Utc::now(),
+            attempts: 0,
+        })
+    }
+}
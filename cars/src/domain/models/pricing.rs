@@ -0,0 +1,350 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::{
+    errors::CarError,
+    interfaces::PricingPolicy,
+    models::{Money, Tariff},
+};
+
+// Период, к которому применяется суточный лимит
+const MINUTES_PER_DAY: u64 = 24 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FareRounding {
+    #[default]
+    Kopeck,
+    RubleUp,
+    RubleNearest,
+}
+
+impl FareRounding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FareRounding::Kopeck => "kopeck",
+            FareRounding::RubleUp => "ruble_up",
+            FareRounding::RubleNearest => "ruble_nearest",
+        }
+    }
+
+    pub fn apply(&self, amount: Money) -> Money {
+        match self {
+            FareRounding::Kopeck => amount,
+            FareRounding::RubleUp => amount.ceil_to_major(),
+            FareRounding::RubleNearest => amount.round_to_major(),
+        }
+    }
+}
+
+impl std::str::FromStr for FareRounding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "kopeck" => Ok(FareRounding::Kopeck),
+            "ruble_up" => Ok(FareRounding::RubleUp),
+            "ruble_nearest" => Ok(FareRounding::RubleNearest),
+            _ => Err(format!("Invalid fare rounding: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TripUsage {
+    pub duration_seconds: u64,
+    pub parking_minutes: u64,
+    pub distance_km: f64,
+}
+
+impl TripUsage {
+    pub fn new(
+        started_at: DateTime<Utc>,
+        ended_at: DateTime<Utc>,
+        parking_minutes: u64,
+        distance_km: f64,
+    ) -> Result<Self, String> {
+        if ended_at < started_at {
+            return Err("ended_at must not be earlier than started_at".to_string());
+        }
+        if !distance_km.is_finite() || distance_km < 0.0 {
+            return Err("distance_km must be a non-negative number".to_string());
+        }
+
+        Ok(Self {
+            duration_seconds: ended_at.signed_duration_since(started_at).num_seconds() as u64,
+            parking_minutes,
+            distance_km,
+        })
+    }
+
+    // Полные минуты поездки, но не меньше одной
+    pub fn billed_minutes(&self) -> u64 {
+        (self.duration_seconds / 60).max(1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FareQuote {
+    pub billed_minutes: u64,
+    pub driving_minutes: u64,
+    pub parking_minutes: u64,
+    pub distance_km: f64,
+    pub base_fare: Money,
+    pub driving_cost: Money,
+    pub parking_cost: Money,
+    pub distance_cost: Money,
+    pub daily_cap_applied: bool,
+    pub minimum_fare_applied: bool,
+    pub rounding: FareRounding,
+    pub total: Money,
+}
+
+// Все суммы в одной валюте - это проверяется при создании политики
+#[derive(Debug, Clone, PartialEq)]
+pub struct TariffPricingPolicy {
+    base_fare: Money,
+    price_per_minute: Money,
+    parking_price_per_minute: Money,
+    price_per_km: Money,
+    minimum_fare: Money,
+    daily_cap: Money,
+    rounding: FareRounding,
+}
+
+impl TariffPricingPolicy {
+    pub fn new(tariff: &Tariff, base_fare: Money) -> Result<Self, CarError> {
+        tariff.ensure_single_currency()?;
+        if base_fare.currency != tariff.currency() {
+            return Err(CarError::CurrencyMismatch {
+                expected: tariff.currency().to_string(),
+                actual: base_fare.currency.to_string(),
+            });
+        }
+
+        Ok(Self {
+            base_fare,
+            price_per_minute: tariff.price_per_minute,
+            parking_price_per_minute: tariff.parking_price_per_minute,
+            price_per_km: tariff.price_per_km,
+            minimum_fare: tariff.minimum_fare,
+            daily_cap: tariff.daily_cap,
+            rounding: tariff.rounding,
+        })
+    }
+}
+
+impl PricingPolicy for TariffPricingPolicy {
+    fn quote(&self, usage: &TripUsage) -> FareQuote {
+        let currency = self.price_per_minute.currency;
+        let billed_minutes = usage.billed_minutes();
+        // Парковка не может длиться дольше самой поездки
+        let parking_minutes = usage.parking_minutes.min(billed_minutes);
+        let driving_minutes = billed_minutes - parking_minutes;
+
+        let mut driving_cost = self.price_per_minute.times(driving_minutes).minor_units;
+        let mut parking_cost = self.parking_price_per_minute.times(parking_minutes).minor_units;
+        let distance_cost = self.price_per_km.mul_rounded(usage.distance_km).minor_units;
+
+        // Суточный лимит ограничивает поминутную часть за каждые начатые сутки
+        let mut daily_cap_applied = false;
+        if self.daily_cap.is_positive() {
+            let days = billed_minutes.div_ceil(MINUTES_PER_DAY);
+            let cap = self.daily_cap.times(days).minor_units;
+            let time_cost = driving_cost.saturating_add(parking_cost);
+            if time_cost > cap {
+                // Распределяем лимит пропорционально (с округлением до копейки),
+                // чтобы разбивка сходилась с итогом
+                driving_cost = ((cap as i128 * driving_cost as i128 * 2 + time_cost as i128)
+                    / (time_cost as i128 * 2)) as i64;
+                parking_cost = cap - driving_cost;
+                daily_cap_applied = true;
+            }
+        }
+
+        let subtotal = self.base_fare.minor_units
+            .saturating_add(driving_cost)
+            .saturating_add(parking_cost)
+            .saturating_add(distance_cost);
+        let minimum_fare_applied = self.minimum_fare.is_positive() && subtotal < self.minimum_fare.minor_units;
+        let subtotal = if minimum_fare_applied { self.minimum_fare.minor_units } else { subtotal };
+
+        FareQuote {
+            billed_minutes,
+            driving_minutes,
+            parking_minutes,
+            distance_km: usage.distance_km,
+            base_fare: self.base_fare,
+            driving_cost: Money::new(driving_cost, currency),
+            parking_cost: Money::new(parking_cost, currency),
+            distance_cost: Money::new(distance_cost, currency),
+            daily_cap_applied,
+            minimum_fare_applied,
+            rounding: self.rounding,
+            total: self.rounding.apply(Money::new(subtotal, currency)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::Currency;
+
+    fn rub(minor_units: i64) -> Money {
+        Money::new(minor_units, Currency::Rub)
+    }
+
+    fn policy() -> TariffPricingPolicy {
+        TariffPricingPolicy {
+            base_fare: rub(5000),
+            price_per_minute: rub(1000),
+            parking_price_per_minute: rub(200),
+            price_per_km: rub(500),
+            minimum_fare: rub(0),
+            daily_cap: rub(0),
+            rounding: FareRounding::Kopeck,
+        }
+    }
+
+    fn usage(minutes: u64, parking_minutes: u64, distance_km: f64) -> TripUsage {
+        TripUsage {
+            duration_seconds: minutes * 60,
+            parking_minutes,
+            distance_km,
+        }
+    }
+
+    #[test]
+    fn test_quote_combines_time_parking_and_distance() {
+        let quote = policy().quote(&usage(30, 10, 12.5));
+
+        assert_eq!(quote.driving_minutes, 20);
+        assert_eq!(quote.parking_minutes, 10);
+        assert_eq!(quote.driving_cost, rub(20000));
+        assert_eq!(quote.parking_cost, rub(2000));
+        assert_eq!(quote.distance_cost, rub(6250));
+        assert_eq!(quote.total, rub(33250));
+        assert!(!quote.daily_cap_applied);
+        assert!(!quote.minimum_fare_applied);
+    }
+
+    #[test]
+    fn test_quote_bills_at_least_one_minute() {
+        let quote = policy().quote(&TripUsage {
+            duration_seconds: 20,
+            parking_minutes: 0,
+            distance_km: 0.0,
+        });
+
+        assert_eq!(quote.billed_minutes, 1);
+        assert_eq!(quote.total, rub(6000));
+    }
+
+    #[test]
+    fn test_quote_clamps_parking_to_trip_duration() {
+        let quote = policy().quote(&usage(5, 60, 0.0));
+
+        assert_eq!(quote.parking_minutes, 5);
+        assert_eq!(quote.driving_minutes, 0);
+        assert_eq!(quote.total, rub(6000));
+    }
+
+    #[test]
+    fn test_quote_applies_minimum_fare() {
+        let policy = TariffPricingPolicy { minimum_fare: rub(15000), ..policy() };
+        let quote = policy.quote(&usage(3, 0, 1.0));
+
+        assert!(quote.minimum_fare_applied);
+        assert_eq!(quote.total, rub(15000));
+    }
+
+    #[test]
+    fn test_quote_caps_time_cost_per_started_day() {
+        let policy = TariffPricingPolicy { daily_cap: rub(300000), ..policy() };
+
+        // 10 часов езды — лимит одних суток
+        let quote = policy.quote(&usage(600, 0, 0.0));
+        assert!(quote.daily_cap_applied);
+        assert_eq!(quote.driving_cost, rub(300000));
+        assert_eq!(quote.total, rub(305000));
+
+        // 25 часов — начаты вторые сутки, лимит удваивается
+        let quote = policy.quote(&usage(25 * 60, 0, 10.0));
+        assert!(quote.daily_cap_applied);
+        assert_eq!(quote.driving_cost.checked_add(quote.parking_cost), Some(rub(600000)));
+        assert_eq!(quote.total, rub(610000));
+    }
+
+    #[test]
+    fn test_quote_splits_daily_cap_between_driving_and_parking() {
+        let policy = TariffPricingPolicy { daily_cap: rub(100000), ..policy() };
+        let quote = policy.quote(&usage(200, 100, 0.0));
+
+        // Без лимита: 1000 ₽ езда + 200 ₽ парковка
+        assert!(quote.daily_cap_applied);
+        assert_eq!(quote.driving_cost, rub(83333));
+        assert_eq!(quote.parking_cost, rub(16667));
+        assert_eq!(quote.total, rub(105000));
+    }
+
+    #[test]
+    fn test_quote_rounds_distance_to_kopecks() {
+        // 5 ₽/км * 0.333 км = 1.665 ₽ -> 1.67 ₽ (166.5 коп. округляются от нуля)
+        let quote = policy().quote(&usage(1, 0, 0.333));
+        assert_eq!(quote.distance_cost, rub(167));
+    }
+
+    #[test]
+    fn test_rounding_rules() {
+        let quote = TariffPricingPolicy { rounding: FareRounding::RubleUp, ..policy() }
+            .quote(&usage(1, 0, 0.01));
+        assert_eq!(quote.total, rub(6100));
+
+        let quote = TariffPricingPolicy { rounding: FareRounding::RubleNearest, ..policy() }
+            .quote(&usage(1, 0, 0.01));
+        assert_eq!(quote.total, rub(6000));
+
+        assert_eq!(FareRounding::Kopeck.apply(rub(1001)), rub(1001));
+        assert_eq!(FareRounding::RubleUp.apply(rub(10000)), rub(10000));
+        assert_eq!(FareRounding::RubleNearest.apply(rub(10050)), rub(10100));
+    }
+
+    #[test]
+    fn test_rounding_parses_only_known_modes() {
+        for rounding in [FareRounding::Kopeck, FareRounding::RubleUp, FareRounding::RubleNearest] {
+            assert_eq!(rounding.as_str().parse::<FareRounding>(), Ok(rounding));
+        }
+        assert!("ruble_down".parse::<FareRounding>().is_err());
+        assert!("".parse::<FareRounding>().is_err());
+    }
+
+    #[test]
+    fn test_policy_accepts_tariff_in_car_currency() {
+        let tariff = Tariff {
+            id: uuid::Uuid::new_v4(),
+            price_per_minute: rub(1000),
+            minimal_rating: 0.0,
+            minimal_experience: 0,
+            reservation_window_minutes: 15,
+            no_show_fee: rub(0),
+            price_per_km: rub(0),
+            parking_price_per_minute: rub(0),
+            minimum_fare: rub(0),
+            daily_cap: rub(0),
+            rounding: FareRounding::Kopeck,
+        };
+        assert!(TariffPricingPolicy::new(&tariff, rub(5000)).is_ok());
+    }
+
+    #[test]
+    fn test_trip_usage_rejects_invalid_input() {
+        let now = Utc::now();
+
+        assert!(TripUsage::new(now, now - chrono::Duration::minutes(1), 0, 0.0).is_err());
+        assert!(TripUsage::new(now, now, 0, -1.0).is_err());
+        assert!(TripUsage::new(now, now, 0, f64::NAN).is_err());
+
+        let usage = TripUsage::new(now - chrono::Duration::seconds(150), now, 1, 2.0).unwrap();
+        assert_eq!(usage.billed_minutes(), 2);
+    }
+}
@@ -1,25 +1,77 @@
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
+use crate::domain::{
+    errors::CarError,
+    models::{Currency, FareRounding, Money},
+};
+
+pub const DEFAULT_RESERVATION_WINDOW_MINUTES: u32 = 15;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tariff {
     pub id: Uuid,
-    pub price_per_minute: f64,
+    pub price_per_minute: Money,
     pub minimal_rating: f64,
     pub minimal_experience: u32, // в годах
+    pub reservation_window_minutes: u32, // сколько резерв ждет активации, прежде чем истечь
+    pub no_show_fee: Money, // штраф за неявку по истекшему резерву (0 — без штрафа)
+    pub price_per_km: Money,
+    pub parking_price_per_minute: Money, // поминутная ставка, пока машина стоит в поездке
+    pub minimum_fare: Money, // минимальная стоимость поездки (0 — без минимума)
+    pub daily_cap: Money, // предел поминутной части за сутки (0 — без лимита)
+    pub rounding: FareRounding,
+}
+
+impl Tariff {
+    // Валюта тарифа — валюта поминутной ставки
+    pub fn currency(&self) -> Currency {
+        self.price_per_minute.currency
+    }
+
+    pub fn ensure_single_currency(&self) -> Result<(), CarError> {
+        let currency = self.currency();
+        let amounts = [
+            self.no_show_fee,
+            self.price_per_km,
+            self.parking_price_per_minute,
+            self.minimum_fare,
+            self.daily_cap,
+        ];
+        match amounts.iter().find(|m| m.currency != currency) {
+            Some(other) => Err(CarError::CurrencyMismatch {
+                expected: currency.to_string(),
+                actual: other.currency.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
 }
 
 #[derive(Deserialize)]
 pub struct CreateTariffRequest {
-    pub price_per_minute: f64,
+    pub price_per_minute: Money,
     pub minimal_rating: f64,
     pub minimal_experience: u32,
+    pub reservation_window_minutes: u32,
+    pub no_show_fee: Money,
+    pub price_per_km: Money,
+    pub parking_price_per_minute: Money,
+    pub minimum_fare: Money,
+    pub daily_cap: Money,
+    pub rounding: FareRounding,
 }
 
 #[derive(Deserialize)]
 pub struct UpdateTariffRequest {
-    pub price_per_minute: Option<f64>,
+    pub price_per_minute: Option<Money>,
     pub minimal_rating: Option<f64>,
     pub minimal_experience: Option<u32>,
+    pub reservation_window_minutes: Option<u32>,
+    pub no_show_fee: Option<Money>,
+    pub price_per_km: Option<Money>,
+    pub parking_price_per_minute: Option<Money>,
+    pub minimum_fare: Option<Money>,
+    pub daily_cap: Option<Money>,
+    pub rounding: Option<FareRounding>,
 }
 
@@ -1,4 +1,6 @@
 pub mod repositories;
+pub mod messaging;
 
 pub use repositories::*;
+pub use messaging::*;
 
@@ -1,6 +1,19 @@
 mod postgres_car_repository;
 mod postgres_tariff_repository;
+mod postgres_outbox_repository;
 
 pub use postgres_car_repository::*;
 pub use postgres_tariff_repository::*;
+pub use postgres_outbox_repository::*;
 
+use sqlx::{postgres::PgRow, Row};
+use crate::domain::{errors::CarError, models::Money};
+
+// Валюта денежных колонок хранится в колонке currency той же строки
+fn money_from_row(r: &PgRow, column: &str) -> Result<Money, CarError> {
+    let currency = r
+        .get::<String, _>("currency")
+        .parse()
+        .map_err(|e: String| CarError::Internal(anyhow::anyhow!(e)))?;
+    Ok(Money::new(r.get(column), currency))
+}
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{postgres::PgRow, PgPool, Row};
+use super::{enqueue_event, money_from_row};
 use uuid::Uuid;
 use crate::domain::{
     errors::CarError,
@@ -35,13 +36,28 @@ fn car_state_from_str(s: &str) -> CarState {
     }
 }
 
+fn car_from_row(r: &PgRow) -> Result<Car, CarError> {
+    Ok(Car {
+        id: r.get("id"),
+        model: r.get("model"),
+        license_plate: r.get("license_plate"),
+        iot_serial_number: r.get("iot_serial_number"),
+        state: car_state_from_str(r.get::<String, _>("state").as_str()),
+        tariff_id: r.get("tariff_id"),
+        base_price: money_from_row(r, "base_price_minor")?,
+    })
+}
+
 #[async_trait]
 impl CarRepository for PostgresCarRepository {
     async fn create(&self, car: &Car) -> Result<(), CarError> {
+        let event = car.created_event().map_err(anyhow::Error::from)?;
+        let mut tx = self.pool.begin().await?;
+
         sqlx::query(
             r#"
-            INSERT INTO cars (id, model, license_plate, iot_serial_number, state, tariff_id, base_price)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            INSERT INTO cars (id, model, license_plate, iot_serial_number, state, tariff_id, base_price_minor, currency)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             "#,
         )
         .bind(car.id)
@@ -50,17 +66,20 @@ impl CarRepository for PostgresCarRepository {
         .bind(&car.iot_serial_number)
         .bind(car.state.as_str())
         .bind(car.tariff_id)
-        .bind(car.base_price)
-        .execute(&self.pool)
+        .bind(car.base_price.minor_units)
+        .bind(car.base_price.currency.as_str())
+        .execute(&mut *tx)
         .await?;
-        
+
+        enqueue_event(&mut tx, &event).await?;
+        tx.commit().await?;
         Ok(())
     }
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Car>, CarError> {
         let row = sqlx::query(
             r#"
-            SELECT id, model, license_plate, iot_serial_number, state, tariff_id, base_price
+            SELECT id, model, license_plate, iot_serial_number, state, tariff_id, base_price_minor, currency
             FROM cars
             WHERE id = $1
             "#,
@@ -69,21 +88,13 @@ impl CarRepository for PostgresCarRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.map(|r| Car {
-            id: r.get("id"),
-            model: r.get("model"),
-            license_plate: r.get("license_plate"),
-            iot_serial_number: r.get("iot_serial_number"),
-            state: car_state_from_str(r.get::<String, _>("state").as_str()),
-            tariff_id: r.get("tariff_id"),
-            base_price: r.get("base_price"),
-        }))
+        row.as_ref().map(car_from_row).transpose()
     }
 
     async fn find_by_license_plate(&self, license_plate: &str) -> Result<Option<Car>, CarError> {
         let row = sqlx::query(
             r#"
-            SELECT id, model, license_plate, iot_serial_number, state, tariff_id, base_price
+            SELECT id, model, license_plate, iot_serial_number, state, tariff_id, base_price_minor, currency
             FROM cars
             WHERE license_plate = $1
             "#,
@@ -92,21 +103,13 @@ impl CarRepository for PostgresCarRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.map(|r| Car {
-            id: r.get("id"),
-            model: r.get("model"),
-            license_plate: r.get("license_plate"),
-            iot_serial_number: r.get("iot_serial_number"),
-            state: car_state_from_str(r.get::<String, _>("state").as_str()),
-            tariff_id: r.get("tariff_id"),
-            base_price: r.get("base_price"),
-        }))
+        row.as_ref().map(car_from_row).transpose()
     }
 
     async fn find_by_iot_serial(&self, iot_serial: &str) -> Result<Option<Car>, CarError> {
         let row = sqlx::query(
             r#"
-            SELECT id, model, license_plate, iot_serial_number, state, tariff_id, base_price
+            SELECT id, model, license_plate, iot_serial_number, state, tariff_id, base_price_minor, currency
             FROM cars
             WHERE iot_serial_number = $1
             "#,
@@ -115,21 +118,13 @@ impl CarRepository for PostgresCarRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.map(|r| Car {
-            id: r.get("id"),
-            model: r.get("model"),
-            license_plate: r.get("license_plate"),
-            iot_serial_number: r.get("iot_serial_number"),
-            state: car_state_from_str(r.get::<String, _>("state").as_str()),
-            tariff_id: r.get("tariff_id"),
-            base_price: r.get("base_price"),
-        }))
+        row.as_ref().map(car_from_row).transpose()
     }
 
     async fn find_by_tariff_id(&self, tariff_id: Uuid) -> Result<Vec<Car>, CarError> {
         let rows = sqlx::query(
             r#"
-            SELECT id, model, license_plate, iot_serial_number, state, tariff_id, base_price
+            SELECT id, model, license_plate, iot_serial_number, state, tariff_id, base_price_minor, currency
             FROM cars
             WHERE tariff_id = $1
             ORDER BY created_at DESC
@@ -139,21 +134,13 @@ impl CarRepository for PostgresCarRepository {
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().map(|r| Car {
-            id: r.get("id"),
-            model: r.get("model"),
-            license_plate: r.get("license_plate"),
-            iot_serial_number: r.get("iot_serial_number"),
-            state: car_state_from_str(r.get::<String, _>("state").as_str()),
-            tariff_id: r.get("tariff_id"),
-            base_price: r.get("base_price"),
-        }).collect())
+        rows.iter().map(car_from_row).collect()
     }
 
     async fn find_all(&self) -> Result<Vec<Car>, CarError> {
         let rows = sqlx::query(
             r#"
-            SELECT id, model, license_plate, iot_serial_number, state, tariff_id, base_price
+            SELECT id, model, license_plate, iot_serial_number, state, tariff_id, base_price_minor, currency
             FROM cars
             ORDER BY created_at DESC
             "#,
@@ -161,22 +148,21 @@ impl CarRepository for PostgresCarRepository {
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().map(|r| Car {
-            id: r.get("id"),
-            model: r.get("model"),
-            license_plate: r.get("license_plate"),
-            iot_serial_number: r.get("iot_serial_number"),
-            state: car_state_from_str(r.get::<String, _>("state").as_str()),
-            tariff_id: r.get("tariff_id"),
-            base_price: r.get("base_price"),
-        }).collect())
+        rows.iter().map(car_from_row).collect()
     }
 
     async fn update(&self, id: Uuid, car: &Car) -> Result<(), CarError> {
+        let mut tx = self.pool.begin().await?;
+
+        let previous_state: Option<String> = sqlx::query_scalar("SELECT state FROM cars WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
         sqlx::query(
             r#"
             UPDATE cars
-            SET model = $2, license_plate = $3, iot_serial_number = $4, state = $5, tariff_id = $6, base_price = $7
+            SET model = $2, license_plate = $3, iot_serial_number = $4, state = $5, tariff_id = $6, base_price_minor = $7, currency = $8
             WHERE id = $1
             "#,
         )
@@ -186,24 +172,68 @@ impl CarRepository for PostgresCarRepository {
         .bind(&car.iot_serial_number)
         .bind(car.state.as_str())
         .bind(car.tariff_id)
-        .bind(car.base_price)
-        .execute(&self.pool)
+        .bind(car.base_price.minor_units)
+        .bind(car.base_price.currency.as_str())
+        .execute(&mut *tx)
         .await?;
-        
+
+        if let Some(previous_state) = previous_state.map(|s| car_state_from_str(&s)) {
+            let changed_state = (previous_state != car.state).then_some(previous_state);
+            let event = car.updated_event(changed_state).map_err(anyhow::Error::from)?;
+            enqueue_event(&mut tx, &event).await?;
+        }
+
+        tx.commit().await?;
         Ok(())
     }
 
+    async fn transition_state(&self, id: Uuid, from: CarState, to: CarState) -> Result<bool, CarError> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE cars
+            SET state = $3
+            WHERE id = $1 AND state = $2
+            RETURNING id, model, license_plate, iot_serial_number, state, tariff_id, base_price_minor, currency
+            "#,
+        )
+        .bind(id)
+        .bind(from.as_str())
+        .bind(to.as_str())
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(false);
+        };
+        let event = car_from_row(&row)?.updated_event(Some(from)).map_err(anyhow::Error::from)?;
+        enqueue_event(&mut tx, &event).await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
     async fn delete(&self, id: Uuid) -> Result<(), CarError> {
-        sqlx::query(
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query(
             r#"
             DELETE FROM cars
             WHERE id = $1
+            RETURNING id, model, license_plate, iot_serial_number, state, tariff_id, base_price_minor, currency
             "#,
         )
         .bind(id)
-        .execute(&self.pool)
+        .fetch_optional(&mut *tx)
         .await?;
-        
+
+        if let Some(row) = row {
+            let event = car_from_row(&row)?.deleted_event().map_err(anyhow::Error::from)?;
+            enqueue_event(&mut tx, &event).await?;
+        }
+
+        tx.commit().await?;
         Ok(())
     }
 }
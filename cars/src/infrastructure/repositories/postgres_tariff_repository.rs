@@ -1,10 +1,11 @@
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{postgres::PgRow, PgPool, Row};
+use super::{enqueue_event, money_from_row};
 use uuid::Uuid;
 use crate::domain::{
     errors::CarError,
     interfaces::TariffRepository,
-    models::Tariff,
+    models::{OutboxEvent, Tariff, TARIFF_AGGREGATE},
 };
 
 pub struct PostgresTariffRepository {
@@ -25,29 +26,63 @@ impl Clone for PostgresTariffRepository {
     }
 }
 
+fn tariff_from_row(r: &PgRow) -> Result<Tariff, CarError> {
+    Ok(Tariff {
+        id: r.get("id"),
+        price_per_minute: money_from_row(r, "price_per_minute_minor")?,
+        minimal_rating: r.get("minimal_rating"),
+        minimal_experience: r.get::<i32, _>("minimal_experience") as u32,
+        reservation_window_minutes: r.get::<i32, _>("reservation_window_minutes") as u32,
+        no_show_fee: money_from_row(r, "no_show_fee_minor")?,
+        price_per_km: money_from_row(r, "price_per_km_minor")?,
+        parking_price_per_minute: money_from_row(r, "parking_price_per_minute_minor")?,
+        minimum_fare: money_from_row(r, "minimum_fare_minor")?,
+        daily_cap: money_from_row(r, "daily_cap_minor")?,
+        rounding: r
+            .get::<String, _>("rounding")
+            .parse()
+            .map_err(|e: String| CarError::Internal(anyhow::anyhow!(e)))?,
+    })
+}
+
 #[async_trait]
 impl TariffRepository for PostgresTariffRepository {
     async fn create(&self, tariff: &Tariff) -> Result<(), CarError> {
+        let event = tariff.created_event().map_err(anyhow::Error::from)?;
+        let mut tx = self.pool.begin().await?;
+
         sqlx::query(
             r#"
-            INSERT INTO tariffs (id, price_per_minute, minimal_rating, minimal_experience)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO tariffs (id, price_per_minute_minor, minimal_rating, minimal_experience, reservation_window_minutes, no_show_fee_minor,
+                                 price_per_km_minor, parking_price_per_minute_minor, minimum_fare_minor, daily_cap_minor, rounding, currency)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             "#,
         )
         .bind(tariff.id)
-        .bind(tariff.price_per_minute)
+        .bind(tariff.price_per_minute.minor_units)
         .bind(tariff.minimal_rating)
         .bind(tariff.minimal_experience as i32)
-        .execute(&self.pool)
+        .bind(tariff.reservation_window_minutes as i32)
+        .bind(tariff.no_show_fee.minor_units)
+        .bind(tariff.price_per_km.minor_units)
+        .bind(tariff.parking_price_per_minute.minor_units)
+        .bind(tariff.minimum_fare.minor_units)
+        .bind(tariff.daily_cap.minor_units)
+        .bind(tariff.rounding.as_str())
+        .bind(tariff.currency().as_str())
+        .execute(&mut *tx)
         .await?;
-        
+
+        enqueue_event(&mut tx, &event).await?;
+        tx.commit().await?;
         Ok(())
     }
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Tariff>, CarError> {
         let row = sqlx::query(
             r#"
-            SELECT id, price_per_minute, minimal_rating, minimal_experience
+            SELECT id, price_per_minute_minor, minimal_rating, minimal_experience, reservation_window_minutes, no_show_fee_minor,
+                   price_per_km_minor, parking_price_per_minute_minor, minimum_fare_minor, daily_cap_minor, rounding, currency
             FROM tariffs
             WHERE id = $1
             "#,
@@ -56,18 +91,14 @@ impl TariffRepository for PostgresTariffRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.map(|r| Tariff {
-            id: r.get("id"),
-            price_per_minute: r.get("price_per_minute"),
-            minimal_rating: r.get("minimal_rating"),
-            minimal_experience: r.get::<i32, _>("minimal_experience") as u32,
-        }))
+        row.as_ref().map(tariff_from_row).transpose()
     }
 
     async fn find_all(&self) -> Result<Vec<Tariff>, CarError> {
         let rows = sqlx::query(
             r#"
-            SELECT id, price_per_minute, minimal_rating, minimal_experience
+            SELECT id, price_per_minute_minor, minimal_rating, minimal_experience, reservation_window_minutes, no_show_fee_minor,
+                   price_per_km_minor, parking_price_per_minute_minor, minimum_fare_minor, daily_cap_minor, rounding, currency
             FROM tariffs
             ORDER BY created_at DESC
             "#,
@@ -75,43 +106,66 @@ impl TariffRepository for PostgresTariffRepository {
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().map(|r| Tariff {
-            id: r.get("id"),
-            price_per_minute: r.get("price_per_minute"),
-            minimal_rating: r.get("minimal_rating"),
-            minimal_experience: r.get::<i32, _>("minimal_experience") as u32,
-        }).collect())
+        rows.iter().map(tariff_from_row).collect()
     }
 
     async fn update(&self, id: Uuid, tariff: &Tariff) -> Result<(), CarError> {
-        sqlx::query(
+        let event = tariff.updated_event().map_err(anyhow::Error::from)?;
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
             r#"
             UPDATE tariffs
-            SET price_per_minute = $2, minimal_rating = $3, minimal_experience = $4
+            SET price_per_minute_minor = $2, minimal_rating = $3, minimal_experience = $4,
+                reservation_window_minutes = $5, no_show_fee_minor = $6,
+                price_per_km_minor = $7, parking_price_per_minute_minor = $8, minimum_fare_minor = $9,
+                daily_cap_minor = $10, rounding = $11, currency = $12
             WHERE id = $1
             "#,
         )
         .bind(id)
-        .bind(tariff.price_per_minute)
+        .bind(tariff.price_per_minute.minor_units)
         .bind(tariff.minimal_rating)
         .bind(tariff.minimal_experience as i32)
-        .execute(&self.pool)
+        .bind(tariff.reservation_window_minutes as i32)
+        .bind(tariff.no_show_fee.minor_units)
+        .bind(tariff.price_per_km.minor_units)
+        .bind(tariff.parking_price_per_minute.minor_units)
+        .bind(tariff.minimum_fare.minor_units)
+        .bind(tariff.daily_cap.minor_units)
+        .bind(tariff.rounding.as_str())
+        .bind(tariff.currency().as_str())
+        .execute(&mut *tx)
         .await?;
-        
+
+        if result.rows_affected() > 0 {
+            enqueue_event(&mut tx, &event).await?;
+        }
+
+        tx.commit().await?;
         Ok(())
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), CarError> {
-        sqlx::query(
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
             r#"
             DELETE FROM tariffs
             WHERE id = $1
             "#,
         )
         .bind(id)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
-        
+
+        if result.rows_affected() > 0 {
+            let event = OutboxEvent::new(TARIFF_AGGREGATE, id, "TariffDeleted", &serde_json::json!({ "id": id }))
+                .map_err(anyhow::Error::from)?;
+            enqueue_event(&mut tx, &event).await?;
+        }
+
+        tx.commit().await?;
         Ok(())
     }
 }
@@ -1,8 +1,8 @@
 use std::sync::Arc;
 use crate::{
     application::use_cases::{
-        CreateCarUseCase, GetCarUseCase, UpdateCarUseCase, DeleteCarUseCase, ListCarsUseCase,
-        CreateTariffUseCase, GetTariffUseCase, UpdateTariffUseCase, ListTariffsUseCase,
+        CreateCarUseCase, GetCarUseCase, UpdateCarUseCase, DeleteCarUseCase, ListCarsUseCase, TransitionCarStateUseCase,
+        CreateTariffUseCase, GetTariffUseCase, UpdateTariffUseCase, ListTariffsUseCase, QuoteTripUseCase,
     },
     domain::interfaces::{CarRepository, TariffRepository},
 };
@@ -17,10 +17,12 @@ where
     pub update_car_use_case: Arc<UpdateCarUseCase<CR, TR>>,
     pub delete_car_use_case: Arc<DeleteCarUseCase<CR>>,
     pub list_cars_use_case: Arc<ListCarsUseCase<CR>>,
+    pub transition_car_state_use_case: Arc<TransitionCarStateUseCase<CR>>,
     pub create_tariff_use_case: Arc<CreateTariffUseCase<TR>>,
     pub get_tariff_use_case: Arc<GetTariffUseCase<TR>>,
     pub update_tariff_use_case: Arc<UpdateTariffUseCase<TR>>,
     pub list_tariffs_use_case: Arc<ListTariffsUseCase<TR>>,
+    pub quote_trip_use_case: Arc<QuoteTripUseCase<CR, TR>>,
 }
 
 impl<CR, TR> Clone for AppState<CR, TR>
@@ -35,10 +37,12 @@ where
             update_car_use_case: Arc::clone(&self.update_car_use_case),
             delete_car_use_case: Arc::clone(&self.delete_car_use_case),
             list_cars_use_case: Arc::clone(&self.list_cars_use_case),
+            transition_car_state_use_case: Arc::clone(&self.transition_car_state_use_case),
             create_tariff_use_case: Arc::clone(&self.create_tariff_use_case),
             get_tariff_use_case: Arc::clone(&self.get_tariff_use_case),
             update_tariff_use_case: Arc::clone(&self.update_tariff_use_case),
             list_tariffs_use_case: Arc::clone(&self.list_tariffs_use_case),
+            quote_trip_use_case: Arc::clone(&self.quote_trip_use_case),
         }
     }
 }
@@ -9,7 +9,7 @@ use tracing::{info, warn, error};
 use crate::{
     presentation::app_state::AppState,
     domain::errors::CarError,
-    domain::models::CarState,
+    domain::models::{CarState, Money},
 };
 
 #[derive(Deserialize)]
@@ -20,7 +20,7 @@ pub struct CreateCarRequest {
     #[serde(with = "car_state_serde")]
     pub state: CarState,
     pub tariff_id: Uuid,
-    pub base_price: f64,
+    pub base_price: Money,
 }
 
 #[derive(Serialize)]
@@ -36,7 +36,7 @@ pub struct UpdateCarRequest {
     #[serde(with = "option_car_state_serde")]
     pub state: Option<CarState>,
     pub tariff_id: Option<Uuid>,
-    pub base_price: Option<f64>,
+    pub base_price: Option<Money>,
 }
 
 #[derive(Serialize)]
@@ -53,7 +53,7 @@ pub struct CarResponse {
     #[serde(with = "car_state_serde")]
     pub state: CarState,
     pub tariff_id: Uuid,
-    pub base_price: f64,
+    pub base_price: Money,
 }
 
 impl From<crate::domain::models::Car> for CarResponse {
@@ -70,6 +70,14 @@ impl From<crate::domain::models::Car> for CarResponse {
     }
 }
 
+#[derive(Deserialize)]
+pub struct TransitionCarStateRequest {
+    #[serde(with = "car_state_serde")]
+    pub from: CarState,
+    #[serde(with = "car_state_serde")]
+    pub to: CarState,
+}
+
 #[derive(Deserialize)]
 pub struct ListCarsQuery {
     pub tariff_id: Option<Uuid>,
@@ -166,6 +174,13 @@ where
                 Json(serde_json::json!({"error": format!("IoT device {} already registered", serial_number)})),
             ))
         }
+        Err(CarError::CurrencyMismatch { expected, actual }) => {
+            warn!("Currency mismatch: expected {}, got {}", expected, actual);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Currency mismatch: expected {}, got {}", expected, actual)})),
+            ))
+        }
         Err(e) => {
             error!("Error creating car: {:?}", e);
             Err((
@@ -261,6 +276,13 @@ where
                 Json(serde_json::json!({"error": format!("IoT device {} already registered", serial_number)})),
             ))
         }
+        Err(CarError::CurrencyMismatch { expected, actual }) => {
+            warn!("Currency mismatch: expected {}, got {}", expected, actual);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Currency mismatch: expected {}, got {}", expected, actual)})),
+            ))
+        }
         Err(e) => {
             error!("Error updating car {}: {:?}", car_id, e);
             Err((
@@ -271,6 +293,47 @@ where
     }
 }
 
+pub async fn transition_car_state_handler<CR, TR>(
+    State(state): State<AppState<CR, TR>>,
+    Path(car_id): Path<Uuid>,
+    Json(request): Json<TransitionCarStateRequest>,
+) -> Result<Json<UpdateCarResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    CR: crate::domain::interfaces::CarRepository + Send + Sync + 'static,
+    TR: crate::domain::interfaces::TariffRepository + Send + Sync + 'static,
+{
+    info!("Transitioning car {} state: {} -> {}", car_id, request.from.as_str(), request.to.as_str());
+    match state.transition_car_state_use_case.execute(car_id, request.from, request.to).await {
+        Ok(_) => {
+            info!("Car {} state changed to {}", car_id, request.to.as_str());
+            Ok(Json(UpdateCarResponse {
+                message: "Car state updated successfully".to_string(),
+            }))
+        }
+        Err(CarError::CarNotFound) => {
+            warn!("Car not found for state transition: {}", car_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Car not found"})),
+            ))
+        }
+        Err(CarError::InvalidStateTransition { from, to }) => {
+            warn!("Invalid state transition for car {}: {} -> {}", car_id, from, to);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": format!("Invalid car state transition from {} to {}", from, to)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error transitioning car {} state: {:?}", car_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
 pub async fn delete_car_handler<CR, TR>(
     State(state): State<AppState<CR, TR>>,
     Path(car_id): Path<Uuid>,
@@ -328,3 +391,72 @@ where
     }
 }
 
+
+#[derive(Deserialize)]
+pub struct QuoteTripRequest {
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub ended_at: chrono::DateTime<chrono::Utc>,
+    // Без расстояния и стоянки стоимость не считается: иначе поездка была бы оплачена без них
+    pub distance_km: f64,
+    pub parking_minutes: u64,
+}
+
+#[derive(Serialize)]
+pub struct QuoteTripResponse {
+    pub car_id: Uuid,
+    pub tariff_id: Uuid,
+    #[serde(flatten)]
+    pub quote: crate::domain::models::FareQuote,
+}
+
+pub async fn quote_trip_handler<CR, TR>(
+    State(state): State<AppState<CR, TR>>,
+    Path(car_id): Path<Uuid>,
+    Json(request): Json<QuoteTripRequest>,
+) -> Result<Json<QuoteTripResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    CR: crate::domain::interfaces::CarRepository + Send + Sync + 'static,
+    TR: crate::domain::interfaces::TariffRepository + Send + Sync + 'static,
+{
+    info!("Quoting trip for car {}: {} - {}", car_id, request.started_at, request.ended_at);
+    match state.quote_trip_use_case.execute(
+        car_id,
+        request.started_at,
+        request.ended_at,
+        request.parking_minutes,
+        request.distance_km,
+    ).await {
+        Ok((tariff_id, quote)) => {
+            info!("Trip quote for car {}: {}", car_id, quote.total);
+            Ok(Json(QuoteTripResponse { car_id, tariff_id, quote }))
+        }
+        Err(CarError::InvalidQuoteRequest(message)) => {
+            warn!("Invalid quote request for car {}: {}", car_id, message);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(CarError::CarNotFound) => {
+            warn!("Car not found for quote: {}", car_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Car not found"})),
+            ))
+        }
+        Err(CarError::TariffNotFound) => {
+            warn!("Tariff not found for car quote: {}", car_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Tariff not found"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error quoting trip for car {}: {:?}", car_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
@@ -8,12 +8,20 @@ use uuid::Uuid;
 use tracing::{info, warn, error};
 use crate::presentation::app_state::AppState;
 use crate::domain::errors::CarError;
+use crate::domain::models::{FareRounding, Money};
 
 #[derive(Deserialize)]
 pub struct CreateTariffRequest {
-    pub price_per_minute: f64,
+    pub price_per_minute: Money,
     pub minimal_rating: f64,
     pub minimal_experience: u32,
+    pub reservation_window_minutes: Option<u32>,
+    pub no_show_fee: Option<Money>,
+    pub price_per_km: Option<Money>,
+    pub parking_price_per_minute: Option<Money>,
+    pub minimum_fare: Option<Money>,
+    pub daily_cap: Option<Money>,
+    pub rounding: Option<FareRounding>,
 }
 
 #[derive(Serialize)]
@@ -23,9 +31,16 @@ pub struct CreateTariffResponse {
 
 #[derive(Deserialize)]
 pub struct UpdateTariffRequest {
-    pub price_per_minute: Option<f64>,
+    pub price_per_minute: Option<Money>,
     pub minimal_rating: Option<f64>,
     pub minimal_experience: Option<u32>,
+    pub reservation_window_minutes: Option<u32>,
+    pub no_show_fee: Option<Money>,
+    pub price_per_km: Option<Money>,
+    pub parking_price_per_minute: Option<Money>,
+    pub minimum_fare: Option<Money>,
+    pub daily_cap: Option<Money>,
+    pub rounding: Option<FareRounding>,
 }
 
 #[derive(Serialize)]
@@ -36,9 +51,16 @@ pub struct UpdateTariffResponse {
 #[derive(Serialize)]
 pub struct TariffResponse {
     pub id: Uuid,
-    pub price_per_minute: f64,
+    pub price_per_minute: Money,
     pub minimal_rating: f64,
     pub minimal_experience: u32,
+    pub reservation_window_minutes: u32,
+    pub no_show_fee: Money,
+    pub price_per_km: Money,
+    pub parking_price_per_minute: Money,
+    pub minimum_fare: Money,
+    pub daily_cap: Money,
+    pub rounding: FareRounding,
 }
 
 impl From<crate::domain::models::Tariff> for TariffResponse {
@@ -48,6 +70,13 @@ impl From<crate::domain::models::Tariff> for TariffResponse {
             price_per_minute: tariff.price_per_minute,
             minimal_rating: tariff.minimal_rating,
             minimal_experience: tariff.minimal_experience,
+            reservation_window_minutes: tariff.reservation_window_minutes,
+            no_show_fee: tariff.no_show_fee,
+            price_per_km: tariff.price_per_km,
+            parking_price_per_minute: tariff.parking_price_per_minute,
+            minimum_fare: tariff.minimum_fare,
+            daily_cap: tariff.daily_cap,
+            rounding: tariff.rounding,
         }
     }
 }
@@ -61,10 +90,20 @@ where
     TR: crate::domain::interfaces::TariffRepository + Send + Sync + 'static,
 {
     info!("Creating tariff with price_per_minute: {}", request.price_per_minute);
+    // Необязательные суммы по умолчанию нулевые в валюте поминутной ставки
+    let zero = Money::zero(request.price_per_minute.currency);
     let create_request = crate::domain::models::CreateTariffRequest {
         price_per_minute: request.price_per_minute,
         minimal_rating: request.minimal_rating,
         minimal_experience: request.minimal_experience,
+        reservation_window_minutes: request.reservation_window_minutes
+            .unwrap_or(crate::domain::models::DEFAULT_RESERVATION_WINDOW_MINUTES),
+        no_show_fee: request.no_show_fee.unwrap_or(zero),
+        price_per_km: request.price_per_km.unwrap_or(zero),
+        parking_price_per_minute: request.parking_price_per_minute.unwrap_or(zero),
+        minimum_fare: request.minimum_fare.unwrap_or(zero),
+        daily_cap: request.daily_cap.unwrap_or(zero),
+        rounding: request.rounding.unwrap_or_default(),
     };
 
     match state.create_tariff_use_case.execute(create_request).await {
@@ -72,6 +111,13 @@ where
             info!("Tariff created successfully: {}", tariff_id);
             Ok(Json(CreateTariffResponse { tariff_id }))
         }
+        Err(CarError::CurrencyMismatch { expected, actual }) => {
+            warn!("Currency mismatch: expected {}, got {}", expected, actual);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Currency mismatch: expected {}, got {}", expected, actual)})),
+            ))
+        }
         Err(e) => {
             error!("Error creating tariff: {:?}", e);
             Err((
@@ -127,6 +173,13 @@ where
         price_per_minute: request.price_per_minute,
         minimal_rating: request.minimal_rating,
         minimal_experience: request.minimal_experience,
+        reservation_window_minutes: request.reservation_window_minutes,
+        no_show_fee: request.no_show_fee,
+        price_per_km: request.price_per_km,
+        parking_price_per_minute: request.parking_price_per_minute,
+        minimum_fare: request.minimum_fare,
+        daily_cap: request.daily_cap,
+        rounding: request.rounding,
     };
 
     match state.update_tariff_use_case.execute(tariff_id, update_request).await {
@@ -143,6 +196,13 @@ where
                 Json(serde_json::json!({"error": "Tariff not found"})),
             ))
         }
+        Err(CarError::CurrencyMismatch { expected, actual }) => {
+            warn!("Currency mismatch: expected {}, got {}", expected, actual);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Currency mismatch: expected {}, got {}", expected, actual)})),
+            ))
+        }
         Err(e) => {
             error!("Error updating tariff {}: {:?}", tariff_id, e);
             Err((
@@ -19,6 +19,8 @@ where
         .route("/cars/:id", get(get_car_handler))
         .route("/cars/:id", put(update_car_handler))
         .route("/cars/:id", delete(delete_car_handler))
+        .route("/cars/:id/state", put(transition_car_state_handler))
+        .route("/cars/:id/quote", post(quote_trip_handler))
         // Tariff routes
         .route("/tariffs", post(create_tariff_handler))
         .route("/tariffs", get(list_tariffs_handler))
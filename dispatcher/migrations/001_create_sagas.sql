@@ -0,0 +1,49 @@
+-- Migration: Create sagas and their step log for multi-service trip flows
+-- Created: 2024-05-06
+
+CREATE TABLE IF NOT EXISTS sagas (
+    id UUID PRIMARY KEY,
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('start_trip', 'end_trip', 'cancel_trip')),
+    user_id UUID NOT NULL,
+    -- У саги начала поездки id появляется только после резервирования
+    trip_id UUID,
+    car_id UUID,
+    status VARCHAR(20) NOT NULL CHECK (status IN ('running', 'compensating', 'completed', 'compensated', 'failed')),
+    attempts INTEGER NOT NULL DEFAULT 0 CHECK (attempts >= 0),
+    last_error TEXT,
+    -- Когда фоновый обработчик может повторить незавершенную сагу
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    -- Аренда: пока она не истекла, сагу выполняет кто-то другой
+    locked_until TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Для поездки одновременно выполняется не больше одной саги каждого вида
+CREATE UNIQUE INDEX IF NOT EXISTS idx_sagas_unfinished_trip
+    ON sagas(kind, trip_id)
+    WHERE trip_id IS NOT NULL AND status IN ('running', 'compensating');
+
+-- Пользователь не может начинать две поездки параллельно
+CREATE UNIQUE INDEX IF NOT EXISTS idx_sagas_unfinished_start
+    ON sagas(user_id)
+    WHERE kind = 'start_trip' AND status IN ('running', 'compensating');
+
+CREATE INDEX IF NOT EXISTS idx_sagas_trip_id ON sagas(trip_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_sagas_due
+    ON sagas(next_attempt_at)
+    WHERE status IN ('running', 'compensating');
+
+-- Журнал шагов только дополняется: по нему сага продолжается с упавшего шага
+CREATE TABLE IF NOT EXISTS saga_steps (
+    id BIGSERIAL PRIMARY KEY,
+    saga_id UUID NOT NULL REFERENCES sagas(id),
+    step VARCHAR(64) NOT NULL,
+    status VARCHAR(20) NOT NULL CHECK (status IN ('completed', 'failed', 'compensated')),
+    -- Результат выполненного шага, который получают повторные запуски вместо нового вызова
+    output JSONB,
+    error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_saga_steps_saga_id ON saga_steps(saga_id, id);
@@ -0,0 +1,18 @@
+-- Migration: Create audit log of rejected car commands
+-- Created: 2024-05-13
+
+-- Журнал только дополняется: каждая отклоненная команда машине с причиной отказа
+CREATE TABLE IF NOT EXISTS command_rejections (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    role VARCHAR(20) NOT NULL,
+    car_id UUID NOT NULL,
+    command_type VARCHAR(64) NOT NULL,
+    trip_id UUID,
+    trip_status VARCHAR(20),
+    reason VARCHAR(32) NOT NULL CHECK (reason IN ('unknown_command', 'not_allowed_for_role', 'no_trip_on_car', 'trip_status_not_allowed')),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_command_rejections_created_at ON command_rejections(created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_command_rejections_user_id ON command_rejections(user_id, created_at DESC);
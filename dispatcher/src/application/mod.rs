@@ -1,2 +1,2 @@
 pub mod use_cases;
-
+pub mod sagas;
@@ -0,0 +1,5 @@
+mod saga_coordinator;
+
+pub use saga_coordinator::*;
+#[cfg(test)]
+pub(crate) use saga_coordinator::tests::InMemorySagaRepository;
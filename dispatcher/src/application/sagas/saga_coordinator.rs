@@ -0,0 +1,436 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use serde::{de::DeserializeOwned, Serialize};
+use uuid::Uuid;
+use tracing::{info, warn, error};
+use crate::domain::{
+    errors::DispatcherError,
+    interfaces::SagaRepository,
+    models::{Saga, SagaKind, SagaStatus, SagaStep, StepStatus},
+};
+
+#[derive(Debug, Clone)]
+pub struct SagaPolicy {
+    // После падения процесса по истечении аренды сагу подхватывает фоновый обработчик
+    pub lease: Duration,
+    // После стольких неудачных запусков сага останавливается в статусе failed
+    pub max_attempts: u32,
+    // Пауза перед первым повтором, дальше удваивается
+    pub retry_base_delay: Duration,
+}
+
+impl Default for SagaPolicy {
+    fn default() -> Self {
+        Self {
+            lease: Duration::from_secs(60),
+            max_attempts: 10,
+            retry_base_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+// Выполненный шаг при повторном запуске не вызывается, а возвращает сохраненный результат
+pub struct SagaCoordinator<SR>
+where
+    SR: SagaRepository + Send + Sync + 'static,
+{
+    repository: Arc<SR>,
+    policy: SagaPolicy,
+}
+
+impl<SR> SagaCoordinator<SR>
+where
+    SR: SagaRepository + Send + Sync + 'static,
+{
+    pub fn new(repository: Arc<SR>, policy: SagaPolicy) -> Self {
+        Self { repository, policy }
+    }
+
+    pub async fn begin(
+        &self,
+        kind: SagaKind,
+        user_id: Uuid,
+        trip_id: Option<Uuid>,
+        car_id: Option<Uuid>,
+    ) -> Result<Saga, DispatcherError> {
+        let saga = Saga::new(kind, user_id, trip_id, car_id, Utc::now() + self.lease());
+        if !self.repository.create(&saga).await? {
+            return Err(DispatcherError::Conflict {
+                message: format!("{} is already in progress", kind.as_str()),
+            });
+        }
+        info!("Saga {} ({}) started for user {}", saga.id, kind.as_str(), user_id);
+        Ok(saga)
+    }
+
+    pub async fn latest_for_trip(&self, kind: SagaKind, trip_id: Uuid) -> Result<Option<Saga>, DispatcherError> {
+        self.repository.find_latest_by_trip(kind, trip_id).await
+    }
+
+    // None, если сагу уже выполняет кто-то другой
+    pub async fn acquire(&self, saga_id: Uuid) -> Result<Option<Saga>, DispatcherError> {
+        let now = Utc::now();
+        if !self.repository.try_lock(saga_id, now, now + self.lease()).await? {
+            return Ok(None);
+        }
+        self.repository.find_by_id(saga_id).await
+    }
+
+    pub async fn due(&self, limit: i64) -> Result<Vec<Uuid>, DispatcherError> {
+        self.repository.find_due(Utc::now(), limit).await
+    }
+
+    // Шаги должны быть идемпотентными: после падения между вызовом сервиса и записью в журнал шаг выполнится повторно
+    pub async fn step<T, F, Fut>(&self, saga: &mut Saga, name: &str, action: F) -> Result<T, DispatcherError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, DispatcherError>>,
+    {
+        if let Some(output) = saga.step_output(name)? {
+            return Ok(output);
+        }
+
+        match action().await {
+            Ok(output) => {
+                let step = SagaStep {
+                    step: name.to_string(),
+                    status: StepStatus::Completed,
+                    output: Some(serde_json::to_value(&output)?),
+                    error: None,
+                    created_at: Utc::now(),
+                };
+                self.repository.append_step(saga.id, &step).await?;
+                saga.steps.push(step);
+                Ok(output)
+            }
+            Err(e) => {
+                self.record_failure(saga, name, &e).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn compensate<F, Fut>(&self, saga: &mut Saga, name: &str, action: F) -> Result<(), DispatcherError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<(), DispatcherError>>,
+    {
+        if saga.is_step_compensated(name) {
+            return Ok(());
+        }
+
+        match action().await {
+            Ok(()) => {
+                let step = SagaStep {
+                    step: name.to_string(),
+                    status: StepStatus::Compensated,
+                    output: None,
+                    error: None,
+                    created_at: Utc::now(),
+                };
+                self.repository.append_step(saga.id, &step).await?;
+                saga.steps.push(step);
+                Ok(())
+            }
+            Err(e) => {
+                self.record_failure(saga, name, &e).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn start_compensation(&self, saga: &mut Saga, cause: &DispatcherError) -> Result<(), DispatcherError> {
+        if saga.status == SagaStatus::Compensating {
+            return Ok(());
+        }
+        warn!("Saga {} ({}) is compensating: {}", saga.id, saga.kind.as_str(), cause);
+        saga.status = SagaStatus::Compensating;
+        saga.last_error = Some(cause.to_string());
+        saga.updated_at = Utc::now();
+        self.repository.update(saga).await
+    }
+
+    pub async fn complete(&self, saga: &mut Saga) -> Result<(), DispatcherError> {
+        if saga.status == SagaStatus::Completed {
+            return Ok(());
+        }
+        self.finish(saga, SagaStatus::Completed).await?;
+        info!("Saga {} ({}) completed", saga.id, saga.kind.as_str());
+        Ok(())
+    }
+
+    pub async fn compensated(&self, saga: &mut Saga) -> Result<(), DispatcherError> {
+        self.finish(saga, SagaStatus::Compensated).await?;
+        info!("Saga {} ({}) compensated", saga.id, saga.kind.as_str());
+        Ok(())
+    }
+
+    // Временная ошибка откладывает повтор с растущей паузой, постоянная или последняя попытка останавливает сагу
+    pub async fn suspend(&self, saga: &mut Saga, cause: &DispatcherError) -> Result<(), DispatcherError> {
+        let now = Utc::now();
+        saga.attempts += 1;
+        saga.last_error = Some(cause.to_string());
+        saga.locked_until = None;
+        saga.updated_at = now;
+
+        if cause.is_transient() && saga.attempts < self.policy.max_attempts {
+            let delay = self.policy.retry_base_delay.saturating_mul(1 << (saga.attempts - 1).min(6));
+            saga.next_attempt_at = now + to_chrono(delay);
+            warn!(
+                "Saga {} ({}) attempt {} failed, retry at {}: {}",
+                saga.id, saga.kind.as_str(), saga.attempts, saga.next_attempt_at, cause
+            );
+        } else {
+            saga.status = SagaStatus::Failed;
+            error!(
+                "Saga {} ({}) failed after {} attempts: {}",
+                saga.id, saga.kind.as_str(), saga.attempts, cause
+            );
+        }
+
+        self.repository.update(saga).await
+    }
+
+    pub async fn set_trip(&self, saga: &mut Saga, trip_id: Uuid) -> Result<(), DispatcherError> {
+        saga.trip_id = Some(trip_id);
+        saga.updated_at = Utc::now();
+        self.repository.update(saga).await
+    }
+
+    async fn finish(&self, saga: &mut Saga, status: SagaStatus) -> Result<(), DispatcherError> {
+        saga.status = status;
+        saga.locked_until = None;
+        saga.updated_at = Utc::now();
+        self.repository.update(saga).await
+    }
+
+    async fn record_failure(&self, saga: &mut Saga, name: &str, cause: &DispatcherError) {
+        let step = SagaStep {
+            step: name.to_string(),
+            status: StepStatus::Failed,
+            output: None,
+            error: Some(cause.to_string()),
+            created_at: Utc::now(),
+        };
+        // Ошибка записи в журнал не должна подменять ошибку шага
+        match self.repository.append_step(saga.id, &step).await {
+            Ok(()) => saga.steps.push(step),
+            Err(e) => error!("Failed to record failed step {} of saga {}: {}", name, saga.id, e),
+        }
+    }
+
+    fn lease(&self) -> chrono::Duration {
+        to_chrono(self.policy.lease)
+    }
+}
+
+fn to_chrono(duration: Duration) -> chrono::Duration {
+    // Заведомо слишком большие значения из настроек ограничиваем годом
+    chrono::Duration::from_std(duration)
+        .unwrap_or_else(|_| chrono::Duration::days(365))
+        .min(chrono::Duration::days(365))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::DateTime;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    // Повторяет ограничения и выборки PostgresSagaRepository
+    #[derive(Default)]
+    pub(crate) struct InMemorySagaRepository {
+        sagas: Mutex<HashMap<Uuid, Saga>>,
+    }
+
+    impl InMemorySagaRepository {
+        pub(crate) fn insert(&self, saga: Saga) {
+            self.sagas.lock().unwrap().insert(saga.id, saga);
+        }
+
+        pub(crate) fn get(&self, id: Uuid) -> Saga {
+            self.sagas.lock().unwrap()[&id].clone()
+        }
+    }
+
+    #[async_trait]
+    impl SagaRepository for InMemorySagaRepository {
+        async fn create(&self, saga: &Saga) -> Result<bool, DispatcherError> {
+            let mut sagas = self.sagas.lock().unwrap();
+            let conflict = sagas.values().any(|other| {
+                !other.status.is_finished()
+                    && ((other.kind == saga.kind && saga.trip_id.is_some() && other.trip_id == saga.trip_id)
+                        || (saga.kind == SagaKind::StartTrip && other.kind == SagaKind::StartTrip && other.user_id == saga.user_id))
+            });
+            if conflict {
+                return Ok(false);
+            }
+            sagas.insert(saga.id, saga.clone());
+            Ok(true)
+        }
+
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Saga>, DispatcherError> {
+            Ok(self.sagas.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_latest_by_trip(&self, kind: SagaKind, trip_id: Uuid) -> Result<Option<Saga>, DispatcherError> {
+            Ok(self.sagas.lock().unwrap().values()
+                .filter(|saga| saga.kind == kind && saga.trip_id == Some(trip_id))
+                .max_by_key(|saga| saga.created_at)
+                .cloned())
+        }
+
+        async fn find_due(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Uuid>, DispatcherError> {
+            let sagas = self.sagas.lock().unwrap();
+            let mut due: Vec<&Saga> = sagas.values()
+                .filter(|saga| !saga.status.is_finished())
+                .filter(|saga| saga.next_attempt_at <= now)
+                .filter(|saga| saga.locked_until.is_none_or(|until| until < now))
+                .collect();
+            due.sort_by_key(|saga| saga.next_attempt_at);
+            Ok(due.into_iter().take(limit as usize).map(|saga| saga.id).collect())
+        }
+
+        async fn try_lock(&self, id: Uuid, now: DateTime<Utc>, until: DateTime<Utc>) -> Result<bool, DispatcherError> {
+            let mut sagas = self.sagas.lock().unwrap();
+            match sagas.get_mut(&id) {
+                Some(saga) if !saga.status.is_finished() && saga.locked_until.is_none_or(|locked| locked < now) => {
+                    saga.locked_until = Some(until);
+                    saga.updated_at = now;
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+
+        async fn append_step(&self, saga_id: Uuid, step: &SagaStep) -> Result<(), DispatcherError> {
+            let mut sagas = self.sagas.lock().unwrap();
+            let saga = sagas.get_mut(&saga_id).expect("saga exists");
+            saga.steps.push(step.clone());
+            Ok(())
+        }
+
+        async fn update(&self, saga: &Saga) -> Result<(), DispatcherError> {
+            let mut sagas = self.sagas.lock().unwrap();
+            let stored = sagas.get_mut(&saga.id).expect("saga exists");
+            // Журнал шагов пишется только через append_step
+            let steps = std::mem::take(&mut stored.steps);
+            *stored = Saga { steps, ..saga.clone() };
+            Ok(())
+        }
+    }
+
+    fn coordinator(max_attempts: u32) -> (SagaCoordinator<InMemorySagaRepository>, Arc<InMemorySagaRepository>) {
+        let repository = Arc::new(InMemorySagaRepository::default());
+        let policy = SagaPolicy {
+            lease: Duration::from_secs(60),
+            max_attempts,
+            retry_base_delay: Duration::from_secs(10),
+        };
+        (SagaCoordinator::new(Arc::clone(&repository), policy), repository)
+    }
+
+    fn unavailable() -> DispatcherError {
+        DispatcherError::ServiceUnavailable { service: "trips".to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_completed_step_is_not_executed_again() {
+        let (sagas, repository) = coordinator(3);
+        let mut saga = sagas.begin(SagaKind::EndTrip, Uuid::new_v4(), Some(Uuid::new_v4()), None).await.unwrap();
+        let first: u32 = sagas.step(&mut saga, "end_trip", || async { Ok(42) }).await.unwrap();
+
+        // Повторный запуск читает сагу из хранилища и получает результат из журнала
+        let mut reloaded = repository.get(saga.id);
+        let second: u32 = sagas
+            .step(&mut reloaded, "end_trip", || async { panic!("completed step must not run again") })
+            .await
+            .unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(repository.get(saga.id).steps.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_step_is_retried_on_resume() {
+        let (sagas, repository) = coordinator(3);
+        let mut saga = sagas.begin(SagaKind::CancelTrip, Uuid::new_v4(), Some(Uuid::new_v4()), None).await.unwrap();
+        let result: Result<(), _> = sagas.step(&mut saga, "cancel_trip", || async { Err(unavailable()) }).await;
+        assert!(result.is_err());
+
+        let mut reloaded = repository.get(saga.id);
+        assert_eq!(reloaded.steps[0].status, StepStatus::Failed);
+        sagas.step(&mut reloaded, "cancel_trip", || async { Ok(()) }).await.unwrap();
+
+        let statuses: Vec<StepStatus> = repository.get(saga.id).steps.iter().map(|step| step.status).collect();
+        assert_eq!(statuses, vec![StepStatus::Failed, StepStatus::Completed]);
+    }
+
+    #[tokio::test]
+    async fn test_transient_error_schedules_retry_with_backoff() {
+        let (sagas, repository) = coordinator(5);
+        let mut saga = sagas.begin(SagaKind::EndTrip, Uuid::new_v4(), Some(Uuid::new_v4()), None).await.unwrap();
+
+        sagas.suspend(&mut saga, &unavailable()).await.unwrap();
+        let first_delay = saga.next_attempt_at - saga.updated_at;
+        sagas.suspend(&mut saga, &unavailable()).await.unwrap();
+        let second_delay = saga.next_attempt_at - saga.updated_at;
+
+        let stored = repository.get(saga.id);
+        assert_eq!(stored.status, SagaStatus::Running);
+        assert_eq!(stored.attempts, 2);
+        assert_eq!(stored.locked_until, None);
+        assert_eq!(first_delay, chrono::Duration::seconds(10));
+        assert_eq!(second_delay, chrono::Duration::seconds(20));
+    }
+
+    #[tokio::test]
+    async fn test_saga_fails_after_max_attempts() {
+        let (sagas, repository) = coordinator(2);
+        let mut saga = sagas.begin(SagaKind::EndTrip, Uuid::new_v4(), Some(Uuid::new_v4()), None).await.unwrap();
+
+        sagas.suspend(&mut saga, &unavailable()).await.unwrap();
+        assert_eq!(repository.get(saga.id).status, SagaStatus::Running);
+        sagas.suspend(&mut saga, &unavailable()).await.unwrap();
+
+        assert_eq!(repository.get(saga.id).status, SagaStatus::Failed);
+        assert!(sagas.due(10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_permanent_error_fails_saga_immediately() {
+        let (sagas, repository) = coordinator(10);
+        let mut saga = sagas.begin(SagaKind::CancelTrip, Uuid::new_v4(), Some(Uuid::new_v4()), None).await.unwrap();
+
+        let cause = DispatcherError::InvalidRequest { message: "Trip cannot be cancelled".to_string() };
+        sagas.suspend(&mut saga, &cause).await.unwrap();
+
+        let stored = repository.get(saga.id);
+        assert_eq!(stored.status, SagaStatus::Failed);
+        assert_eq!(stored.attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_running_saga_cannot_be_acquired_twice() {
+        let (sagas, _) = coordinator(3);
+        let saga = sagas.begin(SagaKind::EndTrip, Uuid::new_v4(), Some(Uuid::new_v4()), None).await.unwrap();
+
+        assert!(sagas.acquire(saga.id).await.unwrap().is_none());
+        assert!(sagas.due(10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_second_saga_for_trip_is_conflict() {
+        let (sagas, _) = coordinator(3);
+        let trip_id = Some(Uuid::new_v4());
+        sagas.begin(SagaKind::EndTrip, Uuid::new_v4(), trip_id, None).await.unwrap();
+
+        let result = sagas.begin(SagaKind::EndTrip, Uuid::new_v4(), trip_id, None).await;
+        assert!(matches!(result, Err(DispatcherError::Conflict { .. })));
+    }
+}
@@ -0,0 +1,31 @@
+use uuid::Uuid;
+use std::sync::Arc;
+use crate::domain::{
+    errors::DispatcherError,
+    interfaces::TripsServiceClient,
+};
+
+pub struct ActivateTripScenario<TC> 
+where
+    TC: TripsServiceClient + Send + Sync + 'static,
+{
+    trips_client: Arc<TC>,
+}
+
+impl<TC> ActivateTripScenario<TC>
+where
+    TC: TripsServiceClient + Send + Sync + 'static,
+{
+    pub fn new(trips_client: Arc<TC>) -> Self {
+        Self { trips_client }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, trip_id: Uuid) -> Result<(), DispatcherError> {
+        let trip = self.trips_client.get_trip(trip_id).await?;
+        if trip.user_id != user_id {
+            return Err(DispatcherError::NotFound { resource: format!("Trip {}", trip_id) });
+        }
+
+        self.trips_client.activate_trip(trip_id).await
+    }
+}
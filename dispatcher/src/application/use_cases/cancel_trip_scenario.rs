@@ -1,27 +1,95 @@
 use uuid::Uuid;
 use std::sync::Arc;
+use crate::application::sagas::SagaCoordinator;
 use crate::domain::{
     errors::DispatcherError,
-    interfaces::TripsServiceClient,
+    interfaces::{SagaRepository, TripsServiceClient},
+    models::{Saga, SagaKind, SagaStatus},
 };
+use tracing::error;
 
-pub struct CancelTripScenario<TC> 
+pub struct CancelTripScenario<TC, SR>
 where
     TC: TripsServiceClient + Send + Sync + 'static,
+    SR: SagaRepository + Send + Sync + 'static,
 {
     trips_client: Arc<TC>,
+    sagas: Arc<SagaCoordinator<SR>>,
 }
 
-impl<TC> CancelTripScenario<TC>
+impl<TC, SR> CancelTripScenario<TC, SR>
 where
     TC: TripsServiceClient + Send + Sync + 'static,
+    SR: SagaRepository + Send + Sync + 'static,
 {
-    pub fn new(trips_client: Arc<TC>) -> Self {
-        Self { trips_client }
+    pub fn new(trips_client: Arc<TC>, sagas: Arc<SagaCoordinator<SR>>) -> Self {
+        Self { trips_client, sagas }
     }
 
-    pub async fn execute(&self, trip_id: Uuid) -> Result<(), DispatcherError> {
-        self.trips_client.cancel_trip(trip_id).await
+    pub async fn execute(&self, user_id: Uuid, trip_id: Uuid) -> Result<(), DispatcherError> {
+        let saga = match self.sagas.latest_for_trip(SagaKind::CancelTrip, trip_id).await? {
+            // Чужую поездку отменить нельзя - для клиента она просто не существует
+            Some(saga) if saga.user_id != user_id => {
+                return Err(DispatcherError::NotFound { resource: format!("Trip {}", trip_id) });
+            }
+            Some(saga) if saga.status == SagaStatus::Completed => return Ok(()),
+            Some(saga) if !saga.status.is_finished() => {
+                self.sagas.acquire(saga.id).await?.ok_or_else(|| DispatcherError::Conflict {
+                    message: format!("Trip {} is already being cancelled", trip_id),
+                })?
+            }
+            _ => {
+                let trip = self.trips_client.get_trip(trip_id).await?;
+                if trip.user_id != user_id {
+                    return Err(DispatcherError::NotFound { resource: format!("Trip {}", trip_id) });
+                }
+                self.sagas.begin(SagaKind::CancelTrip, user_id, Some(trip_id), Some(trip.car_id)).await
+                    .map_err(|e| match e {
+                        DispatcherError::Conflict { .. } => DispatcherError::Conflict {
+                            message: format!("Trip {} is already being cancelled", trip_id),
+                        },
+                        e => e,
+                    })?
+            }
+        };
+
+        self.run(saga).await
     }
-}
 
+    pub async fn resume(&self, saga: Saga) -> Result<(), DispatcherError> {
+        self.run(saga).await
+    }
+
+    async fn run(&self, mut saga: Saga) -> Result<(), DispatcherError> {
+        let trip_id = saga.trip_id.ok_or_else(|| {
+            DispatcherError::Internal(anyhow::anyhow!("Saga {} has no trip", saga.id))
+        })?;
+
+        match self.sagas.step(&mut saga, "cancel_trip", || self.cancel_trip(trip_id)).await {
+            Ok(()) => {
+                if let Err(e) = self.sagas.complete(&mut saga).await {
+                    error!("Failed to complete saga {} for trip {}: {}", saga.id, trip_id, e);
+                }
+                Ok(())
+            }
+            Err(e) => {
+                if let Err(save_error) = self.sagas.suspend(&mut saga, &e).await {
+                    error!("Failed to save saga {} after error: {}", saga.id, save_error);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn cancel_trip(&self, trip_id: Uuid) -> Result<(), DispatcherError> {
+        let trip = self.trips_client.get_trip(trip_id).await?;
+        match trip.status.as_str() {
+            "reserved" | "active" => self.trips_client.cancel_trip(trip_id).await,
+            // Поездку отменила предыдущая попытка, ответ на которую потерялся
+            "cancelled" => Ok(()),
+            status => Err(DispatcherError::InvalidRequest {
+                message: format!("Trip {} cannot be cancelled in status {}", trip_id, status),
+            }),
+        }
+    }
+}
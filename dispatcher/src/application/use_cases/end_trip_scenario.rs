@@ -1,74 +1,342 @@
 use uuid::Uuid;
 use std::sync::Arc;
-use chrono::Utc;
+use crate::application::sagas::SagaCoordinator;
 use crate::domain::{
     errors::DispatcherError,
-    interfaces::{TripsServiceClient, BillingServiceClient, CarsServiceClient},
+    interfaces::{TripsServiceClient, BillingServiceClient, CarsServiceClient, SagaRepository, PaymentInfo, TripInfo, TripQuote, TripQuoteRequest, TripRouteProperties},
+    models::{Saga, SagaKind, SagaStatus},
 };
+use tracing::{info, error};
 
-pub struct EndTripScenario<TC, BC, CC> 
+// Завершение поездки выполняется как сага: завершить поездку, рассчитать стоимость, выставить платеж.
+// Выполненные шаги записываются в журнал, поэтому повторный запрос (или фоновый обработчик)
+// продолжает с упавшего шага, а не завершает поездку заново
+pub struct EndTripScenario<TC, BC, CC, SR>
 where
     TC: TripsServiceClient + Send + Sync + 'static,
     BC: BillingServiceClient + Send + Sync + 'static,
     CC: CarsServiceClient + Send + Sync + 'static,
+    SR: SagaRepository + Send + Sync + 'static,
 {
     trips_client: Arc<TC>,
     billing_client: Arc<BC>,
     cars_client: Arc<CC>,
+    sagas: Arc<SagaCoordinator<SR>>,
 }
 
-impl<TC, BC, CC> EndTripScenario<TC, BC, CC>
+impl<TC, BC, CC, SR> EndTripScenario<TC, BC, CC, SR>
 where
     TC: TripsServiceClient + Send + Sync + 'static,
     BC: BillingServiceClient + Send + Sync + 'static,
     CC: CarsServiceClient + Send + Sync + 'static,
+    SR: SagaRepository + Send + Sync + 'static,
 {
-    pub fn new(trips_client: Arc<TC>, billing_client: Arc<BC>, cars_client: Arc<CC>) -> Self {
-        Self { trips_client, billing_client, cars_client }
+    pub fn new(trips_client: Arc<TC>, billing_client: Arc<BC>, cars_client: Arc<CC>, sagas: Arc<SagaCoordinator<SR>>) -> Self {
+        Self { trips_client, billing_client, cars_client, sagas }
     }
 
-    pub async fn execute(&self, trip_id: Uuid) -> Result<(Uuid, Uuid, String), DispatcherError> {
-        // 1. Завершаем поездку
-        self.trips_client.end_trip(trip_id).await?;
-        
-        // 2. Получаем информацию о поездке для расчета стоимости
-        let trip = self.trips_client.get_trip(trip_id).await?;
-        
-        // 3. Рассчитываем стоимость на основе времени поездки и тарифа
-        let amount = self.calculate_trip_cost(&trip).await?;
-        
-        // 4. Создаем платеж
-        let payment = self.billing_client.create_payment(trip_id, trip.user_id, amount).await?;
-        
-        Ok((
-            trip_id,
-            payment.id,
-            payment.qr_code_url.unwrap_or_default(),
-        ))
-    }
-
-    async fn calculate_trip_cost(&self, trip: &crate::domain::interfaces::TripInfo) -> Result<f64, DispatcherError> {
-        // 1. Получаем данные машины
-        let car = self.cars_client.get_car(trip.car_id).await?;
-        
-        // 2. Получаем тариф
-        let tariff = self.cars_client.get_tariff(car.tariff_id).await?;
-        
-        // 3. Рассчитываем время поездки в минутах
-        let minutes = if let (Some(started_at), Some(ended_at)) = (trip.started_at, trip.ended_at) {
-            let duration = ended_at.signed_duration_since(started_at);
-            duration.num_minutes().max(1) as f64 // Минимум 1 минута
-        } else {
-            // Если нет времени начала/окончания, используем время создания
-            let now = Utc::now();
-            let duration = now.signed_duration_since(trip.created_at);
-            duration.num_minutes().max(1) as f64
+    pub async fn execute(&self, user_id: Uuid, trip_id: Uuid) -> Result<(Uuid, PaymentInfo, Option<f64>), DispatcherError> {
+        let saga = match self.sagas.latest_for_trip(SagaKind::EndTrip, trip_id).await? {
+            Some(saga) if saga.user_id != user_id => {
+                return Err(DispatcherError::NotFound { resource: format!("Trip {}", trip_id) });
+            }
+            // Поездка уже завершена и оплачена - возвращаем тот же результат из журнала
+            Some(saga) if saga.status == SagaStatus::Completed => saga,
+            // Предыдущая попытка прервалась - продолжаем с упавшего шага
+            Some(saga) if !saga.status.is_finished() => {
+                self.sagas.acquire(saga.id).await?.ok_or_else(|| DispatcherError::Conflict {
+                    message: format!("Trip {} is already being ended", trip_id),
+                })?
+            }
+            _ => {
+                // Проверяем, что поездка принадлежит пользователю
+                let trip = self.trips_client.get_trip(trip_id).await?;
+                if trip.user_id != user_id {
+                    return Err(DispatcherError::NotFound { resource: format!("Trip {}", trip_id) });
+                }
+                self.sagas.begin(SagaKind::EndTrip, user_id, Some(trip_id), Some(trip.car_id)).await
+                    .map_err(|e| match e {
+                        DispatcherError::Conflict { .. } => DispatcherError::Conflict {
+                            message: format!("Trip {} is already being ended", trip_id),
+                        },
+                        e => e,
+                    })?
+            }
         };
-        
-        // 4. Применяем формулу: (tariff.price_per_minute * minutes) + car.base_price
-        let amount = (tariff.price_per_minute * minutes) + car.base_price;
-        
-        Ok(amount)
+
+        self.run(saga).await
+    }
+
+    // Прерванную сагу берет в работу фоновый обработчик
+    pub async fn resume(&self, saga: Saga) -> Result<(), DispatcherError> {
+        self.run(saga).await.map(|_| ())
+    }
+
+    async fn run(&self, mut saga: Saga) -> Result<(Uuid, PaymentInfo, Option<f64>), DispatcherError> {
+        match self.run_steps(&mut saga).await {
+            Ok(result) => {
+                // Платеж уже выставлен: если не удалось отметить сагу, ее закроет фоновый обработчик
+                if let Err(e) = self.sagas.complete(&mut saga).await {
+                    error!("Failed to complete saga {} for trip {}: {}", saga.id, result.0, e);
+                }
+                Ok(result)
+            }
+            Err(e) => {
+                if let Err(save_error) = self.sagas.suspend(&mut saga, &e).await {
+                    error!("Failed to save saga {} after error: {}", saga.id, save_error);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn run_steps(&self, saga: &mut Saga) -> Result<(Uuid, PaymentInfo, Option<f64>), DispatcherError> {
+        let trip_id = saga.trip_id.ok_or_else(|| {
+            DispatcherError::Internal(anyhow::anyhow!("Saga {} has no trip", saga.id))
+        })?;
+
+        // 1. Завершаем поездку и получаем актуальные данные для расчета стоимости
+        let trip: TripInfo = self.sagas.step(saga, "end_trip", || self.end_trip(trip_id)).await?;
+
+        // 2. Запрашиваем стоимость поездки по тарифу
+        let quote: TripQuote = self.sagas.step(
+            saga,
+            "quote",
+            || quote_trip(self.trips_client.as_ref(), self.cars_client.as_ref(), &trip),
+        ).await?;
+
+        // 3. Создаем платеж: billing применит скидку по промокоду пользователя, если код действует
+        //    на тарифе, и спишет остаток с кошелька, если хватает средств, иначе выставит QR-код
+        let payment: PaymentInfo = self.sagas.step(saga, "create_payment", || self.create_payment(&trip, &quote)).await?;
+        if let Some(discount) = &payment.discount {
+            info!(
+                "Promo code {} applied to trip {}: {} - {} = {}",
+                discount.code, trip_id, quote.total, discount.amount, payment.amount
+            );
+        }
+
+        Ok((trip_id, payment, Some(quote.distance_km)))
+    }
+
+    async fn end_trip(&self, trip_id: Uuid) -> Result<TripInfo, DispatcherError> {
+        let trip = self.trips_client.get_trip(trip_id).await?;
+        match trip.status.as_str() {
+            "active" => {
+                self.trips_client.end_trip(trip_id).await?;
+                self.trips_client.get_trip(trip_id).await
+            }
+            // Поездку завершила предыдущая попытка, ответ на которую потерялся
+            "completed" => Ok(trip),
+            status => Err(DispatcherError::InvalidRequest {
+                message: format!("Trip {} cannot be ended in status {}", trip_id, status),
+            }),
+        }
+    }
+
+    async fn create_payment(&self, trip: &TripInfo, quote: &TripQuote) -> Result<PaymentInfo, DispatcherError> {
+        // Платеж мог создать предыдущий вызов, ответ на который не дошел
+        if let Some(payment) = self.billing_client.get_trip_payment(trip.id).await? {
+            return Ok(payment);
+        }
+        self.billing_client
+            .create_payment(trip.id, trip.user_id, quote.total, quote.tariff_id)
+            .await
     }
 }
 
+// Стоимость считается только по записанному маршруту: без расстояния и времени стоянки
+// поездка была бы оплачена без них. Если маршрут не записался при завершении поездки,
+// запрос маршрута достраивает его в trips; при ошибке шаг упадет и будет повторен
+async fn quote_trip<TC, CC>(trips_client: &TC, cars_client: &CC, trip: &TripInfo) -> Result<TripQuote, DispatcherError>
+where
+    TC: TripsServiceClient + Send + Sync + 'static,
+    CC: CarsServiceClient + Send + Sync + 'static,
+{
+    let (started_at, ended_at) = trip.started_at.zip(trip.ended_at).ok_or_else(|| DispatcherError::InvalidRequest {
+        message: format!("Trip {} has no start or end time", trip.id),
+    })?;
+
+    let (distance_km, parking_minutes) = match (trip.distance_km, trip.parking_minutes) {
+        (Some(distance_km), Some(parking_minutes)) => (distance_km, parking_minutes),
+        _ => {
+            info!("Route of trip {} is not recorded yet, requesting it from trips", trip.id);
+            let route = trips_client.get_trip_route(trip.id).await?;
+            let properties = serde_json::from_value::<TripRouteProperties>(route["properties"].clone())
+                .map_err(|e| DispatcherError::ServiceError {
+                    service: "trips".to_string(),
+                    message: format!("Invalid route of trip {}: {}", trip.id, e),
+                })?;
+            let parking_minutes = properties.parking_minutes.ok_or_else(|| DispatcherError::ServiceError {
+                service: "trips".to_string(),
+                message: format!("Route of trip {} has no parking time", trip.id),
+            })?;
+            (properties.distance_km, parking_minutes)
+        }
+    };
+
+    let request = TripQuoteRequest { started_at, ended_at, distance_km, parking_minutes };
+    let quote = cars_client.quote_trip(trip.car_id, &request).await?;
+    info!(
+        "Trip {} quoted by tariff {}: {} min ({} parking), {:.2} km, total {}",
+        trip.id, quote.tariff_id, quote.billed_minutes, quote.parking_minutes, quote.distance_km, quote.total
+    );
+
+    Ok(quote)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use std::sync::Mutex;
+    use crate::domain::{
+        interfaces::{CarInfo, TariffInfo},
+        models::{Currency, Money},
+    };
+
+    struct MockTripsClient {
+        route: Option<serde_json::Value>,
+        route_requests: Mutex<usize>,
+    }
+
+    impl MockTripsClient {
+        fn new(route: Option<serde_json::Value>) -> Self {
+            Self { route, route_requests: Mutex::new(0) }
+        }
+    }
+
+    #[async_trait]
+    impl TripsServiceClient for MockTripsClient {
+        async fn start_trip(&self, _user_id: Uuid, _car_id: Uuid) -> Result<Uuid, DispatcherError> { unimplemented!() }
+        async fn activate_trip(&self, _trip_id: Uuid) -> Result<(), DispatcherError> { unimplemented!() }
+        async fn end_trip(&self, _trip_id: Uuid) -> Result<(), DispatcherError> { unimplemented!() }
+        async fn cancel_trip(&self, _trip_id: Uuid) -> Result<(), DispatcherError> { unimplemented!() }
+        async fn get_trip(&self, _trip_id: Uuid) -> Result<TripInfo, DispatcherError> { unimplemented!() }
+        async fn get_user_active_trip(&self, _user_id: Uuid) -> Result<Option<TripInfo>, DispatcherError> { unimplemented!() }
+        async fn get_all_trips(&self) -> Result<Vec<TripInfo>, DispatcherError> { unimplemented!() }
+
+        async fn get_trip_route(&self, trip_id: Uuid) -> Result<serde_json::Value, DispatcherError> {
+            *self.route_requests.lock().unwrap() += 1;
+            self.route.clone().ok_or_else(|| DispatcherError::ServiceError {
+                service: "trips".to_string(),
+                message: format!("telematics unavailable for trip {}", trip_id),
+            })
+        }
+    }
+
+    // Запоминает запрос расчета: (расстояние, минуты стоянки)
+    #[derive(Default)]
+    struct MockCarsClient {
+        requests: Mutex<Vec<(f64, u64)>>,
+    }
+
+    #[async_trait]
+    impl CarsServiceClient for MockCarsClient {
+        async fn get_car(&self, _car_id: Uuid) -> Result<CarInfo, DispatcherError> { unimplemented!() }
+        async fn get_all_cars(&self) -> Result<Vec<CarInfo>, DispatcherError> { unimplemented!() }
+        async fn get_tariff(&self, _tariff_id: Uuid) -> Result<TariffInfo, DispatcherError> { unimplemented!() }
+
+        async fn quote_trip(&self, car_id: Uuid, request: &TripQuoteRequest) -> Result<TripQuote, DispatcherError> {
+            self.requests.lock().unwrap().push((request.distance_km, request.parking_minutes));
+            let zero = Money::new(0, Currency::Rub);
+            let minutes = (request.ended_at - request.started_at).num_minutes() as u64;
+            Ok(TripQuote {
+                car_id,
+                tariff_id: Uuid::new_v4(),
+                billed_minutes: minutes,
+                driving_minutes: minutes - request.parking_minutes,
+                parking_minutes: request.parking_minutes,
+                distance_km: request.distance_km,
+                base_fare: zero,
+                driving_cost: zero,
+                parking_cost: zero,
+                distance_cost: zero,
+                daily_cap_applied: false,
+                minimum_fare_applied: false,
+                rounding: "kopeck".to_string(),
+                total: zero,
+            })
+        }
+    }
+
+    fn started_at() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 10, 12, 0, 0).unwrap()
+    }
+
+    fn completed_trip(distance_km: Option<f64>, parking_minutes: Option<u64>) -> TripInfo {
+        TripInfo {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            car_id: Uuid::new_v4(),
+            status: "completed".to_string(),
+            started_at: Some(started_at()),
+            ended_at: Some(started_at() + Duration::minutes(42)),
+            created_at: started_at() - Duration::minutes(5),
+            expires_at: None,
+            distance_km,
+            parking_minutes,
+        }
+    }
+
+    fn route(distance_km: f64, parking_minutes: Option<u64>) -> serde_json::Value {
+        serde_json::json!({
+            "type": "Feature",
+            "geometry": null,
+            "properties": {
+                "trip_id": Uuid::new_v4(),
+                "distance_km": distance_km,
+                "parking_minutes": parking_minutes,
+                "point_count": 0,
+                "timestamps": [],
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_quote_sends_recorded_distance_and_parking() {
+        let trips = MockTripsClient::new(None);
+        let cars = MockCarsClient::default();
+
+        let quote = quote_trip(&trips, &cars, &completed_trip(Some(12.4), Some(7))).await.unwrap();
+
+        assert_eq!(*cars.requests.lock().unwrap(), vec![(12.4, 7)]);
+        assert_eq!(quote.parking_minutes, 7);
+        assert_eq!(quote.driving_minutes, 35);
+        assert_eq!(*trips.route_requests.lock().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_quote_with_missing_distance_uses_rebuilt_route() {
+        let trips = MockTripsClient::new(Some(route(8.25, Some(3))));
+        let cars = MockCarsClient::default();
+
+        let quote = quote_trip(&trips, &cars, &completed_trip(None, None)).await.unwrap();
+
+        assert_eq!(*trips.route_requests.lock().unwrap(), 1);
+        assert_eq!(*cars.requests.lock().unwrap(), vec![(8.25, 3)]);
+        assert_eq!(quote.distance_km, 8.25);
+    }
+
+    #[tokio::test]
+    async fn test_quote_fails_when_route_cannot_be_rebuilt() {
+        let trips = MockTripsClient::new(None);
+        let cars = MockCarsClient::default();
+
+        let result = quote_trip(&trips, &cars, &completed_trip(None, None)).await;
+
+        assert!(matches!(result, Err(DispatcherError::ServiceError { .. })));
+        assert!(cars.requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_quote_fails_when_route_has_no_parking_time() {
+        let trips = MockTripsClient::new(Some(route(8.25, None)));
+        let cars = MockCarsClient::default();
+
+        let result = quote_trip(&trips, &cars, &completed_trip(Some(8.25), None)).await;
+
+        assert!(matches!(result, Err(DispatcherError::ServiceError { .. })));
+        assert!(cars.requests.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+use uuid::Uuid;
+use std::sync::Arc;
+use tracing::error;
+use crate::domain::{
+    errors::DispatcherError,
+    interfaces::{CarsServiceClient, UsersServiceClient, CarInfo},
+    models::check_tariff_eligibility,
+};
+
+pub struct GetAvailableCarsScenario<CC, UC> 
+where
+    CC: CarsServiceClient + Send + Sync + 'static,
+    UC: UsersServiceClient + Send + Sync + 'static,
+{
+    cars_client: Arc<CC>,
+    users_client: Arc<UC>,
+}
+
+impl<CC, UC> GetAvailableCarsScenario<CC, UC>
+where
+    CC: CarsServiceClient + Send + Sync + 'static,
+    UC: UsersServiceClient + Send + Sync + 'static,
+{
+    pub fn new(cars_client: Arc<CC>, users_client: Arc<UC>) -> Self {
+        Self { cars_client, users_client }
+    }
+
+    // eligible_only оставляет только машины, тариф которых доступен пользователю
+    pub async fn execute(&self, user_id: Uuid, eligible_only: bool) -> Result<Vec<CarInfo>, DispatcherError> {
+        // 1. Фильтруем только доступные машины
+        let cars: Vec<CarInfo> = self.cars_client.get_all_cars().await?
+            .into_iter()
+            .filter(|car| car.state == "available")
+            .collect();
+
+        let user = if eligible_only {
+            Some(self.users_client.get_user(user_id).await?)
+        } else {
+            None
+        };
+
+        // 2. Добавляем тарифы и отсеиваем недоступные пользователю
+        let mut available_cars = Vec::with_capacity(cars.len());
+        for mut car in cars {
+            match self.cars_client.get_tariff(car.tariff_id).await {
+                Ok(tariff) => {
+                    if let Some(user) = &user
+                        && check_tariff_eligibility(user, &tariff).is_err()
+                    {
+                        continue;
+                    }
+                    car.price_per_minute = Some(tariff.price_per_minute);
+                }
+                Err(e) => {
+                    error!("Failed to get tariff {} for car {}: {:?}", car.tariff_id, car.id, e);
+                    // Без тарифа не можем проверить допуск
+                    if user.is_some() {
+                        continue;
+                    }
+                }
+            }
+            available_cars.push(car);
+        }
+
+        Ok(available_cars)
+    }
+}
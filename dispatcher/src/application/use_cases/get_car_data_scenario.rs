@@ -31,23 +31,17 @@ where
         // 2. Получаем тариф для машины
         let tariff = self.cars_client.get_tariff(car.tariff_id).await?;
         
-        // 3. Получаем телематические данные
-        // Пока используем упрощенный подход - получаем все данные и фильтруем
-        let all_sensor_data = self.telematics_client.get_all_sensor_data().await?;
-        let telematics = all_sensor_data
-            .iter()
-            .find(|_data| {
-                // В реальном приложении нужно сравнивать по license_plate
-                // Для MVP пока возвращаем первое совпадение или None
-                true // Упрощенная логика для MVP
-            })
+        // 3. Получаем телематические данные именно этой машины
+        let telematics = self.telematics_client
+            .get_sensor_data_by_car_id(car_id)
+            .await?
             .map(|data| TelematicsInfo {
                 fuel_level: data.fuel_level,
                 location: LocationInfo {
                     latitude: data.location.latitude,
                     longitude: data.location.longitude,
                 },
-                door_status: data.door_status.clone(),
+                door_status: data.door_status,
                 speed: data.speed,
                 temperature: data.temperature,
                 timestamp: data.timestamp,
@@ -0,0 +1,45 @@
+use uuid::Uuid;
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use crate::domain::{
+    errors::DispatcherError,
+    interfaces::{CarsServiceClient, TripQuote, TripQuoteRequest},
+};
+
+pub const MAX_ESTIMATE_MINUTES: u32 = 30 * 24 * 60;
+
+pub struct GetFareEstimateScenario<CC>
+where
+    CC: CarsServiceClient + Send + Sync + 'static,
+{
+    cars_client: Arc<CC>,
+}
+
+impl<CC> GetFareEstimateScenario<CC>
+where
+    CC: CarsServiceClient + Send + Sync + 'static,
+{
+    pub fn new(cars_client: Arc<CC>) -> Self {
+        Self { cars_client }
+    }
+
+    pub async fn execute(&self, car_id: Uuid, minutes: u32, km: f64) -> Result<TripQuote, DispatcherError> {
+        if minutes == 0 || minutes > MAX_ESTIMATE_MINUTES {
+            return Err(DispatcherError::InvalidRequest {
+                message: format!("minutes must be between 1 and {}", MAX_ESTIMATE_MINUTES),
+            });
+        }
+
+        // Оценка идет через тот же расчет, что и итоговый счет при завершении поездки:
+        // гипотетическая поездка длиной ровно `minutes` минут без стоянок
+        let started_at = Utc::now();
+        let request = TripQuoteRequest {
+            started_at,
+            ended_at: started_at + Duration::minutes(minutes as i64),
+            distance_km: km,
+            parking_minutes: 0,
+        };
+
+        self.cars_client.quote_trip(car_id, &request).await
+    }
+}
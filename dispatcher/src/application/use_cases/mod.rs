@@ -1,10 +1,19 @@
 mod start_trip_scenario;
+mod activate_trip_scenario;
 mod end_trip_scenario;
 mod cancel_trip_scenario;
 mod get_car_data_scenario;
+mod get_available_cars_scenario;
+mod get_fare_estimate_scenario;
+mod recover_sagas_scenario;
+mod send_car_command_scenario;
 
 pub use start_trip_scenario::*;
+pub use activate_trip_scenario::*;
 pub use end_trip_scenario::*;
 pub use cancel_trip_scenario::*;
 pub use get_car_data_scenario::*;
-
+pub use get_available_cars_scenario::*;
+pub use get_fare_estimate_scenario::*;
+pub use recover_sagas_scenario::*;
+pub use send_car_command_scenario::*;
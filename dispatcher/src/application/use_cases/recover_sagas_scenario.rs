@@ -0,0 +1,306 @@
+use std::sync::Arc;
+use crate::application::sagas::SagaCoordinator;
+use crate::domain::{
+    errors::DispatcherError,
+    interfaces::{TripsServiceClient, UsersServiceClient, CarsServiceClient, BillingServiceClient, SagaRepository},
+    models::SagaKind,
+};
+use super::{CancelTripScenario, EndTripScenario, StartTripScenario};
+use tracing::{info, warn};
+
+pub struct RecoverSagasScenario<TC, UC, CC, BC, SR>
+where
+    TC: TripsServiceClient + Send + Sync + 'static,
+    UC: UsersServiceClient + Send + Sync + 'static,
+    CC: CarsServiceClient + Send + Sync + 'static,
+    BC: BillingServiceClient + Send + Sync + 'static,
+    SR: SagaRepository + Send + Sync + 'static,
+{
+    sagas: Arc<SagaCoordinator<SR>>,
+    start_trip_scenario: Arc<StartTripScenario<TC, UC, CC, BC, SR>>,
+    end_trip_scenario: Arc<EndTripScenario<TC, BC, CC, SR>>,
+    cancel_trip_scenario: Arc<CancelTripScenario<TC, SR>>,
+    // Сколько саг обрабатывается за один проход
+    batch_size: i64,
+}
+
+impl<TC, UC, CC, BC, SR> RecoverSagasScenario<TC, UC, CC, BC, SR>
+where
+    TC: TripsServiceClient + Send + Sync + 'static,
+    UC: UsersServiceClient + Send + Sync + 'static,
+    CC: CarsServiceClient + Send + Sync + 'static,
+    BC: BillingServiceClient + Send + Sync + 'static,
+    SR: SagaRepository + Send + Sync + 'static,
+{
+    pub fn new(
+        sagas: Arc<SagaCoordinator<SR>>,
+        start_trip_scenario: Arc<StartTripScenario<TC, UC, CC, BC, SR>>,
+        end_trip_scenario: Arc<EndTripScenario<TC, BC, CC, SR>>,
+        cancel_trip_scenario: Arc<CancelTripScenario<TC, SR>>,
+        batch_size: i64,
+    ) -> Self {
+        Self { sagas, start_trip_scenario, end_trip_scenario, cancel_trip_scenario, batch_size }
+    }
+
+    // Возвращает количество саг, взятых в работу
+    pub async fn execute(&self) -> Result<usize, DispatcherError> {
+        let mut resumed = 0;
+        for saga_id in self.sagas.due(self.batch_size).await? {
+            // Сагу мог взять другой экземпляр dispatcher'а или повторный запрос клиента
+            let Some(saga) = self.sagas.acquire(saga_id).await? else {
+                continue;
+            };
+
+            info!("Resuming saga {} ({}), attempt {}", saga.id, saga.kind.as_str(), saga.attempts + 1);
+            let result = match saga.kind {
+                SagaKind::StartTrip => self.start_trip_scenario.resume(saga).await,
+                SagaKind::EndTrip => self.end_trip_scenario.resume(saga).await,
+                SagaKind::CancelTrip => self.cancel_trip_scenario.resume(saga).await,
+            };
+            // Ошибка уже записана в сагу, повтор запланирован или сага остановлена
+            if let Err(e) = result {
+                warn!("Saga {} is still unfinished: {}", saga_id, e);
+            }
+            resumed += 1;
+        }
+
+        Ok(resumed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::{Duration, Utc};
+    use std::sync::Mutex;
+    use uuid::Uuid;
+    use crate::application::sagas::{InMemorySagaRepository, SagaPolicy};
+    use crate::domain::{
+        interfaces::*,
+        models::{Currency, Money, Saga, SagaStatus, SagaStep, StepStatus},
+    };
+
+    // Trips отвечает статусом поездки и считает вызовы отмены
+    struct MockTripsClient {
+        trip_status: &'static str,
+        cancel_result: fn() -> Result<(), DispatcherError>,
+        cancel_calls: Mutex<usize>,
+    }
+
+    impl MockTripsClient {
+        fn new(trip_status: &'static str, cancel_result: fn() -> Result<(), DispatcherError>) -> Self {
+            Self { trip_status, cancel_result, cancel_calls: Mutex::new(0) }
+        }
+    }
+
+    #[async_trait]
+    impl TripsServiceClient for MockTripsClient {
+        async fn start_trip(&self, _user_id: Uuid, _car_id: Uuid) -> Result<Uuid, DispatcherError> { unimplemented!() }
+        async fn activate_trip(&self, _trip_id: Uuid) -> Result<(), DispatcherError> { unimplemented!() }
+        async fn end_trip(&self, _trip_id: Uuid) -> Result<(), DispatcherError> { unimplemented!() }
+
+        async fn cancel_trip(&self, _trip_id: Uuid) -> Result<(), DispatcherError> {
+            *self.cancel_calls.lock().unwrap() += 1;
+            (self.cancel_result)()
+        }
+
+        async fn get_trip(&self, trip_id: Uuid) -> Result<TripInfo, DispatcherError> {
+            Ok(TripInfo {
+                id: trip_id,
+                user_id: Uuid::new_v4(),
+                car_id: Uuid::new_v4(),
+                status: self.trip_status.to_string(),
+                started_at: None,
+                ended_at: None,
+                created_at: Utc::now(),
+                expires_at: None,
+                distance_km: None,
+                parking_minutes: None,
+            })
+        }
+
+        async fn get_user_active_trip(&self, _user_id: Uuid) -> Result<Option<TripInfo>, DispatcherError> { unimplemented!() }
+        async fn get_all_trips(&self) -> Result<Vec<TripInfo>, DispatcherError> { unimplemented!() }
+        async fn get_trip_route(&self, _trip_id: Uuid) -> Result<serde_json::Value, DispatcherError> { unimplemented!() }
+    }
+
+    struct UnusedUsersClient;
+
+    #[async_trait]
+    impl UsersServiceClient for UnusedUsersClient {
+        async fn register(&self, _request: RegisterRequest) -> Result<RegisterResponse, DispatcherError> { unimplemented!() }
+        async fn authenticate(&self, _request: AuthRequest) -> Result<AuthResponse, DispatcherError> { unimplemented!() }
+        async fn refresh_token(&self, _request: RefreshTokenRequest) -> Result<AuthResponse, DispatcherError> { unimplemented!() }
+        async fn logout(&self, _request: LogoutRequest) -> Result<(), DispatcherError> { unimplemented!() }
+        async fn get_user(&self, _user_id: Uuid) -> Result<UserInfo, DispatcherError> { unimplemented!() }
+        async fn get_all_users(&self) -> Result<Vec<UserInfo>, DispatcherError> { unimplemented!() }
+        async fn change_user_role(&self, _user_id: Uuid, _role: &str, _access_token: &str) -> Result<(), DispatcherError> { unimplemented!() }
+    }
+
+    struct UnusedCarsClient;
+
+    #[async_trait]
+    impl CarsServiceClient for UnusedCarsClient {
+        async fn get_car(&self, _car_id: Uuid) -> Result<CarInfo, DispatcherError> { unimplemented!() }
+        async fn get_all_cars(&self) -> Result<Vec<CarInfo>, DispatcherError> { unimplemented!() }
+        async fn get_tariff(&self, _tariff_id: Uuid) -> Result<TariffInfo, DispatcherError> { unimplemented!() }
+        async fn quote_trip(&self, _car_id: Uuid, _request: &TripQuoteRequest) -> Result<TripQuote, DispatcherError> { unimplemented!() }
+    }
+
+    struct UnusedBillingClient;
+
+    #[async_trait]
+    impl BillingServiceClient for UnusedBillingClient {
+        async fn create_payment(&self, _trip_id: Uuid, _user_id: Uuid, _amount: Money, _tariff_id: Uuid) -> Result<PaymentInfo, DispatcherError> { unimplemented!() }
+        async fn get_payment(&self, _payment_id: Uuid) -> Result<PaymentInfo, DispatcherError> { unimplemented!() }
+        async fn get_trip_payment(&self, _trip_id: Uuid) -> Result<Option<PaymentInfo>, DispatcherError> { unimplemented!() }
+        async fn get_payment_qr_png(&self, _payment_id: Uuid) -> Result<Vec<u8>, DispatcherError> { unimplemented!() }
+        async fn create_refund(&self, _payment_id: Uuid, _amount: Money, _reason: &str) -> Result<CreateRefundResult, DispatcherError> { unimplemented!() }
+        async fn get_refunds(&self, _payment_id: Uuid) -> Result<Vec<RefundInfo>, DispatcherError> { unimplemented!() }
+        async fn get_user_balance(&self, _user_id: Uuid) -> Result<UserBalanceInfo, DispatcherError> { unimplemented!() }
+        async fn set_debt_override(&self, _user_id: Uuid, _request: &DebtOverrideRequest) -> Result<DebtOverrideInfo, DispatcherError> { unimplemented!() }
+        async fn remove_debt_override(&self, _user_id: Uuid) -> Result<(), DispatcherError> { unimplemented!() }
+        async fn get_wallet(&self, _user_id: Uuid) -> Result<WalletInfo, DispatcherError> { unimplemented!() }
+        async fn create_top_up(&self, _user_id: Uuid, _amount: Money) -> Result<PaymentInfo, DispatcherError> { unimplemented!() }
+        async fn get_payment_history(&self, _user_id: Uuid) -> Result<Vec<PaymentHistoryItem>, DispatcherError> { unimplemented!() }
+        async fn create_promo_code(&self, _request: &CreatePromoCodeRequest) -> Result<PromoCodeInfo, DispatcherError> { unimplemented!() }
+        async fn get_promo_codes(&self) -> Result<Vec<PromoCodeInfo>, DispatcherError> { unimplemented!() }
+        async fn deactivate_promo_code(&self, _promo_code_id: Uuid) -> Result<PromoCodeInfo, DispatcherError> { unimplemented!() }
+        async fn get_campaign_report(&self, _query: &CampaignReportQuery) -> Result<Vec<CampaignReportInfo>, DispatcherError> { unimplemented!() }
+        async fn attach_promo_code(&self, _user_id: Uuid, _code: &str) -> Result<AttachedPromoCodeInfo, DispatcherError> { unimplemented!() }
+        async fn get_attached_promo_code(&self, _user_id: Uuid) -> Result<AttachedPromoCodeInfo, DispatcherError> { unimplemented!() }
+        async fn detach_promo_code(&self, _user_id: Uuid) -> Result<(), DispatcherError> { unimplemented!() }
+    }
+
+    type TestRecoverScenario =
+        RecoverSagasScenario<MockTripsClient, UnusedUsersClient, UnusedCarsClient, UnusedBillingClient, InMemorySagaRepository>;
+
+    fn scenario(trips_client: MockTripsClient, max_attempts: u32) -> (TestRecoverScenario, Arc<MockTripsClient>, Arc<InMemorySagaRepository>) {
+        let trips_client = Arc::new(trips_client);
+        let repository = Arc::new(InMemorySagaRepository::default());
+        let sagas = Arc::new(SagaCoordinator::new(Arc::clone(&repository), SagaPolicy {
+            max_attempts,
+            ..SagaPolicy::default()
+        }));
+        let billing_client = Arc::new(UnusedBillingClient);
+        let cars_client = Arc::new(UnusedCarsClient);
+
+        let start_trip_scenario = Arc::new(StartTripScenario::new(
+            Arc::clone(&trips_client),
+            Arc::new(UnusedUsersClient),
+            Arc::clone(&cars_client),
+            Arc::clone(&billing_client),
+            Arc::clone(&sagas),
+            Money::new(0, Currency::Rub),
+        ));
+        let end_trip_scenario = Arc::new(EndTripScenario::new(
+            Arc::clone(&trips_client),
+            billing_client,
+            cars_client,
+            Arc::clone(&sagas),
+        ));
+        let cancel_trip_scenario = Arc::new(CancelTripScenario::new(Arc::clone(&trips_client), Arc::clone(&sagas)));
+
+        let scenario = RecoverSagasScenario::new(sagas, start_trip_scenario, end_trip_scenario, cancel_trip_scenario, 10);
+        (scenario, trips_client, repository)
+    }
+
+    // Отмена, прерванная падением процесса: аренда истекла, повтор уже пора делать
+    fn interrupted_cancel(steps: Vec<SagaStep>) -> Saga {
+        let mut saga = Saga::new(
+            SagaKind::CancelTrip,
+            Uuid::new_v4(),
+            Some(Uuid::new_v4()),
+            None,
+            Utc::now() - Duration::seconds(1),
+        );
+        saga.attempts = 1;
+        saga.steps = steps;
+        saga
+    }
+
+    fn step(status: StepStatus) -> SagaStep {
+        SagaStep {
+            step: "cancel_trip".to_string(),
+            status,
+            output: (status == StepStatus::Completed).then_some(serde_json::Value::Null),
+            error: (status == StepStatus::Failed).then(|| "trips service unavailable".to_string()),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn unavailable() -> Result<(), DispatcherError> {
+        Err(DispatcherError::ServiceUnavailable { service: "trips".to_string() })
+    }
+
+    #[tokio::test]
+    async fn test_resumes_saga_from_failed_step() {
+        let (scenario, trips_client, repository) = scenario(MockTripsClient::new("active", || Ok(())), 10);
+        let saga = interrupted_cancel(vec![step(StepStatus::Failed)]);
+        repository.insert(saga.clone());
+
+        assert_eq!(scenario.execute().await.unwrap(), 1);
+
+        assert_eq!(*trips_client.cancel_calls.lock().unwrap(), 1);
+        let stored = repository.get(saga.id);
+        assert_eq!(stored.status, SagaStatus::Completed);
+        assert_eq!(stored.steps.last().unwrap().status, StepStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_completed_steps_are_skipped() {
+        let (scenario, trips_client, repository) = scenario(MockTripsClient::new("active", || Ok(())), 10);
+        // Процесс упал после шага, но до завершения саги
+        let saga = interrupted_cancel(vec![step(StepStatus::Completed)]);
+        repository.insert(saga.clone());
+
+        scenario.execute().await.unwrap();
+
+        assert_eq!(*trips_client.cancel_calls.lock().unwrap(), 0);
+        assert_eq!(repository.get(saga.id).status, SagaStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_transient_failure_keeps_saga_for_retry() {
+        let (scenario, _, repository) = scenario(MockTripsClient::new("active", unavailable), 10);
+        let saga = interrupted_cancel(vec![step(StepStatus::Failed)]);
+        repository.insert(saga.clone());
+
+        scenario.execute().await.unwrap();
+
+        let stored = repository.get(saga.id);
+        assert_eq!(stored.status, SagaStatus::Running);
+        assert_eq!(stored.attempts, 2);
+        assert!(stored.next_attempt_at > Utc::now());
+        // До следующей попытки сага не выбирается повторно
+        assert_eq!(scenario.execute().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_saga_fails_after_max_attempts() {
+        let (scenario, trips_client, repository) = scenario(MockTripsClient::new("active", unavailable), 2);
+        let saga = interrupted_cancel(vec![step(StepStatus::Failed)]);
+        repository.insert(saga.clone());
+
+        scenario.execute().await.unwrap();
+
+        assert_eq!(*trips_client.cancel_calls.lock().unwrap(), 1);
+        let stored = repository.get(saga.id);
+        assert_eq!(stored.status, SagaStatus::Failed);
+        assert_eq!(stored.attempts, 2);
+        assert_eq!(scenario.execute().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_saga_with_active_lease_is_not_resumed() {
+        let (scenario, trips_client, repository) = scenario(MockTripsClient::new("active", || Ok(())), 10);
+        let mut saga = interrupted_cancel(vec![step(StepStatus::Failed)]);
+        saga.locked_until = Some(Utc::now() + Duration::minutes(1));
+        repository.insert(saga.clone());
+
+        assert_eq!(scenario.execute().await.unwrap(), 0);
+        assert_eq!(*trips_client.cancel_calls.lock().unwrap(), 0);
+        assert_eq!(repository.get(saga.id).status, SagaStatus::Running);
+    }
+}
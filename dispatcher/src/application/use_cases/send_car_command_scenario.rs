@@ -0,0 +1,320 @@
+use uuid::Uuid;
+use std::sync::Arc;
+use chrono::Utc;
+use crate::domain::{
+    errors::DispatcherError,
+    interfaces::{CommandAuditRepository, TelematicsServiceClient, TripInfo, TripsServiceClient},
+    models::{AuthenticatedUser, CommandRejection, CommandRejectionReason, CommandType, Role},
+};
+use tracing::{error, info, warn};
+
+pub struct SendCarCommandScenario<TC, TMC, AR>
+where
+    TC: TripsServiceClient + Send + Sync + 'static,
+    TMC: TelematicsServiceClient + Send + Sync + 'static,
+    AR: CommandAuditRepository + Send + Sync + 'static,
+{
+    trips_client: Arc<TC>,
+    telematics_client: Arc<TMC>,
+    audit_repository: Arc<AR>,
+}
+
+impl<TC, TMC, AR> SendCarCommandScenario<TC, TMC, AR>
+where
+    TC: TripsServiceClient + Send + Sync + 'static,
+    TMC: TelematicsServiceClient + Send + Sync + 'static,
+    AR: CommandAuditRepository + Send + Sync + 'static,
+{
+    pub fn new(trips_client: Arc<TC>, telematics_client: Arc<TMC>, audit_repository: Arc<AR>) -> Self {
+        Self { trips_client, telematics_client, audit_repository }
+    }
+
+    pub async fn execute(&self, user: &AuthenticatedUser, car_id: Uuid, command_type: &str) -> Result<Uuid, DispatcherError> {
+        // 1. Команда должна быть известной и разрешенной для роли
+        let command = match command_type.parse::<CommandType>() {
+            Ok(command) => command,
+            Err(e) => {
+                self.reject(user, car_id, command_type, None, CommandRejectionReason::UnknownCommand).await;
+                return Err(e);
+            }
+        };
+
+        if !command.is_allowed_for(user.role) {
+            self.reject(user, car_id, command_type, None, CommandRejectionReason::NotAllowedForRole).await;
+            return Err(DispatcherError::CommandNotAllowed {
+                reason: format!("Command {} is not allowed for role {}", command.as_str(), user.role.as_str()),
+            });
+        }
+
+        // 2. Клиенту нужна поездка именно на этой машине в подходящем статусе.
+        // Поддержка и админы управляют любой машиной без поездки
+        if user.role == Role::Client {
+            let trip = self.trips_client
+                .get_user_active_trip(user.user_id)
+                .await?
+                .filter(|trip| trip.car_id == car_id);
+
+            let Some(trip) = trip else {
+                self.reject(user, car_id, command_type, None, CommandRejectionReason::NoTripOnCar).await;
+                return Err(DispatcherError::CommandNotAllowed {
+                    reason: format!("No active trip on car {}", car_id),
+                });
+            };
+
+            if !command.allowed_trip_statuses().contains(&trip.status.as_str()) {
+                self.reject(user, car_id, command_type, Some(&trip), CommandRejectionReason::TripStatusNotAllowed).await;
+                return Err(DispatcherError::CommandNotAllowed {
+                    reason: format!("Command {} is not allowed for trip in status {}", command.as_str(), trip.status),
+                });
+            }
+        }
+
+        // 3. Отправляем команду
+        let command_id = self.telematics_client
+            .send_command(car_id, command.as_str().to_string())
+            .await?;
+
+        info!("User {} ({}) sent command {} to car {}: {}", user.user_id, user.role.as_str(), command.as_str(), car_id, command_id);
+        Ok(command_id)
+    }
+
+    async fn reject(
+        &self,
+        user: &AuthenticatedUser,
+        car_id: Uuid,
+        command_type: &str,
+        trip: Option<&TripInfo>,
+        reason: CommandRejectionReason,
+    ) {
+        warn!(
+            "Rejected command {} to car {} from user {} ({}): {}",
+            command_type, car_id, user.user_id, user.role.as_str(), reason.as_str()
+        );
+
+        let rejection = CommandRejection {
+            id: Uuid::new_v4(),
+            user_id: user.user_id,
+            role: user.role,
+            car_id,
+            command_type: command_type.to_string(),
+            trip_id: trip.map(|t| t.id),
+            trip_status: trip.map(|t| t.status.clone()),
+            reason,
+            created_at: Utc::now(),
+        };
+
+        // Сбой записи аудита не должен менять ответ клиенту: команда отклонена в любом случае
+        if let Err(e) = self.audit_repository.record_rejection(&rejection).await {
+            error!("Failed to record command rejection {}: {}", rejection.id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use crate::domain::interfaces::*;
+
+    // Trips отдает заранее заданную текущую поездку пользователя
+    struct MockTripsClient {
+        active_trip: Option<(Uuid, &'static str)>,
+        calls: Mutex<usize>,
+    }
+
+    impl MockTripsClient {
+        fn new(active_trip: Option<(Uuid, &'static str)>) -> Self {
+            Self { active_trip, calls: Mutex::new(0) }
+        }
+    }
+
+    #[async_trait]
+    impl TripsServiceClient for MockTripsClient {
+        async fn start_trip(&self, _user_id: Uuid, _car_id: Uuid) -> Result<Uuid, DispatcherError> { unimplemented!() }
+        async fn activate_trip(&self, _trip_id: Uuid) -> Result<(), DispatcherError> { unimplemented!() }
+        async fn end_trip(&self, _trip_id: Uuid) -> Result<(), DispatcherError> { unimplemented!() }
+        async fn cancel_trip(&self, _trip_id: Uuid) -> Result<(), DispatcherError> { unimplemented!() }
+        async fn get_trip(&self, _trip_id: Uuid) -> Result<TripInfo, DispatcherError> { unimplemented!() }
+
+        async fn get_user_active_trip(&self, user_id: Uuid) -> Result<Option<TripInfo>, DispatcherError> {
+            *self.calls.lock().unwrap() += 1;
+            Ok(self.active_trip.map(|(car_id, status)| TripInfo {
+                id: Uuid::new_v4(),
+                user_id,
+                car_id,
+                status: status.to_string(),
+                started_at: None,
+                ended_at: None,
+                created_at: Utc::now(),
+                expires_at: None,
+                distance_km: None,
+                parking_minutes: None,
+            }))
+        }
+
+        async fn get_all_trips(&self) -> Result<Vec<TripInfo>, DispatcherError> { unimplemented!() }
+        async fn get_trip_route(&self, _trip_id: Uuid) -> Result<serde_json::Value, DispatcherError> { unimplemented!() }
+    }
+
+    #[derive(Default)]
+    struct MockTelematicsClient {
+        sent: Mutex<Vec<(Uuid, String)>>,
+    }
+
+    #[async_trait]
+    impl TelematicsServiceClient for MockTelematicsClient {
+        async fn send_command(&self, car_id: Uuid, command_type: String) -> Result<Uuid, DispatcherError> {
+            self.sent.lock().unwrap().push((car_id, command_type));
+            Ok(Uuid::new_v4())
+        }
+
+        async fn get_sensor_data_by_car_id(&self, _car_id: Uuid) -> Result<Option<SensorDataInfo>, DispatcherError> { unimplemented!() }
+        async fn get_all_sensor_data(&self) -> Result<Vec<SensorDataInfo>, DispatcherError> { unimplemented!() }
+        async fn get_sensor_history(&self, _vin: &str, _query: &SensorHistoryQuery) -> Result<Vec<SensorHistoryPointInfo>, DispatcherError> { unimplemented!() }
+        async fn list_dead_letters(&self, _limit: usize) -> Result<Vec<DeadLetterInfo>, DispatcherError> { unimplemented!() }
+        async fn replay_dead_letters(&self, _message_ids: Option<Vec<String>>, _limit: usize) -> Result<usize, DispatcherError> { unimplemented!() }
+    }
+
+    #[derive(Default)]
+    struct MockAuditRepository {
+        rejections: Mutex<Vec<CommandRejection>>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl CommandAuditRepository for MockAuditRepository {
+        async fn record_rejection(&self, rejection: &CommandRejection) -> Result<(), DispatcherError> {
+            if self.fail {
+                return Err(DispatcherError::Internal(anyhow::anyhow!("audit unavailable")));
+            }
+            self.rejections.lock().unwrap().push(rejection.clone());
+            Ok(())
+        }
+
+        async fn find_recent_rejections(&self, _limit: i64) -> Result<Vec<CommandRejection>, DispatcherError> { unimplemented!() }
+    }
+
+    type TestScenario = SendCarCommandScenario<MockTripsClient, MockTelematicsClient, MockAuditRepository>;
+
+    fn scenario(
+        trips: MockTripsClient,
+        audit: MockAuditRepository,
+    ) -> (TestScenario, Arc<MockTripsClient>, Arc<MockTelematicsClient>, Arc<MockAuditRepository>) {
+        let trips = Arc::new(trips);
+        let telematics = Arc::new(MockTelematicsClient::default());
+        let audit = Arc::new(audit);
+        let scenario = SendCarCommandScenario::new(trips.clone(), telematics.clone(), audit.clone());
+        (scenario, trips, telematics, audit)
+    }
+
+    fn user(role: Role) -> AuthenticatedUser {
+        AuthenticatedUser { user_id: Uuid::new_v4(), role }
+    }
+
+    #[tokio::test]
+    async fn test_client_sends_command_to_car_of_active_trip() {
+        let car_id = Uuid::new_v4();
+        let (scenario, _, telematics, audit) = scenario(MockTripsClient::new(Some((car_id, "active"))), MockAuditRepository::default());
+
+        scenario.execute(&user(Role::Client), car_id, "start_engine").await.unwrap();
+
+        assert_eq!(*telematics.sent.lock().unwrap(), vec![(car_id, "start_engine".to_string())]);
+        assert!(audit.rejections.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_client_without_trip_on_car_is_rejected() {
+        let car_id = Uuid::new_v4();
+        let other_car = Uuid::new_v4();
+
+        for active_trip in [None, Some((other_car, "active"))] {
+            let (scenario, _, telematics, audit) = scenario(MockTripsClient::new(active_trip), MockAuditRepository::default());
+            let client = user(Role::Client);
+
+            let result = scenario.execute(&client, car_id, "open_door").await;
+
+            assert!(matches!(result, Err(DispatcherError::CommandNotAllowed { .. })));
+            assert!(telematics.sent.lock().unwrap().is_empty());
+
+            let rejections = audit.rejections.lock().unwrap();
+            assert_eq!(rejections.len(), 1);
+            assert_eq!(rejections[0].reason, CommandRejectionReason::NoTripOnCar);
+            assert_eq!(rejections[0].user_id, client.user_id);
+            assert_eq!(rejections[0].car_id, car_id);
+            assert_eq!(rejections[0].trip_id, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reserved_trip_allows_only_unlock_door() {
+        let car_id = Uuid::new_v4();
+        let (scenario, _, telematics, audit) = scenario(MockTripsClient::new(Some((car_id, "reserved"))), MockAuditRepository::default());
+        let client = user(Role::Client);
+
+        scenario.execute(&client, car_id, "unlock_door").await.unwrap();
+        let result = scenario.execute(&client, car_id, "start_engine").await;
+
+        assert!(matches!(result, Err(DispatcherError::CommandNotAllowed { .. })));
+        assert_eq!(*telematics.sent.lock().unwrap(), vec![(car_id, "unlock_door".to_string())]);
+
+        // В аудит попадает поездка, в статусе которой команда недоступна
+        let rejections = audit.rejections.lock().unwrap();
+        assert_eq!(rejections.len(), 1);
+        assert_eq!(rejections[0].reason, CommandRejectionReason::TripStatusNotAllowed);
+        assert_eq!(rejections[0].command_type, "start_engine");
+        assert!(rejections[0].trip_id.is_some());
+        assert_eq!(rejections[0].trip_status.as_deref(), Some("reserved"));
+    }
+
+    #[tokio::test]
+    async fn test_client_immobilizer_is_rejected_before_trip_lookup() {
+        let car_id = Uuid::new_v4();
+        let (scenario, trips, telematics, audit) = scenario(MockTripsClient::new(Some((car_id, "active"))), MockAuditRepository::default());
+
+        let result = scenario.execute(&user(Role::Client), car_id, "lock_engine").await;
+
+        assert!(matches!(result, Err(DispatcherError::CommandNotAllowed { .. })));
+        assert_eq!(*trips.calls.lock().unwrap(), 0);
+        assert!(telematics.sent.lock().unwrap().is_empty());
+        assert_eq!(audit.rejections.lock().unwrap()[0].reason, CommandRejectionReason::NotAllowedForRole);
+    }
+
+    #[tokio::test]
+    async fn test_operator_controls_car_without_trip() {
+        let car_id = Uuid::new_v4();
+        let (scenario, trips, telematics, _) = scenario(MockTripsClient::new(None), MockAuditRepository::default());
+
+        scenario.execute(&user(Role::Operator), car_id, "lock_engine").await.unwrap();
+
+        assert_eq!(*trips.calls.lock().unwrap(), 0);
+        assert_eq!(*telematics.sent.lock().unwrap(), vec![(car_id, "lock_engine".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_command_is_audited_as_sent() {
+        let car_id = Uuid::new_v4();
+        let (scenario, _, telematics, audit) = scenario(MockTripsClient::new(Some((car_id, "active"))), MockAuditRepository::default());
+
+        let result = scenario.execute(&user(Role::Admin), car_id, "self_destruct").await;
+
+        assert!(matches!(result, Err(DispatcherError::InvalidRequest { .. })));
+        assert!(telematics.sent.lock().unwrap().is_empty());
+
+        let rejections = audit.rejections.lock().unwrap();
+        assert_eq!(rejections[0].reason, CommandRejectionReason::UnknownCommand);
+        assert_eq!(rejections[0].command_type, "self_destruct");
+    }
+
+    #[tokio::test]
+    async fn test_audit_failure_keeps_rejection() {
+        let car_id = Uuid::new_v4();
+        let audit = MockAuditRepository { fail: true, ..Default::default() };
+        let (scenario, _, telematics, _) = scenario(MockTripsClient::new(None), audit);
+
+        let result = scenario.execute(&user(Role::Client), car_id, "open_door").await;
+
+        assert!(matches!(result, Err(DispatcherError::CommandNotAllowed { .. })));
+        assert!(telematics.sent.lock().unwrap().is_empty());
+    }
+}
@@ -1,27 +1,131 @@
 use uuid::Uuid;
 use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use crate::application::sagas::SagaCoordinator;
 use crate::domain::{
     errors::DispatcherError,
-    interfaces::TripsServiceClient,
+    interfaces::{TripsServiceClient, UsersServiceClient, CarsServiceClient, BillingServiceClient, SagaRepository},
+    models::{check_outstanding_debt, check_tariff_eligibility, Money, Saga, SagaKind, SagaStatus},
 };
+use tracing::{info, error};
 
-pub struct StartTripScenario<TC> 
+pub struct StartTripScenario<TC, UC, CC, BC, SR>
 where
     TC: TripsServiceClient + Send + Sync + 'static,
+    UC: UsersServiceClient + Send + Sync + 'static,
+    CC: CarsServiceClient + Send + Sync + 'static,
+    BC: BillingServiceClient + Send + Sync + 'static,
+    SR: SagaRepository + Send + Sync + 'static,
 {
     trips_client: Arc<TC>,
+    users_client: Arc<UC>,
+    cars_client: Arc<CC>,
+    billing_client: Arc<BC>,
+    sagas: Arc<SagaCoordinator<SR>>,
+    // Максимальный долг, с которым еще можно начать поездку
+    max_debt: Money,
 }
 
-impl<TC> StartTripScenario<TC>
+impl<TC, UC, CC, BC, SR> StartTripScenario<TC, UC, CC, BC, SR>
 where
     TC: TripsServiceClient + Send + Sync + 'static,
+    UC: UsersServiceClient + Send + Sync + 'static,
+    CC: CarsServiceClient + Send + Sync + 'static,
+    BC: BillingServiceClient + Send + Sync + 'static,
+    SR: SagaRepository + Send + Sync + 'static,
 {
-    pub fn new(trips_client: Arc<TC>) -> Self {
-        Self { trips_client }
+    pub fn new(
+        trips_client: Arc<TC>,
+        users_client: Arc<UC>,
+        cars_client: Arc<CC>,
+        billing_client: Arc<BC>,
+        sagas: Arc<SagaCoordinator<SR>>,
+        max_debt: Money,
+    ) -> Self {
+        Self { trips_client, users_client, cars_client, billing_client, sagas, max_debt }
     }
 
     pub async fn execute(&self, user_id: Uuid, car_id: Uuid) -> Result<Uuid, DispatcherError> {
-        self.trips_client.start_trip(user_id, car_id).await
+        // 1. Проверяем, что у пользователя нет задолженности выше допустимой
+        let balance = self.billing_client.get_user_balance(user_id).await?;
+        check_outstanding_debt(&balance, self.max_debt)?;
+
+        // 2. Проверяем, что водитель проходит по требованиям тарифа машины
+        let user = self.users_client.get_user(user_id).await?;
+        let car = self.cars_client.get_car(car_id).await?;
+        let tariff = self.cars_client.get_tariff(car.tariff_id).await?;
+        check_tariff_eligibility(&user, &tariff)?;
+
+        // 3. Резервируем поездку. Если ответ trips потерялся, поездка могла быть создана -
+        //    тогда ее резерв снимается компенсацией, пользователь о ней так и не узнал
+        let mut saga = self.sagas.begin(SagaKind::StartTrip, user_id, None, Some(car_id)).await
+            .map_err(|e| match e {
+                DispatcherError::Conflict { .. } => DispatcherError::Conflict {
+                    message: "Trip start is already in progress".to_string(),
+                },
+                e => e,
+            })?;
+
+        match self.sagas.step(&mut saga, "reserve_trip", || self.trips_client.start_trip(user_id, car_id)).await {
+            Ok(trip_id) => {
+                // Поездка создана: если не удалось отметить сагу, ее закроет фоновый обработчик
+                if let Err(e) = self.complete(&mut saga, trip_id).await {
+                    error!("Failed to complete saga {} for trip {}: {}", saga.id, trip_id, e);
+                }
+                Ok(trip_id)
+            }
+            Err(e) => {
+                // Отказ trips по существу запроса означает, что поездка не создана
+                let saved = if e.is_transient() {
+                    self.roll_back(saga, &e).await
+                } else {
+                    self.sagas.suspend(&mut saga, &e).await
+                };
+                if let Err(save_error) = saved {
+                    error!("Failed to roll back start of trip for user {}: {}", user_id, save_error);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    // Записанный резерв сохраняется, иначе снимается
+    pub async fn resume(&self, mut saga: Saga) -> Result<(), DispatcherError> {
+        if saga.status == SagaStatus::Running
+            && let Some(trip_id) = saga.step_output::<Uuid>("reserve_trip")?
+        {
+            return self.complete(&mut saga, trip_id).await;
+        }
+
+        let cause = DispatcherError::Internal(anyhow::anyhow!("trip start was interrupted"));
+        self.roll_back(saga, &cause).await
     }
-}
 
+    async fn complete(&self, saga: &mut Saga, trip_id: Uuid) -> Result<(), DispatcherError> {
+        self.sagas.set_trip(saga, trip_id).await?;
+        self.sagas.complete(saga).await
+    }
+
+    async fn roll_back(&self, mut saga: Saga, cause: &DispatcherError) -> Result<(), DispatcherError> {
+        self.sagas.start_compensation(&mut saga, cause).await?;
+        let (user_id, car_id, started_at) = (saga.user_id, saga.car_id, saga.created_at);
+        match self.sagas.compensate(&mut saga, "reserve_trip", || self.cancel_reservation(user_id, car_id, started_at)).await {
+            Ok(()) => self.sagas.compensated(&mut saga).await,
+            // Компенсацию повторит фоновый обработчик
+            Err(e) => self.sagas.suspend(&mut saga, &e).await,
+        }
+    }
+
+    async fn cancel_reservation(&self, user_id: Uuid, car_id: Option<Uuid>, started_at: DateTime<Utc>) -> Result<(), DispatcherError> {
+        let Some(trip) = self.trips_client.get_user_active_trip(user_id).await? else {
+            return Ok(());
+        };
+        // Поездки, начатые раньше саги или на другой машине, к этой попытке не относятся
+        if trip.status != "reserved" || Some(trip.car_id) != car_id || trip.created_at < started_at {
+            return Ok(());
+        }
+
+        info!("Cancelling orphaned reservation {} of user {}", trip.id, user_id);
+        self.trips_client.cancel_trip(trip.id).await
+    }
+}
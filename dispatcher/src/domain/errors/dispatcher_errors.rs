@@ -14,12 +14,33 @@ pub enum DispatcherError {
     #[error("unauthorized")]
     Unauthorized,
     
+    #[error("forbidden: {reason}")]
+    Forbidden { reason: String },
+    
+    #[error("driver not eligible: {reason}")]
+    DriverNotEligible { reason: String },
+    
+    #[error("outstanding debt {debt} exceeds allowed {threshold}")]
+    OutstandingDebt { debt: crate::domain::models::Money, threshold: crate::domain::models::Money },
+    
+    #[error("command not allowed: {reason}")]
+    CommandNotAllowed { reason: String },
+    
     #[error("not found: {resource}")]
     NotFound { resource: String },
     
+    #[error("conflict: {message}")]
+    Conflict { message: String },
+    
     #[error("http error: {0}")]
     Http(#[from] reqwest::Error),
     
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+    
+    #[error("redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+    
     #[error("serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
     
@@ -27,3 +48,16 @@ pub enum DispatcherError {
     Internal(#[from] anyhow::Error),
 }
 
+impl DispatcherError {
+    // Сервис или база были недоступны либо ответили ошибкой, а не отказом по существу запроса
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            DispatcherError::ServiceUnavailable { .. }
+                | DispatcherError::ServiceError { .. }
+                | DispatcherError::Http(_)
+                | DispatcherError::Database(_)
+                | DispatcherError::Redis(_)
+        )
+    }
+}
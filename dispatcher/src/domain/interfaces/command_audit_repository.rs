@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use crate::domain::{
+    errors::DispatcherError,
+    models::CommandRejection,
+};
+
+#[async_trait]
+pub trait CommandAuditRepository {
+    async fn record_rejection(&self, rejection: &CommandRejection) -> Result<(), DispatcherError>;
+    // Новые первыми
+    async fn find_recent_rejections(&self, limit: i64) -> Result<Vec<CommandRejection>, DispatcherError>;
+}
@@ -0,0 +1,14 @@
+use std::time::Duration;
+use async_trait::async_trait;
+use crate::domain::{
+    errors::DispatcherError,
+    models::IdempotencyRecord,
+};
+
+#[async_trait]
+pub trait IdempotencyStore {
+    // Атомарно: если ключ уже занят, ничего не меняет и возвращает существующую запись
+    async fn reserve(&self, key: &str, record: &IdempotencyRecord, ttl: Duration) -> Result<Option<IdempotencyRecord>, DispatcherError>;
+    async fn save(&self, key: &str, record: &IdempotencyRecord, ttl: Duration) -> Result<(), DispatcherError>;
+    async fn release(&self, key: &str) -> Result<(), DispatcherError>;
+}
@@ -1,4 +1,13 @@
 mod service_clients;
+mod token_validator;
+mod service_diagnostics;
+mod saga_repository;
+mod idempotency_store;
+mod command_audit_repository;
 
 pub use service_clients::*;
-
+pub use token_validator::*;
+pub use service_diagnostics::*;
+pub use saga_repository::*;
+pub use idempotency_store::*;
+pub use command_audit_repository::*;
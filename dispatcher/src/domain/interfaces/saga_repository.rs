@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::{
+    errors::DispatcherError,
+    models::{Saga, SagaKind, SagaStep},
+};
+
+#[async_trait]
+pub trait SagaRepository {
+    // false, если для поездки (или старта у пользователя) уже есть незавершенная сага
+    async fn create(&self, saga: &Saga) -> Result<bool, DispatcherError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Saga>, DispatcherError>;
+    async fn find_latest_by_trip(&self, kind: SagaKind, trip_id: Uuid) -> Result<Option<Saga>, DispatcherError>;
+    // Только те, которые никто не выполняет
+    async fn find_due(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Uuid>, DispatcherError>;
+    // Только если предыдущая аренда истекла
+    async fn try_lock(&self, id: Uuid, now: DateTime<Utc>, until: DateTime<Utc>) -> Result<bool, DispatcherError>;
+    async fn append_step(&self, saga_id: Uuid, step: &SagaStep) -> Result<(), DispatcherError>;
+    async fn update(&self, saga: &Saga) -> Result<(), DispatcherError>;
+}
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use uuid::Uuid;
-use crate::domain::errors::DispatcherError;
+use crate::domain::{errors::DispatcherError, models::Money};
 use serde::{Serialize, Deserialize};
 
 // Интерфейсы для HTTP клиентов к микросервисам
@@ -9,8 +9,12 @@ use serde::{Serialize, Deserialize};
 pub trait UsersServiceClient {
     async fn register(&self, request: RegisterRequest) -> Result<RegisterResponse, DispatcherError>;
     async fn authenticate(&self, request: AuthRequest) -> Result<AuthResponse, DispatcherError>;
+    async fn refresh_token(&self, request: RefreshTokenRequest) -> Result<AuthResponse, DispatcherError>;
+    async fn logout(&self, request: LogoutRequest) -> Result<(), DispatcherError>;
     async fn get_user(&self, user_id: Uuid) -> Result<UserInfo, DispatcherError>;
     async fn get_all_users(&self) -> Result<Vec<UserInfo>, DispatcherError>;
+    // users сервис сам проверяет, что токен принадлежит администратору
+    async fn change_user_role(&self, user_id: Uuid, role: &str, access_token: &str) -> Result<(), DispatcherError>;
 }
 
 #[async_trait]
@@ -18,16 +22,44 @@ pub trait CarsServiceClient {
     async fn get_car(&self, car_id: Uuid) -> Result<CarInfo, DispatcherError>;
     async fn get_all_cars(&self) -> Result<Vec<CarInfo>, DispatcherError>;
     async fn get_tariff(&self, tariff_id: Uuid) -> Result<TariffInfo, DispatcherError>;
+    // Ценообразование живет в сервисе cars
+    async fn quote_trip(&self, car_id: Uuid, request: &TripQuoteRequest) -> Result<TripQuote, DispatcherError>;
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct TariffInfo {
     pub id: Uuid,
-    pub price_per_minute: f64,
+    pub price_per_minute: Money,
     pub minimal_rating: f64,
     pub minimal_experience: u32,
 }
 
+#[derive(Serialize)]
+pub struct TripQuoteRequest {
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub ended_at: chrono::DateTime<chrono::Utc>,
+    pub distance_km: f64,
+    pub parking_minutes: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TripQuote {
+    pub car_id: Uuid,
+    pub tariff_id: Uuid,
+    pub billed_minutes: u64,
+    pub driving_minutes: u64,
+    pub parking_minutes: u64,
+    pub distance_km: f64,
+    pub base_fare: Money,
+    pub driving_cost: Money,
+    pub parking_cost: Money,
+    pub distance_cost: Money,
+    pub daily_cap_applied: bool,
+    pub minimum_fare_applied: bool,
+    pub rounding: String,
+    pub total: Money,
+}
+
 #[async_trait]
 pub trait TripsServiceClient {
     async fn start_trip(&self, user_id: Uuid, car_id: Uuid) -> Result<Uuid, DispatcherError>;
@@ -37,6 +69,8 @@ pub trait TripsServiceClient {
     async fn get_trip(&self, trip_id: Uuid) -> Result<TripInfo, DispatcherError>;
     async fn get_user_active_trip(&self, user_id: Uuid) -> Result<Option<TripInfo>, DispatcherError>;
     async fn get_all_trips(&self) -> Result<Vec<TripInfo>, DispatcherError>;
+    // GeoJSON Feature передается клиенту как есть
+    async fn get_trip_route(&self, trip_id: Uuid) -> Result<serde_json::Value, DispatcherError>;
 }
 
 #[async_trait]
@@ -44,12 +78,40 @@ pub trait TelematicsServiceClient {
     async fn send_command(&self, car_id: Uuid, command_type: String) -> Result<Uuid, DispatcherError>;
     async fn get_sensor_data_by_car_id(&self, car_id: Uuid) -> Result<Option<SensorDataInfo>, DispatcherError>;
     async fn get_all_sensor_data(&self) -> Result<Vec<SensorDataInfo>, DispatcherError>;
+    async fn get_sensor_history(&self, vin: &str, query: &SensorHistoryQuery) -> Result<Vec<SensorHistoryPointInfo>, DispatcherError>;
+    async fn list_dead_letters(&self, limit: usize) -> Result<Vec<DeadLetterInfo>, DispatcherError>;
+    async fn replay_dead_letters(&self, message_ids: Option<Vec<String>>, limit: usize) -> Result<usize, DispatcherError>;
 }
 
 #[async_trait]
 pub trait BillingServiceClient {
-    async fn create_payment(&self, trip_id: Uuid, user_id: Uuid, amount: f64) -> Result<PaymentInfo, DispatcherError>;
+    // Billing применяет к сумме поездки скидку по промокоду пользователя, если код действует на тарифе
+    async fn create_payment(&self, trip_id: Uuid, user_id: Uuid, amount: Money, tariff_id: Uuid) -> Result<PaymentInfo, DispatcherError>;
     async fn get_payment(&self, payment_id: Uuid) -> Result<PaymentInfo, DispatcherError>;
+    // Платеж, выставленный за поездку, если он уже создан
+    async fn get_trip_payment(&self, trip_id: Uuid) -> Result<Option<PaymentInfo>, DispatcherError>;
+    // PNG изображение QR-кода для оплаты, отрисованное billing сервисом
+    async fn get_payment_qr_png(&self, payment_id: Uuid) -> Result<Vec<u8>, DispatcherError>;
+    async fn create_refund(&self, payment_id: Uuid, amount: Money, reason: &str) -> Result<CreateRefundResult, DispatcherError>;
+    async fn get_refunds(&self, payment_id: Uuid) -> Result<Vec<RefundInfo>, DispatcherError>;
+    // Задолженность пользователя по неоплаченным платежам
+    async fn get_user_balance(&self, user_id: Uuid) -> Result<UserBalanceInfo, DispatcherError>;
+    async fn set_debt_override(&self, user_id: Uuid, request: &DebtOverrideRequest) -> Result<DebtOverrideInfo, DispatcherError>;
+    async fn remove_debt_override(&self, user_id: Uuid) -> Result<(), DispatcherError>;
+    async fn get_wallet(&self, user_id: Uuid) -> Result<WalletInfo, DispatcherError>;
+    // Счет на пополнение кошелька; оплачивается по QR-коду как обычный платеж
+    async fn create_top_up(&self, user_id: Uuid, amount: Money) -> Result<PaymentInfo, DispatcherError>;
+    // Платежи и движения по кошельку пользователя, от новых к старым
+    async fn get_payment_history(&self, user_id: Uuid) -> Result<Vec<PaymentHistoryItem>, DispatcherError>;
+    async fn create_promo_code(&self, request: &CreatePromoCodeRequest) -> Result<PromoCodeInfo, DispatcherError>;
+    async fn get_promo_codes(&self) -> Result<Vec<PromoCodeInfo>, DispatcherError>;
+    async fn deactivate_promo_code(&self, promo_code_id: Uuid) -> Result<PromoCodeInfo, DispatcherError>;
+    // Итоги кампаний за период [from, to) по дате платежа
+    async fn get_campaign_report(&self, query: &CampaignReportQuery) -> Result<Vec<CampaignReportInfo>, DispatcherError>;
+    // Промокод, который применится при оплате следующей (или текущей) поездки
+    async fn attach_promo_code(&self, user_id: Uuid, code: &str) -> Result<AttachedPromoCodeInfo, DispatcherError>;
+    async fn get_attached_promo_code(&self, user_id: Uuid) -> Result<AttachedPromoCodeInfo, DispatcherError>;
+    async fn detach_promo_code(&self, user_id: Uuid) -> Result<(), DispatcherError>;
 }
 
 // Модели данных для взаимодействия с сервисами
@@ -77,9 +139,20 @@ pub struct AuthRequest {
 #[derive(Serialize, Deserialize)]
 pub struct AuthResponse {
     pub token: String,
+    pub refresh_token: String,
     pub user_id: Uuid,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LogoutRequest {
+    pub refresh_token: String,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct UserInfo {
     pub id: Uuid,
@@ -87,6 +160,7 @@ pub struct UserInfo {
     pub driving_experience: u32,
     pub rating: f64,
     pub email: String,
+    pub role: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -96,8 +170,8 @@ pub struct CarInfo {
     pub license_plate: String,
     pub state: String,
     pub tariff_id: Uuid,
-    pub base_price: f64,
-    pub price_per_minute: Option<f64>, // Опционально, чтобы не ломать существующий код
+    pub base_price: Money,
+    pub price_per_minute: Option<Money>, // Опционально, чтобы не ломать существующий код
 }
 
 #[derive(Serialize, Deserialize)]
@@ -109,6 +183,19 @@ pub struct TripInfo {
     pub started_at: Option<chrono::DateTime<chrono::Utc>>,
     pub ended_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub distance_km: Option<f64>,
+    #[serde(default)]
+    pub parking_minutes: Option<u64>,
+}
+
+// properties из GeoJSON маршрута поездки
+#[derive(Deserialize)]
+pub struct TripRouteProperties {
+    pub distance_km: f64,
+    pub parking_minutes: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -129,13 +216,176 @@ pub struct LocationInfo {
     pub longitude: f64,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct SensorHistoryQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SensorHistoryPointInfo {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub samples: usize,
+    pub fuel_level: f64,
+    pub location: LocationInfo,
+    pub door_status: String,
+    pub speed: f64,
+    pub max_speed: f64,
+    pub temperature: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeadLetterInfo {
+    pub message_id: String,
+    pub routing_key: String,
+    pub error: String,
+    pub payload: String,
+    pub dead_lettered_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RefundInfo {
+    pub id: Uuid,
+    pub payment_id: Uuid,
+    pub amount: Money,
+    pub reason: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateRefundResult {
+    pub refund: RefundInfo,
+    pub payment_status: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DebtOverrideRequest {
+    pub reason: String,
+    pub granted_by: Uuid,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DebtOverrideInfo {
+    pub user_id: Uuid,
+    pub reason: String,
+    pub granted_by: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UserBalanceInfo {
+    pub user_id: Uuid,
+    pub outstanding: Money,
+    pub unpaid_payments: usize,
+    pub debt_override: Option<DebtOverrideInfo>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WalletInfo {
+    pub user_id: Uuid,
+    pub balance: Money,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WalletTransactionInfo {
+    pub id: Uuid,
+    pub kind: String,
+    pub amount: Money,
+    pub balance_change: Money,
+    pub payment_id: Uuid,
+    pub refund_id: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PaymentHistoryItem {
+    Payment(PaymentInfo),
+    WalletTransaction(WalletTransactionInfo),
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct PaymentInfo {
     pub id: Uuid,
-    pub trip_id: Uuid,
+    pub trip_id: Option<Uuid>,
     pub user_id: Uuid,
-    pub amount: f64,
+    pub kind: String,
+    pub method: String,
+    // Сумма к оплате с учетом скидки
+    pub amount: Money,
+    pub discount: Option<AppliedDiscountInfo>,
     pub status: String,
     pub qr_code_url: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub paid_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AppliedDiscountInfo {
+    pub promo_code_id: Uuid,
+    pub code: String,
+    pub amount: Money,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DiscountInfo {
+    Percentage { percent: u8 },
+    Fixed { amount: Money },
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct CreatePromoCodeRequest {
+    pub code: String,
+    pub campaign: String,
+    pub discount: DiscountInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_user_limit: Option<u32>,
+    #[serde(default)]
+    pub tariff_ids: Vec<Uuid>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PromoCodeInfo {
+    pub id: Uuid,
+    pub code: String,
+    pub campaign: String,
+    pub discount: DiscountInfo,
+    pub per_user_limit: u32,
+    pub tariff_ids: Vec<Uuid>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub active: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AttachedPromoCodeInfo {
+    pub promo_code: PromoCodeInfo,
+    pub redemptions: u32,
+    pub remaining_uses: u32,
+    pub attached_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CampaignReportQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CampaignReportInfo {
+    pub campaign: String,
+    pub promo_codes: u32,
+    pub redemptions: u32,
+    pub discount_total: Money,
+    pub charged_total: Money,
+}
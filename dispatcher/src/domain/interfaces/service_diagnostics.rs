@@ -0,0 +1,5 @@
+use crate::domain::models::CircuitBreakerStatus;
+
+pub trait CircuitBreakerMonitor {
+    fn circuit_breakers(&self) -> Vec<CircuitBreakerStatus>;
+}
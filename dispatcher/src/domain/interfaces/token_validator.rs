@@ -0,0 +1,5 @@
+use crate::domain::{errors::DispatcherError, models::AuthenticatedUser};
+
+pub trait TokenValidator {
+    fn validate_token(&self, token: &str) -> Result<AuthenticatedUser, DispatcherError>;
+}
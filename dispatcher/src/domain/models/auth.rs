@@ -0,0 +1,143 @@
+use uuid::Uuid;
+
+// Идентичность пользователя, извлеченная из JWT токена
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub user_id: Uuid,
+    pub role: Role,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Client,
+    Operator,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Client => "client",
+            Role::Operator => "operator",
+            Role::Admin => "admin",
+        }
+    }
+
+    // Админ может все, оператор (поддержка) работает с машинами, поездками и возвратами
+    // и видит состояние сервисов, но не видит персональные данные пользователей
+    // и не снимает блокировку за долг
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        match self {
+            Role::Admin => true,
+            Role::Operator => matches!(
+                permission,
+                Permission::ViewCars
+                    | Permission::ViewTrips
+                    | Permission::SendCommands
+                    | Permission::ManagePayments
+                    | Permission::ViewDiagnostics
+            ),
+            Role::Client => false,
+        }
+    }
+}
+
+impl std::str::FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "client" => Ok(Role::Client),
+            "operator" => Ok(Role::Operator),
+            "admin" => Ok(Role::Admin),
+            _ => Err(format!("Invalid role: {}", s)),
+        }
+    }
+}
+
+// Права доступа к административным endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    ViewUsers,
+    ManageUsers,
+    ViewCars,
+    ViewTrips,
+    SendCommands,
+    ManageTelemetry,
+    ManagePayments,
+    ManageDebt,
+    ManagePromotions,
+    ViewDiagnostics,
+}
+
+impl Permission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::ViewUsers => "view_users",
+            Permission::ManageUsers => "manage_users",
+            Permission::ViewCars => "view_cars",
+            Permission::ViewTrips => "view_trips",
+            Permission::SendCommands => "send_commands",
+            Permission::ManageTelemetry => "manage_telemetry",
+            Permission::ManagePayments => "manage_payments",
+            Permission::ManageDebt => "manage_debt",
+            Permission::ManagePromotions => "manage_promotions",
+            Permission::ViewDiagnostics => "view_diagnostics",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_PERMISSIONS: [Permission; 10] = [
+        Permission::ViewUsers,
+        Permission::ManageUsers,
+        Permission::ViewCars,
+        Permission::ViewTrips,
+        Permission::SendCommands,
+        Permission::ManageTelemetry,
+        Permission::ManagePayments,
+        Permission::ManageDebt,
+        Permission::ManagePromotions,
+        Permission::ViewDiagnostics,
+    ];
+
+    #[test]
+    fn test_role_permission_matrix() {
+        let operator = [
+            Permission::ViewCars,
+            Permission::ViewTrips,
+            Permission::SendCommands,
+            Permission::ManagePayments,
+            Permission::ViewDiagnostics,
+        ];
+
+        for permission in ALL_PERMISSIONS {
+            assert!(Role::Admin.has_permission(permission), "admin: {}", permission.as_str());
+            assert_eq!(
+                Role::Operator.has_permission(permission),
+                operator.contains(&permission),
+                "operator: {}",
+                permission.as_str()
+            );
+            assert!(!Role::Client.has_permission(permission), "client: {}", permission.as_str());
+        }
+    }
+
+    #[test]
+    fn test_operator_cannot_see_personal_data_or_lift_debt_block() {
+        assert!(!Role::Operator.has_permission(Permission::ViewUsers));
+        assert!(!Role::Operator.has_permission(Permission::ManageUsers));
+        assert!(!Role::Operator.has_permission(Permission::ManageDebt));
+    }
+
+    #[test]
+    fn test_role_round_trips_through_str() {
+        for role in [Role::Client, Role::Operator, Role::Admin] {
+            assert_eq!(role.as_str().parse::<Role>(), Ok(role));
+        }
+        assert!("root".parse::<Role>().is_err());
+    }
+}
@@ -0,0 +1,190 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::{
+    errors::DispatcherError,
+    models::Role,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandType {
+    OpenDoor,
+    CloseDoor,
+    LockDoor,
+    UnlockDoor,
+    LockEngine,
+    UnlockEngine,
+    StartEngine,
+    StopEngine,
+}
+
+impl CommandType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommandType::OpenDoor => "open_door",
+            CommandType::CloseDoor => "close_door",
+            CommandType::LockDoor => "lock_door",
+            CommandType::UnlockDoor => "unlock_door",
+            CommandType::LockEngine => "lock_engine",
+            CommandType::UnlockEngine => "unlock_engine",
+            CommandType::StartEngine => "start_engine",
+            CommandType::StopEngine => "stop_engine",
+        }
+    }
+
+    // Клиент управляет дверями и двигателем своей машины; блокировка двигателя
+    // (иммобилайзер) - только для поддержки и админов. Новая команда клиенту
+    // недоступна, пока ее не добавят в этот список
+    pub fn is_allowed_for(&self, role: Role) -> bool {
+        match role {
+            Role::Admin | Role::Operator => true,
+            Role::Client => matches!(
+                self,
+                CommandType::OpenDoor
+                    | CommandType::CloseDoor
+                    | CommandType::LockDoor
+                    | CommandType::UnlockDoor
+                    | CommandType::StartEngine
+                    | CommandType::StopEngine
+            ),
+        }
+    }
+
+    // Открыть машину можно уже по резерву, остальное - только в активной поездке
+    pub fn allowed_trip_statuses(&self) -> &'static [&'static str] {
+        match self {
+            CommandType::UnlockDoor => &["reserved", "active"],
+            _ => &["active"],
+        }
+    }
+}
+
+impl std::str::FromStr for CommandType {
+    type Err = DispatcherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open_door" => Ok(CommandType::OpenDoor),
+            "close_door" => Ok(CommandType::CloseDoor),
+            "lock_door" => Ok(CommandType::LockDoor),
+            "unlock_door" => Ok(CommandType::UnlockDoor),
+            "lock_engine" => Ok(CommandType::LockEngine),
+            "unlock_engine" => Ok(CommandType::UnlockEngine),
+            "start_engine" => Ok(CommandType::StartEngine),
+            "stop_engine" => Ok(CommandType::StopEngine),
+            other => Err(DispatcherError::InvalidRequest { message: format!("Invalid command type: {}", other) }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandRejectionReason {
+    UnknownCommand,
+    NotAllowedForRole,
+    NoTripOnCar,
+    TripStatusNotAllowed,
+}
+
+impl CommandRejectionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommandRejectionReason::UnknownCommand => "unknown_command",
+            CommandRejectionReason::NotAllowedForRole => "not_allowed_for_role",
+            CommandRejectionReason::NoTripOnCar => "no_trip_on_car",
+            CommandRejectionReason::TripStatusNotAllowed => "trip_status_not_allowed",
+        }
+    }
+}
+
+impl std::str::FromStr for CommandRejectionReason {
+    type Err = DispatcherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unknown_command" => Ok(CommandRejectionReason::UnknownCommand),
+            "not_allowed_for_role" => Ok(CommandRejectionReason::NotAllowedForRole),
+            "no_trip_on_car" => Ok(CommandRejectionReason::NoTripOnCar),
+            "trip_status_not_allowed" => Ok(CommandRejectionReason::TripStatusNotAllowed),
+            other => Err(DispatcherError::Internal(anyhow::anyhow!("Invalid command rejection reason: {}", other))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CommandRejection {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub role: Role,
+    pub car_id: Uuid,
+    // Как прислал клиент: неизвестные команды тоже попадают в аудит
+    pub command_type: String,
+    pub trip_id: Option<Uuid>,
+    pub trip_status: Option<String>,
+    pub reason: CommandRejectionReason,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_COMMANDS: [CommandType; 8] = [
+        CommandType::OpenDoor,
+        CommandType::CloseDoor,
+        CommandType::LockDoor,
+        CommandType::UnlockDoor,
+        CommandType::LockEngine,
+        CommandType::UnlockEngine,
+        CommandType::StartEngine,
+        CommandType::StopEngine,
+    ];
+
+    #[test]
+    fn test_client_allowed_commands() {
+        let allowed: Vec<CommandType> = ALL_COMMANDS.into_iter()
+            .filter(|command| command.is_allowed_for(Role::Client))
+            .collect();
+
+        assert_eq!(allowed, vec![
+            CommandType::OpenDoor,
+            CommandType::CloseDoor,
+            CommandType::LockDoor,
+            CommandType::UnlockDoor,
+            CommandType::StartEngine,
+            CommandType::StopEngine,
+        ]);
+    }
+
+    #[test]
+    fn test_operator_and_admin_can_send_any_command() {
+        for command in ALL_COMMANDS {
+            assert!(command.is_allowed_for(Role::Operator), "{} should be allowed for operator", command.as_str());
+            assert!(command.is_allowed_for(Role::Admin), "{} should be allowed for admin", command.as_str());
+        }
+    }
+
+    #[test]
+    fn test_only_unlock_door_is_allowed_on_reserved_trip() {
+        for command in ALL_COMMANDS {
+            let statuses = command.allowed_trip_statuses();
+            assert!(statuses.contains(&"active"), "{} should be allowed on active trip", command.as_str());
+            assert_eq!(statuses.contains(&"reserved"), command == CommandType::UnlockDoor, "{}", command.as_str());
+        }
+    }
+
+    #[test]
+    fn test_finished_trip_allows_no_commands() {
+        for command in ALL_COMMANDS {
+            for status in ["completed", "cancelled", "expired"] {
+                assert!(!command.allowed_trip_statuses().contains(&status), "{} allowed in {}", command.as_str(), status);
+            }
+        }
+    }
+
+    #[test]
+    fn test_command_type_round_trip() {
+        for command in ALL_COMMANDS {
+            assert_eq!(command.as_str().parse::<CommandType>().unwrap(), command);
+        }
+        assert!(matches!("honk".parse::<CommandType>(), Err(DispatcherError::InvalidRequest { .. })));
+    }
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    // Пропускается один пробный вызов
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitBreakerStatus {
+    pub service: String,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub failure_threshold: u32,
+    pub opened_at: Option<DateTime<Utc>>,
+    // Только для открытого breaker'а
+    pub retry_at: Option<DateTime<Utc>>,
+    // С момента запуска
+    pub rejected_calls: u64,
+}
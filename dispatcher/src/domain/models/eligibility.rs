@@ -0,0 +1,171 @@
+use crate::domain::{
+    errors::DispatcherError,
+    interfaces::{TariffInfo, UserBalanceInfo, UserInfo},
+    models::Money,
+};
+
+// Водитель допускается к тарифу, только если его рейтинг и стаж не ниже минимальных
+pub fn check_tariff_eligibility(user: &UserInfo, tariff: &TariffInfo) -> Result<(), DispatcherError> {
+    // NaN (рейтинг не посчитан) не должен проходить проверку: любое сравнение с ним ложно
+    if user.rating.is_nan() || user.rating < tariff.minimal_rating {
+        return Err(DispatcherError::DriverNotEligible {
+            reason: format!(
+                "rating {:.2} is below tariff minimum {:.2}",
+                user.rating, tariff.minimal_rating
+            ),
+        });
+    }
+
+    if user.driving_experience < tariff.minimal_experience {
+        return Err(DispatcherError::DriverNotEligible {
+            reason: format!(
+                "driving experience {} years is below tariff minimum {} years",
+                user.driving_experience, tariff.minimal_experience
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+// Новую поездку нельзя начать, пока долг выше допустимого, если администратор не выдал разрешение.
+// Billing возвращает только действующее (не истекшее) разрешение
+pub fn check_outstanding_debt(balance: &UserBalanceInfo, max_debt: Money) -> Result<(), DispatcherError> {
+    if balance.debt_override.is_some() {
+        return Ok(());
+    }
+
+    if balance.outstanding.minor_units > max_debt.minor_units {
+        return Err(DispatcherError::OutstandingDebt {
+            debt: balance.outstanding,
+            threshold: max_debt,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{interfaces::DebtOverrideInfo, models::Currency};
+    use uuid::Uuid;
+
+    fn user(rating: f64, driving_experience: u32) -> UserInfo {
+        UserInfo {
+            id: Uuid::new_v4(),
+            license_id: "DL123456".to_string(),
+            driving_experience,
+            rating,
+            email: "driver@example.com".to_string(),
+            role: "client".to_string(),
+        }
+    }
+
+    fn balance(outstanding: i64, debt_override: Option<DebtOverrideInfo>) -> UserBalanceInfo {
+        UserBalanceInfo {
+            user_id: Uuid::new_v4(),
+            outstanding: Money::new(outstanding, Currency::Rub),
+            unpaid_payments: usize::from(outstanding > 0),
+            debt_override,
+        }
+    }
+
+    fn debt_override() -> DebtOverrideInfo {
+        DebtOverrideInfo {
+            user_id: Uuid::new_v4(),
+            reason: "Disputed trip".to_string(),
+            granted_by: Uuid::new_v4(),
+            created_at: chrono::Utc::now(),
+            expires_at: None,
+        }
+    }
+
+    fn tariff(minimal_rating: f64, minimal_experience: u32) -> TariffInfo {
+        TariffInfo {
+            id: Uuid::new_v4(),
+            price_per_minute: Money::new(1000, Currency::Rub),
+            minimal_rating,
+            minimal_experience,
+        }
+    }
+
+    #[test]
+    fn test_values_equal_to_minimum_are_eligible() {
+        assert!(check_tariff_eligibility(&user(4.5, 3), &tariff(4.5, 3)).is_ok());
+    }
+
+    #[test]
+    fn test_values_above_minimum_are_eligible() {
+        assert!(check_tariff_eligibility(&user(4.9, 10), &tariff(4.5, 3)).is_ok());
+    }
+
+    #[test]
+    fn test_rating_below_minimum_is_rejected() {
+        let result = check_tariff_eligibility(&user(4.49, 10), &tariff(4.5, 3));
+        assert!(matches!(result, Err(DispatcherError::DriverNotEligible { reason }) if reason.contains("rating")));
+    }
+
+    #[test]
+    fn test_experience_below_minimum_is_rejected() {
+        let result = check_tariff_eligibility(&user(5.0, 2), &tariff(4.5, 3));
+        assert!(matches!(result, Err(DispatcherError::DriverNotEligible { reason }) if reason.contains("experience")));
+    }
+
+    #[test]
+    fn test_new_driver_without_rating_and_experience() {
+        // Тариф без требований доступен всем, тариф с требованиями - нет
+        assert!(check_tariff_eligibility(&user(0.0, 0), &tariff(0.0, 0)).is_ok());
+        assert!(check_tariff_eligibility(&user(0.0, 0), &tariff(4.0, 1)).is_err());
+    }
+
+    #[test]
+    fn test_nan_rating_is_rejected() {
+        assert!(check_tariff_eligibility(&user(f64::NAN, 10), &tariff(4.5, 3)).is_err());
+    }
+
+    #[test]
+    fn test_user_without_rating_does_not_deserialize() {
+        let json = serde_json::json!({
+            "id": Uuid::new_v4(),
+            "license_id": "DL123456",
+            "driving_experience": 5,
+            "email": "driver@example.com",
+            "role": "client",
+        });
+        assert!(serde_json::from_value::<UserInfo>(json).is_err());
+    }
+
+    #[test]
+    fn test_user_without_debt_can_start_trip() {
+        assert!(check_outstanding_debt(&balance(0, None), Money::new(0, Currency::Rub)).is_ok());
+    }
+
+    #[test]
+    fn test_debt_equal_to_threshold_is_allowed() {
+        assert!(check_outstanding_debt(&balance(50000, None), Money::new(50000, Currency::Rub)).is_ok());
+    }
+
+    #[test]
+    fn test_debt_above_threshold_is_rejected() {
+        let result = check_outstanding_debt(&balance(50001, None), Money::new(50000, Currency::Rub));
+        match result {
+            Err(DispatcherError::OutstandingDebt { debt, threshold }) => {
+                assert_eq!(debt.minor_units, 50001);
+                assert_eq!(threshold.minor_units, 50000);
+            }
+            _ => panic!("expected OutstandingDebt"),
+        }
+    }
+
+    #[test]
+    fn test_any_debt_is_rejected_with_zero_threshold() {
+        assert!(check_outstanding_debt(&balance(1, None), Money::new(0, Currency::Rub)).is_err());
+    }
+
+    #[test]
+    fn test_override_allows_trip_despite_debt() {
+        let result = check_outstanding_debt(&balance(1_000_000, Some(debt_override())), Money::new(0, Currency::Rub));
+        assert!(result.is_ok());
+    }
+}
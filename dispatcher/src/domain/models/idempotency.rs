@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+// fingerprint - хеш тела первого запроса, повтор с другим телом отклоняется
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum IdempotencyRecord {
+    InProgress { fingerprint: String },
+    Completed { fingerprint: String, response: StoredResponse },
+}
+
+impl IdempotencyRecord {
+    pub fn fingerprint(&self) -> &str {
+        match self {
+            IdempotencyRecord::InProgress { fingerprint } => fingerprint,
+            IdempotencyRecord::Completed { fingerprint, .. } => fingerprint,
+        }
+    }
+}
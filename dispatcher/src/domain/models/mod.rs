@@ -1,4 +1,17 @@
 pub mod scenarios;
+pub mod auth;
+pub mod eligibility;
+pub mod money;
+pub mod diagnostics;
+pub mod sagas;
+pub mod idempotency;
+pub mod commands;
 
 pub use scenarios::*;
-
+pub use auth::*;
+pub use eligibility::*;
+pub use money::*;
+pub use diagnostics::*;
+pub use sagas::*;
+pub use idempotency::*;
+pub use commands::*;
@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+use crate::domain::errors::DispatcherError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SagaKind {
+    StartTrip,
+    EndTrip,
+    CancelTrip,
+}
+
+impl SagaKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SagaKind::StartTrip => "start_trip",
+            SagaKind::EndTrip => "end_trip",
+            SagaKind::CancelTrip => "cancel_trip",
+        }
+    }
+}
+
+impl std::str::FromStr for SagaKind {
+    type Err = DispatcherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "start_trip" => Ok(SagaKind::StartTrip),
+            "end_trip" => Ok(SagaKind::EndTrip),
+            "cancel_trip" => Ok(SagaKind::CancelTrip),
+            other => Err(DispatcherError::Internal(anyhow::anyhow!("Invalid saga kind: {}", other))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SagaStatus {
+    // Включая ожидание повтора после временной ошибки
+    Running,
+    Compensating,
+    Completed,
+    Compensated,
+    // Постоянная ошибка или исчерпаны попытки
+    Failed,
+}
+
+impl SagaStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SagaStatus::Running => "running",
+            SagaStatus::Compensating => "compensating",
+            SagaStatus::Completed => "completed",
+            SagaStatus::Compensated => "compensated",
+            SagaStatus::Failed => "failed",
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, SagaStatus::Running | SagaStatus::Compensating)
+    }
+}
+
+impl std::str::FromStr for SagaStatus {
+    type Err = DispatcherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "running" => Ok(SagaStatus::Running),
+            "compensating" => Ok(SagaStatus::Compensating),
+            "completed" => Ok(SagaStatus::Completed),
+            "compensated" => Ok(SagaStatus::Compensated),
+            "failed" => Ok(SagaStatus::Failed),
+            other => Err(DispatcherError::Internal(anyhow::anyhow!("Invalid saga status: {}", other))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepStatus {
+    Completed,
+    Failed,
+    Compensated,
+}
+
+impl StepStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StepStatus::Completed => "completed",
+            StepStatus::Failed => "failed",
+            StepStatus::Compensated => "compensated",
+        }
+    }
+}
+
+impl std::str::FromStr for StepStatus {
+    type Err = DispatcherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "completed" => Ok(StepStatus::Completed),
+            "failed" => Ok(StepStatus::Failed),
+            "compensated" => Ok(StepStatus::Compensated),
+            other => Err(DispatcherError::Internal(anyhow::anyhow!("Invalid saga step status: {}", other))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SagaStep {
+    pub step: String,
+    pub status: StepStatus,
+    pub output: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Saga {
+    pub id: Uuid,
+    pub kind: SagaKind,
+    pub user_id: Uuid,
+    pub trip_id: Option<Uuid>,
+    pub car_id: Option<Uuid>,
+    pub status: SagaStatus,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub steps: Vec<SagaStep>,
+}
+
+impl Saga {
+    pub fn new(kind: SagaKind, user_id: Uuid, trip_id: Option<Uuid>, car_id: Option<Uuid>, lease_until: DateTime<Utc>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            kind,
+            user_id,
+            trip_id,
+            car_id,
+            status: SagaStatus::Running,
+            attempts: 0,
+            last_error: None,
+            next_attempt_at: now,
+            locked_until: Some(lease_until),
+            created_at: now,
+            updated_at: now,
+            steps: Vec::new(),
+        }
+    }
+
+    // Результат последнего успешного выполнения шага
+    pub fn step_output<T: DeserializeOwned>(&self, step: &str) -> Result<Option<T>, DispatcherError> {
+        self.steps.iter()
+            .rev()
+            .find(|s| s.step == step && s.status == StepStatus::Completed)
+            .map(|s| serde_json::from_value(s.output.clone().unwrap_or(serde_json::Value::Null)))
+            .transpose()
+            .map_err(DispatcherError::from)
+    }
+
+    pub fn is_step_compensated(&self, step: &str) -> bool {
+        self.steps.iter().any(|s| s.step == step && s.status == StepStatus::Compensated)
+    }
+}
@@ -6,7 +6,6 @@ use crate::domain::interfaces::{CarInfo, LocationInfo};
 
 #[derive(Deserialize)]
 pub struct StartTripRequest {
-    pub user_id: Uuid,
     pub car_id: Uuid,
 }
 
@@ -24,7 +23,8 @@ pub struct EndTripRequest {
 pub struct EndTripResponse {
     pub trip_id: Uuid,
     pub payment_id: Uuid,
-    pub qr_code_url: String,
+    pub payment_status: String,
+    pub qr_code_url: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -41,7 +41,7 @@ pub struct CancelTripResponse {
 #[derive(Serialize)]
 pub struct CarDataResponse {
     pub car: CarInfo,
-    pub price_per_minute: f64,
+    pub price_per_minute: crate::domain::models::Money,
     pub telematics: Option<TelematicsInfo>,
 }
 
@@ -1,24 +1,30 @@
 use async_trait::async_trait;
-use reqwest::Client;
 use uuid::Uuid;
 use tracing::{info, error, warn};
+use std::sync::Arc;
 use crate::domain::{
     errors::DispatcherError,
     interfaces::*,
+    models::Money,
 };
+use super::resilience::{CircuitBreaker, ServiceClientConfig, ServiceHttpClient};
 
 pub struct HttpUsersServiceClient {
-    client: Client,
+    client: ServiceHttpClient,
     base_url: String,
 }
 
 impl HttpUsersServiceClient {
-    pub fn new(base_url: String) -> Self {
+    pub fn new(base_url: String, config: &ServiceClientConfig) -> Self {
         Self {
-            client: Client::new(),
+            client: ServiceHttpClient::new("users", config),
             base_url,
         }
     }
+
+    pub fn circuit_breaker(&self) -> Arc<CircuitBreaker> {
+        self.client.circuit_breaker()
+    }
 }
 
 #[async_trait]
@@ -71,6 +77,56 @@ impl UsersServiceClient for HttpUsersServiceClient {
         }
     }
 
+    async fn refresh_token(&self, request: RefreshTokenRequest) -> Result<AuthResponse, DispatcherError> {
+        let url = format!("{}/users/token/refresh", self.base_url);
+        info!("Calling users service: POST {}", url);
+        
+        let response = self.client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            Err(DispatcherError::Unauthorized)
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Users service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "users".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn logout(&self, request: LogoutRequest) -> Result<(), DispatcherError> {
+        let url = format!("{}/users/logout", self.base_url);
+        info!("Calling users service: POST {}", url);
+        
+        let response = self.client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(())
+        } else if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            Err(DispatcherError::Unauthorized)
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Users service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "users".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
     async fn get_user(&self, user_id: Uuid) -> Result<UserInfo, DispatcherError> {
         let url = format!("{}/users/{}", self.base_url, user_id);
         info!("Calling users service: GET {}", url);
@@ -118,20 +174,58 @@ impl UsersServiceClient for HttpUsersServiceClient {
             })
         }
     }
+
+    async fn change_user_role(&self, user_id: Uuid, role: &str, access_token: &str) -> Result<(), DispatcherError> {
+        let url = format!("{}/users/{}/role", self.base_url, user_id);
+        info!("Calling users service: PUT {}", url);
+        
+        let response = self.client
+            .put(&url)
+            .bearer_auth(access_token)
+            .json(&serde_json::json!({"role": role}))
+            .send()
+            .await?;
+        
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            reqwest::StatusCode::NOT_FOUND => Err(DispatcherError::NotFound {
+                resource: format!("user {}", user_id),
+            }),
+            reqwest::StatusCode::UNAUTHORIZED => Err(DispatcherError::Unauthorized),
+            reqwest::StatusCode::FORBIDDEN => Err(DispatcherError::Forbidden {
+                reason: "only admins can change user roles".to_string(),
+            }),
+            reqwest::StatusCode::BAD_REQUEST => Err(DispatcherError::InvalidRequest {
+                message: response.text().await.unwrap_or_default(),
+            }),
+            status => {
+                let error_text = response.text().await.unwrap_or_default();
+                error!("Users service error: {} - {}", status, error_text);
+                Err(DispatcherError::ServiceError {
+                    service: "users".to_string(),
+                    message: format!("{}: {}", status, error_text),
+                })
+            }
+        }
+    }
 }
 
 pub struct HttpCarsServiceClient {
-    client: Client,
+    client: ServiceHttpClient,
     base_url: String,
 }
 
 impl HttpCarsServiceClient {
-    pub fn new(base_url: String) -> Self {
+    pub fn new(base_url: String, config: &ServiceClientConfig) -> Self {
         Self {
-            client: Client::new(),
+            client: ServiceHttpClient::new("cars", config),
             base_url,
         }
     }
+
+    pub fn circuit_breaker(&self) -> Arc<CircuitBreaker> {
+        self.client.circuit_breaker()
+    }
 }
 
 #[async_trait]
@@ -209,20 +303,56 @@ impl CarsServiceClient for HttpCarsServiceClient {
             })
         }
     }
+
+    async fn quote_trip(&self, car_id: Uuid, request: &TripQuoteRequest) -> Result<TripQuote, DispatcherError> {
+        let url = format!("{}/cars/{}/quote", self.base_url, car_id);
+        info!("Calling cars service: POST {}", url);
+        
+        let response = self.client
+            .post(&url)
+            .json(request)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Err(DispatcherError::NotFound {
+                resource: format!("car {}", car_id),
+            })
+        } else if response.status() == reqwest::StatusCode::BAD_REQUEST {
+            let error: serde_json::Value = response.json().await?;
+            Err(DispatcherError::InvalidRequest {
+                message: error["error"].as_str().unwrap_or("Invalid quote request").to_string(),
+            })
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Cars service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "cars".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
 }
 
 pub struct HttpTripsServiceClient {
-    client: Client,
+    client: ServiceHttpClient,
     base_url: String,
 }
 
 impl HttpTripsServiceClient {
-    pub fn new(base_url: String) -> Self {
+    pub fn new(base_url: String, config: &ServiceClientConfig) -> Self {
         Self {
-            client: Client::new(),
+            client: ServiceHttpClient::new("trips", config),
             base_url,
         }
     }
+
+    pub fn circuit_breaker(&self) -> Arc<CircuitBreaker> {
+        self.client.circuit_breaker()
+    }
 }
 
 #[async_trait]
@@ -249,6 +379,13 @@ impl TripsServiceClient for HttpTripsServiceClient {
                 .ok_or_else(|| DispatcherError::InvalidRequest {
                     message: "Invalid response format".to_string(),
                 })?)
+        } else if response.status() == reqwest::StatusCode::CONFLICT {
+            // Пользователь уже в поездке или машина занята - поездка не создана
+            let error: serde_json::Value = response.json().await?;
+            let message = error["error"].as_str().unwrap_or("Trip rejected").to_string();
+            Err(DispatcherError::Conflict { message })
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Err(DispatcherError::NotFound { resource: format!("Car {}", car_id) })
         } else {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
@@ -352,6 +489,32 @@ impl TripsServiceClient for HttpTripsServiceClient {
         }
     }
 
+    async fn get_trip_route(&self, trip_id: Uuid) -> Result<serde_json::Value, DispatcherError> {
+        let url = format!("{}/trips/{}/route", self.base_url, trip_id);
+        info!("Calling trips service: GET {}", url);
+        
+        let response = self.client
+            .get(&url)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Err(DispatcherError::NotFound {
+                resource: format!("trip {}", trip_id),
+            })
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Trips service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "trips".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
     async fn get_user_active_trip(&self, user_id: Uuid) -> Result<Option<TripInfo>, DispatcherError> {
         let url = format!("{}/users/{}/trips", self.base_url, user_id);
         info!("Calling trips service: GET {}", url);
@@ -403,17 +566,21 @@ impl TripsServiceClient for HttpTripsServiceClient {
 }
 
 pub struct HttpTelematicsServiceClient {
-    client: Client,
+    client: ServiceHttpClient,
     base_url: String,
 }
 
 impl HttpTelematicsServiceClient {
-    pub fn new(base_url: String) -> Self {
+    pub fn new(base_url: String, config: &ServiceClientConfig) -> Self {
         Self {
-            client: Client::new(),
+            client: ServiceHttpClient::new("telematics", config),
             base_url,
         }
     }
+
+    pub fn circuit_breaker(&self) -> Arc<CircuitBreaker> {
+        self.client.circuit_breaker()
+    }
 }
 
 #[async_trait]
@@ -451,18 +618,30 @@ impl TelematicsServiceClient for HttpTelematicsServiceClient {
         }
     }
 
-    async fn get_sensor_data_by_car_id(&self, _car_id: Uuid) -> Result<Option<SensorDataInfo>, DispatcherError> {
-        // Пока endpoint возвращает 501, используем get_all_sensor_data и фильтруем
-        // В будущем можно реализовать через cars сервис для получения license_plate
-        let _all_data = self.get_all_sensor_data().await?;
+    async fn get_sensor_data_by_car_id(&self, car_id: Uuid) -> Result<Option<SensorDataInfo>, DispatcherError> {
+        let url = format!("{}/sensors/car/{}", self.base_url, car_id);
+        info!("Calling telematics service: GET {}", url);
         
-        // Получаем car из cars сервиса для получения license_plate
-        // Пока используем упрощенный подход - ищем по car_id в данных
-        // В реальном приложении нужно делать запрос к cars сервису
+        let response = self.client
+            .get(&url)
+            .send()
+            .await?;
         
-        // Для MVP возвращаем None, так как нужна интеграция с cars
-        warn!("get_sensor_data_by_car_id: requires integration with cars service");
-        Ok(None)
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            // Машина еще не попала в реестр telematics - телеметрии для нее нет
+            warn!("Car {} not found in telematics car registry", car_id);
+            Ok(None)
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Telematics service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "telematics".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
     }
 
     async fn get_all_sensor_data(&self) -> Result<Vec<SensorDataInfo>, DispatcherError> {
@@ -486,25 +665,112 @@ impl TelematicsServiceClient for HttpTelematicsServiceClient {
             })
         }
     }
+
+    async fn get_sensor_history(&self, vin: &str, query: &SensorHistoryQuery) -> Result<Vec<SensorHistoryPointInfo>, DispatcherError> {
+        let url = format!("{}/sensors/{}/history", self.base_url, vin);
+        info!("Calling telematics service: GET {}", url);
+        
+        let response = self.client
+            .get(&url)
+            .query(query)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else if response.status() == reqwest::StatusCode::BAD_REQUEST {
+            let error: serde_json::Value = response.json().await?;
+            Err(DispatcherError::InvalidRequest {
+                message: error["error"].as_str().unwrap_or("Invalid history query").to_string(),
+            })
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Telematics service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "telematics".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn list_dead_letters(&self, limit: usize) -> Result<Vec<DeadLetterInfo>, DispatcherError> {
+        let url = format!("{}/dead-letters?limit={}", self.base_url, limit);
+        info!("Calling telematics service: GET {}", url);
+        
+        let response = self.client
+            .get(&url)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Telematics service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "telematics".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn replay_dead_letters(&self, message_ids: Option<Vec<String>>, limit: usize) -> Result<usize, DispatcherError> {
+        let url = format!("{}/dead-letters/replay", self.base_url);
+        info!("Calling telematics service: POST {}", url);
+        
+        let request = serde_json::json!({
+            "message_ids": message_ids,
+            "limit": limit,
+        });
+        
+        let response = self.client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            let result: serde_json::Value = response.json().await?;
+            Ok(result["replayed"].as_u64()
+                .map(|replayed| replayed as usize)
+                .ok_or_else(|| DispatcherError::InvalidRequest {
+                    message: "Invalid response format".to_string(),
+                })?)
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Telematics service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "telematics".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
 }
 
 pub struct HttpBillingServiceClient {
-    client: Client,
+    client: ServiceHttpClient,
     base_url: String,
 }
 
 impl HttpBillingServiceClient {
-    pub fn new(base_url: String) -> Self {
+    pub fn new(base_url: String, config: &ServiceClientConfig) -> Self {
         Self {
-            client: Client::new(),
+            client: ServiceHttpClient::new("billing", config),
             base_url,
         }
     }
+
+    pub fn circuit_breaker(&self) -> Arc<CircuitBreaker> {
+        self.client.circuit_breaker()
+    }
 }
 
 #[async_trait]
 impl BillingServiceClient for HttpBillingServiceClient {
-    async fn create_payment(&self, trip_id: Uuid, user_id: Uuid, amount: f64) -> Result<PaymentInfo, DispatcherError> {
+    async fn create_payment(&self, trip_id: Uuid, user_id: Uuid, amount: Money, tariff_id: Uuid) -> Result<PaymentInfo, DispatcherError> {
         let url = format!("{}/payments", self.base_url);
         info!("Calling billing service: POST {}", url);
         
@@ -512,6 +778,7 @@ impl BillingServiceClient for HttpBillingServiceClient {
             "trip_id": trip_id,
             "user_id": user_id,
             "amount": amount,
+            "tariff_id": tariff_id,
         });
         
         let response = self.client
@@ -566,5 +833,470 @@ impl BillingServiceClient for HttpBillingServiceClient {
             })
         }
     }
-}
 
+    async fn get_trip_payment(&self, trip_id: Uuid) -> Result<Option<PaymentInfo>, DispatcherError> {
+        let url = format!("{}/trips/{}/payment", self.base_url, trip_id);
+        info!("Calling billing service: GET {}", url);
+        
+        let response = self.client
+            .get(&url)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(Some(response.json().await?))
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Ok(None)
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn get_payment_qr_png(&self, payment_id: Uuid) -> Result<Vec<u8>, DispatcherError> {
+        let url = format!("{}/payments/{}/qr.png", self.base_url, payment_id);
+        info!("Calling billing service: GET {}", url);
+        
+        let response = self.client
+            .get(&url)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.bytes().await?.to_vec())
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Err(DispatcherError::NotFound {
+                resource: format!("QR code for payment {}", payment_id),
+            })
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn create_refund(&self, payment_id: Uuid, amount: Money, reason: &str) -> Result<CreateRefundResult, DispatcherError> {
+        let url = format!("{}/payments/{}/refunds", self.base_url, payment_id);
+        info!("Calling billing service: POST {}", url);
+        
+        let request = serde_json::json!({
+            "amount": amount,
+            "reason": reason,
+        });
+        
+        let response = self.client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await?;
+        
+        let status = response.status();
+        if status.is_success() {
+            Ok(response.json().await?)
+        } else if status == reqwest::StatusCode::NOT_FOUND {
+            Err(DispatcherError::NotFound {
+                resource: format!("Payment {}", payment_id),
+            })
+        } else if status == reqwest::StatusCode::BAD_REQUEST || status == reqwest::StatusCode::CONFLICT {
+            let error: serde_json::Value = response.json().await?;
+            let message = error["error"].as_str().unwrap_or("Refund rejected").to_string();
+            if status == reqwest::StatusCode::CONFLICT {
+                Err(DispatcherError::Conflict { message })
+            } else {
+                Err(DispatcherError::InvalidRequest { message })
+            }
+        } else {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn get_refunds(&self, payment_id: Uuid) -> Result<Vec<RefundInfo>, DispatcherError> {
+        let url = format!("{}/payments/{}/refunds", self.base_url, payment_id);
+        info!("Calling billing service: GET {}", url);
+        
+        let response = self.client
+            .get(&url)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Err(DispatcherError::NotFound {
+                resource: format!("Payment {}", payment_id),
+            })
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn get_user_balance(&self, user_id: Uuid) -> Result<UserBalanceInfo, DispatcherError> {
+        let url = format!("{}/users/{}/balance", self.base_url, user_id);
+        info!("Calling billing service: GET {}", url);
+        
+        let response = self.client
+            .get(&url)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn set_debt_override(&self, user_id: Uuid, request: &DebtOverrideRequest) -> Result<DebtOverrideInfo, DispatcherError> {
+        let url = format!("{}/users/{}/debt-override", self.base_url, user_id);
+        info!("Calling billing service: PUT {}", url);
+        
+        let response = self.client
+            .put(&url)
+            .json(request)
+            .send()
+            .await?;
+        
+        let status = response.status();
+        if status.is_success() {
+            Ok(response.json().await?)
+        } else if status == reqwest::StatusCode::BAD_REQUEST {
+            let error: serde_json::Value = response.json().await?;
+            Err(DispatcherError::InvalidRequest {
+                message: error["error"].as_str().unwrap_or("Debt override rejected").to_string(),
+            })
+        } else {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn remove_debt_override(&self, user_id: Uuid) -> Result<(), DispatcherError> {
+        let url = format!("{}/users/{}/debt-override", self.base_url, user_id);
+        info!("Calling billing service: DELETE {}", url);
+        
+        let response = self.client
+            .delete(&url)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(())
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Err(DispatcherError::NotFound {
+                resource: format!("debt override for user {}", user_id),
+            })
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn get_wallet(&self, user_id: Uuid) -> Result<WalletInfo, DispatcherError> {
+        let url = format!("{}/users/{}/wallet", self.base_url, user_id);
+        info!("Calling billing service: GET {}", url);
+        
+        let response = self.client
+            .get(&url)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn create_top_up(&self, user_id: Uuid, amount: Money) -> Result<PaymentInfo, DispatcherError> {
+        let url = format!("{}/users/{}/wallet/top-ups", self.base_url, user_id);
+        info!("Calling billing service: POST {}", url);
+        
+        let request = serde_json::json!({
+            "amount": amount,
+        });
+        
+        let response = self.client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await?;
+        
+        let status = response.status();
+        if status.is_success() {
+            let result: serde_json::Value = response.json().await?;
+            let payment_id: Uuid = result["payment_id"].as_str()
+                .and_then(|s| Uuid::parse_str(s).ok())
+                .ok_or_else(|| DispatcherError::InvalidRequest {
+                    message: "Invalid response format".to_string(),
+                })?;
+            
+            self.get_payment(payment_id).await
+        } else if status == reqwest::StatusCode::BAD_REQUEST {
+            let error: serde_json::Value = response.json().await?;
+            Err(DispatcherError::InvalidRequest {
+                message: error["error"].as_str().unwrap_or("Top-up rejected").to_string(),
+            })
+        } else {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn get_payment_history(&self, user_id: Uuid) -> Result<Vec<PaymentHistoryItem>, DispatcherError> {
+        let url = format!("{}/users/{}/payments", self.base_url, user_id);
+        info!("Calling billing service: GET {}", url);
+        
+        let response = self.client
+            .get(&url)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn create_promo_code(&self, request: &CreatePromoCodeRequest) -> Result<PromoCodeInfo, DispatcherError> {
+        let url = format!("{}/promo-codes", self.base_url);
+        info!("Calling billing service: POST {}", url);
+        
+        let response = self.client
+            .post(&url)
+            .json(request)
+            .send()
+            .await?;
+        
+        let status = response.status();
+        if status.is_success() {
+            Ok(response.json().await?)
+        } else if status == reqwest::StatusCode::BAD_REQUEST || status == reqwest::StatusCode::CONFLICT {
+            let error: serde_json::Value = response.json().await?;
+            let message = error["error"].as_str().unwrap_or("Promo code rejected").to_string();
+            if status == reqwest::StatusCode::CONFLICT {
+                Err(DispatcherError::Conflict { message })
+            } else {
+                Err(DispatcherError::InvalidRequest { message })
+            }
+        } else {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn get_promo_codes(&self) -> Result<Vec<PromoCodeInfo>, DispatcherError> {
+        let url = format!("{}/promo-codes", self.base_url);
+        info!("Calling billing service: GET {}", url);
+        
+        let response = self.client
+            .get(&url)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn deactivate_promo_code(&self, promo_code_id: Uuid) -> Result<PromoCodeInfo, DispatcherError> {
+        let url = format!("{}/promo-codes/{}/deactivate", self.base_url, promo_code_id);
+        info!("Calling billing service: POST {}", url);
+        
+        let response = self.client
+            .post(&url)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Err(DispatcherError::NotFound {
+                resource: format!("Promo code {}", promo_code_id),
+            })
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn get_campaign_report(&self, query: &CampaignReportQuery) -> Result<Vec<CampaignReportInfo>, DispatcherError> {
+        let url = format!("{}/promo-codes/campaigns", self.base_url);
+        info!("Calling billing service: GET {}", url);
+        
+        let response = self.client
+            .get(&url)
+            .query(query)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else if response.status() == reqwest::StatusCode::BAD_REQUEST {
+            let error: serde_json::Value = response.json().await?;
+            Err(DispatcherError::InvalidRequest {
+                message: error["error"].as_str().unwrap_or("Invalid report period").to_string(),
+            })
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn attach_promo_code(&self, user_id: Uuid, code: &str) -> Result<AttachedPromoCodeInfo, DispatcherError> {
+        let url = format!("{}/users/{}/promo-code", self.base_url, user_id);
+        info!("Calling billing service: PUT {}", url);
+        
+        let response = self.client
+            .put(&url)
+            .json(&serde_json::json!({ "code": code }))
+            .send()
+            .await?;
+        
+        let status = response.status();
+        if status.is_success() {
+            Ok(response.json().await?)
+        } else if status == reqwest::StatusCode::NOT_FOUND {
+            Err(DispatcherError::NotFound {
+                resource: "Promo code".to_string(),
+            })
+        } else if status == reqwest::StatusCode::CONFLICT {
+            let error: serde_json::Value = response.json().await?;
+            Err(DispatcherError::Conflict {
+                message: error["error"].as_str().unwrap_or("Promo code cannot be applied").to_string(),
+            })
+        } else {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn get_attached_promo_code(&self, user_id: Uuid) -> Result<AttachedPromoCodeInfo, DispatcherError> {
+        let url = format!("{}/users/{}/promo-code", self.base_url, user_id);
+        info!("Calling billing service: GET {}", url);
+        
+        let response = self.client
+            .get(&url)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Err(DispatcherError::NotFound {
+                resource: "Attached promo code".to_string(),
+            })
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+
+    async fn detach_promo_code(&self, user_id: Uuid) -> Result<(), DispatcherError> {
+        let url = format!("{}/users/{}/promo-code", self.base_url, user_id);
+        info!("Calling billing service: DELETE {}", url);
+        
+        let response = self.client
+            .delete(&url)
+            .send()
+            .await?;
+        
+        if response.status().is_success() {
+            Ok(())
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Err(DispatcherError::NotFound {
+                resource: "Attached promo code".to_string(),
+            })
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(DispatcherError::ServiceError {
+                service: "billing".to_string(),
+                message: format!("{}: {}", status, error_text),
+            })
+        }
+    }
+}
@@ -1,4 +1,5 @@
 mod http_clients;
+mod resilience;
 
 pub use http_clients::*;
-
+pub use resilience::*;
@@ -0,0 +1,539 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use reqwest::{Client, Method, Request, RequestBuilder, Response};
+use serde::Serialize;
+use uuid::Uuid;
+use tracing::{info, warn};
+use crate::domain::{
+    errors::DispatcherError,
+    interfaces::CircuitBreakerMonitor,
+    models::{CircuitBreakerStatus, CircuitState},
+};
+
+#[derive(Debug, Clone)]
+pub struct ServiceClientConfig {
+    // Таймаут запроса целиком, включая чтение тела ответа
+    pub timeout: Duration,
+    // Повторы только для GET
+    pub max_retries: u32,
+    // Удваивается с каждой попыткой
+    pub retry_base_delay: Duration,
+    pub failure_threshold: u32,
+    pub open_duration: Duration,
+}
+
+impl Default for ServiceClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(5),
+            max_retries: 2,
+            retry_base_delay: Duration::from_millis(100),
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ServiceClientConfig {
+    // Сначала `{SERVICE}_SERVICE_*` (например, `TRIPS_SERVICE_TIMEOUT_MS`), затем общие `SERVICE_*`
+    pub fn from_env(service: &str) -> anyhow::Result<Self> {
+        let defaults = Self::default();
+        let config = Self {
+            timeout: Duration::from_millis(env_u64(service, "TIMEOUT_MS", defaults.timeout.as_millis() as u64)?),
+            max_retries: env_u64(service, "MAX_RETRIES", u64::from(defaults.max_retries))? as u32,
+            retry_base_delay: Duration::from_millis(env_u64(
+                service,
+                "RETRY_BASE_DELAY_MS",
+                defaults.retry_base_delay.as_millis() as u64,
+            )?),
+            failure_threshold: env_u64(service, "BREAKER_FAILURE_THRESHOLD", u64::from(defaults.failure_threshold))? as u32,
+            open_duration: Duration::from_secs(env_u64(service, "BREAKER_OPEN_SECS", defaults.open_duration.as_secs())?),
+        };
+
+        if config.timeout.is_zero() {
+            anyhow::bail!("{}_SERVICE_TIMEOUT_MS must be greater than 0", service.to_uppercase());
+        }
+        if config.failure_threshold == 0 {
+            anyhow::bail!("{}_SERVICE_BREAKER_FAILURE_THRESHOLD must be greater than 0", service.to_uppercase());
+        }
+        Ok(config)
+    }
+}
+
+fn env_u64(service: &str, name: &str, default: u64) -> anyhow::Result<u64> {
+    let service_var = format!("{}_SERVICE_{}", service.to_uppercase(), name);
+    let shared_var = format!("SERVICE_{}", name);
+    for var in [service_var, shared_var] {
+        if let Ok(value) = std::env::var(&var) {
+            return value
+                .parse::<u64>()
+                .map_err(|e| anyhow::anyhow!("{} must be a non-negative number: {}", var, e));
+        }
+    }
+    Ok(default)
+}
+
+struct BreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<(Instant, DateTime<Utc>)>,
+    probe_in_flight: bool,
+    rejected_calls: u64,
+}
+
+pub struct CircuitBreaker {
+    service: String,
+    failure_threshold: u32,
+    open_duration: Duration,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(service: &str, failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            service: service.to_string(),
+            failure_threshold,
+            open_duration,
+            state: Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_in_flight: false,
+                rejected_calls: 0,
+            }),
+        }
+    }
+
+    // В half-open пропускается только один пробный вызов
+    fn try_acquire(&self) -> Option<CallPermit<'_>> {
+        let mut state = self.state.lock().unwrap();
+        let probe = match state.state {
+            CircuitState::Closed => false,
+            CircuitState::Open => {
+                let ready = state.opened_at.is_none_or(|(opened_at, _)| opened_at.elapsed() >= self.open_duration);
+                if !ready {
+                    state.rejected_calls += 1;
+                    return None;
+                }
+                info!("Circuit breaker for {} service is half-open, sending probe request", self.service);
+                state.state = CircuitState::HalfOpen;
+                true
+            }
+            CircuitState::HalfOpen => {
+                if state.probe_in_flight {
+                    state.rejected_calls += 1;
+                    return None;
+                }
+                true
+            }
+        };
+        if probe {
+            state.probe_in_flight = true;
+        }
+        Some(CallPermit { breaker: self, probe, finished: false })
+    }
+
+    fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.state != CircuitState::Closed {
+            info!("Circuit breaker for {} service closed", self.service);
+        }
+        state.state = CircuitState::Closed;
+        state.consecutive_failures = 0;
+        state.opened_at = None;
+        state.probe_in_flight = false;
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        state.probe_in_flight = false;
+        // Неудачный пробный вызов сразу открывает breaker заново
+        if state.state == CircuitState::HalfOpen || state.consecutive_failures >= self.failure_threshold {
+            if state.state != CircuitState::Open {
+                warn!(
+                    "Circuit breaker for {} service opened after {} consecutive failures",
+                    self.service, state.consecutive_failures
+                );
+            }
+            state.state = CircuitState::Open;
+            state.opened_at = Some((Instant::now(), Utc::now()));
+        }
+    }
+
+    fn release_probe(&self) {
+        self.state.lock().unwrap().probe_in_flight = false;
+    }
+
+    pub fn status(&self) -> CircuitBreakerStatus {
+        let state = self.state.lock().unwrap();
+        let opened_at = state.opened_at.map(|(_, opened_at)| opened_at);
+        let retry_at = match state.state {
+            CircuitState::Open => opened_at.and_then(|opened_at| {
+                chrono::Duration::from_std(self.open_duration).ok().map(|duration| opened_at + duration)
+            }),
+            _ => None,
+        };
+
+        CircuitBreakerStatus {
+            service: self.service.clone(),
+            state: state.state,
+            consecutive_failures: state.consecutive_failures,
+            failure_threshold: self.failure_threshold,
+            opened_at,
+            retry_at,
+            rejected_calls: state.rejected_calls,
+        }
+    }
+}
+
+// Пробный вызов, прерванный без результата (клиент закрыл соединение), освобождает место для следующей пробы
+struct CallPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+    finished: bool,
+}
+
+impl CallPermit<'_> {
+    fn success(mut self) {
+        self.finished = true;
+        self.breaker.record_success();
+    }
+
+    fn failure(mut self) {
+        self.finished = true;
+        self.breaker.record_failure();
+    }
+}
+
+impl Drop for CallPermit<'_> {
+    fn drop(&mut self) {
+        if self.probe && !self.finished {
+            self.breaker.release_probe();
+        }
+    }
+}
+
+pub struct ServiceHttpClient {
+    client: Client,
+    service: &'static str,
+    max_retries: u32,
+    retry_base_delay: Duration,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl ServiceHttpClient {
+    pub fn new(service: &'static str, config: &ServiceClientConfig) -> Self {
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .expect("failed to build HTTP client");
+
+        Self {
+            client,
+            service,
+            max_retries: config.max_retries,
+            retry_base_delay: config.retry_base_delay,
+            breaker: Arc::new(CircuitBreaker::new(service, config.failure_threshold, config.open_duration)),
+        }
+    }
+
+    pub fn circuit_breaker(&self) -> Arc<CircuitBreaker> {
+        Arc::clone(&self.breaker)
+    }
+
+    pub fn get(&self, url: &str) -> ServiceRequest<'_> {
+        self.request(Method::GET, url)
+    }
+
+    pub fn post(&self, url: &str) -> ServiceRequest<'_> {
+        self.request(Method::POST, url)
+    }
+
+    pub fn put(&self, url: &str) -> ServiceRequest<'_> {
+        self.request(Method::PUT, url)
+    }
+
+    pub fn delete(&self, url: &str) -> ServiceRequest<'_> {
+        self.request(Method::DELETE, url)
+    }
+
+    fn request(&self, method: Method, url: &str) -> ServiceRequest<'_> {
+        ServiceRequest {
+            client: self,
+            builder: self.client.request(method, url),
+        }
+    }
+
+    async fn execute(&self, builder: RequestBuilder) -> Result<Response, DispatcherError> {
+        let request = builder.build()?;
+        // Один логический вызов - одно разрешение breaker'а и один итог, сколько бы ни было повторов
+        let permit = self.breaker.try_acquire().ok_or_else(|| {
+            warn!("Circuit breaker for {} service is open, rejecting {} {}", self.service, request.method(), request.url());
+            DispatcherError::ServiceUnavailable { service: self.service.to_string() }
+        })?;
+
+        let result = self.send_with_retries(request).await;
+        if is_service_failure(&result) {
+            permit.failure();
+        } else {
+            permit.success();
+        }
+        result.map_err(|e| self.transport_error(e))
+    }
+
+    async fn send_with_retries(&self, mut request: Request) -> Result<Response, reqwest::Error> {
+        // Повторяются только GET: POST/PUT/DELETE могли выполниться, хотя ответ не дошел
+        let max_attempts = if request.method() == Method::GET { self.max_retries + 1 } else { 1 };
+        let mut attempt = 1;
+
+        loop {
+            let retry_request = if attempt < max_attempts { request.try_clone() } else { None };
+            let result = self.client.execute(request).await;
+
+            match retry_request {
+                Some(next_request) if is_service_failure(&result) => {
+                    let delay = self.retry_delay(attempt);
+                    warn!(
+                        "{} service call {} {} failed (attempt {}/{}), retrying in {:?}",
+                        self.service, next_request.method(), next_request.url(), attempt, max_attempts, delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    request = next_request;
+                    attempt += 1;
+                }
+                _ => return result,
+            }
+        }
+    }
+
+    // Случайная составляющая разводит повторы разных запросов во времени
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        let delay = self.retry_base_delay.saturating_mul(1 << (attempt - 1).min(16));
+        let half = delay.as_millis() as u64 / 2;
+        let jitter = if half == 0 { 0 } else { (Uuid::new_v4().as_u128() % u128::from(half + 1)) as u64 };
+        Duration::from_millis(half + jitter)
+    }
+
+    fn transport_error(&self, error: reqwest::Error) -> DispatcherError {
+        let message = if error.is_timeout() {
+            "request timed out".to_string()
+        } else if error.is_connect() {
+            "connection failed".to_string()
+        } else {
+            return DispatcherError::Http(error);
+        };
+        warn!("{} service call failed: {}", self.service, message);
+        DispatcherError::ServiceError {
+            service: self.service.to_string(),
+            message,
+        }
+    }
+}
+
+// Сетевые ошибки, таймауты и ответы 5xx считаются отказом сервиса, 4xx - нет
+fn is_service_failure(result: &Result<Response, reqwest::Error>) -> bool {
+    match result {
+        Ok(response) => response.status().is_server_error(),
+        Err(_) => true,
+    }
+}
+
+pub struct ServiceRequest<'a> {
+    client: &'a ServiceHttpClient,
+    builder: RequestBuilder,
+}
+
+impl ServiceRequest<'_> {
+    pub fn json<T: Serialize + ?Sized>(mut self, json: &T) -> Self {
+        self.builder = self.builder.json(json);
+        self
+    }
+
+    pub fn query<T: Serialize + ?Sized>(mut self, query: &T) -> Self {
+        self.builder = self.builder.query(query);
+        self
+    }
+
+    pub fn bearer_auth(mut self, token: &str) -> Self {
+        self.builder = self.builder.bearer_auth(token);
+        self
+    }
+
+    pub async fn send(self) -> Result<Response, DispatcherError> {
+        self.client.execute(self.builder).await
+    }
+}
+
+pub struct CircuitBreakerRegistry {
+    breakers: Vec<Arc<CircuitBreaker>>,
+}
+
+impl CircuitBreakerRegistry {
+    pub fn new(breakers: Vec<Arc<CircuitBreaker>>) -> Self {
+        Self { breakers }
+    }
+}
+
+impl CircuitBreakerMonitor for CircuitBreakerRegistry {
+    fn circuit_breakers(&self) -> Vec<CircuitBreakerStatus> {
+        self.breakers.iter().map(|breaker| breaker.status()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, http::StatusCode, routing::any};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn breaker(failure_threshold: u32, open_duration: Duration) -> CircuitBreaker {
+        CircuitBreaker::new("test", failure_threshold, open_duration)
+    }
+
+    fn fail(breaker: &CircuitBreaker) {
+        breaker.try_acquire().expect("call should be allowed").failure();
+    }
+
+    #[test]
+    fn test_breaker_opens_at_failure_threshold() {
+        let breaker = breaker(3, Duration::from_secs(60));
+        fail(&breaker);
+        fail(&breaker);
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+
+        fail(&breaker);
+        assert_eq!(breaker.status().state, CircuitState::Open);
+        assert!(breaker.try_acquire().is_none());
+        assert_eq!(breaker.status().rejected_calls, 1);
+    }
+
+    #[test]
+    fn test_success_resets_consecutive_failures() {
+        let breaker = breaker(2, Duration::from_secs(60));
+        fail(&breaker);
+        breaker.try_acquire().unwrap().success();
+        fail(&breaker);
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+        assert_eq!(breaker.status().consecutive_failures, 1);
+    }
+
+    #[test]
+    fn test_only_one_probe_in_half_open() {
+        let breaker = breaker(1, Duration::ZERO);
+        fail(&breaker);
+
+        let probe = breaker.try_acquire().expect("probe should be allowed");
+        assert_eq!(breaker.status().state, CircuitState::HalfOpen);
+        assert!(breaker.try_acquire().is_none());
+
+        probe.success();
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+        assert!(breaker.try_acquire().is_some());
+    }
+
+    #[test]
+    fn test_failed_probe_reopens_breaker() {
+        let breaker = breaker(3, Duration::from_millis(50));
+        for _ in 0..3 {
+            fail(&breaker);
+        }
+        std::thread::sleep(Duration::from_millis(60));
+
+        // Одной неудачной пробы достаточно, порог ошибок не важен
+        fail(&breaker);
+        assert_eq!(breaker.status().state, CircuitState::Open);
+        assert!(breaker.try_acquire().is_none());
+    }
+
+    #[test]
+    fn test_dropped_probe_releases_slot() {
+        let breaker = breaker(1, Duration::ZERO);
+        fail(&breaker);
+
+        drop(breaker.try_acquire().expect("probe should be allowed"));
+        assert_eq!(breaker.status().state, CircuitState::HalfOpen);
+        assert!(breaker.try_acquire().is_some());
+    }
+
+    // Сервис, который отвечает заданным статусом и считает запросы
+    async fn serve(status: StatusCode) -> (String, Arc<AtomicU32>) {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&calls);
+        let app = Router::new().route("/", any(move || {
+            let counter = Arc::clone(&counter);
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                status
+            }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, calls)
+    }
+
+    fn client(max_retries: u32, failure_threshold: u32) -> ServiceHttpClient {
+        ServiceHttpClient::new("test", &ServiceClientConfig {
+            timeout: Duration::from_secs(5),
+            max_retries,
+            retry_base_delay: Duration::from_millis(1),
+            failure_threshold,
+            open_duration: Duration::from_secs(60),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_get_is_retried_on_server_error() {
+        let (url, calls) = serve(StatusCode::SERVICE_UNAVAILABLE).await;
+        let client = client(2, 10);
+
+        let response = client.get(&url).send().await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_non_idempotent_requests_are_not_retried() {
+        let (url, calls) = serve(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let client = client(2, 10);
+
+        client.post(&url).send().await.unwrap();
+        client.put(&url).send().await.unwrap();
+        client.delete(&url).send().await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_client_error_is_not_retried_or_counted() {
+        let (url, calls) = serve(StatusCode::NOT_FOUND).await;
+        let client = client(2, 1);
+
+        client.get(&url).send().await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(client.circuit_breaker().status().state, CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_retried_call_counts_as_one_failure() {
+        let (url, calls) = serve(StatusCode::BAD_GATEWAY).await;
+        let client = client(2, 2);
+
+        client.get(&url).send().await.unwrap();
+        let status = client.circuit_breaker().status();
+        assert_eq!(status.consecutive_failures, 1);
+        assert_eq!(status.state, CircuitState::Closed);
+
+        client.get(&url).send().await.unwrap();
+        assert_eq!(client.circuit_breaker().status().state, CircuitState::Open);
+        assert_eq!(calls.load(Ordering::SeqCst), 6);
+
+        let result = client.get(&url).send().await;
+        assert!(matches!(result, Err(DispatcherError::ServiceUnavailable { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), 6);
+    }
+}
@@ -1,4 +1,9 @@
 pub mod clients;
+pub mod redis;
+pub mod repositories;
+pub mod services;
 
 pub use clients::*;
-
+pub use self::redis::*;
+pub use repositories::*;
+pub use services::*;
@@ -0,0 +1,3 @@
+mod redis_idempotency_store;
+
+pub use redis_idempotency_store::*;
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use tokio::sync::Mutex;
+use crate::domain::{
+    errors::DispatcherError,
+    interfaces::IdempotencyStore,
+    models::IdempotencyRecord,
+};
+
+// Сколько раз пробуем занять ключ, если он истек между SET NX и GET
+const RESERVE_ATTEMPTS: usize = 3;
+
+pub struct RedisIdempotencyStore {
+    connection: Arc<Mutex<ConnectionManager>>,
+}
+
+impl RedisIdempotencyStore {
+    pub async fn new(redis_url: &str) -> Result<Self, anyhow::Error> {
+        let client = redis::Client::open(redis_url)?;
+        let connection = ConnectionManager::new(client).await?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+}
+
+impl Clone for RedisIdempotencyStore {
+    fn clone(&self) -> Self {
+        Self {
+            connection: Arc::clone(&self.connection),
+        }
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for RedisIdempotencyStore {
+    async fn reserve(&self, key: &str, record: &IdempotencyRecord, ttl: Duration) -> Result<Option<IdempotencyRecord>, DispatcherError> {
+        let value = serde_json::to_string(record)?;
+        let mut conn = self.connection.lock().await;
+
+        for _ in 0..RESERVE_ATTEMPTS {
+            let reserved: Option<String> = redis::cmd("SET")
+                .arg(key)
+                .arg(&value)
+                .arg("NX")
+                .arg("EX")
+                .arg(ttl.as_secs().max(1))
+                .query_async(&mut *conn)
+                .await?;
+            if reserved.is_some() {
+                return Ok(None);
+            }
+
+            let existing: Option<String> = conn.get(key).await?;
+            if let Some(existing) = existing {
+                return Ok(Some(serde_json::from_str(&existing)?));
+            }
+        }
+
+        Err(DispatcherError::Internal(anyhow::anyhow!(
+            "Failed to reserve idempotency key {} after {} attempts",
+            key, RESERVE_ATTEMPTS
+        )))
+    }
+
+    async fn save(&self, key: &str, record: &IdempotencyRecord, ttl: Duration) -> Result<(), DispatcherError> {
+        let value = serde_json::to_string(record)?;
+        let mut conn = self.connection.lock().await;
+        let _: () = conn.set_ex(key, value, ttl.as_secs().max(1)).await?;
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> Result<(), DispatcherError> {
+        let mut conn = self.connection.lock().await;
+        let _: () = conn.del(key).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+mod postgres_saga_repository;
+mod postgres_command_audit_repository;
+
+pub use postgres_saga_repository::*;
+pub use postgres_command_audit_repository::*;
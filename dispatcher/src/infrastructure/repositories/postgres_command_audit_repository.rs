@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use crate::domain::{
+    errors::DispatcherError,
+    interfaces::CommandAuditRepository,
+    models::CommandRejection,
+};
+
+pub struct PostgresCommandAuditRepository {
+    pool: PgPool,
+}
+
+impl PostgresCommandAuditRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl Clone for PostgresCommandAuditRepository {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+fn rejection_from_row(r: &PgRow) -> Result<CommandRejection, DispatcherError> {
+    Ok(CommandRejection {
+        id: r.get("id"),
+        user_id: r.get("user_id"),
+        role: r.get::<String, _>("role")
+            .parse()
+            .map_err(|e: String| DispatcherError::Internal(anyhow::anyhow!(e)))?,
+        car_id: r.get("car_id"),
+        command_type: r.get("command_type"),
+        trip_id: r.get("trip_id"),
+        trip_status: r.get("trip_status"),
+        reason: r.get::<String, _>("reason").parse()?,
+        created_at: r.get("created_at"),
+    })
+}
+
+#[async_trait]
+impl CommandAuditRepository for PostgresCommandAuditRepository {
+    async fn record_rejection(&self, rejection: &CommandRejection) -> Result<(), DispatcherError> {
+        sqlx::query(
+            r#"
+            INSERT INTO command_rejections (id, user_id, role, car_id, command_type, trip_id, trip_status, reason, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(rejection.id)
+        .bind(rejection.user_id)
+        .bind(rejection.role.as_str())
+        .bind(rejection.car_id)
+        .bind(&rejection.command_type)
+        .bind(rejection.trip_id)
+        .bind(&rejection.trip_status)
+        .bind(rejection.reason.as_str())
+        .bind(rejection.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_recent_rejections(&self, limit: i64) -> Result<Vec<CommandRejection>, DispatcherError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, user_id, role, car_id, command_type, trip_id, trip_status, reason, created_at
+            FROM command_rejections
+            ORDER BY created_at DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(rejection_from_row).collect()
+    }
+}
@@ -0,0 +1,229 @@
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::domain::{
+    errors::DispatcherError,
+    interfaces::SagaRepository,
+    models::{Saga, SagaKind, SagaStep},
+};
+
+pub struct PostgresSagaRepository {
+    pool: PgPool,
+}
+
+impl PostgresSagaRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn load_steps(&self, saga: &mut Saga) -> Result<(), DispatcherError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT step, status, output, error, created_at
+            FROM saga_steps
+            WHERE saga_id = $1
+            ORDER BY id
+            "#,
+        )
+        .bind(saga.id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        saga.steps = rows.iter().map(|r| {
+            Ok(SagaStep {
+                step: r.get("step"),
+                status: r.get::<String, _>("status").parse()?,
+                output: r.get("output"),
+                error: r.get("error"),
+                created_at: r.get("created_at"),
+            })
+        }).collect::<Result<_, DispatcherError>>()?;
+
+        Ok(())
+    }
+}
+
+impl Clone for PostgresSagaRepository {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+fn saga_from_row(r: &PgRow) -> Result<Saga, DispatcherError> {
+    Ok(Saga {
+        id: r.get("id"),
+        kind: r.get::<String, _>("kind").parse()?,
+        user_id: r.get("user_id"),
+        trip_id: r.get("trip_id"),
+        car_id: r.get("car_id"),
+        status: r.get::<String, _>("status").parse()?,
+        attempts: r.get::<i32, _>("attempts") as u32,
+        last_error: r.get("last_error"),
+        next_attempt_at: r.get("next_attempt_at"),
+        locked_until: r.get("locked_until"),
+        created_at: r.get("created_at"),
+        updated_at: r.get("updated_at"),
+        steps: Vec::new(),
+    })
+}
+
+#[async_trait]
+impl SagaRepository for PostgresSagaRepository {
+    async fn create(&self, saga: &Saga) -> Result<bool, DispatcherError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO sagas (id, kind, user_id, trip_id, car_id, status, attempts, last_error, next_attempt_at, locked_until, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            "#,
+        )
+        .bind(saga.id)
+        .bind(saga.kind.as_str())
+        .bind(saga.user_id)
+        .bind(saga.trip_id)
+        .bind(saga.car_id)
+        .bind(saga.status.as_str())
+        .bind(saga.attempts as i32)
+        .bind(&saga.last_error)
+        .bind(saga.next_attempt_at)
+        .bind(saga.locked_until)
+        .bind(saga.created_at)
+        .bind(saga.updated_at)
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) => match e.as_database_error() {
+                Some(db_error) if db_error.is_unique_violation() => Ok(false),
+                _ => Err(DispatcherError::Database(e)),
+            },
+        }
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Saga>, DispatcherError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, kind, user_id, trip_id, car_id, status, attempts, last_error, next_attempt_at, locked_until, created_at, updated_at
+            FROM sagas
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let mut saga = saga_from_row(&row)?;
+        self.load_steps(&mut saga).await?;
+        Ok(Some(saga))
+    }
+
+    async fn find_latest_by_trip(&self, kind: SagaKind, trip_id: Uuid) -> Result<Option<Saga>, DispatcherError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, kind, user_id, trip_id, car_id, status, attempts, last_error, next_attempt_at, locked_until, created_at, updated_at
+            FROM sagas
+            WHERE kind = $1 AND trip_id = $2
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(kind.as_str())
+        .bind(trip_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let mut saga = saga_from_row(&row)?;
+        self.load_steps(&mut saga).await?;
+        Ok(Some(saga))
+    }
+
+    async fn find_due(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Uuid>, DispatcherError> {
+        let ids = sqlx::query_scalar(
+            r#"
+            SELECT id
+            FROM sagas
+            WHERE status IN ('running', 'compensating')
+              AND next_attempt_at <= $1
+              AND (locked_until IS NULL OR locked_until < $1)
+            ORDER BY next_attempt_at
+            LIMIT $2
+            "#,
+        )
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    async fn try_lock(&self, id: Uuid, now: DateTime<Utc>, until: DateTime<Utc>) -> Result<bool, DispatcherError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE sagas
+            SET locked_until = $3, updated_at = $2
+            WHERE id = $1
+              AND status IN ('running', 'compensating')
+              AND (locked_until IS NULL OR locked_until < $2)
+            "#,
+        )
+        .bind(id)
+        .bind(now)
+        .bind(until)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn append_step(&self, saga_id: Uuid, step: &SagaStep) -> Result<(), DispatcherError> {
+        sqlx::query(
+            r#"
+            INSERT INTO saga_steps (saga_id, step, status, output, error, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(saga_id)
+        .bind(&step.step)
+        .bind(step.status.as_str())
+        .bind(&step.output)
+        .bind(&step.error)
+        .bind(step.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update(&self, saga: &Saga) -> Result<(), DispatcherError> {
+        sqlx::query(
+            r#"
+            UPDATE sagas
+            SET trip_id = $2, status = $3, attempts = $4, last_error = $5,
+                next_attempt_at = $6, locked_until = $7, updated_at = $8
+            WHERE id = $1
+            "#,
+        )
+        .bind(saga.id)
+        .bind(saga.trip_id)
+        .bind(saga.status.as_str())
+        .bind(saga.attempts as i32)
+        .bind(&saga.last_error)
+        .bind(saga.next_attempt_at)
+        .bind(saga.locked_until)
+        .bind(saga.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
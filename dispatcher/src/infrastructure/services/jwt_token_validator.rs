@@ -0,0 +1,158 @@
+use uuid::Uuid;
+use jsonwebtoken::{decode, Algorithm, Validation, DecodingKey};
+use serde::{Deserialize, Serialize};
+use crate::domain::{
+    errors::DispatcherError,
+    interfaces::TokenValidator,
+    models::{AuthenticatedUser, Role},
+};
+
+// Должны совпадать с claims, которые выпускает users сервис
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sub: String, // user_id
+    role: String,
+    exp: usize,
+    iat: usize,
+}
+
+#[derive(Clone)]
+pub struct JwtTokenValidator {
+    secret: String,
+}
+
+impl JwtTokenValidator {
+    pub fn new(secret: String) -> Self {
+        Self { secret }
+    }
+}
+
+impl TokenValidator for JwtTokenValidator {
+    fn validate_token(&self, token: &str) -> Result<AuthenticatedUser, DispatcherError> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.validate_exp = true;
+
+        let token_data = decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(self.secret.as_ref()),
+            &validation,
+        )
+        .map_err(|_| DispatcherError::Unauthorized)?;
+
+        let user_id = Uuid::parse_str(&token_data.claims.sub)
+            .map_err(|_| DispatcherError::Unauthorized)?;
+
+        // Токен без роли не проходит разбор claims и отклоняется выше
+        let role = token_data.claims.role
+            .parse::<Role>()
+            .map_err(|_| DispatcherError::Unauthorized)?;
+
+        Ok(AuthenticatedUser { user_id, role })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+
+    const SECRET: &str = "test-secret";
+
+    fn signed(claims: &serde_json::Value, algorithm: Algorithm, secret: &str) -> String {
+        encode(&Header::new(algorithm), claims, &EncodingKey::from_secret(secret.as_ref())).unwrap()
+    }
+
+    fn token(claims: &serde_json::Value) -> String {
+        signed(claims, Algorithm::HS256, SECRET)
+    }
+
+    fn claims(role: Option<&str>) -> serde_json::Value {
+        let now = chrono::Utc::now().timestamp();
+        let mut claims = serde_json::json!({
+            "sub": Uuid::new_v4().to_string(),
+            "exp": now + 3600,
+            "iat": now,
+        });
+        if let Some(role) = role {
+            claims["role"] = role.into();
+        }
+        claims
+    }
+
+    fn validator() -> JwtTokenValidator {
+        JwtTokenValidator::new(SECRET.to_string())
+    }
+
+    #[test]
+    fn test_role_is_taken_from_token() {
+        for role in [Role::Client, Role::Operator, Role::Admin] {
+            let user = validator().validate_token(&token(&claims(Some(role.as_str())))).unwrap();
+            assert_eq!(user.role, role);
+        }
+    }
+
+    #[test]
+    fn test_token_without_role_is_rejected() {
+        let result = validator().validate_token(&token(&claims(None)));
+
+        assert!(matches!(result, Err(DispatcherError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_token_with_unknown_role_is_rejected() {
+        let result = validator().validate_token(&token(&claims(Some("superuser"))));
+
+        assert!(matches!(result, Err(DispatcherError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_valid_token_yields_user() {
+        let claims = claims(Some("client"));
+
+        let user = validator().validate_token(&token(&claims)).unwrap();
+
+        assert_eq!(user.user_id.to_string(), claims["sub"].as_str().unwrap());
+    }
+
+    #[test]
+    fn test_expired_token_is_rejected() {
+        let mut claims = claims(Some("client"));
+        // Больше стандартного допуска на расхождение часов (60 секунд)
+        let issued_at = chrono::Utc::now().timestamp() - 7200;
+        claims["iat"] = issued_at.into();
+        claims["exp"] = (issued_at + 3600).into();
+
+        let result = validator().validate_token(&token(&claims));
+
+        assert!(matches!(result, Err(DispatcherError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_token_with_wrong_signature_is_rejected() {
+        let forged = signed(&claims(Some("admin")), Algorithm::HS256, "other-secret");
+
+        assert!(matches!(validator().validate_token(&forged), Err(DispatcherError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_token_with_wrong_algorithm_is_rejected() {
+        let token = signed(&claims(Some("client")), Algorithm::HS512, SECRET);
+
+        assert!(matches!(validator().validate_token(&token), Err(DispatcherError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_malformed_token_is_rejected() {
+        for token in ["", "not-a-jwt", "a.b.c"] {
+            assert!(matches!(validator().validate_token(token), Err(DispatcherError::Unauthorized)), "{:?}", token);
+        }
+    }
+
+    #[test]
+    fn test_token_with_non_uuid_subject_is_rejected() {
+        let mut claims = claims(Some("client"));
+        claims["sub"] = "user-1".into();
+
+        assert!(matches!(validator().validate_token(&token(&claims)), Err(DispatcherError::Unauthorized)));
+    }
+}
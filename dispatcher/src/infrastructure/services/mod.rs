@@ -0,0 +1,3 @@
+mod jwt_token_validator;
+
+pub use jwt_token_validator::*;
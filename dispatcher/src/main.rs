@@ -2,9 +2,11 @@ use dispatcher::*;
 
 use dotenv::dotenv;
 use tokio::net::TcpListener;
-use tracing::{info, error};
+use tracing::{info, error, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 use std::sync::Arc;
+use std::time::Duration;
+use sqlx::PgPool;
 
 use infrastructure::{
     HttpUsersServiceClient,
@@ -12,14 +14,27 @@ use infrastructure::{
     HttpTripsServiceClient,
     HttpTelematicsServiceClient,
     HttpBillingServiceClient,
+    PostgresSagaRepository,
+    PostgresCommandAuditRepository,
+    RedisIdempotencyStore,
+    CircuitBreakerRegistry,
+    ServiceClientConfig,
+    JwtTokenValidator,
 };
+use application::sagas::{SagaCoordinator, SagaPolicy};
 use application::use_cases::{
     StartTripScenario,
+    ActivateTripScenario,
     EndTripScenario,
     CancelTripScenario,
     GetCarDataScenario,
+    GetAvailableCarsScenario,
+    GetFareEstimateScenario,
+    RecoverSagasScenario,
+    SendCarCommandScenario,
 };
-use presentation::{create_router, AppState};
+use domain::models::{Currency, Money};
+use presentation::{create_router, AppState, middleware::IdempotencyState};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -45,6 +60,50 @@ async fn main() -> anyhow::Result<()> {
     let billing_url = std::env::var("BILLING_SERVICE_URL")
         .unwrap_or_else(|_| "http://localhost:3004".to_string());
     
+    let database_url = std::env::var("DATABASE_URL")
+        .map_err(|_| {
+            error!("DATABASE_URL environment variable is not set");
+            anyhow::anyhow!("DATABASE_URL must be set")
+        })?;
+    
+    let redis_url = std::env::var("REDIS_URL")
+        .unwrap_or_else(|_| "redis://localhost:6379".to_string());
+    
+    let jwt_secret = std::env::var("JWT_SECRET")
+        .unwrap_or_else(|_| {
+            warn!("JWT_SECRET not set, using default (not recommended for production)");
+            "your-secret-key".to_string()
+        });
+    
+    // Порог задолженности в копейках, выше которого новые поездки запрещены
+    let max_outstanding_debt = std::env::var("MAX_OUTSTANDING_DEBT_MINOR")
+        .unwrap_or_else(|_| "0".to_string())
+        .parse::<i64>()
+        .map_err(|e| {
+            error!("Invalid MAX_OUTSTANDING_DEBT_MINOR value: {}", e);
+            anyhow::anyhow!("MAX_OUTSTANDING_DEBT_MINOR must be a valid number")
+        })?;
+    
+    // Аренда саги, число попыток и пауза перед первым повтором
+    let saga_defaults = SagaPolicy::default();
+    let saga_policy = SagaPolicy {
+        lease: Duration::from_secs(env_u64("SAGA_LEASE_SECS", saga_defaults.lease.as_secs())?),
+        max_attempts: env_u64("SAGA_MAX_ATTEMPTS", u64::from(saga_defaults.max_attempts))? as u32,
+        retry_base_delay: Duration::from_secs(env_u64("SAGA_RETRY_BASE_DELAY_SECS", saga_defaults.retry_base_delay.as_secs())?),
+    };
+    // Сколько хранятся ответы для повторов с Idempotency-Key и сколько ключ занят выполняющимся запросом
+    let idempotency_ttl = Duration::from_secs(env_u64("IDEMPOTENCY_TTL_SECS", 86400)?);
+    let idempotency_lock_ttl = Duration::from_secs(env_u64("IDEMPOTENCY_LOCK_TTL_SECS", 60)?);
+    if idempotency_ttl.is_zero() || idempotency_lock_ttl.is_zero() {
+        return Err(anyhow::anyhow!("IDEMPOTENCY_TTL_SECS and IDEMPOTENCY_LOCK_TTL_SECS must be greater than 0"));
+    }
+    let saga_recovery_interval_secs = env_u64("SAGA_RECOVERY_INTERVAL_SECS", 15)?;
+    if saga_policy.lease.is_zero() || saga_policy.max_attempts == 0 || saga_recovery_interval_secs == 0 {
+        return Err(anyhow::anyhow!(
+            "SAGA_LEASE_SECS, SAGA_MAX_ATTEMPTS and SAGA_RECOVERY_INTERVAL_SECS must be greater than 0"
+        ));
+    }
+    
     let port = std::env::var("PORT")
         .unwrap_or_else(|_| "8080".to_string())
         .parse::<u16>()
@@ -59,21 +118,134 @@ async fn main() -> anyhow::Result<()> {
     info!("  Trips: {}", trips_url);
     info!("  Telematics: {}", telematics_url);
     info!("  Billing: {}", billing_url);
+    info!("  Redis: {}", redis_url);
+    info!("Max outstanding debt for new trips: {} minor units", max_outstanding_debt);
+    info!(
+        "Sagas: lease {:?}, {} attempts, retry after {:?}, recovery every {}s",
+        saga_policy.lease, saga_policy.max_attempts, saga_policy.retry_base_delay, saga_recovery_interval_secs
+    );
+
+    info!(
+        "Idempotency keys: responses kept for {:?}, in-flight lock {:?}",
+        idempotency_ttl, idempotency_lock_ttl
+    );
+
+    // Таймауты, повторы и circuit breaker'ы для каждого сервиса
+    let users_config = ServiceClientConfig::from_env("users")?;
+    let cars_config = ServiceClientConfig::from_env("cars")?;
+    let trips_config = ServiceClientConfig::from_env("trips")?;
+    let telematics_config = ServiceClientConfig::from_env("telematics")?;
+    let billing_config = ServiceClientConfig::from_env("billing")?;
+    for (service, config) in [
+        ("Users", &users_config),
+        ("Cars", &cars_config),
+        ("Trips", &trips_config),
+        ("Telematics", &telematics_config),
+        ("Billing", &billing_config),
+    ] {
+        info!(
+            "  {}: timeout {:?}, {} retries for GET, breaker opens after {} failures for {:?}",
+            service, config.timeout, config.max_retries, config.failure_threshold, config.open_duration
+        );
+    }
+
+    info!("Connecting to database...");
+    let pool = PgPool::connect(&database_url).await
+        .map_err(|e| {
+            error!("Failed to connect to database: {}", e);
+            e
+        })?;
+    info!("Database connection established");
+    
+    // Проверяем, что миграции были выполнены
+    let sagas_table_exists: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT FROM information_schema.tables 
+            WHERE table_schema = 'public' 
+            AND table_name = 'sagas'
+        )
+        "#
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap_or(false);
+
+    if !sagas_table_exists {
+        warn!("Table 'sagas' does not exist. Please run migrations first:");
+        warn!("  cargo run --package dispatcher --bin migrate");
+        warn!("  or: sqlx migrate run");
+        return Err(anyhow::anyhow!("Database schema not initialized. Run migrations first."));
+    }
+
+    info!("Connecting to Redis...");
+    let idempotency_store = RedisIdempotencyStore::new(&redis_url).await
+        .map_err(|e| {
+            error!("Failed to connect to Redis: {}", e);
+            e
+        })?;
+    info!("Redis connection established");
+    let idempotency = Arc::new(IdempotencyState {
+        store: Arc::new(idempotency_store),
+        ttl: idempotency_ttl,
+        lock_ttl: idempotency_lock_ttl,
+    });
 
     // Инициализируем HTTP клиенты
     info!("Initializing service clients...");
-    let users_client = Arc::new(HttpUsersServiceClient::new(users_url));
-    let cars_client = Arc::new(HttpCarsServiceClient::new(cars_url));
-    let trips_client = Arc::new(HttpTripsServiceClient::new(trips_url));
-    let telematics_client = Arc::new(HttpTelematicsServiceClient::new(telematics_url));
-    let billing_client = Arc::new(HttpBillingServiceClient::new(billing_url));
+    let users_client = Arc::new(HttpUsersServiceClient::new(users_url, &users_config));
+    let cars_client = Arc::new(HttpCarsServiceClient::new(cars_url, &cars_config));
+    let trips_client = Arc::new(HttpTripsServiceClient::new(trips_url, &trips_config));
+    let telematics_client = Arc::new(HttpTelematicsServiceClient::new(telematics_url, &telematics_config));
+    let billing_client = Arc::new(HttpBillingServiceClient::new(billing_url, &billing_config));
+    let circuit_breakers = Arc::new(CircuitBreakerRegistry::new(vec![
+        users_client.circuit_breaker(),
+        cars_client.circuit_breaker(),
+        trips_client.circuit_breaker(),
+        telematics_client.circuit_breaker(),
+        billing_client.circuit_breaker(),
+    ]));
+    
+    // Аудит отклоненных команд машинам
+    let command_audit_repository = Arc::new(PostgresCommandAuditRepository::new(pool.clone()));
+
+    // Журнал саг: начало, завершение и отмена поездки продолжаются с упавшего шага
+    let sagas = Arc::new(SagaCoordinator::new(Arc::new(PostgresSagaRepository::new(pool)), saga_policy));
     
     // Создаем сценарии
     info!("Initializing scenarios...");
-    let start_trip_scenario = Arc::new(StartTripScenario::new(trips_client.clone()));
-    let end_trip_scenario = Arc::new(EndTripScenario::new(trips_client.clone(), billing_client.clone(), cars_client.clone()));
-    let cancel_trip_scenario = Arc::new(CancelTripScenario::new(trips_client.clone()));
+    let start_trip_scenario = Arc::new(StartTripScenario::new(
+        trips_client.clone(),
+        users_client.clone(),
+        cars_client.clone(),
+        billing_client.clone(),
+        sagas.clone(),
+        Money::new(max_outstanding_debt, Currency::Rub),
+    ));
+    let activate_trip_scenario = Arc::new(ActivateTripScenario::new(trips_client.clone()));
+    let end_trip_scenario = Arc::new(EndTripScenario::new(
+        trips_client.clone(),
+        billing_client.clone(),
+        cars_client.clone(),
+        sagas.clone(),
+    ));
+    let cancel_trip_scenario = Arc::new(CancelTripScenario::new(trips_client.clone(), sagas.clone()));
     let get_car_data_scenario = Arc::new(GetCarDataScenario::new(cars_client.clone(), telematics_client.clone()));
+    let get_available_cars_scenario = Arc::new(GetAvailableCarsScenario::new(cars_client.clone(), users_client.clone()));
+    let get_fare_estimate_scenario = Arc::new(GetFareEstimateScenario::new(cars_client.clone()));
+    let send_car_command_scenario = Arc::new(SendCarCommandScenario::new(
+        trips_client.clone(),
+        telematics_client.clone(),
+        command_audit_repository.clone(),
+    ));
+
+    let recover_sagas_scenario = RecoverSagasScenario::new(
+        sagas,
+        start_trip_scenario.clone(),
+        end_trip_scenario.clone(),
+        cancel_trip_scenario.clone(),
+        50,
+    );
 
     // Создаем состояние приложения
     let app_state = AppState {
@@ -83,13 +255,35 @@ async fn main() -> anyhow::Result<()> {
         telematics_client,
         billing_client,
         start_trip_scenario,
+        activate_trip_scenario,
         end_trip_scenario,
         cancel_trip_scenario,
         get_car_data_scenario,
+        get_available_cars_scenario,
+        get_fare_estimate_scenario,
+        send_car_command_scenario,
+        command_audit_repository,
     };
 
+    // Запускаем фоновое восстановление прерванных саг
+    tokio::spawn(async move {
+        info!("Saga recovery worker started (interval: {}s)", saga_recovery_interval_secs);
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(saga_recovery_interval_secs));
+        loop {
+            interval.tick().await;
+            match recover_sagas_scenario.execute().await {
+                Ok(0) => {}
+                Ok(count) => info!("Resumed {} unfinished saga(s)", count),
+                Err(e) => error!("Saga recovery pass failed: {:?}", e),
+            }
+        }
+    });
+
+    // Валидатор JWT токенов, выпущенных users сервисом
+    let token_validator = Arc::new(JwtTokenValidator::new(jwt_secret));
+
     // Создаем роутер
-    let app = create_router(app_state);
+    let app = create_router(app_state, token_validator, circuit_breakers, idempotency);
 
     // Запускаем сервер
     let addr = format!("0.0.0.0:{}", port);
@@ -112,3 +306,13 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+
+fn env_u64(name: &str, default: u64) -> anyhow::Result<u64> {
+    match std::env::var(name) {
+        Ok(value) => value.parse::<u64>().map_err(|e| {
+            error!("Invalid {} value: {}", name, e);
+            anyhow::anyhow!("{} must be a valid number", name)
+        }),
+        Err(_) => Ok(default),
+    }
+}
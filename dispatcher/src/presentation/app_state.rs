@@ -1,37 +1,47 @@
 use std::sync::Arc;
 use crate::{
     application::use_cases::{
-        StartTripScenario, EndTripScenario, CancelTripScenario, GetCarDataScenario,
+        StartTripScenario, ActivateTripScenario, EndTripScenario, CancelTripScenario, GetCarDataScenario,
+        GetAvailableCarsScenario, GetFareEstimateScenario, SendCarCommandScenario,
     },
     domain::interfaces::*,
 };
 
-pub struct AppState<UC, CC, TC, TMC, BC> 
+pub struct AppState<UC, CC, TC, TMC, BC, SR, AR>
 where
     UC: UsersServiceClient + Send + Sync + 'static,
     CC: CarsServiceClient + Send + Sync + 'static,
     TC: TripsServiceClient + Send + Sync + 'static,
     TMC: TelematicsServiceClient + Send + Sync + 'static,
     BC: BillingServiceClient + Send + Sync + 'static,
+    SR: SagaRepository + Send + Sync + 'static,
+    AR: CommandAuditRepository + Send + Sync + 'static,
 {
     pub users_client: Arc<UC>,
     pub cars_client: Arc<CC>,
     pub trips_client: Arc<TC>,
     pub telematics_client: Arc<TMC>,
     pub billing_client: Arc<BC>,
-    pub start_trip_scenario: Arc<StartTripScenario<TC>>,
-    pub end_trip_scenario: Arc<EndTripScenario<TC, BC, CC>>,
-    pub cancel_trip_scenario: Arc<CancelTripScenario<TC>>,
+    pub start_trip_scenario: Arc<StartTripScenario<TC, UC, CC, BC, SR>>,
+    pub activate_trip_scenario: Arc<ActivateTripScenario<TC>>,
+    pub end_trip_scenario: Arc<EndTripScenario<TC, BC, CC, SR>>,
+    pub cancel_trip_scenario: Arc<CancelTripScenario<TC, SR>>,
     pub get_car_data_scenario: Arc<GetCarDataScenario<CC, TMC>>,
+    pub get_available_cars_scenario: Arc<GetAvailableCarsScenario<CC, UC>>,
+    pub get_fare_estimate_scenario: Arc<GetFareEstimateScenario<CC>>,
+    pub send_car_command_scenario: Arc<SendCarCommandScenario<TC, TMC, AR>>,
+    pub command_audit_repository: Arc<AR>,
 }
 
-impl<UC, CC, TC, TMC, BC> Clone for AppState<UC, CC, TC, TMC, BC>
+impl<UC, CC, TC, TMC, BC, SR, AR> Clone for AppState<UC, CC, TC, TMC, BC, SR, AR>
 where
     UC: UsersServiceClient + Send + Sync + 'static,
     CC: CarsServiceClient + Send + Sync + 'static,
     TC: TripsServiceClient + Send + Sync + 'static,
     TMC: TelematicsServiceClient + Send + Sync + 'static,
     BC: BillingServiceClient + Send + Sync + 'static,
+    SR: SagaRepository + Send + Sync + 'static,
+    AR: CommandAuditRepository + Send + Sync + 'static,
 {
     fn clone(&self) -> Self {
         Self {
@@ -41,9 +51,14 @@ where
             telematics_client: Arc::clone(&self.telematics_client),
             billing_client: Arc::clone(&self.billing_client),
             start_trip_scenario: Arc::clone(&self.start_trip_scenario),
+            activate_trip_scenario: Arc::clone(&self.activate_trip_scenario),
             end_trip_scenario: Arc::clone(&self.end_trip_scenario),
             cancel_trip_scenario: Arc::clone(&self.cancel_trip_scenario),
             get_car_data_scenario: Arc::clone(&self.get_car_data_scenario),
+            get_available_cars_scenario: Arc::clone(&self.get_available_cars_scenario),
+            get_fare_estimate_scenario: Arc::clone(&self.get_fare_estimate_scenario),
+            send_car_command_scenario: Arc::clone(&self.send_car_command_scenario),
+            command_audit_repository: Arc::clone(&self.command_audit_repository),
         }
     }
 }
@@ -1,13 +1,14 @@
 use axum::{
-    extract::{State, Path},
-    http::StatusCode,
+    extract::{State, Path, Query, Extension},
+    http::{header, HeaderMap, StatusCode},
     response::Json,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use tracing::{info, error};
+use tracing::{info, warn, error};
 use crate::presentation::app_state::AppState;
 use crate::domain::errors::DispatcherError;
+use crate::domain::models::AuthenticatedUser;
 
 #[derive(Serialize)]
 pub struct UserInfo {
@@ -16,6 +17,7 @@ pub struct UserInfo {
     pub driving_experience: u32,
     pub rating: f64,
     pub email: String,
+    pub role: String,
 }
 
 impl From<crate::domain::interfaces::UserInfo> for UserInfo {
@@ -26,6 +28,7 @@ impl From<crate::domain::interfaces::UserInfo> for UserInfo {
             driving_experience: user.driving_experience,
             rating: user.rating,
             email: user.email,
+            role: user.role,
         }
     }
 }
@@ -37,7 +40,7 @@ pub struct CarInfo {
     pub license_plate: String,
     pub state: String,
     pub tariff_id: Uuid,
-    pub base_price: f64,
+    pub base_price: crate::domain::models::Money,
 }
 
 impl From<crate::domain::interfaces::CarInfo> for CarInfo {
@@ -62,6 +65,8 @@ pub struct TripInfo {
     pub started_at: Option<chrono::DateTime<chrono::Utc>>,
     pub ended_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub distance_km: Option<f64>,
 }
 
 impl From<crate::domain::interfaces::TripInfo> for TripInfo {
@@ -74,6 +79,8 @@ impl From<crate::domain::interfaces::TripInfo> for TripInfo {
             started_at: trip.started_at,
             ended_at: trip.ended_at,
             created_at: trip.created_at,
+            expires_at: trip.expires_at,
+            distance_km: trip.distance_km,
         }
     }
 }
@@ -89,8 +96,128 @@ pub struct SendCommandResponse {
     pub command_id: Uuid,
 }
 
-pub async fn get_all_users_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+const DEFAULT_COMMAND_REJECTIONS_LIMIT: i64 = 100;
+
+fn default_command_rejections_limit() -> i64 {
+    DEFAULT_COMMAND_REJECTIONS_LIMIT
+}
+
+#[derive(Deserialize)]
+pub struct CommandRejectionsQuery {
+    #[serde(default = "default_command_rejections_limit")]
+    pub limit: i64,
+}
+
+#[derive(Serialize)]
+pub struct CommandRejectionInfo {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub role: String,
+    pub car_id: Uuid,
+    pub command_type: String,
+    pub trip_id: Option<Uuid>,
+    pub trip_status: Option<String>,
+    pub reason: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<crate::domain::models::CommandRejection> for CommandRejectionInfo {
+    fn from(rejection: crate::domain::models::CommandRejection) -> Self {
+        Self {
+            id: rejection.id,
+            user_id: rejection.user_id,
+            role: rejection.role.as_str().to_string(),
+            car_id: rejection.car_id,
+            command_type: rejection.command_type,
+            trip_id: rejection.trip_id,
+            trip_status: rejection.trip_status,
+            reason: rejection.reason.as_str().to_string(),
+            created_at: rejection.created_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct SensorHistoryQuery {
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub resolution: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct SensorHistoryPoint {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub samples: usize,
+    pub fuel_level: f64,
+    pub location: crate::domain::interfaces::LocationInfo,
+    pub door_status: String,
+    pub speed: f64,
+    pub max_speed: f64,
+    pub temperature: f64,
+}
+
+impl From<crate::domain::interfaces::SensorHistoryPointInfo> for SensorHistoryPoint {
+    fn from(point: crate::domain::interfaces::SensorHistoryPointInfo) -> Self {
+        Self {
+            timestamp: point.timestamp,
+            samples: point.samples,
+            fuel_level: point.fuel_level,
+            location: point.location,
+            door_status: point.door_status,
+            speed: point.speed,
+            max_speed: point.max_speed,
+            temperature: point.temperature,
+        }
+    }
+}
+
+const DEFAULT_DEAD_LETTER_LIMIT: usize = 100;
+
+fn default_dead_letter_limit() -> usize {
+    DEFAULT_DEAD_LETTER_LIMIT
+}
+
+#[derive(Deserialize)]
+pub struct DeadLettersQuery {
+    #[serde(default = "default_dead_letter_limit")]
+    pub limit: usize,
+}
+
+#[derive(Serialize)]
+pub struct DeadLetterInfo {
+    pub message_id: String,
+    pub routing_key: String,
+    pub error: String,
+    pub payload: String,
+    pub dead_lettered_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<crate::domain::interfaces::DeadLetterInfo> for DeadLetterInfo {
+    fn from(dead_letter: crate::domain::interfaces::DeadLetterInfo) -> Self {
+        Self {
+            message_id: dead_letter.message_id,
+            routing_key: dead_letter.routing_key,
+            error: dead_letter.error,
+            payload: dead_letter.payload,
+            dead_lettered_at: dead_letter.dead_lettered_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ReplayDeadLettersRequest {
+    pub message_ids: Option<Vec<String>>,
+    #[serde(default = "default_dead_letter_limit")]
+    pub limit: usize,
+}
+
+#[derive(Serialize)]
+pub struct ReplayDeadLettersResponse {
+    pub replayed: usize,
+}
+
+pub async fn get_all_users_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
 ) -> Result<Json<Vec<UserInfo>>, (StatusCode, Json<serde_json::Value>)>
 where
     UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
@@ -98,6 +225,8 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
     info!("Getting all users (admin)");
     match state.users_client.get_all_users().await {
@@ -105,6 +234,13 @@ where
             info!("Users retrieved successfully: {} users", users.len());
             Ok(Json(users.into_iter().map(|u| u.into()).collect()))
         }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -122,8 +258,8 @@ where
     }
 }
 
-pub async fn get_user_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+pub async fn get_user_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
     Path(user_id): Path<Uuid>,
 ) -> Result<Json<UserInfo>, (StatusCode, Json<serde_json::Value>)>
 where
@@ -132,6 +268,8 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
     info!("Getting user: {} (admin)", user_id);
     match state.users_client.get_user(user_id).await {
@@ -146,6 +284,13 @@ where
                 Json(serde_json::json!({"error": format!("{} not found", resource)})),
             ))
         }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -163,8 +308,82 @@ where
     }
 }
 
-pub async fn get_all_cars_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+#[derive(Deserialize)]
+pub struct ChangeUserRoleRequest {
+    pub role: String,
+}
+
+pub async fn change_user_role_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(user_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(request): Json<ChangeUserRoleRequest>,
+) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    // Токен уже проверен auth middleware; users сервис проверяет его еще раз и сверяет роль по своей базе
+    let access_token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+
+    info!("Admin {} changing role of user {} to {}", user.user_id, user_id, request.role);
+    match state.users_client.change_user_role(user_id, &request.role, access_token).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(DispatcherError::NotFound { resource }) => Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("{} not found", resource)})),
+        )),
+        Err(DispatcherError::InvalidRequest { message }) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": message})),
+        )),
+        Err(DispatcherError::Unauthorized) => Err((
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Invalid or expired token"})),
+        )),
+        Err(DispatcherError::Forbidden { reason }) => {
+            warn!("Role change by {} rejected by users service: {}", user.user_id, reason);
+            Err((
+                StatusCode::FORBIDDEN,
+                Json(serde_json::json!({"error": "Insufficient permissions"})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error changing user role: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_all_cars_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
 ) -> Result<Json<Vec<CarInfo>>, (StatusCode, Json<serde_json::Value>)>
 where
     UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
@@ -172,6 +391,8 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
     info!("Getting all cars (admin)");
     match state.cars_client.get_all_cars().await {
@@ -179,6 +400,13 @@ where
             info!("Cars retrieved successfully: {} cars", cars.len());
             Ok(Json(cars.into_iter().map(|c| c.into()).collect()))
         }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -196,8 +424,8 @@ where
     }
 }
 
-pub async fn get_car_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+pub async fn get_car_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
     Path(car_id): Path<Uuid>,
 ) -> Result<Json<CarInfo>, (StatusCode, Json<serde_json::Value>)>
 where
@@ -206,6 +434,8 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
     info!("Getting car: {} (admin)", car_id);
     match state.cars_client.get_car(car_id).await {
@@ -220,6 +450,13 @@ where
                 Json(serde_json::json!({"error": format!("{} not found", resource)})),
             ))
         }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -237,8 +474,8 @@ where
     }
 }
 
-pub async fn get_all_trips_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+pub async fn get_all_trips_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
 ) -> Result<Json<Vec<TripInfo>>, (StatusCode, Json<serde_json::Value>)>
 where
     UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
@@ -246,6 +483,8 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
     info!("Getting all trips (admin)");
     match state.trips_client.get_all_trips().await {
@@ -253,6 +492,13 @@ where
             info!("Trips retrieved successfully: {} trips", trips.len());
             Ok(Json(trips.into_iter().map(|t| t.into()).collect()))
         }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -270,8 +516,8 @@ where
     }
 }
 
-pub async fn get_trip_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+pub async fn get_trip_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
     Path(trip_id): Path<Uuid>,
 ) -> Result<Json<TripInfo>, (StatusCode, Json<serde_json::Value>)>
 where
@@ -280,6 +526,8 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
     info!("Getting trip: {} (admin)", trip_id);
     match state.trips_client.get_trip(trip_id).await {
@@ -294,6 +542,13 @@ where
                 Json(serde_json::json!({"error": format!("{} not found", resource)})),
             ))
         }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -311,8 +566,9 @@ where
     }
 }
 
-pub async fn send_command_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+pub async fn send_command_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
     Json(request): Json<SendCommandRequest>,
 ) -> Result<Json<SendCommandResponse>, (StatusCode, Json<serde_json::Value>)>
 where
@@ -321,13 +577,36 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
-    info!("Sending command {} to car {} (admin)", request.command_type, request.car_id);
-    match state.telematics_client.send_command(request.car_id, request.command_type).await {
+    info!("Sending command {} to car {} ({} {})", request.command_type, request.car_id, user.role.as_str(), user.user_id);
+    match state.send_car_command_scenario.execute(&user, request.car_id, &request.command_type).await {
         Ok(command_id) => {
             info!("Command sent successfully: {}", command_id);
             Ok(Json(SendCommandResponse { command_id }))
         }
+        Err(DispatcherError::CommandNotAllowed { reason }) => {
+            warn!("Command to car {} rejected for user {}: {}", request.car_id, user.user_id, reason);
+            Err((
+                StatusCode::FORBIDDEN,
+                Json(serde_json::json!({"error": format!("Command not allowed: {}", reason)})),
+            ))
+        }
+        Err(DispatcherError::InvalidRequest { message }) => {
+            warn!("Invalid command request: {}", message);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -345,3 +624,220 @@ where
     }
 }
 
+pub async fn list_command_rejections_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Query(query): Query<CommandRejectionsQuery>,
+) -> Result<Json<Vec<CommandRejectionInfo>>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    if query.limit <= 0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "limit must be positive"})),
+        ));
+    }
+
+    info!("Admin: Listing last {} command rejections", query.limit);
+    match state.command_audit_repository.find_recent_rejections(query.limit).await {
+        Ok(rejections) => Ok(Json(rejections.into_iter().map(CommandRejectionInfo::from).collect())),
+        Err(e) => {
+            error!("Error listing command rejections: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn list_dead_letters_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Query(query): Query<DeadLettersQuery>,
+) -> Result<Json<Vec<DeadLetterInfo>>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Getting telemetry dead letters (admin), limit {}", query.limit);
+    match state.telematics_client.list_dead_letters(query.limit).await {
+        Ok(dead_letters) => Ok(Json(dead_letters.into_iter().map(DeadLetterInfo::from).collect())),
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting dead letters: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn replay_dead_letters_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Json(request): Json<ReplayDeadLettersRequest>,
+) -> Result<Json<ReplayDeadLettersResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Replaying telemetry dead letters (admin)");
+    match state.telematics_client.replay_dead_letters(request.message_ids, request.limit).await {
+        Ok(replayed) => {
+            info!("Replayed {} dead letters", replayed);
+            Ok(Json(ReplayDeadLettersResponse { replayed }))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error replaying dead letters: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_sensor_history_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Path(vin): Path<String>,
+    Query(query): Query<SensorHistoryQuery>,
+) -> Result<Json<Vec<SensorHistoryPoint>>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Getting sensor history for VIN {} (admin)", vin);
+    let query = crate::domain::interfaces::SensorHistoryQuery {
+        from: query.from,
+        to: query.to,
+        resolution: query.resolution,
+    };
+    match state.telematics_client.get_sensor_history(&vin, &query).await {
+        Ok(points) => {
+            info!("Sensor history retrieved successfully: {} points", points.len());
+            Ok(Json(points.into_iter().map(SensorHistoryPoint::from).collect()))
+        }
+        Err(DispatcherError::InvalidRequest { message }) => {
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting sensor history: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_trip_route_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Path(trip_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Getting route for trip {} (admin)", trip_id);
+    match state.trips_client.get_trip_route(trip_id).await {
+        Ok(route) => Ok(Json(route)),
+        Err(DispatcherError::NotFound { resource }) => {
+            info!("Not found: {}", resource);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("{} not found", resource)})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting trip route: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
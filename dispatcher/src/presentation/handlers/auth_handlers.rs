@@ -4,7 +4,7 @@ use axum::{
     response::Json,
 };
 use serde::{Deserialize, Serialize};
-use tracing::{info, error};
+use tracing::{info, warn, error};
 use crate::presentation::app_state::AppState;
 use crate::domain::errors::DispatcherError;
 
@@ -31,11 +31,27 @@ pub struct AuthRequest {
 #[derive(Serialize)]
 pub struct AuthResponse {
     pub token: String,
+    pub refresh_token: String,
     pub user_id: uuid::Uuid,
 }
 
-pub async fn register_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+#[derive(Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Deserialize)]
+pub struct LogoutRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Serialize)]
+pub struct LogoutResponse {
+    pub message: String,
+}
+
+pub async fn register_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
     Json(request): Json<RegisterRequest>,
 ) -> Result<Json<RegisterResponse>, (StatusCode, Json<serde_json::Value>)>
 where
@@ -44,6 +60,8 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
     info!("Registering new user: {}", request.email);
     let register_req = crate::domain::interfaces::RegisterRequest {
@@ -61,6 +79,13 @@ where
                 user_id: response.user_id,
             }))
         }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -78,8 +103,8 @@ where
     }
 }
 
-pub async fn authenticate_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+pub async fn authenticate_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
     Json(request): Json<AuthRequest>,
 ) -> Result<Json<AuthResponse>, (StatusCode, Json<serde_json::Value>)>
 where
@@ -88,6 +113,8 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
     let email = request.email.clone();
     info!("Authenticating user: {}", email);
@@ -101,6 +128,7 @@ where
             info!("User authenticated successfully: {}", response.user_id);
             Ok(Json(AuthResponse {
                 token: response.token,
+                refresh_token: response.refresh_token,
                 user_id: response.user_id,
             }))
         }
@@ -111,6 +139,13 @@ where
                 Json(serde_json::json!({"error": "Invalid credentials"})),
             ))
         }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -128,3 +163,114 @@ where
     }
 }
 
+
+pub async fn refresh_token_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Json(request): Json<RefreshTokenRequest>,
+) -> Result<Json<AuthResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Refreshing access token");
+    let refresh_req = crate::domain::interfaces::RefreshTokenRequest {
+        refresh_token: request.refresh_token,
+    };
+
+    match state.users_client.refresh_token(refresh_req).await {
+        Ok(response) => {
+            info!("Access token refreshed for user: {}", response.user_id);
+            Ok(Json(AuthResponse {
+                token: response.token,
+                refresh_token: response.refresh_token,
+                user_id: response.user_id,
+            }))
+        }
+        Err(DispatcherError::Unauthorized) => {
+            error!("Token refresh rejected");
+            Err((
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Invalid or expired refresh token"})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error refreshing token: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn logout_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Json(request): Json<LogoutRequest>,
+) -> Result<Json<LogoutResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Logging out");
+    let logout_req = crate::domain::interfaces::LogoutRequest {
+        refresh_token: request.refresh_token,
+    };
+
+    match state.users_client.logout(logout_req).await {
+        Ok(_) => Ok(Json(LogoutResponse {
+            message: "Logged out successfully".to_string(),
+        })),
+        Err(DispatcherError::Unauthorized) => {
+            error!("Logout rejected: invalid refresh token");
+            Err((
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Invalid refresh token"})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error logging out: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
@@ -1,17 +1,25 @@
 use axum::{
-    extract::{State, Path},
+    extract::{State, Path, Query, Extension},
     http::StatusCode,
     response::Json,
 };
 use uuid::Uuid;
-use tracing::{info, error};
+use tracing::{info, warn, error};
 use crate::presentation::app_state::AppState;
 use crate::domain::errors::DispatcherError;
 use crate::domain::models::scenarios::CarDataResponse;
-use crate::domain::interfaces::CarInfo;
+use crate::domain::interfaces::{CarInfo, TripQuote};
+use crate::domain::models::AuthenticatedUser;
 
-pub async fn get_car_data_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+#[derive(serde::Deserialize)]
+pub struct AvailableCarsQuery {
+    // Показывать только машины, тариф которых доступен текущему пользователю
+    #[serde(default)]
+    pub eligible_only: bool,
+}
+
+pub async fn get_car_data_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
     Path(car_id): Path<Uuid>,
 ) -> Result<Json<CarDataResponse>, (StatusCode, Json<serde_json::Value>)>
 where
@@ -20,6 +28,8 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
     info!("Getting car data: {}", car_id);
     match state.get_car_data_scenario.execute(car_id).await {
@@ -34,6 +44,13 @@ where
                 Json(serde_json::json!({"error": format!("{} not found", resource)})),
             ))
         }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -51,8 +68,10 @@ where
     }
 }
 
-pub async fn get_available_cars_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+pub async fn get_available_cars_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Query(query): Query<AvailableCarsQuery>,
 ) -> Result<Json<Vec<CarInfo>>, (StatusCode, Json<serde_json::Value>)>
 where
     UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
@@ -60,32 +79,29 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
-    info!("Getting available cars");
-    match state.cars_client.get_all_cars().await {
-        Ok(cars) => {
-            // Фильтруем только доступные машины и добавляем тарифы
-            let mut available_cars: Vec<CarInfo> = cars
-                .into_iter()
-                .filter(|car| car.state == "available")
-                .collect();
-            
-            // Получаем тарифы для каждой машины
-            for car in &mut available_cars {
-                match state.cars_client.get_tariff(car.tariff_id).await {
-                    Ok(tariff) => {
-                        car.price_per_minute = Some(tariff.price_per_minute);
-                    }
-                    Err(e) => {
-                        error!("Failed to get tariff {} for car {}: {:?}", car.tariff_id, car.id, e);
-                        // Продолжаем без тарифа
-                    }
-                }
-            }
-            
+    info!("Getting available cars (eligible_only: {})", query.eligible_only);
+    match state.get_available_cars_scenario.execute(user.user_id, query.eligible_only).await {
+        Ok(available_cars) => {
             info!("Available cars retrieved successfully: {} cars", available_cars.len());
             Ok(Json(available_cars))
         }
+        Err(DispatcherError::NotFound { resource }) => {
+            error!("Not found: {}", resource);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("{} not found", resource)})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -103,6 +119,71 @@ where
     }
 }
 
+#[derive(serde::Deserialize)]
+pub struct FareEstimateQuery {
+    pub minutes: u32,
+    #[serde(default)]
+    pub km: f64,
+}
+
+pub async fn get_fare_estimate_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Path(car_id): Path<Uuid>,
+    Query(query): Query<FareEstimateQuery>,
+) -> Result<Json<TripQuote>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Estimating fare for car {}: {} min, {} km", car_id, query.minutes, query.km);
+    match state.get_fare_estimate_scenario.execute(car_id, query.minutes, query.km).await {
+        Ok(estimate) => {
+            info!("Fare estimate for car {}: {}", car_id, estimate.total);
+            Ok(Json(estimate))
+        }
+        Err(DispatcherError::InvalidRequest { message }) => {
+            warn!("Invalid fare estimate request for car {}: {}", car_id, message);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::NotFound { resource }) => {
+            error!("Not found: {}", resource);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("{} not found", resource)})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error estimating fare: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
 #[derive(serde::Deserialize)]
 pub struct SendCarCommandRequest {
     pub command_type: String,
@@ -114,8 +195,9 @@ pub struct SendCarCommandResponse {
     pub message: String,
 }
 
-pub async fn send_car_command_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+pub async fn send_car_command_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
     Path(car_id): Path<Uuid>,
     Json(request): Json<SendCarCommandRequest>,
 ) -> Result<Json<SendCarCommandResponse>, (StatusCode, Json<serde_json::Value>)>
@@ -125,17 +207,39 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
-    let command_type = request.command_type.clone();
-    info!("Sending command {} to car {} (client)", command_type, car_id);
-    match state.telematics_client.send_command(car_id, command_type.clone()).await {
+    info!("Sending command {} to car {} (client {})", request.command_type, car_id, user.user_id);
+    match state.send_car_command_scenario.execute(&user, car_id, &request.command_type).await {
         Ok(command_id) => {
             info!("Command sent successfully: {}", command_id);
             Ok(Json(SendCarCommandResponse {
                 command_id,
-                message: format!("Command {} sent successfully", command_type),
+                message: format!("Command {} sent successfully", request.command_type),
             }))
         }
+        Err(DispatcherError::CommandNotAllowed { reason }) => {
+            warn!("Command to car {} rejected for user {}: {}", car_id, user.user_id, reason);
+            Err((
+                StatusCode::FORBIDDEN,
+                Json(serde_json::json!({"error": format!("Command not allowed: {}", reason)})),
+            ))
+        }
+        Err(DispatcherError::InvalidRequest { message }) => {
+            warn!("Invalid command request: {}", message);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
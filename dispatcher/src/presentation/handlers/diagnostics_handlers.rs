@@ -0,0 +1,14 @@
+use std::sync::Arc;
+use axum::{extract::State, response::Json};
+use tracing::info;
+use crate::domain::{interfaces::CircuitBreakerMonitor, models::CircuitBreakerStatus};
+
+pub async fn get_circuit_breakers_handler<M>(
+    State(monitor): State<Arc<M>>,
+) -> Json<Vec<CircuitBreakerStatus>>
+where
+    M: CircuitBreakerMonitor + Send + Sync + 'static,
+{
+    info!("Getting circuit breaker states");
+    Json(monitor.circuit_breakers())
+}
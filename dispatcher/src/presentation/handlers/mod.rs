@@ -2,9 +2,15 @@ mod auth_handlers;
 mod trip_handlers;
 mod car_handlers;
 mod admin_handlers;
+mod payment_handlers;
+mod promo_code_handlers;
+mod diagnostics_handlers;
 
 pub use auth_handlers::*;
 pub use trip_handlers::*;
 pub use car_handlers::*;
 pub use admin_handlers::*;
+pub use payment_handlers::*;
+pub use promo_code_handlers::*;
+pub use diagnostics_handlers::*;
 
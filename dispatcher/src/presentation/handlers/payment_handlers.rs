@@ -0,0 +1,479 @@
+use axum::{
+    extract::{State, Extension, Path},
+    http::{header, StatusCode},
+    response::Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use tracing::{info, warn, error};
+use crate::presentation::app_state::AppState;
+use crate::domain::errors::DispatcherError;
+use crate::domain::interfaces::{
+    CreateRefundResult, DebtOverrideInfo, DebtOverrideRequest, PaymentHistoryItem, PaymentInfo, RefundInfo,
+    UserBalanceInfo, WalletInfo,
+};
+use crate::domain::models::{AuthenticatedUser, Money};
+
+#[derive(Deserialize)]
+pub struct CreateRefundRequest {
+    pub amount: Money,
+    pub reason: String,
+}
+
+#[derive(Deserialize)]
+pub struct CreateTopUpRequest {
+    pub amount: Money,
+}
+
+#[derive(Deserialize)]
+pub struct SetDebtOverrideRequest {
+    pub reason: String,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+pub async fn get_payment_qr_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(payment_id): Path<Uuid>,
+) -> Result<([(header::HeaderName, &'static str); 1], Vec<u8>), (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Getting QR code for payment {} of user {}", payment_id, user.user_id);
+    // Чужие платежи не раскрываем: для них ответ такой же, как для несуществующих
+    let result = match state.billing_client.get_payment(payment_id).await {
+        Ok(payment) if payment.user_id == user.user_id => state.billing_client.get_payment_qr_png(payment_id).await,
+        Ok(_) => Err(DispatcherError::NotFound { resource: format!("Payment {}", payment_id) }),
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(png) => Ok(([(header::CONTENT_TYPE, "image/png")], png)),
+        Err(DispatcherError::NotFound { resource }) => {
+            info!("Not found: {}", resource);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("{} not found", resource)})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting payment QR code: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_wallet_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<Json<WalletInfo>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Getting wallet of user {}", user.user_id);
+    match state.billing_client.get_wallet(user.user_id).await {
+        Ok(wallet) => Ok(Json(wallet)),
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting wallet: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn create_wallet_top_up_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(request): Json<CreateTopUpRequest>,
+) -> Result<Json<PaymentInfo>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Wallet top-up of {} requested by user {}", request.amount, user.user_id);
+    match state.billing_client.create_top_up(user.user_id, request.amount).await {
+        Ok(payment) => {
+            info!("Top-up payment {} created for user {}", payment.id, user.user_id);
+            Ok(Json(payment))
+        }
+        Err(DispatcherError::InvalidRequest { message }) => {
+            warn!("Top-up for user {} rejected: {}", user.user_id, message);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error creating wallet top-up: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_payment_history_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<Json<Vec<PaymentHistoryItem>>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Getting payment history of user {}", user.user_id);
+    match state.billing_client.get_payment_history(user.user_id).await {
+        Ok(history) => Ok(Json(history)),
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting payment history: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn create_payment_refund_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(payment_id): Path<Uuid>,
+    Json(request): Json<CreateRefundRequest>,
+) -> Result<Json<CreateRefundResult>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!(
+        "Refund of {} for payment {} requested by {} ({}): {}",
+        request.amount, payment_id, user.user_id, user.role.as_str(), request.reason
+    );
+    match state.billing_client.create_refund(payment_id, request.amount, &request.reason).await {
+        Ok(result) => {
+            info!("Refund {} created, payment {} is now {}", result.refund.id, payment_id, result.payment_status);
+            Ok(Json(result))
+        }
+        Err(DispatcherError::InvalidRequest { message }) => {
+            warn!("Refund for payment {} rejected: {}", payment_id, message);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::Conflict { message }) => {
+            warn!("Refund for payment {} rejected: {}", payment_id, message);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::NotFound { resource }) => {
+            info!("Not found: {}", resource);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("{} not found", resource)})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error creating refund: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_payment_refunds_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Path(payment_id): Path<Uuid>,
+) -> Result<Json<Vec<RefundInfo>>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Getting refunds for payment {} (admin)", payment_id);
+    match state.billing_client.get_refunds(payment_id).await {
+        Ok(refunds) => Ok(Json(refunds)),
+        Err(DispatcherError::NotFound { resource }) => {
+            info!("Not found: {}", resource);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("{} not found", resource)})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting refunds: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn get_user_balance_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<UserBalanceInfo>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Getting balance for user {} (admin)", user_id);
+    match state.billing_client.get_user_balance(user_id).await {
+        Ok(balance) => Ok(Json(balance)),
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting user balance: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn set_debt_override_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<SetDebtOverrideRequest>,
+) -> Result<Json<DebtOverrideInfo>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!(
+        "Debt override for user {} granted by {} ({}): {}",
+        user_id, user.user_id, user.role.as_str(), request.reason
+    );
+    let override_request = DebtOverrideRequest {
+        reason: request.reason,
+        granted_by: user.user_id,
+        expires_at: request.expires_at,
+    };
+    match state.billing_client.set_debt_override(user_id, &override_request).await {
+        Ok(debt_override) => Ok(Json(debt_override)),
+        Err(DispatcherError::InvalidRequest { message }) => {
+            warn!("Debt override for user {} rejected: {}", user_id, message);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error setting debt override: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn remove_debt_override_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(user_id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Debt override for user {} revoked by {} ({})", user_id, user.user_id, user.role.as_str());
+    match state.billing_client.remove_debt_override(user_id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(DispatcherError::NotFound { resource }) => {
+            info!("Not found: {}", resource);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("{} not found", resource)})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error removing debt override: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
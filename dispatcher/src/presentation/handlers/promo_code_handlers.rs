@@ -0,0 +1,220 @@
+use axum::{
+    extract::{State, Extension, Path, Query},
+    http::StatusCode,
+    response::Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use tracing::{info, warn, error};
+use crate::presentation::app_state::AppState;
+use crate::domain::errors::DispatcherError;
+use crate::domain::interfaces::{
+    AttachedPromoCodeInfo, CampaignReportInfo, CreatePromoCodeRequest, PromoCodeInfo,
+};
+use crate::domain::models::AuthenticatedUser;
+
+#[derive(Deserialize)]
+pub struct AttachPromoCodeRequest {
+    pub code: String,
+}
+
+#[derive(Deserialize)]
+pub struct CampaignReportQuery {
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+fn promo_code_error(error: DispatcherError) -> (StatusCode, Json<serde_json::Value>) {
+    match error {
+        DispatcherError::NotFound { resource } => {
+            info!("Not found: {}", resource);
+            (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("{} not found", resource)})),
+            )
+        }
+        DispatcherError::InvalidRequest { message } => {
+            warn!("Promo code request rejected: {}", message);
+            (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": message})),
+            )
+        }
+        DispatcherError::Conflict { message } => {
+            warn!("Promo code conflict: {}", message);
+            (
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": message})),
+            )
+        }
+        DispatcherError::ServiceUnavailable { service } => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            )
+        }
+        DispatcherError::ServiceError { service, message } => {
+            error!("Service error from {}: {}", service, message);
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            )
+        }
+        e => {
+            error!("Error handling promo code request: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            )
+        }
+    }
+}
+
+pub async fn attach_promo_code_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(request): Json<AttachPromoCodeRequest>,
+) -> Result<Json<AttachedPromoCodeInfo>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Attaching promo code for user {}", user.user_id);
+    match state.billing_client.attach_promo_code(user.user_id, &request.code).await {
+        Ok(attached) => {
+            info!("Promo code {} attached for user {}", attached.promo_code.code, user.user_id);
+            Ok(Json(attached))
+        }
+        Err(e) => Err(promo_code_error(e)),
+    }
+}
+
+pub async fn get_promo_code_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<Json<AttachedPromoCodeInfo>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Getting attached promo code for user {}", user.user_id);
+    state.billing_client.get_attached_promo_code(user.user_id).await
+        .map(Json)
+        .map_err(promo_code_error)
+}
+
+pub async fn detach_promo_code_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Detaching promo code for user {}", user.user_id);
+    match state.billing_client.detach_promo_code(user.user_id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => Err(promo_code_error(e)),
+    }
+}
+
+pub async fn create_promo_code_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(request): Json<CreatePromoCodeRequest>,
+) -> Result<Json<PromoCodeInfo>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!(
+        "Promo code {} for campaign {} created by {} ({})",
+        request.code, request.campaign, user.user_id, user.role.as_str()
+    );
+    match state.billing_client.create_promo_code(&request).await {
+        Ok(promo_code) => Ok(Json(promo_code)),
+        Err(e) => Err(promo_code_error(e)),
+    }
+}
+
+pub async fn get_promo_codes_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+) -> Result<Json<Vec<PromoCodeInfo>>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Getting promo codes");
+    state.billing_client.get_promo_codes().await
+        .map(Json)
+        .map_err(promo_code_error)
+}
+
+pub async fn deactivate_promo_code_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(promo_code_id): Path<Uuid>,
+) -> Result<Json<PromoCodeInfo>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Promo code {} deactivated by {} ({})", promo_code_id, user.user_id, user.role.as_str());
+    state.billing_client.deactivate_promo_code(promo_code_id).await
+        .map(Json)
+        .map_err(promo_code_error)
+}
+
+pub async fn get_campaign_report_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Query(query): Query<CampaignReportQuery>,
+) -> Result<Json<Vec<CampaignReportInfo>>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Getting promo campaign report");
+    let query = crate::domain::interfaces::CampaignReportQuery {
+        from: query.from,
+        to: query.to,
+    };
+    state.billing_client.get_campaign_report(&query).await
+        .map(Json)
+        .map_err(promo_code_error)
+}
@@ -1,17 +1,18 @@
 use axum::{
-    extract::State,
+    extract::{State, Extension, Path},
     http::StatusCode,
     response::Json,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use tracing::{info, error};
+use tracing::{info, warn, error};
 use crate::presentation::app_state::AppState;
 use crate::domain::errors::DispatcherError;
+use crate::domain::interfaces::AppliedDiscountInfo;
+use crate::domain::models::{AuthenticatedUser, Money};
 
 #[derive(Deserialize)]
 pub struct StartTripRequest {
-    pub user_id: Uuid,
     pub car_id: Uuid,
 }
 
@@ -29,7 +30,14 @@ pub struct EndTripRequest {
 pub struct EndTripResponse {
     pub trip_id: Uuid,
     pub payment_id: Uuid,
-    pub qr_code_url: String,
+    pub payment_status: String,
+    pub payment_method: String,
+    // Сумма к оплате с учетом скидки по промокоду
+    pub amount: Money,
+    pub discount: Option<AppliedDiscountInfo>,
+    // Нет, если поездка оплачена с кошелька или скидкой
+    pub qr_code_url: Option<String>,
+    pub distance_km: Option<f64>,
 }
 
 #[derive(Deserialize)]
@@ -59,8 +67,9 @@ pub struct ActiveTripResponse {
     pub trip: Option<crate::domain::interfaces::TripInfo>,
 }
 
-pub async fn start_trip_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+pub async fn start_trip_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
     Json(request): Json<StartTripRequest>,
 ) -> Result<Json<StartTripResponse>, (StatusCode, Json<serde_json::Value>)>
 where
@@ -69,13 +78,61 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
-    info!("Starting trip for user {} with car {}", request.user_id, request.car_id);
-    match state.start_trip_scenario.execute(request.user_id, request.car_id).await {
+    info!("Starting trip for user {} with car {}", user.user_id, request.car_id);
+    match state.start_trip_scenario.execute(user.user_id, request.car_id).await {
         Ok(trip_id) => {
             info!("Trip started successfully: {}", trip_id);
             Ok(Json(StartTripResponse { trip_id }))
         }
+        Err(DispatcherError::DriverNotEligible { reason }) => {
+            warn!("User {} is not eligible for car {}: {}", user.user_id, request.car_id, reason);
+            Err((
+                StatusCode::FORBIDDEN,
+                Json(serde_json::json!({"error": format!("Driver not eligible: {}", reason)})),
+            ))
+        }
+        Err(DispatcherError::OutstandingDebt { debt, threshold }) => {
+            warn!("User {} has outstanding debt {} above {}", user.user_id, debt, threshold);
+            Err((
+                StatusCode::PAYMENT_REQUIRED,
+                Json(serde_json::json!({
+                    "error": format!("Outstanding debt of {} must be paid before starting a new trip", debt),
+                    "outstanding": debt,
+                    "threshold": threshold,
+                })),
+            ))
+        }
+        Err(DispatcherError::NotFound { resource }) => {
+            info!("Not found: {}", resource);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("{} not found", resource)})),
+            ))
+        }
+        Err(DispatcherError::InvalidRequest { message }) => {
+            warn!("Trip request rejected: {}", message);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::Conflict { message }) => {
+            warn!("Trip conflict: {}", message);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -93,8 +150,9 @@ where
     }
 }
 
-pub async fn end_trip_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+pub async fn end_trip_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
     Json(request): Json<EndTripRequest>,
 ) -> Result<Json<EndTripResponse>, (StatusCode, Json<serde_json::Value>)>
 where
@@ -103,17 +161,55 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
     info!("Ending trip: {}", request.trip_id);
-    match state.end_trip_scenario.execute(request.trip_id).await {
-        Ok((trip_id, payment_id, qr_code_url)) => {
-            info!("Trip ended successfully: {}, payment: {}", trip_id, payment_id);
+    match state.end_trip_scenario.execute(user.user_id, request.trip_id).await {
+        Ok((trip_id, payment, distance_km)) => {
+            info!(
+                "Trip ended successfully: {}, payment: {} ({}, {})",
+                trip_id, payment.id, payment.method, payment.status
+            );
             Ok(Json(EndTripResponse {
                 trip_id,
-                payment_id,
-                qr_code_url,
+                payment_id: payment.id,
+                payment_status: payment.status,
+                payment_method: payment.method,
+                amount: payment.amount,
+                discount: payment.discount,
+                qr_code_url: payment.qr_code_url,
+                distance_km,
             }))
         }
+        Err(DispatcherError::NotFound { resource }) => {
+            info!("Not found: {}", resource);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("{} not found", resource)})),
+            ))
+        }
+        Err(DispatcherError::InvalidRequest { message }) => {
+            warn!("Trip request rejected: {}", message);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::Conflict { message }) => {
+            warn!("Trip conflict: {}", message);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -131,8 +227,9 @@ where
     }
 }
 
-pub async fn activate_trip_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+pub async fn activate_trip_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
     Json(request): Json<ActivateTripRequest>,
 ) -> Result<Json<ActivateTripResponse>, (StatusCode, Json<serde_json::Value>)>
 where
@@ -141,9 +238,11 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
     info!("Activating trip: {}", request.trip_id);
-    match state.trips_client.activate_trip(request.trip_id).await {
+    match state.activate_trip_scenario.execute(user.user_id, request.trip_id).await {
         Ok(_) => {
             info!("Trip activated successfully: {}", request.trip_id);
             Ok(Json(ActivateTripResponse {
@@ -151,6 +250,20 @@ where
                 message: "Trip activated successfully".to_string(),
             }))
         }
+        Err(DispatcherError::NotFound { resource }) => {
+            info!("Not found: {}", resource);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("{} not found", resource)})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -168,9 +281,9 @@ where
     }
 }
 
-pub async fn get_active_trip_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
-    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+pub async fn get_active_trip_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
 ) -> Result<Json<ActiveTripResponse>, (StatusCode, Json<serde_json::Value>)>
 where
     UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
@@ -178,19 +291,21 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
-    let user_id = params.get("user_id")
-        .and_then(|s| Uuid::parse_str(s).ok())
-        .ok_or_else(|| (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": "Missing or invalid user_id parameter"})),
-        ))?;
-    
-    info!("Getting active trip for user: {}", user_id);
-    match state.trips_client.get_user_active_trip(user_id).await {
+    info!("Getting active trip for user: {}", user.user_id);
+    match state.trips_client.get_user_active_trip(user.user_id).await {
         Ok(trip) => {
             Ok(Json(ActiveTripResponse { trip }))
         }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -208,8 +323,9 @@ where
     }
 }
 
-pub async fn cancel_trip_handler<UC, CC, TC, TMC, BC>(
-    State(state): State<AppState<UC, CC, TC, TMC, BC>>,
+pub async fn cancel_trip_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
     Json(request): Json<CancelTripRequest>,
 ) -> Result<Json<CancelTripResponse>, (StatusCode, Json<serde_json::Value>)>
 where
@@ -218,9 +334,11 @@ where
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
 {
     info!("Cancelling trip: {}", request.trip_id);
-    match state.cancel_trip_scenario.execute(request.trip_id).await {
+    match state.cancel_trip_scenario.execute(user.user_id, request.trip_id).await {
         Ok(_) => {
             info!("Trip cancelled successfully: {}", request.trip_id);
             Ok(Json(CancelTripResponse {
@@ -228,6 +346,34 @@ where
                 message: "Trip cancelled successfully".to_string(),
             }))
         }
+        Err(DispatcherError::NotFound { resource }) => {
+            info!("Not found: {}", resource);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("{} not found", resource)})),
+            ))
+        }
+        Err(DispatcherError::InvalidRequest { message }) => {
+            warn!("Trip request rejected: {}", message);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::Conflict { message }) => {
+            warn!("Trip conflict: {}", message);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
         Err(DispatcherError::ServiceError { service, message }) => {
             error!("Service error from {}: {}", service, message);
             Err((
@@ -245,3 +391,58 @@ where
     }
 }
 
+
+pub async fn get_own_trip_route_handler<UC, CC, TC, TMC, BC, SR, AR>(
+    State(state): State<AppState<UC, CC, TC, TMC, BC, SR, AR>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(trip_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)>
+where
+    UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
+    CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
+    TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
+    TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
+    BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+{
+    info!("Getting route for trip {} of user {}", trip_id, user.user_id);
+    // Чужие поездки не раскрываем: для них ответ такой же, как для несуществующих
+    let result = match state.trips_client.get_trip(trip_id).await {
+        Ok(trip) if trip.user_id == user.user_id => state.trips_client.get_trip_route(trip_id).await,
+        Ok(_) => Err(DispatcherError::NotFound { resource: format!("Trip {}", trip_id) }),
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(route) => Ok(Json(route)),
+        Err(DispatcherError::NotFound { resource }) => {
+            info!("Not found: {}", resource);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": format!("{} not found", resource)})),
+            ))
+        }
+        Err(DispatcherError::ServiceUnavailable { service }) => {
+            warn!("Service {} unavailable, circuit breaker is open", service);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(DispatcherError::ServiceError { service, message }) => {
+            error!("Service error from {}: {}", service, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("Service {} unavailable", service)})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting trip route: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
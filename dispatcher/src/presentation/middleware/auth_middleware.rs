@@ -0,0 +1,135 @@
+use std::sync::Arc;
+use axum::{
+    extract::{Request, State},
+    http::{header::AUTHORIZATION, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use tracing::warn;
+use crate::domain::interfaces::TokenValidator;
+
+// Проверяет Bearer токен и кладет AuthenticatedUser в extensions запроса
+pub async fn auth_middleware<V>(
+    State(validator): State<Arc<V>>,
+    mut request: Request,
+    next: Next,
+) -> Response
+where
+    V: TokenValidator + Send + Sync + 'static,
+{
+    let token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    let Some(token) = token else {
+        warn!("Missing bearer token for {}", request.uri().path());
+        return unauthorized("Missing or invalid Authorization header");
+    };
+
+    match validator.validate_token(token) {
+        Ok(user) => {
+            request.extensions_mut().insert(user);
+            next.run(request).await
+        }
+        Err(e) => {
+            warn!("Token validation failed for {}: {}", request.uri().path(), e);
+            unauthorized("Invalid or expired token")
+        }
+    }
+}
+
+fn unauthorized(message: &str) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(serde_json::json!({"error": message})),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get, Extension, Router};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+    use crate::domain::{
+        errors::DispatcherError,
+        models::{AuthenticatedUser, Role},
+    };
+
+    const VALID_TOKEN: &str = "valid-token";
+
+    struct MockTokenValidator {
+        user_id: Uuid,
+    }
+
+    impl TokenValidator for MockTokenValidator {
+        fn validate_token(&self, token: &str) -> Result<AuthenticatedUser, DispatcherError> {
+            if token == VALID_TOKEN {
+                Ok(AuthenticatedUser { user_id: self.user_id, role: Role::Operator })
+            } else {
+                Err(DispatcherError::Unauthorized)
+            }
+        }
+    }
+
+    async fn whoami(Extension(user): Extension<AuthenticatedUser>) -> Json<serde_json::Value> {
+        Json(serde_json::json!({"user_id": user.user_id, "role": user.role.as_str()}))
+    }
+
+    fn app(user_id: Uuid) -> Router {
+        let validator = Arc::new(MockTokenValidator { user_id });
+        Router::new()
+            .route("/whoami", get(whoami))
+            .route_layer(axum::middleware::from_fn_with_state(validator, auth_middleware::<MockTokenValidator>))
+    }
+
+    async fn call(authorization: Option<&str>) -> (StatusCode, serde_json::Value, Uuid) {
+        let user_id = Uuid::new_v4();
+        let mut request = Request::builder().uri("/whoami");
+        if let Some(authorization) = authorization {
+            request = request.header(AUTHORIZATION, authorization);
+        }
+
+        let response = app(user_id).oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap(), user_id)
+    }
+
+    #[tokio::test]
+    async fn test_valid_token_puts_user_into_extensions() {
+        let (status, body, user_id) = call(Some("Bearer valid-token")).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["user_id"], user_id.to_string());
+        assert_eq!(body["role"], "operator");
+    }
+
+    #[tokio::test]
+    async fn test_missing_header_is_unauthorized() {
+        let (status, body, _) = call(None).await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"], "Missing or invalid Authorization header");
+    }
+
+    #[tokio::test]
+    async fn test_malformed_header_is_unauthorized() {
+        for header in ["valid-token", "Basic valid-token", "bearer valid-token", "Bearer"] {
+            let (status, _, _) = call(Some(header)).await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED, "{:?}", header);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejected_token_is_unauthorized() {
+        let (status, body, _) = call(Some("Bearer expired-token")).await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"], "Invalid or expired token");
+    }
+}
@@ -0,0 +1,42 @@
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use tracing::warn;
+use crate::domain::models::{AuthenticatedUser, Permission};
+
+// Пропускает запрос, только если роль пользователя дает нужное право.
+// Должен стоять после auth_middleware, который кладет AuthenticatedUser в extensions
+pub async fn require_permission(
+    State(permission): State<Permission>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(user) = request.extensions().get::<AuthenticatedUser>() else {
+        warn!("No authenticated user for {}", request.uri().path());
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Missing or invalid Authorization header"})),
+        )
+            .into_response();
+    };
+
+    if !user.role.has_permission(permission) {
+        warn!(
+            "User {} with role {} lacks permission {} for {}",
+            user.user_id,
+            user.role.as_str(),
+            permission.as_str(),
+            request.uri().path()
+        );
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({"error": "Insufficient permissions"})),
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
@@ -0,0 +1,241 @@
+use std::sync::Arc;
+use std::time::Duration;
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header::CONTENT_TYPE, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn, error};
+use crate::domain::{
+    interfaces::IdempotencyStore,
+    models::{AuthenticatedUser, IdempotencyRecord, StoredResponse},
+};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+// Выставляется на ответах, возвращенных из сохраненных
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+const MAX_KEY_LENGTH: usize = 255;
+// Тела запросов и ответов этих endpoints - небольшие JSON
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+pub struct IdempotencyState<IS>
+where
+    IS: IdempotencyStore + Send + Sync + 'static,
+{
+    pub store: Arc<IS>,
+    pub ttl: Duration,
+    // После падения процесса по истечении срока запрос можно повторить
+    pub lock_ttl: Duration,
+}
+
+// Запрос с заголовком Idempotency-Key выполняется один раз: повтор с тем же ключом
+// получает сохраненный ответ, повтор с другим телом - 422.
+// Должен стоять после auth_middleware: ключи разных пользователей не пересекаются
+pub async fn idempotency_middleware<IS>(
+    State(state): State<Arc<IdempotencyState<IS>>>,
+    request: Request,
+    next: Next,
+) -> Response
+where
+    IS: IdempotencyStore + Send + Sync + 'static,
+{
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+    let key = match key.to_str() {
+        Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => key.to_string(),
+        _ => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Idempotency-Key must be 1 to {} ASCII characters", MAX_KEY_LENGTH),
+            );
+        }
+    };
+
+    let Some(user) = request.extensions().get::<AuthenticatedUser>() else {
+        warn!("No authenticated user for {}", request.uri().path());
+        return error_response(StatusCode::UNAUTHORIZED, "Missing or invalid Authorization header");
+    };
+    let path = request.uri().path().to_string();
+    let storage_key = format!("idempotency:{}:{}:{}:{}", user.user_id, request.method(), path, key);
+
+    let (parts, body) = request.into_parts();
+    let body = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => {
+            warn!("Failed to read body of {} with Idempotency-Key: {}", path, e);
+            return error_response(StatusCode::PAYLOAD_TOO_LARGE, "Request body is too large");
+        }
+    };
+    let fingerprint = fingerprint(&body);
+
+    let in_progress = IdempotencyRecord::InProgress { fingerprint: fingerprint.clone() };
+    let existing = match state.store.reserve(&storage_key, &in_progress, state.lock_ttl).await {
+        Ok(existing) => existing,
+        Err(e) => {
+            error!("Failed to reserve idempotency key for {}: {}", path, e);
+            return error_response(StatusCode::SERVICE_UNAVAILABLE, "Idempotency store is unavailable");
+        }
+    };
+
+    match existing {
+        None => {}
+        Some(record) if record.fingerprint() != fingerprint => {
+            warn!("Idempotency-Key {} reused with a different body for {}", key, path);
+            return error_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Idempotency-Key was already used with a different request body",
+            );
+        }
+        Some(IdempotencyRecord::InProgress { .. }) => {
+            return error_response(
+                StatusCode::CONFLICT,
+                "A request with this Idempotency-Key is still being processed",
+            );
+        }
+        Some(IdempotencyRecord::Completed { response, .. }) => {
+            info!("Replaying stored response for Idempotency-Key {} on {}", key, path);
+            return replay(response);
+        }
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => {
+            error!("Failed to read response of {} with Idempotency-Key: {}", path, e);
+            release(&state, &storage_key).await;
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, "Failed to read response");
+        }
+    };
+
+    match stored_response(parts.status, parts.headers.get(CONTENT_TYPE), &body) {
+        Some(response) => {
+            let completed = IdempotencyRecord::Completed { fingerprint, response };
+            // Ответ уже получен: если его не удалось сохранить, повтор выполнится заново
+            // после истечения блокировки
+            if let Err(e) = state.store.save(&storage_key, &completed, state.ttl).await {
+                error!("Failed to store response for Idempotency-Key {} on {}: {}", key, path, e);
+            }
+        }
+        None => release(&state, &storage_key).await,
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+// JSON приводится к каноничному виду, чтобы порядок полей и пробелы не делали одинаковые запросы разными
+fn fingerprint(body: &[u8]) -> String {
+    let canonical = match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(value) => value.to_string().into_bytes(),
+        Err(_) => body.to_vec(),
+    };
+    format!("{:x}", Sha256::digest(canonical))
+}
+
+// Ошибки сервера и отказы, которые могут пройти при повторе (408, 409, 429), не сохраняются
+fn stored_response(status: StatusCode, content_type: Option<&HeaderValue>, body: &[u8]) -> Option<StoredResponse> {
+    let retryable = status.is_server_error()
+        || status == StatusCode::CONFLICT
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT;
+    if retryable {
+        return None;
+    }
+
+    Some(StoredResponse {
+        status: status.as_u16(),
+        content_type: content_type.and_then(|value| value.to_str().ok()).map(str::to_string),
+        body: String::from_utf8(body.to_vec()).ok()?,
+    })
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let mut response = (status, stored.body).into_response();
+    let headers = response.headers_mut();
+    if let Some(content_type) = stored.content_type.and_then(|value| HeaderValue::from_str(&value).ok()) {
+        headers.insert(CONTENT_TYPE, content_type);
+    }
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+async fn release<IS>(state: &IdempotencyState<IS>, storage_key: &str)
+where
+    IS: IdempotencyStore + Send + Sync + 'static,
+{
+    if let Err(e) = state.store.release(storage_key).await {
+        error!("Failed to release idempotency key {}: {}", storage_key, e);
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (
+        status,
+        Json(serde_json::json!({"error": message})),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_ignores_field_order_and_whitespace() {
+        let compact = fingerprint(br#"{"car_id":"c1","options":{"child_seat":true,"insurance":"full"}}"#);
+        let reordered = fingerprint(b"{\n  \"options\": {\"insurance\": \"full\", \"child_seat\": true},\n  \"car_id\": \"c1\"\n}");
+        assert_eq!(compact, reordered);
+    }
+
+    #[test]
+    fn test_fingerprint_differs_for_different_values() {
+        assert_ne!(fingerprint(br#"{"car_id":"c1"}"#), fingerprint(br#"{"car_id":"c2"}"#));
+        // Порядок элементов массива значим
+        assert_ne!(fingerprint(br#"{"ids":[1,2]}"#), fingerprint(br#"{"ids":[2,1]}"#));
+    }
+
+    #[test]
+    fn test_fingerprint_of_non_json_body_uses_raw_bytes() {
+        assert_eq!(fingerprint(b"not json"), fingerprint(b"not json"));
+        assert_ne!(fingerprint(b"not json"), fingerprint(b"not  json"));
+        assert_eq!(fingerprint(b""), format!("{:x}", Sha256::digest(b"")));
+    }
+
+    #[test]
+    fn test_successful_and_final_responses_are_stored() {
+        let json = HeaderValue::from_static("application/json");
+        for status in [StatusCode::OK, StatusCode::CREATED, StatusCode::BAD_REQUEST, StatusCode::NOT_FOUND, StatusCode::UNPROCESSABLE_ENTITY] {
+            let stored = stored_response(status, Some(&json), br#"{"trip_id":"t1"}"#)
+                .unwrap_or_else(|| panic!("{} should be stored", status));
+            assert_eq!(stored.status, status.as_u16());
+            assert_eq!(stored.content_type.as_deref(), Some("application/json"));
+            assert_eq!(stored.body, r#"{"trip_id":"t1"}"#);
+        }
+    }
+
+    #[test]
+    fn test_retryable_responses_are_not_stored() {
+        for status in [
+            StatusCode::REQUEST_TIMEOUT,
+            StatusCode::CONFLICT,
+            StatusCode::TOO_MANY_REQUESTS,
+            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::BAD_GATEWAY,
+            StatusCode::SERVICE_UNAVAILABLE,
+        ] {
+            assert!(stored_response(status, None, b"{}").is_none(), "{} should not be stored", status);
+        }
+    }
+
+    #[test]
+    fn test_non_utf8_body_is_not_stored() {
+        assert!(stored_response(StatusCode::OK, None, &[0xff, 0xfe]).is_none());
+    }
+}
@@ -0,0 +1,7 @@
+mod auth_middleware;
+mod authorization_middleware;
+mod idempotency_middleware;
+
+pub use auth_middleware::*;
+pub use authorization_middleware::*;
+pub use idempotency_middleware::*;
@@ -1,7 +1,7 @@
 pub mod handlers;
+pub mod middleware;
 pub mod routes;
 pub mod app_state;
 
 pub use routes::*;
 pub use app_state::*;
-
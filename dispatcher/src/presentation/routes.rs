@@ -1,40 +1,148 @@
+use std::sync::Arc;
 use axum::{
     Router,
+    middleware,
     routing::{get, post, put},
 };
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing::info;
-use crate::presentation::{handlers::*, app_state::AppState};
+use crate::domain::{
+    interfaces::{CircuitBreakerMonitor, IdempotencyStore, TokenValidator},
+    models::Permission,
+};
+use crate::presentation::{
+    handlers::*,
+    app_state::AppState,
+    middleware::{auth_middleware, idempotency_middleware, require_permission, IdempotencyState},
+};
 
-pub fn create_router<UC, CC, TC, TMC, BC>(app_state: AppState<UC, CC, TC, TMC, BC>) -> Router
+pub fn create_router<UC, CC, TC, TMC, BC, SR, AR, V, M, IS>(
+    app_state: AppState<UC, CC, TC, TMC, BC, SR, AR>,
+    token_validator: Arc<V>,
+    circuit_breakers: Arc<M>,
+    idempotency: Arc<IdempotencyState<IS>>,
+) -> Router
 where
     UC: crate::domain::interfaces::UsersServiceClient + Send + Sync + 'static,
     CC: crate::domain::interfaces::CarsServiceClient + Send + Sync + 'static,
     TC: crate::domain::interfaces::TripsServiceClient + Send + Sync + 'static,
     TMC: crate::domain::interfaces::TelematicsServiceClient + Send + Sync + 'static,
     BC: crate::domain::interfaces::BillingServiceClient + Send + Sync + 'static,
+    SR: crate::domain::interfaces::SagaRepository + Send + Sync + 'static,
+    AR: crate::domain::interfaces::CommandAuditRepository + Send + Sync + 'static,
+    V: TokenValidator + Send + Sync + 'static,
+    M: CircuitBreakerMonitor + Send + Sync + 'static,
+    IS: IdempotencyStore + Send + Sync + 'static,
 {
     info!("Setting up routes...");
-    Router::new()
-        // Client endpoints
+    // Публичные endpoints (без токена)
+    let public_routes = Router::new()
         .route("/auth/register", post(register_handler))
         .route("/auth/authenticate", post(authenticate_handler))
-        .route("/trips/start", post(start_trip_handler))
-        .route("/trips/activate", put(activate_trip_handler))
-        .route("/trips/end", put(end_trip_handler))
-        .route("/trips/cancel", put(cancel_trip_handler))
-        .route("/trips/active", get(get_active_trip_handler))
-        .route("/cars", get(get_available_cars_handler))
-        .route("/cars/:car_id/data", get(get_car_data_handler))
-        .route("/cars/:car_id/commands", post(send_car_command_handler))
-        // Admin endpoints
+        .route("/auth/refresh", post(refresh_token_handler))
+        .route("/auth/logout", post(logout_handler));
+
+    // Admin endpoints, сгруппированные по требуемому праву
+    let admin_users_routes = Router::new()
         .route("/admin/users", get(get_all_users_handler))
         .route("/admin/users/:id", get(get_user_handler))
+        .route_layer(middleware::from_fn_with_state(Permission::ViewUsers, require_permission));
+
+    let admin_user_roles_routes = Router::new()
+        .route("/admin/users/:id/role", put(change_user_role_handler))
+        .route_layer(middleware::from_fn_with_state(Permission::ManageUsers, require_permission));
+
+    let admin_cars_routes = Router::new()
         .route("/admin/cars", get(get_all_cars_handler))
         .route("/admin/cars/:id", get(get_car_handler))
+        .route("/admin/sensors/:vin/history", get(get_sensor_history_handler))
+        .route_layer(middleware::from_fn_with_state(Permission::ViewCars, require_permission));
+
+    let admin_trips_routes = Router::new()
         .route("/admin/trips", get(get_all_trips_handler))
         .route("/admin/trips/:id", get(get_trip_handler))
+        .route("/admin/trips/:id/route", get(get_trip_route_handler))
+        .route_layer(middleware::from_fn_with_state(Permission::ViewTrips, require_permission));
+
+    let admin_commands_routes = Router::new()
         .route("/admin/commands", post(send_command_handler))
+        .route("/admin/commands/rejections", get(list_command_rejections_handler))
+        .route_layer(middleware::from_fn_with_state(Permission::SendCommands, require_permission));
+
+    let admin_telemetry_routes = Router::new()
+        .route("/admin/telematics/dead-letters", get(list_dead_letters_handler))
+        .route("/admin/telematics/dead-letters/replay", post(replay_dead_letters_handler))
+        .route_layer(middleware::from_fn_with_state(Permission::ManageTelemetry, require_permission));
+
+    let admin_payments_routes = Router::new()
+        .route(
+            "/admin/payments/:id/refunds",
+            post(create_payment_refund_handler).get(get_payment_refunds_handler),
+        )
+        .route_layer(middleware::from_fn_with_state(Permission::ManagePayments, require_permission));
+
+    let admin_debt_routes = Router::new()
+        .route("/admin/users/:id/balance", get(get_user_balance_handler))
+        .route(
+            "/admin/users/:id/debt-override",
+            put(set_debt_override_handler).delete(remove_debt_override_handler),
+        )
+        .route_layer(middleware::from_fn_with_state(Permission::ManageDebt, require_permission));
+
+    let admin_promotions_routes = Router::new()
+        .route("/admin/promo-codes", post(create_promo_code_handler).get(get_promo_codes_handler))
+        .route("/admin/promo-codes/campaigns", get(get_campaign_report_handler))
+        .route("/admin/promo-codes/:id/deactivate", post(deactivate_promo_code_handler))
+        .route_layer(middleware::from_fn_with_state(Permission::ManagePromotions, require_permission));
+
+    // Состояние circuit breaker'ов не зависит от клиентов сервисов, поэтому у группы свое состояние
+    let admin_diagnostics_routes = Router::new()
+        .route("/admin/diagnostics/circuit-breakers", get(get_circuit_breakers_handler::<M>))
+        .with_state(circuit_breakers)
+        .route_layer(middleware::from_fn_with_state(Permission::ViewDiagnostics, require_permission));
+
+    // Мобильные клиенты повторяют эти запросы при обрыве сети: с заголовком Idempotency-Key
+    // повтор получает ответ первого запроса, а не выполняет действие заново
+    let idempotent_routes = Router::new()
+        .route("/trips/start", post(start_trip_handler))
+        .route("/trips/end", put(end_trip_handler))
+        .route("/cars/:car_id/commands", post(send_car_command_handler))
+        .route_layer(middleware::from_fn_with_state(idempotency, idempotency_middleware::<IS>));
+
+    // Все остальные endpoints требуют валидный Bearer токен
+    let protected_routes = Router::new()
+        // Client endpoints
+        .route("/trips/activate", put(activate_trip_handler))
+        .route("/trips/cancel", put(cancel_trip_handler))
+        .route("/trips/active", get(get_active_trip_handler))
+        .route("/trips/:trip_id/route", get(get_own_trip_route_handler))
+        .route("/cars", get(get_available_cars_handler))
+        .route("/cars/:car_id/data", get(get_car_data_handler))
+        .route("/cars/:car_id/estimate", get(get_fare_estimate_handler))
+        .route("/payments", get(get_payment_history_handler))
+        .route("/payments/:payment_id/qr.png", get(get_payment_qr_handler))
+        .route("/wallet", get(get_wallet_handler))
+        .route("/wallet/top-ups", post(create_wallet_top_up_handler))
+        .route(
+            "/promo-code",
+            put(attach_promo_code_handler).get(get_promo_code_handler).delete(detach_promo_code_handler),
+        )
+        .merge(idempotent_routes)
+        .merge(admin_users_routes)
+        .merge(admin_user_roles_routes)
+        .merge(admin_cars_routes)
+        .merge(admin_trips_routes)
+        .merge(admin_commands_routes)
+        .merge(admin_telemetry_routes)
+        .merge(admin_payments_routes)
+        .merge(admin_debt_routes)
+        .merge(admin_promotions_routes)
+        .merge(admin_diagnostics_routes)
+        .route_layer(middleware::from_fn_with_state(token_validator, auth_middleware::<V>));
+
+    Router::new()
+        .merge(public_routes)
+        .merge(protected_routes)
         .with_state(app_state)
         .layer(CorsLayer::permissive())
         .layer(TraceLayer::new_for_http())
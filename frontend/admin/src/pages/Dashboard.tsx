@@ -1,8 +1,10 @@
 import { useState, useEffect } from 'react';
 import { useNavigate } from 'react-router-dom';
 import { useAuthStore } from '../store/authStore';
+import { authService } from '../services/authService';
 import { adminService } from '../services/adminService';
 import type { User, Car, Trip, CommandRequest } from '../types';
+import { toMajorUnits } from '../types';
 
 // Компонент для отображения строки машины с деталями
 function CarRow({ car }: { car: Car }) {
@@ -34,7 +36,7 @@ function CarRow({ car }: { car: Car }) {
         <td style={{ padding: '10px', border: '1px solid #ddd' }}>{car.model}</td>
         <td style={{ padding: '10px', border: '1px solid #ddd' }}>{car.license_plate}</td>
         <td style={{ padding: '10px', border: '1px solid #ddd' }}>{car.state}</td>
-        <td style={{ padding: '10px', border: '1px solid #ddd' }}>{car.base_price} ₽</td>
+        <td style={{ padding: '10px', border: '1px solid #ddd' }}>{toMajorUnits(car.base_price).toFixed(2)} ₽</td>
         <td style={{ padding: '10px', border: '1px solid #ddd' }}>
           <button
             onClick={handleShowDetails}
@@ -151,7 +153,8 @@ export default function Dashboard() {
     }
   };
 
-  const handleLogout = () => {
+  const handleLogout = async () => {
+    await authService.logout();
     clearAuth();
     navigate('/login');
   };
@@ -283,6 +286,7 @@ export default function Dashboard() {
                 <th style={{ padding: '10px', textAlign: 'left', border: '1px solid #ddd' }}>Статус</th>
                 <th style={{ padding: '10px', textAlign: 'left', border: '1px solid #ddd' }}>Начало</th>
                 <th style={{ padding: '10px', textAlign: 'left', border: '1px solid #ddd' }}>Конец</th>
+                <th style={{ padding: '10px', textAlign: 'left', border: '1px solid #ddd' }}>Пробег</th>
               </tr>
             </thead>
             <tbody>
@@ -298,6 +302,9 @@ export default function Dashboard() {
                   <td style={{ padding: '10px', border: '1px solid #ddd' }}>
                     {trip.ended_at ? new Date(trip.ended_at).toLocaleString() : '-'}
                   </td>
+                  <td style={{ padding: '10px', border: '1px solid #ddd' }}>
+                    {trip.distance_km != null ? `${trip.distance_km.toFixed(2)} км` : '-'}
+                  </td>
                 </tr>
               ))}
             </tbody>
@@ -18,7 +18,7 @@ export default function Login() {
 
     try {
       const response = await authService.login({ email, password });
-      setAuth(response.token, response.user_id);
+      setAuth(response.token, response.user_id, response.refresh_token);
       navigate('/dashboard');
     } catch (err: any) {
       setError(err.response?.data?.error || 'Ошибка входа');
@@ -18,5 +18,45 @@ api.interceptors.request.use((config) => {
   return config;
 });
 
+// При истекшем access токене пробуем один раз обновить его по refresh токену
+let refreshPromise: Promise<string | null> | null = null;
+
+const refreshAccessToken = async (): Promise<string | null> => {
+  const refreshToken = localStorage.getItem('admin_refresh_token');
+  if (!refreshToken) {
+    return null;
+  }
+  try {
+    const response = await axios.post(`${API_URL}/auth/refresh`, { refresh_token: refreshToken });
+    localStorage.setItem('admin_token', response.data.token);
+    localStorage.setItem('admin_refresh_token', response.data.refresh_token);
+    return response.data.token;
+  } catch {
+    localStorage.removeItem('admin_token');
+    localStorage.removeItem('admin_user_id');
+    localStorage.removeItem('admin_refresh_token');
+    return null;
+  }
+};
+
+api.interceptors.response.use(
+  (response) => response,
+  async (error) => {
+    const original = error.config;
+    if (error.response?.status === 401 && original && !original._retry && !original.url?.startsWith('/auth/')) {
+      original._retry = true;
+      refreshPromise = refreshPromise ?? refreshAccessToken().finally(() => {
+        refreshPromise = null;
+      });
+      const token = await refreshPromise;
+      if (token) {
+        original.headers.Authorization = `Bearer ${token}`;
+        return api(original);
+      }
+    }
+    return Promise.reject(error);
+  }
+);
+
 export default api;
 
@@ -7,9 +7,14 @@ export const authService = {
     return response.data;
   },
 
-  logout() {
+  async logout() {
+    const refreshToken = localStorage.getItem('admin_refresh_token');
+    if (refreshToken) {
+      await api.post('/auth/logout', { refresh_token: refreshToken }).catch(() => undefined);
+    }
     localStorage.removeItem('admin_token');
     localStorage.removeItem('admin_user_id');
+    localStorage.removeItem('admin_refresh_token');
   },
 
   isAuthenticated(): boolean {
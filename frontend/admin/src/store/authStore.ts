@@ -3,21 +3,25 @@ import { create } from 'zustand';
 interface AuthState {
   token: string | null;
   userId: string | null;
-  setAuth: (token: string, userId: string) => void;
+  setAuth: (token: string, userId: string, refreshToken?: string) => void;
   clearAuth: () => void;
 }
 
 export const useAuthStore = create<AuthState>((set) => ({
   token: localStorage.getItem('admin_token'),
   userId: localStorage.getItem('admin_user_id'),
-  setAuth: (token: string, userId: string) => {
+  setAuth: (token: string, userId: string, refreshToken?: string) => {
     localStorage.setItem('admin_token', token);
     localStorage.setItem('admin_user_id', userId);
+    if (refreshToken) {
+      localStorage.setItem('admin_refresh_token', refreshToken);
+    }
     set({ token, userId });
   },
   clearAuth: () => {
     localStorage.removeItem('admin_token');
     localStorage.removeItem('admin_user_id');
+    localStorage.removeItem('admin_refresh_token');
     set({ token: null, userId: null });
   },
 }));
@@ -6,27 +6,38 @@ export interface User {
   email: string;
 }
 
+export interface Money {
+  minor_units: number;
+  currency: 'RUB';
+}
+
+// Сумма в основных единицах валюты (рублях) для отображения
+export const toMajorUnits = (money: Money): number => money.minor_units / 100;
+
 export interface Car {
   id: string;
   model: string;
   license_plate: string;
   state: string;
   tariff_id: string;
-  base_price: number;
+  base_price: Money;
 }
 
 export interface Trip {
   id: string;
   user_id: string;
   car_id: string;
-  status: 'reserved' | 'active' | 'completed' | 'cancelled';
+  status: 'reserved' | 'active' | 'completed' | 'cancelled' | 'expired';
   started_at?: string;
   ended_at?: string;
   created_at: string;
+  expires_at?: string;
+  distance_km?: number | null;
 }
 
 export interface AuthResponse {
   token: string;
+  refresh_token: string;
   user_id: string;
 }
 
@@ -1,9 +1,13 @@
 import { useState, useEffect } from 'react';
 import { useNavigate } from 'react-router-dom';
 import { useAuthStore } from '../store/authStore';
+import { authService } from '../services/authService';
 import { carService } from '../services/carService';
 import { tripService } from '../services/tripService';
-import type { CarData, Car } from '../types';
+import { walletService } from '../services/walletService';
+import { promoCodeService } from '../services/promoCodeService';
+import type { AttachedPromoCode, CarData, Car, Wallet } from '../types';
+import { toMajorUnits } from '../types';
 
 export default function Dashboard() {
   const [carId, setCarId] = useState('');
@@ -20,6 +24,10 @@ export default function Dashboard() {
   const [estimatedCost, setEstimatedCost] = useState<number | null>(null);
   const [sendingCommand, setSendingCommand] = useState(false);
   const [paymentQr, setPaymentQr] = useState<string | null>(null);
+  const [wallet, setWallet] = useState<Wallet | null>(null);
+  const [topUpAmount, setTopUpAmount] = useState('');
+  const [promoCode, setPromoCode] = useState<AttachedPromoCode | null>(null);
+  const [promoCodeInput, setPromoCodeInput] = useState('');
   const userId = useAuthStore((state) => state.userId);
   const clearAuth = useAuthStore((state) => state.clearAuth);
   const navigate = useNavigate();
@@ -28,9 +36,78 @@ export default function Dashboard() {
     loadAvailableCars();
     if (userId) {
       loadActiveTrip();
+      loadWallet();
+      loadPromoCode();
     }
   }, [userId]);
 
+  const loadPromoCode = async () => {
+    try {
+      setPromoCode(await promoCodeService.getPromoCode());
+    } catch (err) {
+      console.error('Ошибка загрузки промокода:', err);
+    }
+  };
+
+  const handleAttachPromoCode = async () => {
+    if (!promoCodeInput.trim()) {
+      setError('Введите промокод');
+      return;
+    }
+    setLoading(true);
+    setError('');
+    try {
+      setPromoCode(await promoCodeService.attachPromoCode(promoCodeInput.trim()));
+      setPromoCodeInput('');
+    } catch (err: any) {
+      setError(err.response?.data?.error || 'Ошибка применения промокода');
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const handleDetachPromoCode = async () => {
+    setLoading(true);
+    setError('');
+    try {
+      await promoCodeService.detachPromoCode();
+      setPromoCode(null);
+    } catch (err: any) {
+      setError(err.response?.data?.error || 'Ошибка отмены промокода');
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const loadWallet = async () => {
+    try {
+      setWallet(await walletService.getWallet());
+    } catch (err) {
+      console.error('Ошибка загрузки кошелька:', err);
+    }
+  };
+
+  const handleTopUp = async () => {
+    const rubles = parseFloat(topUpAmount);
+    if (!rubles || rubles <= 0) {
+      setError('Введите сумму пополнения');
+      return;
+    }
+    setLoading(true);
+    setError('');
+    try {
+      const payment = await walletService.topUp({ minor_units: Math.round(rubles * 100), currency: 'RUB' });
+      if (payment.qr_code_url) {
+        setPaymentQr(await tripService.getPaymentQr(payment.qr_code_url));
+      }
+      setTopUpAmount('');
+    } catch (err: any) {
+      setError(err.response?.data?.error || 'Ошибка пополнения');
+    } finally {
+      setLoading(false);
+    }
+  };
+
   const handleGetCarData = async () => {
     if (!carId) {
       setError('Введите ID машины');
@@ -76,7 +153,7 @@ export default function Dashboard() {
   const calculateEstimatedCost = (carData: CarData, startTime: Date) => {
     const now = new Date();
     const minutes = Math.max(1, Math.floor((now.getTime() - startTime.getTime()) / (1000 * 60)));
-    const cost = (carData.price_per_minute * minutes) + carData.car.base_price;
+    const cost = (toMajorUnits(carData.price_per_minute) * minutes) + toMajorUnits(carData.car.base_price);
     setEstimatedCost(cost);
   };
 
@@ -87,7 +164,7 @@ export default function Dashboard() {
         if (!tripStartTime || !tripCarData) return;
         const now = new Date();
         const minutes = Math.max(1, (now.getTime() - tripStartTime.getTime()) / (1000 * 60));
-        const cost = (tripCarData.price_per_minute * minutes) + tripCarData.car.base_price;
+        const cost = (toMajorUnits(tripCarData.price_per_minute) * minutes) + toMajorUnits(tripCarData.car.base_price);
         setEstimatedCost(cost);
       };
       
@@ -142,14 +219,32 @@ export default function Dashboard() {
     setError('');
     try {
       const response = await tripService.endTrip(activeTripId);
-      setPaymentQr(response.qr_code_url);
+      if (response.qr_code_url) {
+        try {
+          setPaymentQr(await tripService.getPaymentQr(response.qr_code_url));
+        } catch (err) {
+          console.error('Ошибка загрузки QR-кода:', err);
+        }
+      } else {
+        // Поездка оплачена с кошелька или полностью покрыта скидкой
+        setPaymentQr(null);
+        loadWallet();
+      }
+      // Исчерпанный промокод отвязывается при оплате
+      loadPromoCode();
       setActiveTripId(null);
       setTripStatus(null);
       setTripCarId(null);
       setTripCarData(null);
       setTripStartTime(null);
       setEstimatedCost(null);
-      alert('Поездка завершена!');
+      const paidWith = response.payment_method === 'wallet'
+        ? ' и оплачена с кошелька'
+        : response.payment_method === 'promo' ? ' и оплачена промокодом' : '';
+      const discount = response.discount
+        ? ` Скидка по промокоду ${response.discount.code}: ${toMajorUnits(response.discount.amount).toFixed(2)} ₽.`
+        : '';
+      alert(`Поездка завершена${paidWith}! К оплате: ${toMajorUnits(response.amount).toFixed(2)} ₽.${discount}`);
     } catch (err: any) {
       setError(err.response?.data?.error || 'Ошибка завершения поездки');
     } finally {
@@ -226,7 +321,8 @@ export default function Dashboard() {
     }
   };
 
-  const handleLogout = () => {
+  const handleLogout = async () => {
+    await authService.logout();
     clearAuth();
     navigate('/login');
   };
@@ -242,6 +338,69 @@ export default function Dashboard() {
 
       {error && <div style={{ color: 'red', marginBottom: '15px', padding: '10px', backgroundColor: '#ffe6e6' }}>{error}</div>}
 
+      <div style={{ marginBottom: '30px', padding: '20px', border: '1px solid #ddd', borderRadius: '8px' }}>
+        <h2>Кошелек</h2>
+        <p>
+          Баланс: <strong>{wallet ? `${toMajorUnits(wallet.balance).toFixed(2)} ₽` : '—'}</strong>{' '}
+          <button onClick={loadWallet} style={{ padding: '4px 10px', cursor: 'pointer' }}>
+            Обновить
+          </button>
+        </p>
+        <p style={{ color: '#666' }}>Поездка оплачивается с кошелька автоматически, если на нем достаточно средств.</p>
+        <div style={{ display: 'flex', gap: '10px' }}>
+          <input
+            type="number"
+            min="1"
+            step="0.01"
+            value={topUpAmount}
+            onChange={(e) => setTopUpAmount(e.target.value)}
+            placeholder="Сумма, ₽"
+            style={{ padding: '8px', width: '150px' }}
+          />
+          <button
+            onClick={handleTopUp}
+            disabled={loading}
+            style={{ padding: '8px 16px', cursor: 'pointer' }}
+          >
+            Пополнить
+          </button>
+        </div>
+      </div>
+
+      <div style={{ marginBottom: '30px', padding: '20px', border: '1px solid #ddd', borderRadius: '8px' }}>
+        <h2>Промокод</h2>
+        {promoCode ? (
+          <p>
+            <strong>{promoCode.promo_code.code}</strong>{' '}
+            ({promoCode.promo_code.discount.type === 'percentage'
+              ? `${promoCode.promo_code.discount.percent}%`
+              : `${toMajorUnits(promoCode.promo_code.discount.amount).toFixed(2)} ₽`}
+            , осталось использований: {promoCode.remaining_uses}){' '}
+            <button onClick={handleDetachPromoCode} disabled={loading} style={{ padding: '4px 10px', cursor: 'pointer' }}>
+              Отменить
+            </button>
+          </p>
+        ) : (
+          <p style={{ color: '#666' }}>Скидка по промокоду применяется при завершении поездки.</p>
+        )}
+        <div style={{ display: 'flex', gap: '10px' }}>
+          <input
+            type="text"
+            value={promoCodeInput}
+            onChange={(e) => setPromoCodeInput(e.target.value)}
+            placeholder="Промокод"
+            style={{ padding: '8px', width: '150px' }}
+          />
+          <button
+            onClick={handleAttachPromoCode}
+            disabled={loading}
+            style={{ padding: '8px 16px', cursor: 'pointer' }}
+          >
+            Применить
+          </button>
+        </div>
+      </div>
+
       <div style={{ marginBottom: '30px', padding: '20px', border: '1px solid #ddd', borderRadius: '8px' }}>
         <h2>Доступные машины</h2>
         {loadingCars ? (
@@ -275,9 +434,9 @@ export default function Dashboard() {
               >
                 <h3 style={{ margin: '0 0 10px 0' }}>{car.model}</h3>
                 <p style={{ margin: '5px 0' }}><strong>Госномер:</strong> {car.license_plate}</p>
-                <p style={{ margin: '5px 0' }}><strong>Базовая цена:</strong> {car.base_price} ₽</p>
+                <p style={{ margin: '5px 0' }}><strong>Базовая цена:</strong> {toMajorUnits(car.base_price).toFixed(2)} ₽</p>
                 {car.price_per_minute !== undefined && (
-                  <p style={{ margin: '5px 0' }}><strong>Цена за минуту:</strong> {car.price_per_minute.toFixed(2)} ₽/мин</p>
+                  <p style={{ margin: '5px 0' }}><strong>Цена за минуту:</strong> {toMajorUnits(car.price_per_minute).toFixed(2)} ₽/мин</p>
                 )}
                 <p style={{ margin: '5px 0', color: '#28a745' }}><strong>Доступна</strong></p>
               </div>
@@ -295,8 +454,8 @@ export default function Dashboard() {
             <h3>{carData.car.model}</h3>
             <p><strong>Госномер:</strong> {carData.car.license_plate}</p>
             <p><strong>Состояние:</strong> {carData.car.state}</p>
-            <p><strong>Базовая цена:</strong> {carData.car.base_price} ₽</p>
-            <p><strong>Цена за минуту:</strong> {carData.price_per_minute.toFixed(2)} ₽/мин</p>
+            <p><strong>Базовая цена:</strong> {toMajorUnits(carData.car.base_price).toFixed(2)} ₽</p>
+            <p><strong>Цена за минуту:</strong> {toMajorUnits(carData.price_per_minute).toFixed(2)} ₽/мин</p>
             
             {carData.telematics && (
               <div style={{ marginTop: '15px', padding: '10px', backgroundColor: 'white', borderRadius: '4px' }}>
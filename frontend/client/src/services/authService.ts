@@ -12,9 +12,14 @@ export const authService = {
     return response.data;
   },
 
-  logout() {
+  async logout() {
+    const refreshToken = localStorage.getItem('refresh_token');
+    if (refreshToken) {
+      await api.post('/auth/logout', { refresh_token: refreshToken }).catch(() => undefined);
+    }
     localStorage.removeItem('token');
     localStorage.removeItem('user_id');
+    localStorage.removeItem('refresh_token');
   },
 
   isAuthenticated(): boolean {
@@ -1,5 +1,5 @@
 import api from './api';
-import type { CarData, Car } from '../types';
+import type { CarData, Car, FareEstimate } from '../types';
 
 export const carService = {
   async getCarData(carId: string): Promise<CarData> {
@@ -10,5 +10,11 @@ export const carService = {
     const response = await api.get<Car[]>('/cars');
     return response.data;
   },
+  async getFareEstimate(carId: string, minutes: number, km = 0): Promise<FareEstimate> {
+    const response = await api.get<FareEstimate>(`/cars/${carId}/estimate`, {
+      params: { minutes, km },
+    });
+    return response.data;
+  },
 };
 
@@ -0,0 +1,25 @@
+import api from './api';
+import type { AttachedPromoCode } from '../types';
+
+export const promoCodeService = {
+  // Возвращает null, если промокод не указан
+  async getPromoCode(): Promise<AttachedPromoCode | null> {
+    try {
+      const response = await api.get<AttachedPromoCode>('/promo-code');
+      return response.data;
+    } catch (err: any) {
+      if (err.response?.status === 404) {
+        return null;
+      }
+      throw err;
+    }
+  },
+  // Скидка применится при завершении поездки по подходящему тарифу
+  async attachPromoCode(code: string): Promise<AttachedPromoCode> {
+    const response = await api.put<AttachedPromoCode>('/promo-code', { code });
+    return response.data;
+  },
+  async detachPromoCode(): Promise<void> {
+    await api.delete('/promo-code');
+  },
+};
@@ -1,5 +1,5 @@
 import api from './api';
-import type { Trip } from '../types';
+import type { AppliedDiscount, Money, Trip } from '../types';
 
 export const tripService = {
   async startTrip(userId: string, carId: string): Promise<{ trip_id: string }> {
@@ -25,16 +25,32 @@ export const tripService = {
   async endTrip(tripId: string): Promise<{
     trip_id: string;
     payment_id: string;
-    qr_code_url: string;
+    payment_status: string;
+    payment_method: 'sbp' | 'wallet' | 'promo';
+    amount: Money;
+    discount: AppliedDiscount | null;
+    qr_code_url: string | null;
+    distance_km: number | null;
   }> {
     const response = await api.put<{
       trip_id: string;
       payment_id: string;
-      qr_code_url: string;
+      payment_status: string;
+      payment_method: 'sbp' | 'wallet' | 'promo';
+      amount: Money;
+      discount: AppliedDiscount | null;
+      qr_code_url: string | null;
+      distance_km: number | null;
     }>('/trips/end', { trip_id: tripId });
     return response.data;
   },
 
+  // QR-код отдается только с токеном, поэтому загружаем его как Blob и показываем через object URL
+  async getPaymentQr(qrCodeUrl: string): Promise<string> {
+    const response = await api.get<Blob>(qrCodeUrl, { responseType: 'blob' });
+    return URL.createObjectURL(response.data);
+  },
+
   async cancelTrip(tripId: string): Promise<{ trip_id: string; message: string }> {
     const response = await api.put<{ trip_id: string; message: string }>('/trips/cancel', {
       trip_id: tripId,
@@ -0,0 +1,14 @@
+import api from './api';
+import type { Money, Payment, Wallet } from '../types';
+
+export const walletService = {
+  async getWallet(): Promise<Wallet> {
+    const response = await api.get<Wallet>('/wallet');
+    return response.data;
+  },
+  // Пополнение оплачивается по QR-коду; деньги зачисляются после подтверждения банком
+  async topUp(amount: Money): Promise<Payment> {
+    const response = await api.post<Payment>('/wallet/top-ups', { amount });
+    return response.data;
+  },
+};
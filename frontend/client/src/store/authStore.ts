@@ -3,21 +3,25 @@ import { create } from 'zustand';
 interface AuthState {
   token: string | null;
   userId: string | null;
-  setAuth: (token: string, userId: string) => void;
+  setAuth: (token: string, userId: string, refreshToken?: string) => void;
   clearAuth: () => void;
 }
 
 export const useAuthStore = create<AuthState>((set) => ({
   token: localStorage.getItem('token'),
   userId: localStorage.getItem('user_id'),
-  setAuth: (token: string, userId: string) => {
+  setAuth: (token: string, userId: string, refreshToken?: string) => {
     localStorage.setItem('token', token);
     localStorage.setItem('user_id', userId);
+    if (refreshToken) {
+      localStorage.setItem('refresh_token', refreshToken);
+    }
     set({ token, userId });
   },
   clearAuth: () => {
     localStorage.removeItem('token');
     localStorage.removeItem('user_id');
+    localStorage.removeItem('refresh_token');
     set({ token: null, userId: null });
   },
 }));
@@ -6,19 +6,64 @@ export interface User {
   email: string;
 }
 
+export interface Money {
+  minor_units: number;
+  currency: 'RUB';
+}
+
+// Сумма в основных единицах валюты (рублях) для отображения
+export const toMajorUnits = (money: Money): number => money.minor_units / 100;
+
+export interface Wallet {
+  user_id: string;
+  balance: Money;
+}
+
+export interface AppliedDiscount {
+  promo_code_id: string;
+  code: string;
+  amount: Money;
+}
+
+export interface Payment {
+  id: string;
+  trip_id: string | null;
+  kind: 'trip' | 'wallet_top_up';
+  method: 'sbp' | 'wallet' | 'promo';
+  amount: Money;
+  discount: AppliedDiscount | null;
+  status: string;
+  qr_code_url: string | null;
+}
+
+export interface AttachedPromoCode {
+  user_id: string;
+  promo_code: {
+    id: string;
+    code: string;
+    campaign: string;
+    discount: { type: 'percentage'; percent: number } | { type: 'fixed'; amount: Money };
+    tariff_ids: string[];
+    expires_at: string | null;
+  };
+  redemptions: number;
+  remaining_uses: number;
+  attached_at: string;
+}
+
 export interface Car {
   id: string;
   model: string;
   license_plate: string;
   state: string;
   tariff_id: string;
-  base_price: number;
-  price_per_minute?: number;
+  base_price: Money;
+  price_per_minute?: Money;
 }
 
 export interface CarData {
   car: Car;
-  price_per_minute: number;
+  price_per_minute: Money;
   telematics?: {
     fuel_level: number;
     location: {
@@ -32,18 +77,38 @@ export interface CarData {
   };
 }
 
+export interface FareEstimate {
+  car_id: string;
+  tariff_id: string;
+  billed_minutes: number;
+  driving_minutes: number;
+  parking_minutes: number;
+  distance_km: number;
+  base_fare: Money;
+  driving_cost: Money;
+  parking_cost: Money;
+  distance_cost: Money;
+  daily_cap_applied: boolean;
+  minimum_fare_applied: boolean;
+  rounding: 'kopeck' | 'ruble_up' | 'ruble_nearest';
+  total: Money;
+}
+
 export interface Trip {
   id: string;
   user_id: string;
   car_id: string;
-  status: 'reserved' | 'active' | 'completed' | 'cancelled';
+  status: 'reserved' | 'active' | 'completed' | 'cancelled' | 'expired';
   started_at?: string;
   ended_at?: string;
   created_at: string;
+  expires_at?: string;
+  distance_km?: number | null;
 }
 
 export interface AuthResponse {
   token: string;
+  refresh_token: string;
   user_id: string;
 }
 
@@ -0,0 +1,176 @@
+use std::time::Duration;
+use tracing::{info, warn};
+use crate::domain::{
+    errors::TelematicsError,
+    interfaces::RedisRepository,
+    models::{CarEventPayload, CarRegistryEntry, DomainEvent},
+};
+
+// Повторная доставка из outbox приходит в пределах минут, запас - на долгую недоступность брокера
+const PROCESSED_EVENT_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
+
+// Реестр car_id -> VIN обновляется, не дожидаясь периодической синхронизации
+pub struct ApplyCarEventUseCase<R>
+where
+    R: RedisRepository,
+{
+    repository: R,
+}
+
+impl<R> ApplyCarEventUseCase<R>
+where
+    R: RedisRepository,
+{
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    // false, если событие уже было обработано
+    pub async fn execute(&self, event: DomainEvent) -> Result<bool, TelematicsError> {
+        if self.repository.is_event_processed(event.event_id).await? {
+            info!("Skipping already processed event {} ({})", event.event_id, event.event_type);
+            return Ok(false);
+        }
+
+        match event.event_type.as_str() {
+            "CarCreated" | "CarUpdated" => {
+                let car: CarEventPayload = serde_json::from_value(event.payload).map_err(|e| {
+                    TelematicsError::InvalidEvent(format!("{} {} has invalid payload: {}", event.event_type, event.event_id, e))
+                })?;
+                self.repository
+                    .upsert_car_registry_entry(&CarRegistryEntry { car_id: car.id, vin: car.iot_serial_number })
+                    .await?;
+            }
+            "CarDeleted" => {
+                self.repository.remove_car_registry_entry(event.aggregate_id).await?;
+            }
+            other => warn!("Ignoring event {} of unsupported type {}", event.event_id, other),
+        }
+
+        // Отметка ставится после применения: при падении между ними событие применится
+        // повторно, что безопасно - обе операции идемпотентны
+        self.repository.mark_event_processed(event.event_id, PROCESSED_EVENT_TTL).await?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Mutex;
+    use uuid::Uuid;
+    use crate::domain::models::SensorData;
+
+    #[derive(Default)]
+    struct MockRedisRepository {
+        registry: Mutex<HashMap<Uuid, String>>,
+        processed: Mutex<HashSet<Uuid>>,
+        fail_upsert: bool,
+    }
+
+    #[async_trait]
+    impl RedisRepository for MockRedisRepository {
+        async fn save_sensor_data(&self, _vin: &str, _sensor_data: &SensorData) -> Result<(), TelematicsError> { unimplemented!() }
+        async fn get_sensor_data(&self, _vin: &str) -> Result<Option<SensorData>, TelematicsError> { unimplemented!() }
+        async fn get_all_sensor_data(&self) -> Result<Vec<(String, SensorData)>, TelematicsError> { unimplemented!() }
+        async fn get_sensor_data_by_license_plate(&self, _license_plate: &str) -> Result<Option<(String, SensorData)>, TelematicsError> { unimplemented!() }
+        async fn replace_car_registry(&self, _entries: &[CarRegistryEntry]) -> Result<(), TelematicsError> { unimplemented!() }
+        async fn get_vin_by_car_id(&self, _car_id: Uuid) -> Result<Option<String>, TelematicsError> { unimplemented!() }
+
+        async fn upsert_car_registry_entry(&self, entry: &CarRegistryEntry) -> Result<(), TelematicsError> {
+            if self.fail_upsert {
+                return Err(TelematicsError::Internal(anyhow::anyhow!("redis unavailable")));
+            }
+            self.registry.lock().unwrap().insert(entry.car_id, entry.vin.clone());
+            Ok(())
+        }
+
+        async fn remove_car_registry_entry(&self, car_id: Uuid) -> Result<(), TelematicsError> {
+            self.registry.lock().unwrap().remove(&car_id);
+            Ok(())
+        }
+
+        async fn is_event_processed(&self, event_id: Uuid) -> Result<bool, TelematicsError> {
+            Ok(self.processed.lock().unwrap().contains(&event_id))
+        }
+
+        async fn mark_event_processed(&self, event_id: Uuid, _ttl: Duration) -> Result<(), TelematicsError> {
+            self.processed.lock().unwrap().insert(event_id);
+            Ok(())
+        }
+    }
+
+    fn car_event(event_type: &str, car_id: Uuid, vin: &str) -> DomainEvent {
+        DomainEvent {
+            event_id: Uuid::new_v4(),
+            event_type: event_type.to_string(),
+            source: "cars".to_string(),
+            aggregate_type: "car".to_string(),
+            aggregate_id: car_id,
+            occurred_at: Utc::now(),
+            payload: serde_json::json!({ "id": car_id, "iot_serial_number": vin, "previous_state": null }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redelivered_event_is_skipped() {
+        let use_case = ApplyCarEventUseCase::new(MockRedisRepository::default());
+        let car_id = Uuid::new_v4();
+        let created = car_event("CarCreated", car_id, "VIN-OLD");
+        let updated = car_event("CarUpdated", car_id, "VIN-NEW");
+
+        assert!(use_case.execute(created.clone()).await.unwrap());
+        assert!(use_case.execute(updated).await.unwrap());
+        // Повторная доставка старого события не откатывает реестр
+        assert!(!use_case.execute(created).await.unwrap());
+
+        assert_eq!(use_case.repository.registry.lock().unwrap()[&car_id], "VIN-NEW");
+    }
+
+    #[tokio::test]
+    async fn test_car_deleted_removes_registry_entry() {
+        let use_case = ApplyCarEventUseCase::new(MockRedisRepository::default());
+        let car_id = Uuid::new_v4();
+        use_case.execute(car_event("CarCreated", car_id, "VIN-1")).await.unwrap();
+
+        assert!(use_case.execute(car_event("CarDeleted", car_id, "VIN-1")).await.unwrap());
+
+        assert!(use_case.repository.registry.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_event_is_marked_processed() {
+        let use_case = ApplyCarEventUseCase::new(MockRedisRepository::default());
+        let event = car_event("TariffCreated", Uuid::new_v4(), "VIN-1");
+
+        assert!(use_case.execute(event.clone()).await.unwrap());
+        assert!(!use_case.execute(event).await.unwrap());
+        assert!(use_case.repository.registry.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_event_is_not_marked_processed() {
+        let use_case = ApplyCarEventUseCase::new(MockRedisRepository { fail_upsert: true, ..Default::default() });
+        let event = car_event("CarCreated", Uuid::new_v4(), "VIN-1");
+
+        assert!(use_case.execute(event.clone()).await.is_err());
+
+        assert!(!use_case.repository.processed.lock().unwrap().contains(&event.event_id));
+    }
+
+    #[tokio::test]
+    async fn test_car_event_with_invalid_payload_is_rejected() {
+        let use_case = ApplyCarEventUseCase::new(MockRedisRepository::default());
+        let mut event = car_event("CarUpdated", Uuid::new_v4(), "VIN-1");
+        event.payload = serde_json::json!({ "id": "not-a-uuid" });
+
+        let result = use_case.execute(event.clone()).await;
+
+        assert!(matches!(result, Err(TelematicsError::InvalidEvent(_))));
+        assert!(use_case.repository.registry.lock().unwrap().is_empty());
+        assert!(!use_case.repository.processed.lock().unwrap().contains(&event.event_id));
+    }
+}
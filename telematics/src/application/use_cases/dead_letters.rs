@@ -0,0 +1,47 @@
+use crate::domain::{
+    errors::TelematicsError,
+    interfaces::DeadLetterQueue,
+    models::DeadLetter,
+};
+
+pub const MAX_DEAD_LETTER_BATCH: usize = 1000;
+
+pub struct ListDeadLettersUseCase<D> 
+where
+    D: DeadLetterQueue,
+{
+    dead_letter_queue: D,
+}
+
+impl<D> ListDeadLettersUseCase<D>
+where
+    D: DeadLetterQueue,
+{
+    pub fn new(dead_letter_queue: D) -> Self {
+        Self { dead_letter_queue }
+    }
+
+    pub async fn execute(&self, limit: usize) -> Result<Vec<DeadLetter>, TelematicsError> {
+        self.dead_letter_queue.list(limit.min(MAX_DEAD_LETTER_BATCH)).await
+    }
+}
+
+pub struct ReplayDeadLettersUseCase<D> 
+where
+    D: DeadLetterQueue,
+{
+    dead_letter_queue: D,
+}
+
+impl<D> ReplayDeadLettersUseCase<D>
+where
+    D: DeadLetterQueue,
+{
+    pub fn new(dead_letter_queue: D) -> Self {
+        Self { dead_letter_queue }
+    }
+
+    pub async fn execute(&self, message_ids: Option<Vec<String>>, limit: usize) -> Result<usize, TelematicsError> {
+        self.dead_letter_queue.replay(message_ids, limit.min(MAX_DEAD_LETTER_BATCH)).await
+    }
+}
@@ -3,6 +3,7 @@ use crate::domain::{
     interfaces::RedisRepository,
     models::SensorData,
 };
+use uuid::Uuid;
 
 pub struct GetSensorDataUseCase<R> 
 where
@@ -38,5 +39,14 @@ where
     pub async fn execute_by_license_plate(&self, license_plate: &str) -> Result<Option<(String, SensorData)>, TelematicsError> {
         self.repository.get_sensor_data_by_license_plate(license_plate).await
     }
-}
 
+    // CarNotFound, если машины нет в реестре; None, если телеметрия от нее еще не приходила
+    pub async fn execute_by_car_id(&self, car_id: Uuid) -> Result<Option<(String, SensorData)>, TelematicsError> {
+        let vin = self.repository
+            .get_vin_by_car_id(car_id)
+            .await?
+            .ok_or(TelematicsError::CarNotFound)?;
+        let data = self.repository.get_sensor_data(&vin).await?;
+        Ok(data.map(|data| (vin, data)))
+    }
+}
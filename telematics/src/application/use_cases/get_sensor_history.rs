@@ -0,0 +1,281 @@
+use crate::domain::{
+    errors::TelematicsError,
+    interfaces::SensorHistoryRepository,
+    models::{SensorData, SensorHistoryPoint},
+};
+
+pub const MAX_HISTORY_READINGS: usize = 100_000;
+const HISTORY_PAGE_SIZE: usize = 1000;
+// Поток упорядочен по времени поступления, а фильтруем по времени показания: показание
+// приходит не раньше, чем на MAX_CLOCK_SKEW, и не позже, чем через MAX_DELIVERY_DELAY
+const MAX_CLOCK_SKEW: chrono::Duration = chrono::Duration::minutes(5);
+const MAX_DELIVERY_DELAY: chrono::Duration = chrono::Duration::hours(1);
+
+pub struct GetSensorHistoryUseCase<H> 
+where
+    H: SensorHistoryRepository,
+{
+    history_repository: H,
+}
+
+impl<H> GetSensorHistoryUseCase<H>
+where
+    H: SensorHistoryRepository,
+{
+    pub fn new(history_repository: H) -> Self {
+        Self { history_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        vin: &str,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+        resolution_seconds: Option<i64>,
+    ) -> Result<Vec<SensorHistoryPoint>, TelematicsError> {
+        if from > to {
+            return Err(TelematicsError::InvalidHistoryQuery(
+                "`from` must not be later than `to`".to_string(),
+            ));
+        }
+        if resolution_seconds.is_some_and(|resolution| resolution <= 0) {
+            return Err(TelematicsError::InvalidHistoryQuery(
+                "`resolution` must be a positive number of seconds".to_string(),
+            ));
+        }
+
+        let mut readings = self.read_readings(vin, from, to).await?;
+        readings.sort_by_key(|reading| reading.timestamp);
+
+        Ok(match resolution_seconds {
+            Some(resolution) => downsample(readings, resolution),
+            None => readings.into_iter().map(SensorHistoryPoint::from).collect(),
+        })
+    }
+
+    async fn read_readings(
+        &self,
+        vin: &str,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<SensorData>, TelematicsError> {
+        let mut readings = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = self.history_repository
+                .get_history_page(vin, from - MAX_CLOCK_SKEW, to + MAX_DELIVERY_DELAY, cursor, HISTORY_PAGE_SIZE)
+                .await?;
+
+            readings.extend(
+                page.readings
+                    .into_iter()
+                    .filter(|reading| reading.timestamp >= from && reading.timestamp <= to),
+            );
+            if readings.len() > MAX_HISTORY_READINGS {
+                return Err(TelematicsError::InvalidHistoryQuery(format!(
+                    "interval contains more than {} readings, narrow `from`/`to`",
+                    MAX_HISTORY_READINGS
+                )));
+            }
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return Ok(readings),
+            }
+        }
+    }
+}
+
+fn downsample(readings: Vec<SensorData>, resolution_seconds: i64) -> Vec<SensorHistoryPoint> {
+    let mut points: Vec<SensorHistoryPoint> = Vec::new();
+    let mut current_bucket: Option<i64> = None;
+
+    for reading in readings {
+        let bucket = reading.timestamp.timestamp().div_euclid(resolution_seconds);
+        match points.last_mut() {
+            Some(point) if current_bucket == Some(bucket) => {
+                // Накапливаем суммы, средние считаем после обхода
+                point.samples += 1;
+                point.fuel_level += reading.fuel_level;
+                point.speed += reading.speed;
+                point.temperature += reading.temperature;
+                point.max_speed = point.max_speed.max(reading.speed);
+                point.location = reading.location;
+                point.door_status = reading.door_status;
+            }
+            _ => {
+                current_bucket = Some(bucket);
+                let mut point = SensorHistoryPoint::from(reading);
+                point.timestamp = chrono::DateTime::from_timestamp(bucket * resolution_seconds, 0)
+                    .unwrap_or(point.timestamp);
+                points.push(point);
+            }
+        }
+    }
+
+    for point in &mut points {
+        let samples = point.samples as f64;
+        point.fuel_level /= samples;
+        point.speed /= samples;
+        point.temperature /= samples;
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{DoorStatus, Location, SensorHistoryPage};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use std::sync::{Arc, Mutex};
+
+    // Поток в памяти: (время поступления, показание), курсор - индекс последней прочитанной записи
+    type HistoryEntries = Vec<(DateTime<Utc>, SensorData)>;
+
+    #[derive(Clone, Default)]
+    struct MockSensorHistoryRepository {
+        entries: Arc<Mutex<HistoryEntries>>,
+        pages_read: Arc<Mutex<usize>>,
+    }
+
+    #[async_trait]
+    impl SensorHistoryRepository for MockSensorHistoryRepository {
+        async fn append(&self, _vin: &str, sensor_data: &SensorData) -> Result<(), TelematicsError> {
+            self.entries.lock().unwrap().push((Utc::now(), sensor_data.clone()));
+            Ok(())
+        }
+
+        async fn get_history_page(
+            &self,
+            _vin: &str,
+            arrived_from: DateTime<Utc>,
+            arrived_to: DateTime<Utc>,
+            cursor: Option<String>,
+            count: usize,
+        ) -> Result<SensorHistoryPage, TelematicsError> {
+            *self.pages_read.lock().unwrap() += 1;
+            let start = cursor.map(|c| c.parse::<usize>().unwrap() + 1).unwrap_or(0);
+            let entries = self.entries.lock().unwrap();
+
+            let page: Vec<(usize, &SensorData)> = entries
+                .iter()
+                .enumerate()
+                .skip(start)
+                .filter(|(_, (arrived, _))| *arrived >= arrived_from && *arrived <= arrived_to)
+                .take(count)
+                .map(|(i, (_, reading))| (i, reading))
+                .collect();
+
+            let next_cursor = if page.len() >= count {
+                page.last().map(|(i, _)| i.to_string())
+            } else {
+                None
+            };
+
+            Ok(SensorHistoryPage {
+                readings: page.into_iter().map(|(_, reading)| reading.clone()).collect(),
+                next_cursor,
+            })
+        }
+    }
+
+    fn base_time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 10, 12, 0, 0).unwrap()
+    }
+
+    fn reading(timestamp: DateTime<Utc>, speed: f64) -> SensorData {
+        SensorData {
+            vin: "VIN1".to_string(),
+            license_plate: "A001AA".to_string(),
+            fuel_level: 50.0,
+            location: Location { latitude: 55.75, longitude: 37.61 },
+            door_status: DoorStatus::Locked,
+            speed,
+            temperature: 20.0,
+            timestamp,
+        }
+    }
+
+    fn repository_with(entries: HistoryEntries) -> MockSensorHistoryRepository {
+        let repository = MockSensorHistoryRepository::default();
+        *repository.entries.lock().unwrap() = entries;
+        repository
+    }
+
+    #[tokio::test]
+    async fn test_history_reads_all_pages() {
+        let from = base_time();
+        let entries: Vec<_> = (0..HISTORY_PAGE_SIZE as i64 * 2 + 10)
+            .map(|i| {
+                let at = from + Duration::milliseconds(i * 10);
+                (at, reading(at, 1.0))
+            })
+            .collect();
+        let repository = repository_with(entries);
+        let use_case = GetSensorHistoryUseCase::new(repository.clone());
+
+        let points = use_case.execute("VIN1", from, from + Duration::minutes(1), None).await.unwrap();
+
+        assert_eq!(points.len(), HISTORY_PAGE_SIZE * 2 + 10);
+        assert_eq!(*repository.pages_read.lock().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_history_filters_by_reading_timestamp() {
+        let from = base_time();
+        let to = from + Duration::minutes(10);
+        let repository = repository_with(vec![
+            // Снято до интервала, поступило внутри него
+            (from + Duration::minutes(1), reading(from - Duration::minutes(1), 1.0)),
+            // Снято в интервале, поступило с задержкой после его конца
+            (to + Duration::minutes(20), reading(to - Duration::minutes(1), 2.0)),
+            // Снято в интервале, поступило раньше из-за расхождения часов
+            (from - Duration::minutes(1), reading(from + Duration::seconds(30), 3.0)),
+        ]);
+        let use_case = GetSensorHistoryUseCase::new(repository);
+
+        let points = use_case.execute("VIN1", from, to, None).await.unwrap();
+
+        let speeds: Vec<f64> = points.iter().map(|point| point.speed).collect();
+        assert_eq!(speeds, vec![3.0, 2.0]);
+    }
+
+    #[tokio::test]
+    async fn test_history_over_limit_is_rejected() {
+        let from = base_time();
+        let entries: Vec<_> = (0..MAX_HISTORY_READINGS as i64 + 1)
+            .map(|i| {
+                let at = from + Duration::milliseconds(i);
+                (at, reading(at, 1.0))
+            })
+            .collect();
+        let use_case = GetSensorHistoryUseCase::new(repository_with(entries));
+
+        let result = use_case.execute("VIN1", from, from + Duration::minutes(5), None).await;
+
+        assert!(matches!(result, Err(TelematicsError::InvalidHistoryQuery(_))));
+    }
+
+    #[tokio::test]
+    async fn test_history_downsamples_by_reading_timestamp() {
+        let from = base_time();
+        let repository = repository_with(vec![
+            (from + Duration::seconds(70), reading(from + Duration::seconds(5), 10.0)),
+            (from + Duration::seconds(71), reading(from + Duration::seconds(65), 40.0)),
+            (from + Duration::seconds(72), reading(from + Duration::seconds(30), 30.0)),
+        ]);
+        let use_case = GetSensorHistoryUseCase::new(repository);
+
+        let points = use_case.execute("VIN1", from, from + Duration::minutes(2), Some(60)).await.unwrap();
+
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].samples, 2);
+        assert_eq!(points[0].speed, 20.0);
+        assert_eq!(points[0].max_speed, 30.0);
+        assert_eq!(points[1].samples, 1);
+        assert_eq!(points[1].timestamp, from + Duration::seconds(60));
+    }
+}
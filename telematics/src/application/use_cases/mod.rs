@@ -1,8 +1,15 @@
 mod send_command;
 mod process_sensor_data;
 mod get_sensor_data;
+mod dead_letters;
+mod sync_car_registry;
+mod get_sensor_history;
+mod apply_car_event;
 
 pub use send_command::*;
 pub use process_sensor_data::*;
 pub use get_sensor_data::*;
-
+pub use dead_letters::*;
+pub use sync_car_registry::*;
+pub use get_sensor_history::*;
+pub use apply_car_event::*;
@@ -1,28 +1,32 @@
 use crate::domain::{
     errors::TelematicsError,
-    interfaces::RedisRepository,
+    interfaces::{RedisRepository, SensorHistoryRepository},
     models::SensorData,
 };
 
-pub struct ProcessSensorDataUseCase<R> 
+pub struct ProcessSensorDataUseCase<R, H> 
 where
     R: RedisRepository,
+    H: SensorHistoryRepository,
 {
     repository: R,
+    history_repository: H,
 }
 
-impl<R> ProcessSensorDataUseCase<R>
+impl<R, H> ProcessSensorDataUseCase<R, H>
 where
     R: RedisRepository,
+    H: SensorHistoryRepository,
 {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+    pub fn new(repository: R, history_repository: H) -> Self {
+        Self { repository, history_repository }
     }
 
     pub async fn execute(&self, sensor_data: SensorData) -> Result<(), TelematicsError> {
-        // Сохраняем в Redis с ключом VIN
+        // Сохраняем последнее состояние в Redis с ключом VIN
         self.repository.save_sensor_data(&sensor_data.vin, &sensor_data).await?;
+        // И добавляем показание в историю
+        self.history_repository.append(&sensor_data.vin, &sensor_data).await?;
         Ok(())
     }
 }
-
@@ -0,0 +1,29 @@
+use crate::domain::{
+    errors::TelematicsError,
+    interfaces::{CarsClient, RedisRepository},
+};
+
+pub struct SyncCarRegistryUseCase<C, R>
+where
+    C: CarsClient,
+    R: RedisRepository,
+{
+    cars_client: C,
+    repository: R,
+}
+
+impl<C, R> SyncCarRegistryUseCase<C, R>
+where
+    C: CarsClient,
+    R: RedisRepository,
+{
+    pub fn new(cars_client: C, repository: R) -> Self {
+        Self { cars_client, repository }
+    }
+
+    pub async fn execute(&self) -> Result<usize, TelematicsError> {
+        let entries = self.cars_client.list_car_registry().await?;
+        self.repository.replace_car_registry(&entries).await?;
+        Ok(entries.len())
+    }
+}
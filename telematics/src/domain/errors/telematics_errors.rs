@@ -25,5 +25,15 @@ pub enum TelematicsError {
     
     #[error("invalid data: {0}")]
     InvalidData(String),
+
+    // Событие не удастся применить и при повторной доставке
+    #[error("invalid event: {0}")]
+    InvalidEvent(String),
+
+    #[error("cars service error: {0}")]
+    CarsService(String),
+
+    #[error("invalid history query: {0}")]
+    InvalidHistoryQuery(String),
 }
 
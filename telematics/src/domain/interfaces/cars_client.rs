@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use crate::domain::{
+    errors::TelematicsError,
+    models::CarRegistryEntry,
+};
+
+#[async_trait]
+pub trait CarsClient {
+    async fn list_car_registry(&self) -> Result<Vec<CarRegistryEntry>, TelematicsError>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use crate::domain::{
+    errors::TelematicsError,
+    models::DeadLetter,
+};
+
+#[async_trait]
+pub trait DeadLetterQueue {
+    // Просмотр без удаления из очереди
+    async fn list(&self, limit: usize) -> Result<Vec<DeadLetter>, TelematicsError>;
+    // Без message_ids переотправляются все сообщения в пределах limit
+    async fn replay(&self, message_ids: Option<Vec<String>>, limit: usize) -> Result<usize, TelematicsError>;
+}
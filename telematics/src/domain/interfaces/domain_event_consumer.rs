@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use crate::domain::models::DomainEvent;
+
+#[async_trait]
+pub trait DomainEventConsumer {
+    // binding_key - `<source>.<EventType>`, допускаются wildcard `*` и `#`
+    async fn consume_events<F>(&self, binding_key: &str, callback: F) -> Result<(), anyhow::Error>
+    where
+        F: Fn(DomainEvent) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), anyhow::Error>> + Send>> + Send + Sync + 'static;
+}
@@ -1,8 +1,15 @@
 mod rabbitmq_publisher;
 mod rabbitmq_consumer;
 mod redis_repository;
+mod dead_letter_queue;
+mod cars_client;
+mod sensor_history_repository;
+mod domain_event_consumer;
 
 pub use rabbitmq_publisher::*;
 pub use rabbitmq_consumer::*;
 pub use redis_repository::*;
-
+pub use dead_letter_queue::*;
+pub use cars_client::*;
+pub use sensor_history_repository::*;
+pub use domain_event_consumer::*;
@@ -1,8 +1,10 @@
 use async_trait::async_trait;
 use crate::domain::{
     errors::TelematicsError,
-    models::SensorData,
+    models::{SensorData, CarRegistryEntry},
 };
+use std::time::Duration;
+use uuid::Uuid;
 
 #[async_trait]
 pub trait RedisRepository {
@@ -10,5 +12,13 @@ pub trait RedisRepository {
     async fn get_sensor_data(&self, vin: &str) -> Result<Option<SensorData>, TelematicsError>;
     async fn get_all_sensor_data(&self) -> Result<Vec<(String, SensorData)>, TelematicsError>;
     async fn get_sensor_data_by_license_plate(&self, license_plate: &str) -> Result<Option<(String, SensorData)>, TelematicsError>;
+    // Полностью заменяет реестр car_id -> VIN
+    async fn replace_car_registry(&self, entries: &[CarRegistryEntry]) -> Result<(), TelematicsError>;
+    async fn get_vin_by_car_id(&self, car_id: Uuid) -> Result<Option<String>, TelematicsError>;
+    async fn upsert_car_registry_entry(&self, entry: &CarRegistryEntry) -> Result<(), TelematicsError>;
+    async fn remove_car_registry_entry(&self, car_id: Uuid) -> Result<(), TelematicsError>;
+    // Дедупликация повторных доставок
+    async fn is_event_processed(&self, event_id: Uuid) -> Result<bool, TelematicsError>;
+    async fn mark_event_processed(&self, event_id: Uuid, ttl: Duration) -> Result<(), TelematicsError>;
 }
 
@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use crate::domain::{
+    errors::TelematicsError,
+    models::{SensorData, SensorHistoryPage},
+};
+
+#[async_trait]
+pub trait SensorHistoryRepository {
+    async fn append(&self, vin: &str, sensor_data: &SensorData) -> Result<(), TelematicsError>;
+    // Показания, поступившие в интервале [arrived_from, arrived_to], начиная после cursor
+    async fn get_history_page(
+        &self,
+        vin: &str,
+        arrived_from: chrono::DateTime<chrono::Utc>,
+        arrived_to: chrono::DateTime<chrono::Utc>,
+        cursor: Option<String>,
+        count: usize,
+    ) -> Result<SensorHistoryPage, TelematicsError>;
+}
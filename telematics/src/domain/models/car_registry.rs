@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// VIN, под которым машина шлет телеметрию, совпадает с ее iot_serial_number
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CarRegistryEntry {
+    pub car_id: Uuid,
+    pub vin: String,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub message_id: String,
+    pub routing_key: String,
+    pub error: String,
+    pub payload: String,
+    pub dead_lettered_at: Option<chrono::DateTime<chrono::Utc>>,
+}
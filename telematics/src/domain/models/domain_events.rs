@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+// Доставка хотя бы один раз, event_id используется для дедупликации
+#[derive(Debug, Clone, Deserialize)]
+pub struct DomainEvent {
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub source: String,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub payload: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CarEventPayload {
+    pub id: Uuid,
+    pub iot_serial_number: String,
+}
@@ -1,6 +1,13 @@
 mod sensors;
 mod commands;
+mod dead_letters;
+mod car_registry;
+mod sensor_history;
+mod domain_events;
 
 pub use sensors::*;
 pub use commands::*;
-
+pub use dead_letters::*;
+pub use car_registry::*;
+pub use sensor_history::*;
+pub use domain_events::*;
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use super::{DoorStatus, Location, SensorData};
+
+// Без прореживания точка соответствует одному показанию, с прореживанием - агрегату за интервал resolution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensorHistoryPoint {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub samples: usize,
+    pub fuel_level: f64, // среднее за интервал
+    pub location: Location, // последняя известная за интервал
+    pub door_status: DoorStatus, // последнее известное за интервал
+    pub speed: f64, // среднее за интервал
+    pub max_speed: f64,
+    pub temperature: f64, // среднее за интервал
+}
+
+impl From<SensorData> for SensorHistoryPoint {
+    fn from(data: SensorData) -> Self {
+        Self {
+            timestamp: data.timestamp,
+            samples: 1,
+            fuel_level: data.fuel_level,
+            location: data.location,
+            door_status: data.door_status,
+            speed: data.speed,
+            max_speed: data.speed,
+            temperature: data.temperature,
+        }
+    }
+}
+
+// Страница истории в порядке поступления показаний. next_cursor пуст, если поток дочитан
+#[derive(Debug, Clone, Default)]
+pub struct SensorHistoryPage {
+    pub readings: Vec<SensorData>,
+    pub next_cursor: Option<String>,
+}
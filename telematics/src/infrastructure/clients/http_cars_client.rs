@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use uuid::Uuid;
+use tracing::{info, error};
+use crate::domain::{
+    errors::TelematicsError,
+    interfaces::CarsClient,
+    models::CarRegistryEntry,
+};
+
+#[derive(Deserialize)]
+struct CarInfo {
+    id: Uuid,
+    iot_serial_number: String,
+}
+
+pub struct HttpCarsClient {
+    client: Client,
+    base_url: String,
+}
+
+impl HttpCarsClient {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url,
+        }
+    }
+}
+
+impl Clone for HttpCarsClient {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            base_url: self.base_url.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl CarsClient for HttpCarsClient {
+    async fn list_car_registry(&self) -> Result<Vec<CarRegistryEntry>, TelematicsError> {
+        let url = format!("{}/cars", self.base_url);
+        info!("Calling cars service: GET {}", url);
+
+        let response = self.client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| TelematicsError::CarsService(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Cars service error: {} - {}", status, error_text);
+            return Err(TelematicsError::CarsService(format!("{}: {}", status, error_text)));
+        }
+
+        let cars: Vec<CarInfo> = response
+            .json()
+            .await
+            .map_err(|e| TelematicsError::CarsService(e.to_string()))?;
+
+        Ok(cars
+            .into_iter()
+            .map(|car| CarRegistryEntry {
+                car_id: car.id,
+                vin: car.iot_serial_number,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,3 @@
+mod http_cars_client;
+
+pub use http_cars_client::*;
@@ -1,6 +1,8 @@
 pub mod rabbitmq;
 pub mod redis;
+pub mod clients;
 
 pub use rabbitmq::*;
 pub use redis::*;
+pub use clients::*;
 
@@ -1,34 +1,61 @@
 use async_trait::async_trait;
 use lapin::{
-    options::BasicConsumeOptions,
+    options::{BasicConsumeOptions, BasicQosOptions},
     types::FieldTable,
     Channel, Connection, ConnectionProperties,
     message::Delivery,
 };
 use futures_util::StreamExt;
+use std::time::Duration;
 use tracing::{info, error, warn};
 use crate::domain::{
     interfaces::RabbitMQConsumer,
     models::{SensorData, SensorDataMessage},
 };
+use super::{declare_dead_letter_topology, process_delivery, settle_delivery, DEAD_LETTER_EXCHANGE, DEAD_LETTER_QUEUE};
+
+pub(crate) const SENSORS_EXCHANGE: &str = "telematics_sensors";
+// Общая очередь: несколько экземпляров telematics разбирают ее как конкурирующие consumers
+const SENSORS_QUEUE: &str = "telematics_sensors_ingest";
+const PREFETCH_COUNT: u16 = 50;
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+// Ошибка разбора означает, что сообщение невалидно и повтор не поможет
+fn parse_sensor_data(data: &[u8]) -> Result<SensorData, String> {
+    let json_str = std::str::from_utf8(data)
+        .map_err(|e| format!("Invalid UTF-8 in message: {}", e))?;
+    let msg = serde_json::from_str::<SensorDataMessage>(json_str)
+        .map_err(|e| format!("Failed to deserialize sensor data: {}", e))?;
+    SensorData::try_from(msg)
+        .map_err(|e| format!("Failed to convert sensor data message: {}", e))
+}
 
 pub struct RabbitMQConsumerImpl {
-    channel: Channel,
+    amqp_url: String,
 }
 
 impl RabbitMQConsumerImpl {
-    pub async fn new(amqp_url: &str) -> Result<Self, anyhow::Error> {
-        let connection = Connection::connect(amqp_url, ConnectionProperties::default()).await?;
+    // Подключение устанавливается при запуске consume и восстанавливается при обрыве
+    pub fn new(amqp_url: &str) -> Self {
+        Self {
+            amqp_url: amqp_url.to_string(),
+        }
+    }
+
+    async fn connect(&self) -> Result<(Connection, Channel), anyhow::Error> {
+        let connection = Connection::connect(&self.amqp_url, ConnectionProperties::default()).await?;
         let channel = connection.create_channel().await?;
-        
-        Ok(Self { channel })
+        Ok((connection, channel))
     }
 
-    pub async fn setup_exchange_and_queue(&self, topic: &str) -> Result<(), anyhow::Error> {
+    async fn setup_exchange_and_queue(&self, channel: &Channel, binding_key: &str) -> Result<(), anyhow::Error> {
+        declare_dead_letter_topology(channel, DEAD_LETTER_EXCHANGE, DEAD_LETTER_QUEUE).await?;
+
         // Создаем exchange для сенсорных данных
-        self.channel
+        channel
             .exchange_declare(
-                "telematics_sensors",
+                SENSORS_EXCHANGE,
                 lapin::ExchangeKind::Topic,
                 lapin::options::ExchangeDeclareOptions {
                     durable: true,
@@ -38,11 +65,9 @@ impl RabbitMQConsumerImpl {
             )
             .await?;
 
-        // Создаем очередь для каждого топика
-        let queue_name = format!("sensors_{}", topic);
-        self.channel
+        channel
             .queue_declare(
-                &queue_name,
+                SENSORS_QUEUE,
                 lapin::options::QueueDeclareOptions {
                     durable: true,
                     ..Default::default()
@@ -51,79 +76,61 @@ impl RabbitMQConsumerImpl {
             )
             .await?;
 
-        // Биндим очередь к exchange с routing key = topic
-        self.channel
+        // Биндим очередь к exchange по wildcard ключу, чтобы получать данные всех машин
+        channel
             .queue_bind(
-                &queue_name,
-                "telematics_sensors",
-                topic,
+                SENSORS_QUEUE,
+                SENSORS_EXCHANGE,
+                binding_key,
                 lapin::options::QueueBindOptions::default(),
                 FieldTable::default(),
             )
             .await?;
 
-        info!("Exchange and queue setup completed for topic: {}", topic);
+        info!("Queue {} bound to exchange {} with key {}", SENSORS_QUEUE, SENSORS_EXCHANGE, binding_key);
         Ok(())
     }
 
-    pub async fn consume_from_topic<F>(
-        &self,
-        topic: &str,
-        callback: F,
-    ) -> Result<(), anyhow::Error>
+    // Один сеанс потребления: возвращается, когда соединение оборвалось
+    async fn consume_once<F>(&self, binding_key: &str, callback: &F) -> Result<(), anyhow::Error>
     where
         F: Fn(SensorData) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), anyhow::Error>> + Send>> + Send + Sync + 'static,
     {
-        let queue_name = format!("sensors_{}", topic);
-        
-        let mut consumer = self.channel
+        // Соединение должно жить, пока идет потребление
+        let (_connection, channel) = self.connect().await?;
+        self.setup_exchange_and_queue(&channel, binding_key).await?;
+
+        channel
+            .basic_qos(PREFETCH_COUNT, BasicQosOptions::default())
+            .await?;
+
+        let mut consumer = channel
             .basic_consume(
-                &queue_name,
+                SENSORS_QUEUE,
                 "telematics_consumer",
                 BasicConsumeOptions::default(),
                 FieldTable::default(),
             )
             .await?;
 
-        info!("Started consuming from queue: {}", queue_name);
+        info!("Started consuming from queue: {}", SENSORS_QUEUE);
 
         while let Some(delivery_result) = consumer.next().await {
             let delivery: Delivery = match delivery_result {
                 Ok(d) => d,
                 Err(e) => {
-                    error!("Error receiving delivery: {}", e);
-                    continue;
+                    // Ошибка доставки означает проблему с каналом или соединением
+                    return Err(e.into());
                 }
             };
-            
-            match std::str::from_utf8(&delivery.data) {
-                Ok(json_str) => {
-                    match serde_json::from_str::<SensorDataMessage>(json_str) {
-                        Ok(msg) => {
-                            match SensorData::try_from(msg) {
-                                Ok(sensor_data) => {
-                                    if let Err(e) = callback(sensor_data).await {
-                                        error!("Error processing sensor data: {}", e);
-                                    }
-                                }
-                                Err(e) => {
-                                    warn!("Failed to convert sensor data message: {}", e);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Failed to deserialize sensor data: {}", e);
-                        }
-                    }
-                }
-                Err(e) => {
-                    warn!("Invalid UTF-8 in message: {}", e);
-                }
-            }
 
-            // Подтверждаем обработку сообщения
-            if let Err(e) = delivery.ack(lapin::options::BasicAckOptions::default()).await {
-                error!("Failed to ack delivery: {}", e);
+            // Невалидное сообщение уходит в dead-letter exchange, временная ошибка обработки
+            // (например, Redis недоступен) возвращает его в очередь
+            let outcome = process_delivery(parse_sensor_data(&delivery.data), callback).await;
+            let result = settle_delivery(&channel, DEAD_LETTER_EXCHANGE, &delivery, outcome).await;
+
+            if let Err(e) = result {
+                error!("Failed to acknowledge delivery: {}", e);
             }
         }
 
@@ -133,12 +140,101 @@ impl RabbitMQConsumerImpl {
 
 #[async_trait]
 impl RabbitMQConsumer for RabbitMQConsumerImpl {
-    async fn consume_sensor_data<F>(&self, topic: &str, callback: F) -> Result<(), anyhow::Error>
+    // Переподключается с экспоненциальной задержкой
+    async fn consume_sensor_data<F>(&self, binding_key: &str, callback: F) -> Result<(), anyhow::Error>
     where
         F: Fn(SensorData) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), anyhow::Error>> + Send>> + Send + Sync + 'static,
     {
-        let topic = topic.to_string();
-        self.consume_from_topic(&topic, callback).await
+        let mut delay = INITIAL_RECONNECT_DELAY;
+        loop {
+            let started = std::time::Instant::now();
+            match self.consume_once(binding_key, &callback).await {
+                Ok(()) => warn!("RabbitMQ consumer stream closed"),
+                Err(e) => error!("RabbitMQ consumer error: {}", e),
+            }
+
+            // Если сеанс продержался дольше максимальной задержки, начинаем отсчет заново
+            if started.elapsed() > MAX_RECONNECT_DELAY {
+                delay = INITIAL_RECONNECT_DELAY;
+            }
+
+            warn!("Reconnecting to RabbitMQ in {:?}", delay);
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use crate::infrastructure::rabbitmq::{DeliveryCallback, DeliveryOutcome};
+
+    fn sensor_json(door_status: &str) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::json!({
+            "vin": "VIN-1",
+            "license_plate": "A123BC77",
+            "fuel_level": 42.5,
+            "location": {"latitude": 55.75, "longitude": 37.61},
+            "door_status": door_status,
+            "speed": 0.0,
+            "temperature": 21.0,
+        }))
+        .unwrap()
+    }
+
+    fn recording_callback(saved: Arc<Mutex<Vec<String>>>, fail: bool) -> Box<DeliveryCallback<SensorData>> {
+        Box::new(move |sensor_data: SensorData| {
+            saved.lock().unwrap().push(sensor_data.vin);
+            Box::pin(async move {
+                if fail {
+                    anyhow::bail!("redis unavailable");
+                }
+                Ok(())
+            })
+        })
+    }
+
+    #[test]
+    fn test_parse_sensor_data() {
+        let sensor_data = parse_sensor_data(&sensor_json("Locked")).unwrap();
+        assert_eq!(sensor_data.vin, "VIN-1");
+
+        assert!(parse_sensor_data(&[0xff, 0xfe]).unwrap_err().starts_with("Invalid UTF-8"));
+        assert!(parse_sensor_data(b"{}").unwrap_err().starts_with("Failed to deserialize"));
+        assert!(parse_sensor_data(&sensor_json("ajar")).unwrap_err().starts_with("Failed to convert"));
+    }
+
+    #[tokio::test]
+    async fn test_valid_payload_is_acked() {
+        let saved = Arc::new(Mutex::new(Vec::new()));
+        let callback = recording_callback(saved.clone(), false);
+
+        let outcome = process_delivery(parse_sensor_data(&sensor_json("closed")), callback.as_ref()).await;
+
+        assert_eq!(outcome, DeliveryOutcome::Ack);
+        assert_eq!(*saved.lock().unwrap(), vec!["VIN-1"]);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_payload_is_dead_lettered() {
+        let saved = Arc::new(Mutex::new(Vec::new()));
+        let callback = recording_callback(saved.clone(), false);
+
+        for data in [b"not json".to_vec(), sensor_json("ajar")] {
+            let outcome = process_delivery(parse_sensor_data(&data), callback.as_ref()).await;
+            assert!(matches!(outcome, DeliveryOutcome::DeadLetter(_)), "{:?}", outcome);
+        }
+        assert!(saved.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_callback_error_is_requeued() {
+        let callback = recording_callback(Arc::default(), true);
+
+        let outcome = process_delivery(parse_sensor_data(&sensor_json("open")), callback.as_ref()).await;
+
+        assert_eq!(outcome, DeliveryOutcome::Requeue("redis unavailable".to_string()));
+    }
+}
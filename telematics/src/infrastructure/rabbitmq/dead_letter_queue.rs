@@ -0,0 +1,292 @@
+use async_trait::async_trait;
+use lapin::{
+    message::BasicGetMessage,
+    options::{BasicAckOptions, BasicGetOptions, BasicNackOptions, BasicPublishOptions},
+    types::{AMQPValue, FieldTable, LongString, ShortString},
+    BasicProperties, Channel, Connection, ConnectionProperties,
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{info, error, warn};
+use crate::domain::{
+    errors::TelematicsError,
+    interfaces::DeadLetterQueue,
+    models::DeadLetter,
+};
+use super::SENSORS_EXCHANGE;
+
+pub(crate) const DEAD_LETTER_EXCHANGE: &str = "telematics_sensors_dlx";
+pub(crate) const DEAD_LETTER_QUEUE: &str = "telematics_sensors_dead_letter";
+
+const HEADER_ERROR: &str = "x-parse-error";
+const HEADER_ORIGINAL_ROUTING_KEY: &str = "x-original-routing-key";
+const HEADER_DEAD_LETTERED_AT: &str = "x-dead-lettered-at";
+
+// Dead-letter exchange и очередь для невалидных сообщений одного потока
+pub(crate) async fn declare_dead_letter_topology(channel: &Channel, exchange: &str, queue: &str) -> Result<(), lapin::Error> {
+    channel
+        .exchange_declare(
+            exchange,
+            lapin::ExchangeKind::Fanout,
+            lapin::options::ExchangeDeclareOptions {
+                durable: true,
+                ..Default::default()
+            },
+            FieldTable::default(),
+        )
+        .await?;
+
+    channel
+        .queue_declare(
+            queue,
+            lapin::options::QueueDeclareOptions {
+                durable: true,
+                ..Default::default()
+            },
+            FieldTable::default(),
+        )
+        .await?;
+
+    channel
+        .queue_bind(
+            queue,
+            exchange,
+            "",
+            lapin::options::QueueBindOptions::default(),
+            FieldTable::default(),
+        )
+        .await?;
+
+    Ok(())
+}
+
+// Ошибка разбора уходит в заголовке сообщения
+pub(crate) async fn publish_dead_letter(
+    channel: &Channel,
+    exchange: &str,
+    routing_key: &str,
+    payload: &[u8],
+    error: &str,
+) -> Result<(), lapin::Error> {
+    let mut headers = FieldTable::default();
+    headers.insert(HEADER_ERROR.into(), AMQPValue::LongString(LongString::from(error)));
+    headers.insert(HEADER_ORIGINAL_ROUTING_KEY.into(), AMQPValue::LongString(LongString::from(routing_key)));
+    headers.insert(
+        HEADER_DEAD_LETTERED_AT.into(),
+        AMQPValue::LongString(LongString::from(chrono::Utc::now().to_rfc3339())),
+    );
+
+    let properties = BasicProperties::default()
+        .with_message_id(ShortString::from(uuid::Uuid::new_v4().to_string()))
+        .with_delivery_mode(2) // persistent
+        .with_headers(headers);
+
+    channel
+        .basic_publish(
+            exchange,
+            "",
+            BasicPublishOptions::default(),
+            payload,
+            properties,
+        )
+        .await?
+        .await?;
+
+    Ok(())
+}
+
+fn header_str(properties: &BasicProperties, key: &str) -> Option<String> {
+    properties
+        .headers()
+        .as_ref()
+        .and_then(|headers| headers.inner().get(key))
+        .and_then(|value| match value {
+            AMQPValue::LongString(s) => Some(String::from_utf8_lossy(s.as_bytes()).into_owned()),
+            _ => None,
+        })
+}
+
+fn message_id(message: &BasicGetMessage) -> String {
+    message
+        .delivery
+        .properties
+        .message_id()
+        .as_ref()
+        .map(|id| id.to_string())
+        .unwrap_or_default()
+}
+
+impl From<&BasicGetMessage> for DeadLetter {
+    fn from(message: &BasicGetMessage) -> Self {
+        let properties = &message.delivery.properties;
+        Self {
+            message_id: message_id(message),
+            routing_key: header_str(properties, HEADER_ORIGINAL_ROUTING_KEY).unwrap_or_default(),
+            error: header_str(properties, HEADER_ERROR).unwrap_or_default(),
+            payload: String::from_utf8_lossy(&message.delivery.data).into_owned(),
+            dead_lettered_at: header_str(properties, HEADER_DEAD_LETTERED_AT)
+                .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&chrono::Utc)),
+        }
+    }
+}
+
+// Соединение переоткрывается при обрыве, канал берется свой на каждую операцию:
+// неподтвержденные сообщения вернутся в очередь при закрытии канала
+#[derive(Clone)]
+pub struct RabbitMQDeadLetterQueueImpl {
+    amqp_url: String,
+    connection: Arc<Mutex<Option<Connection>>>,
+}
+
+impl RabbitMQDeadLetterQueueImpl {
+    pub async fn new(amqp_url: &str) -> Result<Self, anyhow::Error> {
+        let queue = Self {
+            amqp_url: amqp_url.to_string(),
+            connection: Arc::new(Mutex::new(None)),
+        };
+        let channel = queue.open_channel().await?;
+        Self::close(&channel).await;
+
+        Ok(queue)
+    }
+
+    async fn open_channel(&self) -> Result<Channel, TelematicsError> {
+        let mut connection = self.connection.lock().await;
+
+        if let Some(current) = connection.as_ref() {
+            if current.status().connected() {
+                match current.create_channel().await {
+                    Ok(channel) => return Ok(channel),
+                    Err(e) => warn!("Failed to open dead-letter channel, reconnecting: {}", e),
+                }
+            } else {
+                warn!("Dead-letter connection lost, reconnecting");
+            }
+        }
+        *connection = None;
+
+        let reconnected = Connection::connect(&self.amqp_url, ConnectionProperties::default())
+            .await
+            .map_err(|e| TelematicsError::RabbitMQConsumeError(e.to_string()))?;
+        let channel = reconnected
+            .create_channel()
+            .await
+            .map_err(|e| TelematicsError::RabbitMQConsumeError(e.to_string()))?;
+        declare_dead_letter_topology(&channel, DEAD_LETTER_EXCHANGE, DEAD_LETTER_QUEUE)
+            .await
+            .map_err(|e| TelematicsError::RabbitMQConsumeError(e.to_string()))?;
+
+        *connection = Some(reconnected);
+        info!("Dead-letter queue connected");
+        Ok(channel)
+    }
+
+    async fn close(channel: &Channel) {
+        if let Err(e) = channel.close(200, "OK").await {
+            warn!("Failed to close dead-letter channel: {}", e);
+        }
+    }
+
+    // Сообщения забираются без подтверждения: пока они не подтверждены,
+    // повторный запрос их не получит
+    async fn fetch(channel: &Channel, limit: usize) -> Result<Vec<BasicGetMessage>, TelematicsError> {
+        let mut messages = Vec::new();
+        while messages.len() < limit {
+            let message = channel
+                .basic_get(DEAD_LETTER_QUEUE, BasicGetOptions { no_ack: false })
+                .await
+                .map_err(|e| TelematicsError::RabbitMQConsumeError(e.to_string()))?;
+            match message {
+                Some(message) => messages.push(message),
+                None => break,
+            }
+        }
+        Ok(messages)
+    }
+
+    async fn requeue(message: &BasicGetMessage) {
+        if let Err(e) = message.delivery.acker
+            .nack(BasicNackOptions { requeue: true, ..Default::default() })
+            .await
+        {
+            error!("Failed to return dead letter {} to queue: {}", message_id(message), e);
+        }
+    }
+
+    async fn replay_messages(
+        channel: &Channel,
+        messages: &[BasicGetMessage],
+        message_ids: Option<&Vec<String>>,
+    ) -> usize {
+        let mut replayed = 0;
+        for message in messages {
+            let id = message_id(message);
+            let selected = message_ids.is_none_or(|ids| ids.contains(&id));
+            if !selected {
+                Self::requeue(message).await;
+                continue;
+            }
+
+            let routing_key = header_str(&message.delivery.properties, HEADER_ORIGINAL_ROUTING_KEY)
+                .unwrap_or_default();
+            let published = match channel
+                .basic_publish(
+                    SENSORS_EXCHANGE,
+                    &routing_key,
+                    BasicPublishOptions::default(),
+                    &message.delivery.data,
+                    BasicProperties::default().with_delivery_mode(2),
+                )
+                .await
+            {
+                Ok(confirm) => confirm.await.map(|_| ()),
+                Err(e) => Err(e),
+            };
+
+            match published {
+                Ok(()) => {
+                    if let Err(e) = message.delivery.acker.ack(BasicAckOptions::default()).await {
+                        error!("Failed to ack replayed dead letter {}: {}", id, e);
+                    }
+                    info!("Dead letter {} replayed with routing key {}", id, routing_key);
+                    replayed += 1;
+                }
+                Err(e) => {
+                    error!("Failed to replay dead letter {}: {}", id, e);
+                    Self::requeue(message).await;
+                }
+            }
+        }
+        replayed
+    }
+}
+
+#[async_trait]
+impl DeadLetterQueue for RabbitMQDeadLetterQueueImpl {
+    async fn list(&self, limit: usize) -> Result<Vec<DeadLetter>, TelematicsError> {
+        let channel = self.open_channel().await?;
+        let result = Self::fetch(&channel, limit).await;
+
+        if let Ok(messages) = &result {
+            // Возвращаем сообщения в очередь: просмотр не должен их удалять
+            for message in messages {
+                Self::requeue(message).await;
+            }
+        }
+        Self::close(&channel).await;
+
+        Ok(result?.iter().map(DeadLetter::from).collect())
+    }
+
+    async fn replay(&self, message_ids: Option<Vec<String>>, limit: usize) -> Result<usize, TelematicsError> {
+        let channel = self.open_channel().await?;
+        let result = match Self::fetch(&channel, limit).await {
+            Ok(messages) => Ok(Self::replay_messages(&channel, &messages, message_ids.as_ref()).await),
+            Err(e) => Err(e),
+        };
+        Self::close(&channel).await;
+
+        result
+    }
+}
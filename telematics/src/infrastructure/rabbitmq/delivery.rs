@@ -0,0 +1,69 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use lapin::{
+    message::Delivery,
+    options::{BasicAckOptions, BasicNackOptions},
+    Channel,
+};
+use tracing::{error, warn};
+use crate::domain::errors::TelematicsError;
+use super::publish_dead_letter;
+
+// Пауза перед возвратом сообщения в очередь при временной ошибке, чтобы не крутить его вхолостую
+const TRANSIENT_FAILURE_DELAY: Duration = Duration::from_secs(1);
+
+pub(crate) type DeliveryCallback<T> =
+    dyn Fn(T) -> Pin<Box<dyn Future<Output = Result<(), anyhow::Error>> + Send>> + Send + Sync;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum DeliveryOutcome {
+    Ack,
+    // Временная ошибка обработки: сообщение вернется в очередь
+    Requeue(String),
+    // Повтор не поможет: сообщение уходит в dead-letter очередь с причиной
+    DeadLetter(String),
+}
+
+// Не трогает брокер: решение зависит только от разбора сообщения и результата обработки
+pub(crate) async fn process_delivery<T>(parsed: Result<T, String>, callback: &DeliveryCallback<T>) -> DeliveryOutcome {
+    let message = match parsed {
+        Ok(message) => message,
+        Err(reason) => return DeliveryOutcome::DeadLetter(reason),
+    };
+
+    match callback(message).await {
+        Ok(()) => DeliveryOutcome::Ack,
+        Err(e) => match e.downcast_ref::<TelematicsError>() {
+            Some(TelematicsError::InvalidEvent(reason)) => DeliveryOutcome::DeadLetter(reason.clone()),
+            _ => DeliveryOutcome::Requeue(e.to_string()),
+        },
+    }
+}
+
+pub(crate) async fn settle_delivery(
+    channel: &Channel,
+    dead_letter_exchange: &str,
+    delivery: &Delivery,
+    outcome: DeliveryOutcome,
+) -> Result<(), lapin::Error> {
+    let requeue = BasicNackOptions { requeue: true, ..Default::default() };
+    match outcome {
+        DeliveryOutcome::Ack => delivery.ack(BasicAckOptions::default()).await,
+        DeliveryOutcome::Requeue(reason) => {
+            error!("Error processing message with key {}, requeueing: {}", delivery.routing_key, reason);
+            tokio::time::sleep(TRANSIENT_FAILURE_DELAY).await;
+            delivery.nack(requeue).await
+        }
+        DeliveryOutcome::DeadLetter(reason) => {
+            warn!("{}, routing to dead-letter exchange {}", reason, dead_letter_exchange);
+            match publish_dead_letter(channel, dead_letter_exchange, delivery.routing_key.as_str(), &delivery.data, &reason).await {
+                Ok(()) => delivery.ack(BasicAckOptions::default()).await,
+                Err(e) => {
+                    error!("Failed to dead-letter message, requeueing: {}", e);
+                    delivery.nack(requeue).await
+                }
+            }
+        }
+    }
+}
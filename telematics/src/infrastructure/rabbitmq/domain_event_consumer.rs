@@ -0,0 +1,229 @@
+use async_trait::async_trait;
+use lapin::{
+    options::{BasicConsumeOptions, BasicQosOptions},
+    types::FieldTable,
+    Channel, Connection, ConnectionProperties,
+    message::Delivery,
+};
+use futures_util::StreamExt;
+use std::time::Duration;
+use tracing::{info, error, warn};
+use crate::domain::{
+    interfaces::DomainEventConsumer,
+    models::DomainEvent,
+};
+use super::{declare_dead_letter_topology, process_delivery, settle_delivery};
+
+pub(crate) const EVENTS_EXCHANGE: &str = "zdrive_events";
+// Общая очередь: несколько экземпляров telematics разбирают ее как конкурирующие consumers
+const EVENTS_QUEUE: &str = "telematics_domain_events";
+const PREFETCH_COUNT: u16 = 20;
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+// Отдельно от сенсоров: переотправка из dead-letter API публикует в exchange сенсоров
+pub(crate) const EVENTS_DEAD_LETTER_EXCHANGE: &str = "telematics_domain_events_dlx";
+const EVENTS_DEAD_LETTER_QUEUE: &str = "telematics_domain_events_dead_letter";
+
+fn parse_domain_event(data: &[u8]) -> Result<DomainEvent, String> {
+    serde_json::from_slice(data).map_err(|e| format!("Failed to deserialize domain event: {}", e))
+}
+
+pub struct RabbitMQDomainEventConsumerImpl {
+    amqp_url: String,
+}
+
+impl RabbitMQDomainEventConsumerImpl {
+    // Подключение восстанавливается при обрыве
+    pub fn new(amqp_url: &str) -> Self {
+        Self {
+            amqp_url: amqp_url.to_string(),
+        }
+    }
+
+    async fn connect(&self) -> Result<(Connection, Channel), anyhow::Error> {
+        let connection = Connection::connect(&self.amqp_url, ConnectionProperties::default()).await?;
+        let channel = connection.create_channel().await?;
+        Ok((connection, channel))
+    }
+
+    async fn setup_exchange_and_queue(&self, channel: &Channel, binding_key: &str) -> Result<(), anyhow::Error> {
+        declare_dead_letter_topology(channel, EVENTS_DEAD_LETTER_EXCHANGE, EVENTS_DEAD_LETTER_QUEUE).await?;
+
+        channel
+            .exchange_declare(
+                EVENTS_EXCHANGE,
+                lapin::ExchangeKind::Topic,
+                lapin::options::ExchangeDeclareOptions {
+                    durable: true,
+                    ..Default::default()
+                },
+                FieldTable::default(),
+            )
+            .await?;
+
+        channel
+            .queue_declare(
+                EVENTS_QUEUE,
+                lapin::options::QueueDeclareOptions {
+                    durable: true,
+                    ..Default::default()
+                },
+                FieldTable::default(),
+            )
+            .await?;
+
+        channel
+            .queue_bind(
+                EVENTS_QUEUE,
+                EVENTS_EXCHANGE,
+                binding_key,
+                lapin::options::QueueBindOptions::default(),
+                FieldTable::default(),
+            )
+            .await?;
+
+        info!("Queue {} bound to exchange {} with key {}", EVENTS_QUEUE, EVENTS_EXCHANGE, binding_key);
+        Ok(())
+    }
+
+    // Возвращается, когда соединение оборвалось
+    async fn consume_once<F>(&self, binding_key: &str, callback: &F) -> Result<(), anyhow::Error>
+    where
+        F: Fn(DomainEvent) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), anyhow::Error>> + Send>> + Send + Sync + 'static,
+    {
+        // Соединение должно жить, пока идет потребление
+        let (_connection, channel) = self.connect().await?;
+        self.setup_exchange_and_queue(&channel, binding_key).await?;
+
+        channel
+            .basic_qos(PREFETCH_COUNT, BasicQosOptions::default())
+            .await?;
+
+        let mut consumer = channel
+            .basic_consume(
+                EVENTS_QUEUE,
+                "telematics_domain_event_consumer",
+                BasicConsumeOptions::default(),
+                FieldTable::default(),
+            )
+            .await?;
+
+        info!("Started consuming from queue: {}", EVENTS_QUEUE);
+
+        while let Some(delivery_result) = consumer.next().await {
+            let delivery: Delivery = delivery_result?;
+
+            let outcome = process_delivery(parse_domain_event(&delivery.data), callback).await;
+            let result = settle_delivery(&channel, EVENTS_DEAD_LETTER_EXCHANGE, &delivery, outcome).await;
+
+            if let Err(e) = result {
+                error!("Failed to acknowledge delivery: {}", e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DomainEventConsumer for RabbitMQDomainEventConsumerImpl {
+    // Переподключается с экспоненциальной задержкой
+    async fn consume_events<F>(&self, binding_key: &str, callback: F) -> Result<(), anyhow::Error>
+    where
+        F: Fn(DomainEvent) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), anyhow::Error>> + Send>> + Send + Sync + 'static,
+    {
+        let mut delay = INITIAL_RECONNECT_DELAY;
+        loop {
+            let started = std::time::Instant::now();
+            match self.consume_once(binding_key, &callback).await {
+                Ok(()) => warn!("Domain event consumer stream closed"),
+                Err(e) => error!("Domain event consumer error: {}", e),
+            }
+
+            // Если сеанс продержался дольше максимальной задержки, начинаем отсчет заново
+            if started.elapsed() > MAX_RECONNECT_DELAY {
+                delay = INITIAL_RECONNECT_DELAY;
+            }
+
+            warn!("Reconnecting to RabbitMQ in {:?}", delay);
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use crate::domain::errors::TelematicsError;
+    use crate::infrastructure::rabbitmq::{DeliveryCallback, DeliveryOutcome};
+
+    fn event_json() -> Vec<u8> {
+        serde_json::to_vec(&serde_json::json!({
+            "event_id": uuid::Uuid::new_v4(),
+            "event_type": "CarUpdated",
+            "source": "cars",
+            "aggregate_type": "car",
+            "aggregate_id": uuid::Uuid::new_v4(),
+            "occurred_at": chrono::Utc::now(),
+            "payload": {},
+        }))
+        .unwrap()
+    }
+
+    // Обработчик возвращает заданную ошибку и запоминает полученные события
+    fn callback(
+        error: fn() -> Option<anyhow::Error>,
+        received: Arc<Mutex<Vec<String>>>,
+    ) -> Box<DeliveryCallback<DomainEvent>> {
+        Box::new(move |event: DomainEvent| {
+            received.lock().unwrap().push(event.event_type);
+            Box::pin(async move { error().map_or(Ok(()), Err) })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_applied_event_is_acked() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let callback = callback(|| None, received.clone());
+
+        let outcome = process_delivery(parse_domain_event(&event_json()), callback.as_ref()).await;
+
+        assert_eq!(outcome, DeliveryOutcome::Ack);
+        assert_eq!(*received.lock().unwrap(), vec!["CarUpdated"]);
+    }
+
+    #[tokio::test]
+    async fn test_unparsable_event_is_dead_lettered() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let callback = callback(|| None, received.clone());
+
+        for data in [&b"not json"[..], br#"{"event_type":"CarUpdated"}"#, &[0xff, 0xfe]] {
+            let outcome = process_delivery(parse_domain_event(data), callback.as_ref()).await;
+            assert!(matches!(outcome, DeliveryOutcome::DeadLetter(reason) if reason.starts_with("Failed to deserialize domain event")));
+        }
+        assert!(received.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_event_is_dead_lettered() {
+        let callback = callback(
+            || Some(TelematicsError::InvalidEvent("CarUpdated has invalid payload".to_string()).into()),
+            Arc::default(),
+        );
+
+        let outcome = process_delivery(parse_domain_event(&event_json()), callback.as_ref()).await;
+
+        assert_eq!(outcome, DeliveryOutcome::DeadLetter("CarUpdated has invalid payload".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_failed_processing_is_requeued() {
+        let callback = callback(|| Some(anyhow::anyhow!("redis unavailable")), Arc::default());
+
+        let outcome = process_delivery(parse_domain_event(&event_json()), callback.as_ref()).await;
+
+        assert_eq!(outcome, DeliveryOutcome::Requeue("redis unavailable".to_string()));
+    }
+}
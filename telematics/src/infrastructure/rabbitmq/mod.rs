@@ -1,6 +1,11 @@
 mod publisher;
 mod consumer;
+mod dead_letter_queue;
+mod delivery;
+mod domain_event_consumer;
 
 pub use publisher::*;
 pub use consumer::*;
-
+pub use dead_letter_queue::*;
+pub(crate) use delivery::*;
+pub use domain_event_consumer::*;
@@ -1,4 +1,5 @@
 mod redis_repository;
+mod sensor_history_repository;
 
 pub use redis_repository::*;
-
+pub use sensor_history_repository::*;
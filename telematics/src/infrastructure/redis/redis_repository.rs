@@ -2,13 +2,21 @@ use async_trait::async_trait;
 use redis::aio::ConnectionManager;
 use redis::AsyncCommands;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 use tracing::{info, error};
 use crate::domain::{
     errors::TelematicsError,
     interfaces::RedisRepository,
-    models::SensorData,
+    models::{SensorData, CarRegistryEntry},
 };
+use uuid::Uuid;
+
+// Hash car_id -> VIN, синхронизируется из cars сервиса
+const CAR_REGISTRY_KEY: &str = "car_registry";
+const CAR_REGISTRY_STAGING_KEY: &str = "car_registry:staging";
+// Отметки обработанных доменных событий: processed_event:<event_id>
+const PROCESSED_EVENT_KEY_PREFIX: &str = "processed_event";
 
 pub struct RedisRepositoryImpl {
     connection: Arc<Mutex<ConnectionManager>>,
@@ -35,7 +43,7 @@ impl Clone for RedisRepositoryImpl {
 
 impl RedisRepositoryImpl {
     // Вспомогательная функция для парсинга данных из hash
-    fn parse_sensor_data_from_hash(
+    pub(super) fn parse_sensor_data_from_hash(
         data: &std::collections::HashMap<String, String>,
     ) -> Result<SensorData, TelematicsError> {
         let vin_value = data.get("vin").ok_or_else(|| TelematicsError::InvalidData("Missing vin".to_string()))?;
@@ -103,13 +111,13 @@ impl RedisRepository for RedisRepositoryImpl {
         // Используем несколько HSET вызовов для установки всех полей
         let _: () = conn.hset(&hash_key, "vin", vin).await?;
         let _: () = conn.hset(&hash_key, "license_plate", &sensor_data.license_plate).await?;
-        let _: () = conn.hset(&hash_key, "fuel_level", &sensor_data.fuel_level.to_string()).await?;
-        let _: () = conn.hset(&hash_key, "location_latitude", &sensor_data.location.latitude.to_string()).await?;
-        let _: () = conn.hset(&hash_key, "location_longitude", &sensor_data.location.longitude.to_string()).await?;
+        let _: () = conn.hset(&hash_key, "fuel_level", sensor_data.fuel_level.to_string()).await?;
+        let _: () = conn.hset(&hash_key, "location_latitude", sensor_data.location.latitude.to_string()).await?;
+        let _: () = conn.hset(&hash_key, "location_longitude", sensor_data.location.longitude.to_string()).await?;
         let _: () = conn.hset(&hash_key, "door_status", sensor_data.door_status.as_str()).await?;
-        let _: () = conn.hset(&hash_key, "speed", &sensor_data.speed.to_string()).await?;
-        let _: () = conn.hset(&hash_key, "temperature", &sensor_data.temperature.to_string()).await?;
-        let _: () = conn.hset(&hash_key, "timestamp", &sensor_data.timestamp.to_rfc3339()).await?;
+        let _: () = conn.hset(&hash_key, "speed", sensor_data.speed.to_string()).await?;
+        let _: () = conn.hset(&hash_key, "temperature", sensor_data.temperature.to_string()).await?;
+        let _: () = conn.hset(&hash_key, "timestamp", sensor_data.timestamp.to_rfc3339()).await?;
         
         info!("Sensor data saved for VIN: {}", vin);
         Ok(())
@@ -184,16 +192,16 @@ impl RedisRepository for RedisRepositoryImpl {
                     .hget(&hash_key, "license_plate")
                     .await?;
 
-                if let Some(stored) = stored_license_plate {
-                    if stored == license_plate {
-                        // Нашли совпадение, получаем полные данные
-                        match self.get_sensor_data(vin).await {
-                            Ok(Some(sensor_data)) => return Ok(Some((vin.to_string(), sensor_data))),
-                            Ok(None) => continue,
-                            Err(e) => {
-                                error!("Failed to get sensor data for VIN {}: {:?}", vin, e);
-                                continue;
-                            }
+                if let Some(stored) = stored_license_plate
+                    && stored == license_plate
+                {
+                    // Нашли совпадение, получаем полные данные
+                    match self.get_sensor_data(vin).await {
+                        Ok(Some(sensor_data)) => return Ok(Some((vin.to_string(), sensor_data))),
+                        Ok(None) => continue,
+                        Err(e) => {
+                            error!("Failed to get sensor data for VIN {}: {:?}", vin, e);
+                            continue;
                         }
                     }
                 }
@@ -202,5 +210,62 @@ impl RedisRepository for RedisRepositoryImpl {
 
         Ok(None)
     }
-}
 
+    async fn replace_car_registry(&self, entries: &[CarRegistryEntry]) -> Result<(), TelematicsError> {
+        let mut conn = self.connection.lock().await;
+
+        if entries.is_empty() {
+            let _: () = conn.del(CAR_REGISTRY_KEY).await?;
+            return Ok(());
+        }
+
+        // Собираем новый реестр во временном ключе и атомарно подменяем старый,
+        // чтобы читатели не увидели частично заполненный реестр
+        let fields: Vec<(String, &str)> = entries
+            .iter()
+            .map(|entry| (entry.car_id.to_string(), entry.vin.as_str()))
+            .collect();
+        let _: () = redis::pipe()
+            .atomic()
+            .del(CAR_REGISTRY_STAGING_KEY)
+            .hset_multiple(CAR_REGISTRY_STAGING_KEY, &fields)
+            .rename(CAR_REGISTRY_STAGING_KEY, CAR_REGISTRY_KEY)
+            .query_async(&mut *conn)
+            .await?;
+
+        info!("Car registry replaced: {} cars", entries.len());
+        Ok(())
+    }
+
+    async fn get_vin_by_car_id(&self, car_id: Uuid) -> Result<Option<String>, TelematicsError> {
+        let mut conn = self.connection.lock().await;
+        let vin: Option<String> = conn.hget(CAR_REGISTRY_KEY, car_id.to_string()).await?;
+        Ok(vin)
+    }
+
+    async fn upsert_car_registry_entry(&self, entry: &CarRegistryEntry) -> Result<(), TelematicsError> {
+        let mut conn = self.connection.lock().await;
+        let _: () = conn.hset(CAR_REGISTRY_KEY, entry.car_id.to_string(), &entry.vin).await?;
+        Ok(())
+    }
+
+    async fn remove_car_registry_entry(&self, car_id: Uuid) -> Result<(), TelematicsError> {
+        let mut conn = self.connection.lock().await;
+        let _: () = conn.hdel(CAR_REGISTRY_KEY, car_id.to_string()).await?;
+        Ok(())
+    }
+
+    async fn is_event_processed(&self, event_id: Uuid) -> Result<bool, TelematicsError> {
+        let mut conn = self.connection.lock().await;
+        let processed: bool = conn.exists(format!("{}:{}", PROCESSED_EVENT_KEY_PREFIX, event_id)).await?;
+        Ok(processed)
+    }
+
+    async fn mark_event_processed(&self, event_id: Uuid, ttl: Duration) -> Result<(), TelematicsError> {
+        let mut conn = self.connection.lock().await;
+        let _: () = conn
+            .set_ex(format!("{}:{}", PROCESSED_EVENT_KEY_PREFIX, event_id), 1, ttl.as_secs())
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use redis::streams::StreamRangeReply;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::error;
+use crate::domain::{
+    errors::TelematicsError,
+    interfaces::SensorHistoryRepository,
+    models::{SensorData, SensorHistoryPage},
+};
+use super::RedisRepositoryImpl;
+
+// История хранится в Redis Stream на каждую машину: XADD sensor_history:{vin} * field1 value1 ...
+// Идентификаторы записей - время поступления показания в миллисекундах
+fn history_key(vin: &str) -> String {
+    format!("sensor_history:{}", vin)
+}
+
+// Наименьший идентификатор после id: XRANGE включает границу, а "(" поддерживается не всеми версиями Redis
+fn next_stream_id(id: &str) -> Result<String, TelematicsError> {
+    let (millis, sequence) = id
+        .split_once('-')
+        .and_then(|(millis, sequence)| Some((millis.parse::<u64>().ok()?, sequence.parse::<u64>().ok()?)))
+        .ok_or_else(|| TelematicsError::InvalidData(format!("invalid stream id: {}", id)))?;
+
+    Ok(match sequence.checked_add(1) {
+        Some(sequence) => format!("{}-{}", millis, sequence),
+        None => format!("{}-0", millis + 1),
+    })
+}
+
+pub struct RedisSensorHistoryRepositoryImpl {
+    connection: Arc<Mutex<ConnectionManager>>,
+    retention: chrono::Duration,
+}
+
+impl RedisSensorHistoryRepositoryImpl {
+    pub async fn new(redis_url: &str, retention: chrono::Duration) -> Result<Self, anyhow::Error> {
+        let client = redis::Client::open(redis_url)?;
+        let connection = ConnectionManager::new(client).await?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+            retention,
+        })
+    }
+}
+
+impl Clone for RedisSensorHistoryRepositoryImpl {
+    fn clone(&self) -> Self {
+        Self {
+            connection: Arc::clone(&self.connection),
+            retention: self.retention,
+        }
+    }
+}
+
+#[async_trait]
+impl SensorHistoryRepository for RedisSensorHistoryRepositoryImpl {
+    async fn append(&self, vin: &str, sensor_data: &SensorData) -> Result<(), TelematicsError> {
+        let key = history_key(vin);
+        let fields: Vec<(&str, String)> = vec![
+            ("vin", vin.to_string()),
+            ("license_plate", sensor_data.license_plate.clone()),
+            ("fuel_level", sensor_data.fuel_level.to_string()),
+            ("location_latitude", sensor_data.location.latitude.to_string()),
+            ("location_longitude", sensor_data.location.longitude.to_string()),
+            ("door_status", sensor_data.door_status.as_str().to_string()),
+            ("speed", sensor_data.speed.to_string()),
+            ("temperature", sensor_data.temperature.to_string()),
+            ("timestamp", sensor_data.timestamp.to_rfc3339()),
+        ];
+
+        // Записи старше срока хранения обрезаются при каждой вставке (приблизительно, MINID ~),
+        // а поток машины, которая перестала слать данные, удаляется целиком по TTL
+        let min_id = (chrono::Utc::now() - self.retention).timestamp_millis();
+        let mut conn = self.connection.lock().await;
+        let _: () = redis::pipe()
+            .cmd("XADD")
+            .arg(&key)
+            .arg("MINID")
+            .arg("~")
+            .arg(min_id)
+            .arg("*")
+            .arg(&fields)
+            .ignore()
+            .expire(&key, self.retention.num_seconds())
+            .ignore()
+            .query_async(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_history_page(
+        &self,
+        vin: &str,
+        arrived_from: chrono::DateTime<chrono::Utc>,
+        arrived_to: chrono::DateTime<chrono::Utc>,
+        cursor: Option<String>,
+        count: usize,
+    ) -> Result<SensorHistoryPage, TelematicsError> {
+        let start = match cursor {
+            Some(cursor) => next_stream_id(&cursor)?,
+            None => arrived_from.timestamp_millis().to_string(),
+        };
+
+        // Блокировка держится только на время одной страницы
+        let reply: StreamRangeReply = {
+            let mut conn = self.connection.lock().await;
+            conn.xrange_count(history_key(vin), start, arrived_to.timestamp_millis(), count)
+                .await?
+        };
+
+        let next_cursor = if reply.ids.len() >= count {
+            reply.ids.last().map(|entry| entry.id.clone())
+        } else {
+            None
+        };
+
+        let mut readings = Vec::with_capacity(reply.ids.len());
+        for entry in reply.ids {
+            let mut data = std::collections::HashMap::with_capacity(entry.map.len());
+            for (field, value) in &entry.map {
+                data.insert(field.clone(), redis::from_redis_value::<String>(value)?);
+            }
+
+            match RedisRepositoryImpl::parse_sensor_data_from_hash(&data) {
+                Ok(sensor_data) => readings.push(sensor_data),
+                Err(e) => {
+                    error!("Failed to parse history entry {} for VIN {}: {:?}", entry.id, vin, e);
+                }
+            }
+        }
+
+        Ok(SensorHistoryPage { readings, next_cursor })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_stream_id_increments_sequence() {
+        assert_eq!(next_stream_id("1700000000000-0").unwrap(), "1700000000000-1");
+        assert_eq!(next_stream_id("1700000000000-41").unwrap(), "1700000000000-42");
+    }
+
+    #[test]
+    fn test_next_stream_id_overflows_into_next_millisecond() {
+        let id = format!("1700000000000-{}", u64::MAX);
+        assert_eq!(next_stream_id(&id).unwrap(), "1700000000001-0");
+    }
+
+    #[test]
+    fn test_next_stream_id_rejects_malformed_id() {
+        assert!(next_stream_id("1700000000000").is_err());
+        assert!(next_stream_id("abc-0").is_err());
+    }
+}
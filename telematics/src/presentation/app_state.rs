@@ -1,30 +1,40 @@
 use std::sync::Arc;
 use crate::{
     application::use_cases::{
-        SendCommandUseCase, GetSensorDataUseCase,
+        SendCommandUseCase, GetSensorDataUseCase, ListDeadLettersUseCase, ReplayDeadLettersUseCase,
+        GetSensorHistoryUseCase,
     },
-    domain::interfaces::{RabbitMQPublisher, RedisRepository},
+    domain::interfaces::{RabbitMQPublisher, RedisRepository, DeadLetterQueue, SensorHistoryRepository},
 };
 
-pub struct AppState<P, R>
+pub struct AppState<P, R, D, H>
 where
     P: RabbitMQPublisher + Send + Sync + 'static,
     R: RedisRepository + Send + Sync + 'static,
+    D: DeadLetterQueue + Send + Sync + 'static,
+    H: SensorHistoryRepository + Send + Sync + 'static,
 {
     pub send_command_use_case: Arc<SendCommandUseCase<P>>,
     pub get_sensor_data_use_case: Arc<GetSensorDataUseCase<R>>,
+    pub list_dead_letters_use_case: Arc<ListDeadLettersUseCase<D>>,
+    pub replay_dead_letters_use_case: Arc<ReplayDeadLettersUseCase<D>>,
+    pub get_sensor_history_use_case: Arc<GetSensorHistoryUseCase<H>>,
 }
 
-impl<P, R> Clone for AppState<P, R>
+impl<P, R, D, H> Clone for AppState<P, R, D, H>
 where
     P: RabbitMQPublisher + Send + Sync + 'static,
     R: RedisRepository + Send + Sync + 'static,
+    D: DeadLetterQueue + Send + Sync + 'static,
+    H: SensorHistoryRepository + Send + Sync + 'static,
 {
     fn clone(&self) -> Self {
         Self {
             send_command_use_case: Arc::clone(&self.send_command_use_case),
             get_sensor_data_use_case: Arc::clone(&self.get_sensor_data_use_case),
+            list_dead_letters_use_case: Arc::clone(&self.list_dead_letters_use_case),
+            replay_dead_letters_use_case: Arc::clone(&self.replay_dead_letters_use_case),
+            get_sensor_history_use_case: Arc::clone(&self.get_sensor_history_use_case),
         }
     }
 }
-
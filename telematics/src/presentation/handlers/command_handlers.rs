@@ -21,13 +21,15 @@ pub struct SendCommandResponse {
     pub command_id: Uuid,
 }
 
-pub async fn send_command_handler<P, R>(
-    State(state): State<AppState<P, R>>,
+pub async fn send_command_handler<P, R, D, H>(
+    State(state): State<AppState<P, R, D, H>>,
     Json(request): Json<SendCommandRequest>,
 ) -> Result<Json<SendCommandResponse>, (StatusCode, Json<serde_json::Value>)>
 where
     P: crate::domain::interfaces::RabbitMQPublisher + Send + Sync + 'static,
     R: crate::domain::interfaces::RedisRepository + Send + Sync + 'static,
+    D: crate::domain::interfaces::DeadLetterQueue + Send + Sync + 'static,
+    H: crate::domain::interfaces::SensorHistoryRepository + Send + Sync + 'static,
 {
     info!("Sending command {} to car {}", request.command_type, request.car_id);
     
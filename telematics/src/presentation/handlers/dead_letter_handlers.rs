@@ -0,0 +1,121 @@
+use axum::{
+    extract::{State, Query},
+    http::StatusCode,
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{info, error};
+use crate::presentation::app_state::AppState;
+use crate::domain::errors::TelematicsError;
+
+const DEFAULT_DEAD_LETTER_LIMIT: usize = 100;
+
+fn default_limit() -> usize {
+    DEFAULT_DEAD_LETTER_LIMIT
+}
+
+#[derive(Deserialize)]
+pub struct ListDeadLettersQuery {
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+#[derive(Serialize)]
+pub struct DeadLetterResponse {
+    pub message_id: String,
+    pub routing_key: String,
+    pub error: String,
+    pub payload: String,
+    pub dead_lettered_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<crate::domain::models::DeadLetter> for DeadLetterResponse {
+    fn from(dead_letter: crate::domain::models::DeadLetter) -> Self {
+        Self {
+            message_id: dead_letter.message_id,
+            routing_key: dead_letter.routing_key,
+            error: dead_letter.error,
+            payload: dead_letter.payload,
+            dead_lettered_at: dead_letter.dead_lettered_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ReplayDeadLettersRequest {
+    // Без message_ids переотправляются все сообщения в пределах limit
+    pub message_ids: Option<Vec<String>>,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+#[derive(Serialize)]
+pub struct ReplayDeadLettersResponse {
+    pub replayed: usize,
+}
+
+pub async fn list_dead_letters_handler<P, R, D, H>(
+    State(state): State<AppState<P, R, D, H>>,
+    Query(query): Query<ListDeadLettersQuery>,
+) -> Result<Json<Vec<DeadLetterResponse>>, (StatusCode, Json<serde_json::Value>)>
+where
+    P: crate::domain::interfaces::RabbitMQPublisher + Send + Sync + 'static,
+    R: crate::domain::interfaces::RedisRepository + Send + Sync + 'static,
+    D: crate::domain::interfaces::DeadLetterQueue + Send + Sync + 'static,
+    H: crate::domain::interfaces::SensorHistoryRepository + Send + Sync + 'static,
+{
+    info!("Listing dead-lettered sensor messages (limit: {})", query.limit);
+    match state.list_dead_letters_use_case.execute(query.limit).await {
+        Ok(dead_letters) => {
+            info!("Retrieved {} dead-lettered messages", dead_letters.len());
+            Ok(Json(dead_letters.into_iter().map(DeadLetterResponse::from).collect()))
+        }
+        Err(TelematicsError::RabbitMQConsumeError(e)) => {
+            error!("Failed to read dead-letter queue: {}", e);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": "Dead-letter queue unavailable"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error listing dead letters: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
+
+pub async fn replay_dead_letters_handler<P, R, D, H>(
+    State(state): State<AppState<P, R, D, H>>,
+    Json(request): Json<ReplayDeadLettersRequest>,
+) -> Result<Json<ReplayDeadLettersResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    P: crate::domain::interfaces::RabbitMQPublisher + Send + Sync + 'static,
+    R: crate::domain::interfaces::RedisRepository + Send + Sync + 'static,
+    D: crate::domain::interfaces::DeadLetterQueue + Send + Sync + 'static,
+    H: crate::domain::interfaces::SensorHistoryRepository + Send + Sync + 'static,
+{
+    info!("Replaying dead-lettered sensor messages (limit: {})", request.limit);
+    match state.replay_dead_letters_use_case.execute(request.message_ids, request.limit).await {
+        Ok(replayed) => {
+            info!("Replayed {} dead-lettered messages", replayed);
+            Ok(Json(ReplayDeadLettersResponse { replayed }))
+        }
+        Err(TelematicsError::RabbitMQConsumeError(e)) => {
+            error!("Failed to read dead-letter queue: {}", e);
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": "Dead-letter queue unavailable"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error replaying dead letters: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
@@ -1,6 +1,7 @@
 mod command_handlers;
 mod sensor_handlers;
+mod dead_letter_handlers;
 
 pub use command_handlers::*;
 pub use sensor_handlers::*;
-
+pub use dead_letter_handlers::*;
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use tracing::{info, error, warn};
 use crate::presentation::app_state::AppState;
+use crate::domain::errors::TelematicsError;
 
 #[derive(Serialize)]
 pub struct SensorDataResponse {
@@ -49,13 +50,15 @@ pub struct GetSensorDataQuery {
     pub vin: Option<String>,
 }
 
-pub async fn get_sensor_data_handler<P, R>(
-    State(state): State<AppState<P, R>>,
+pub async fn get_sensor_data_handler<P, R, D, H>(
+    State(state): State<AppState<P, R, D, H>>,
     Query(params): Query<GetSensorDataQuery>,
 ) -> Result<Json<Vec<SensorDataResponse>>, (StatusCode, Json<serde_json::Value>)>
 where
     P: crate::domain::interfaces::RabbitMQPublisher + Send + Sync + 'static,
     R: crate::domain::interfaces::RedisRepository + Send + Sync + 'static,
+    D: crate::domain::interfaces::DeadLetterQueue + Send + Sync + 'static,
+    H: crate::domain::interfaces::SensorHistoryRepository + Send + Sync + 'static,
 {
     info!("Getting sensor data, VIN: {:?}", params.vin);
     match state.get_sensor_data_use_case.execute(params.vin.as_deref()).await {
@@ -85,37 +88,61 @@ pub struct SensorDataWithVin {
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
-pub async fn get_sensor_data_by_car_id_handler<P, R>(
-    State(_state): State<AppState<P, R>>,
+pub async fn get_sensor_data_by_car_id_handler<P, R, D, H>(
+    State(state): State<AppState<P, R, D, H>>,
     Path(car_id): Path<Uuid>,
 ) -> Result<Json<Option<SensorDataWithVin>>, (StatusCode, Json<serde_json::Value>)>
 where
     P: crate::domain::interfaces::RabbitMQPublisher + Send + Sync + 'static,
     R: crate::domain::interfaces::RedisRepository + Send + Sync + 'static,
+    D: crate::domain::interfaces::DeadLetterQueue + Send + Sync + 'static,
+    H: crate::domain::interfaces::SensorHistoryRepository + Send + Sync + 'static,
 {
     info!("Getting sensor data by car_id: {}", car_id);
-    
-    // Примечание: В реальном приложении здесь нужно сделать запрос к cars сервису
-    // для получения license_plate по car_id, а затем искать в Redis по license_plate.
-    // Для MVP пока возвращаем ошибку с подсказкой.
-    
-    warn!("get_sensor_data_by_car_id: car_id lookup requires integration with cars service. Use /sensors?vin=... or /sensors/all instead");
-    Err((
-        StatusCode::NOT_IMPLEMENTED,
-        Json(serde_json::json!({
-            "error": "Car ID lookup requires integration with cars service",
-            "message": "To get sensor data by car_id, first get license_plate from cars service, then use /sensors?vin={vin} or /sensors/all",
-            "alternative": "Use /sensors/all to get all sensor data and filter by car_id on client side"
-        })),
-    ))
+    match state.get_sensor_data_use_case.execute_by_car_id(car_id).await {
+        Ok(Some((vin, data))) => {
+            info!("Sensor data retrieved for car {} (VIN: {})", car_id, vin);
+            let sensor_response: SensorDataResponse = data.into();
+            Ok(Json(Some(SensorDataWithVin {
+                vin,
+                license_plate: sensor_response.license_plate,
+                fuel_level: sensor_response.fuel_level,
+                location: sensor_response.location,
+                door_status: sensor_response.door_status,
+                speed: sensor_response.speed,
+                temperature: sensor_response.temperature,
+                timestamp: sensor_response.timestamp,
+            })))
+        }
+        Ok(None) => {
+            info!("No sensor data received yet for car {}", car_id);
+            Ok(Json(None))
+        }
+        Err(TelematicsError::CarNotFound) => {
+            warn!("Car {} not found in car registry", car_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Car not found"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting sensor data by car_id: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
 }
 
-pub async fn get_all_sensor_data_handler<P, R>(
-    State(state): State<AppState<P, R>>,
+pub async fn get_all_sensor_data_handler<P, R, D, H>(
+    State(state): State<AppState<P, R, D, H>>,
 ) -> Result<Json<Vec<SensorDataWithVin>>, (StatusCode, Json<serde_json::Value>)>
 where
     P: crate::domain::interfaces::RabbitMQPublisher + Send + Sync + 'static,
     R: crate::domain::interfaces::RedisRepository + Send + Sync + 'static,
+    D: crate::domain::interfaces::DeadLetterQueue + Send + Sync + 'static,
+    H: crate::domain::interfaces::SensorHistoryRepository + Send + Sync + 'static,
 {
     info!("Getting all sensor data");
     match state.get_sensor_data_use_case.execute(None).await {
@@ -149,3 +176,80 @@ where
     }
 }
 
+
+const DEFAULT_HISTORY_WINDOW_HOURS: i64 = 1;
+
+#[derive(Deserialize)]
+pub struct GetSensorHistoryQuery {
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    // Без resolution возвращаются все показания
+    pub resolution: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct SensorHistoryPointResponse {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub samples: usize,
+    pub fuel_level: f64,
+    pub location: LocationResponse,
+    pub door_status: String,
+    pub speed: f64,
+    pub max_speed: f64,
+    pub temperature: f64,
+}
+
+impl From<crate::domain::models::SensorHistoryPoint> for SensorHistoryPointResponse {
+    fn from(point: crate::domain::models::SensorHistoryPoint) -> Self {
+        Self {
+            timestamp: point.timestamp,
+            samples: point.samples,
+            fuel_level: point.fuel_level,
+            location: LocationResponse {
+                latitude: point.location.latitude,
+                longitude: point.location.longitude,
+            },
+            door_status: point.door_status.as_str().to_string(),
+            speed: point.speed,
+            max_speed: point.max_speed,
+            temperature: point.temperature,
+        }
+    }
+}
+
+pub async fn get_sensor_history_handler<P, R, D, H>(
+    State(state): State<AppState<P, R, D, H>>,
+    Path(vin): Path<String>,
+    Query(params): Query<GetSensorHistoryQuery>,
+) -> Result<Json<Vec<SensorHistoryPointResponse>>, (StatusCode, Json<serde_json::Value>)>
+where
+    P: crate::domain::interfaces::RabbitMQPublisher + Send + Sync + 'static,
+    R: crate::domain::interfaces::RedisRepository + Send + Sync + 'static,
+    D: crate::domain::interfaces::DeadLetterQueue + Send + Sync + 'static,
+    H: crate::domain::interfaces::SensorHistoryRepository + Send + Sync + 'static,
+{
+    let to = params.to.unwrap_or_else(chrono::Utc::now);
+    let from = params.from.unwrap_or(to - chrono::Duration::hours(DEFAULT_HISTORY_WINDOW_HOURS));
+    info!("Getting sensor history for VIN {}: {} - {}, resolution: {:?}", vin, from, to, params.resolution);
+
+    match state.get_sensor_history_use_case.execute(&vin, from, to, params.resolution).await {
+        Ok(points) => {
+            info!("Sensor history retrieved for VIN {}: {} points", vin, points.len());
+            Ok(Json(points.into_iter().map(|point| point.into()).collect()))
+        }
+        Err(TelematicsError::InvalidHistoryQuery(message)) => {
+            warn!("Invalid sensor history query: {}", message);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": message})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting sensor history: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
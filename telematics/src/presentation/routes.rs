@@ -6,10 +6,12 @@ use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing::info;
 use crate::presentation::{handlers::*, app_state::AppState};
 
-pub fn create_router<P, R>(app_state: AppState<P, R>) -> Router
+pub fn create_router<P, R, D, H>(app_state: AppState<P, R, D, H>) -> Router
 where
     P: crate::domain::interfaces::RabbitMQPublisher + Send + Sync + 'static,
     R: crate::domain::interfaces::RedisRepository + Send + Sync + 'static,
+    D: crate::domain::interfaces::DeadLetterQueue + Send + Sync + 'static,
+    H: crate::domain::interfaces::SensorHistoryRepository + Send + Sync + 'static,
 {
     info!("Setting up routes...");
     Router::new()
@@ -17,6 +19,9 @@ where
         .route("/sensors", get(get_sensor_data_handler))
         .route("/sensors/car/:car_id", get(get_sensor_data_by_car_id_handler))
         .route("/sensors/all", get(get_all_sensor_data_handler))
+        .route("/sensors/:vin/history", get(get_sensor_history_handler))
+        .route("/dead-letters", get(list_dead_letters_handler))
+        .route("/dead-letters/replay", post(replay_dead_letters_handler))
         .with_state(app_state)
         .layer(CorsLayer::permissive())
         .layer(TraceLayer::new_for_http())
@@ -0,0 +1,6 @@
+-- Migration: Enforce at most one open trip per car and per user
+-- Created: 2024-02-15
+
+-- Гарантия на уровне БД: конкурентные запросы на одну машину не создадут две поездки
+CREATE UNIQUE INDEX IF NOT EXISTS uq_trips_car_open ON trips(car_id) WHERE status IN ('reserved', 'active');
+CREATE UNIQUE INDEX IF NOT EXISTS uq_trips_user_open ON trips(user_id) WHERE status IN ('reserved', 'active');
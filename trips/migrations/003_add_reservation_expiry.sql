@@ -0,0 +1,13 @@
+-- Migration: Add reservation expiry
+-- Created: 2024-02-20
+
+-- Новый статус: резерв истек без активации
+ALTER TABLE trips DROP CONSTRAINT IF EXISTS trips_status_check;
+ALTER TABLE trips ADD CONSTRAINT trips_status_check CHECK (status IN ('reserved', 'active', 'completed', 'cancelled', 'expired'));
+
+-- Срок резерва и штраф за неявку фиксируются по тарифу машины при создании поездки
+ALTER TABLE trips ADD COLUMN IF NOT EXISTS expires_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE trips ADD COLUMN IF NOT EXISTS no_show_fee DOUBLE PRECISION NOT NULL DEFAULT 0;
+
+-- Индекс для фонового поиска истекших резервов
+CREATE INDEX IF NOT EXISTS idx_trips_reserved_expires_at ON trips(expires_at) WHERE status = 'reserved';
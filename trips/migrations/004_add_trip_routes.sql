@@ -0,0 +1,15 @@
+-- Migration: Add trip routes
+-- Created: 2024-03-05
+
+-- Пройденное расстояние; NULL, пока маршрут поездки не записан
+ALTER TABLE trips ADD COLUMN IF NOT EXISTS distance_km DOUBLE PRECISION;
+
+-- Полилиния маршрута поездки из телеметрии машины
+CREATE TABLE IF NOT EXISTS trip_route_points (
+    trip_id UUID NOT NULL REFERENCES trips(id) ON DELETE CASCADE,
+    seq INTEGER NOT NULL,
+    latitude DOUBLE PRECISION NOT NULL,
+    longitude DOUBLE PRECISION NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (trip_id, seq)
+);
@@ -0,0 +1,10 @@
+-- Migration: Store no-show fee as integer minor units with currency
+-- Created: 2024-03-12
+
+-- Штраф за неявку переводится из DOUBLE PRECISION (рубли) в BIGINT (копейки)
+ALTER TABLE trips ADD COLUMN IF NOT EXISTS currency VARCHAR(3) NOT NULL DEFAULT 'RUB' CHECK (currency IN ('RUB'));
+
+ALTER TABLE trips ALTER COLUMN no_show_fee DROP DEFAULT;
+ALTER TABLE trips ALTER COLUMN no_show_fee TYPE BIGINT USING round(no_show_fee * 100)::BIGINT;
+ALTER TABLE trips ALTER COLUMN no_show_fee SET DEFAULT 0;
+ALTER TABLE trips RENAME COLUMN no_show_fee TO no_show_fee_minor;
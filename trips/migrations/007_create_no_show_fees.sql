@@ -0,0 +1,22 @@
+-- Migration: Persist no-show fees for reliable delivery to billing
+-- Created: 2024-05-14
+
+-- Штраф записывается в одной транзакции с истечением резерва и отправляется в billing
+-- фоновым обработчиком с повторами, пока billing его не примет
+CREATE TABLE IF NOT EXISTS no_show_fees (
+    trip_id UUID PRIMARY KEY REFERENCES trips(id),
+    user_id UUID NOT NULL,
+    amount_minor BIGINT NOT NULL CHECK (amount_minor > 0),
+    currency VARCHAR(3) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    -- NULL, пока billing не подтвердил платеж
+    charged_at TIMESTAMP WITH TIME ZONE,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    locked_until TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_no_show_fees_pending
+    ON no_show_fees (next_attempt_at)
+    WHERE charged_at IS NULL;
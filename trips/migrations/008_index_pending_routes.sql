@@ -0,0 +1,7 @@
+-- Migration: Index trips waiting for their route
+-- Created: 2024-05-15
+
+-- Закрытые после активации поездки без записанного маршрута достраивает фоновый обработчик
+CREATE INDEX IF NOT EXISTS idx_trips_pending_route
+    ON trips (COALESCE(ended_at, cancelled_at))
+    WHERE status IN ('completed', 'cancelled') AND started_at IS NOT NULL AND distance_km IS NULL;
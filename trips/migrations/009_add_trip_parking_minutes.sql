@@ -0,0 +1,14 @@
+-- Migration: Add parking time to recorded trip routes
+-- Created: 2024-05-15
+
+-- Время стоянки в поездке записывается вместе с маршрутом; NULL, пока маршрут не записан
+ALTER TABLE trips ADD COLUMN IF NOT EXISTS parking_minutes INTEGER CHECK (parking_minutes >= 0);
+
+-- Поездки, записанные до учета стоянки, уже оплачены без нее
+UPDATE trips SET parking_minutes = 0 WHERE distance_km IS NOT NULL AND parking_minutes IS NULL;
+
+DROP INDEX IF EXISTS idx_trips_pending_route;
+CREATE INDEX IF NOT EXISTS idx_trips_pending_route
+    ON trips (COALESCE(ended_at, cancelled_at))
+    WHERE status IN ('completed', 'cancelled') AND started_at IS NOT NULL
+      AND (distance_km IS NULL OR parking_minutes IS NULL);
@@ -0,0 +1,23 @@
+-- Migration: Persist car state transitions for retries
+-- Created: 2024-05-16
+
+-- Перевод машины записывается до вызова cars. Если cars недоступен или процесс упал,
+-- перевод повторяет фоновый обработчик, пока cars его не примет
+CREATE TABLE IF NOT EXISTS car_transitions (
+    id UUID PRIMARY KEY,
+    -- Без внешнего ключа: при резерве перевод записывается до создания поездки
+    trip_id UUID NOT NULL,
+    car_id UUID NOT NULL,
+    from_state VARCHAR(20) NOT NULL,
+    to_state VARCHAR(20) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    done_at TIMESTAMP WITH TIME ZONE,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    locked_until TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_car_transitions_pending
+    ON car_transitions (next_attempt_at)
+    WHERE done_at IS NULL;
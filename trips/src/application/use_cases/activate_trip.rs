@@ -1,24 +1,32 @@
 use uuid::Uuid;
 use chrono::Utc;
+use tracing::{info, error};
 use crate::domain::{
     errors::TripError,
-    interfaces::TripRepository,
-    models::TripStatus,
+    interfaces::{TripRepository, CarTransitionRepository, CarsClient},
+    models::{TripStatus, CarState},
 };
+use super::car_transitions::{apply_car_transition, enqueue_car_transition, CarTransitionOutcome};
 
-pub struct ActivateTripUseCase<R> 
+pub struct ActivateTripUseCase<R, P, C> 
 where
     R: TripRepository,
+    P: CarTransitionRepository,
+    C: CarsClient,
 {
     repository: R,
+    transitions: P,
+    cars_client: C,
 }
 
-impl<R> ActivateTripUseCase<R>
+impl<R, P, C> ActivateTripUseCase<R, P, C>
 where
     R: TripRepository,
+    P: CarTransitionRepository,
+    C: CarsClient,
 {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+    pub fn new(repository: R, transitions: P, cars_client: C) -> Self {
+        Self { repository, transitions, cars_client }
     }
 
     pub async fn execute(&self, trip_id: Uuid) -> Result<(), TripError> {
@@ -33,12 +41,122 @@ where
             });
         }
 
-        // Обновляем статус и время начала
+        let previous = trip.clone();
+
+        // Обновляем статус и время начала.
+        // Условное обновление не даст активировать резерв, который параллельно истек или был отменен
         trip.status = TripStatus::Active;
         trip.started_at = Some(Utc::now());
 
-        self.repository.update(trip_id, &trip).await?;
-        Ok(())
+        let in_use = enqueue_car_transition(
+            &self.transitions, trip_id, trip.car_id, CarState::Reserved, CarState::InUse,
+        ).await?;
+
+        if !self.repository.update_if_status(trip_id, TripStatus::Reserved, &trip).await? {
+            // Если отметка не записалась, устаревший перевод отбросит фоновый обработчик
+            if let Err(e) = self.transitions.mark_done(in_use.id, Utc::now()).await {
+                error!("Failed to drop car transition {}: {:?}", in_use.id, e);
+            }
+            return Err(TripError::InvalidTripState);
+        }
+
+        // Машина уже закреплена за поездкой, переводим ее в использование.
+        // Недоступность cars не отменяет активацию: машина остается в резерве за пользователем,
+        // а перевод повторит фоновый обработчик
+        match apply_car_transition(&self.transitions, &self.cars_client, &in_use).await {
+            CarTransitionOutcome::Applied => Ok(()),
+            CarTransitionOutcome::Deferred => {
+                info!("Trip {} activated, car {} transition to in_use pending", trip_id, trip.car_id);
+                Ok(())
+            }
+            CarTransitionOutcome::Conflict(e) => {
+                // Компенсация: машину забрали из резерва, возвращаем поездку в резерв
+                if !self.repository.update_if_status(trip_id, TripStatus::Active, &previous).await? {
+                    error!("Trip {} changed status before revert to reserved", trip_id);
+                }
+                Err(e)
+            }
+        }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::use_cases::ApplyPendingCarTransitionsUseCase;
+    use crate::application::use_cases::car_transitions::tests::{
+        trip, InMemoryCarTransitionRepository, InMemoryTripRepository, MockCarsClient,
+    };
+    use crate::domain::models::Trip;
+
+    type TestUseCase = ActivateTripUseCase<InMemoryTripRepository, InMemoryCarTransitionRepository, MockCarsClient>;
+
+    fn setup(car_state: CarState) -> (TestUseCase, InMemoryTripRepository, InMemoryCarTransitionRepository, MockCarsClient, Trip) {
+        let reservation = trip(TripStatus::Reserved);
+        let repository = InMemoryTripRepository::default();
+        repository.insert(reservation.clone());
+        let transitions = InMemoryCarTransitionRepository::default();
+        let cars = MockCarsClient::with_car(reservation.car_id, car_state);
+        let use_case = ActivateTripUseCase::new(repository.clone(), transitions.clone(), cars.clone());
+        (use_case, repository, transitions, cars, reservation)
+    }
+
+    #[tokio::test]
+    async fn test_reservation_is_activated() {
+        let (use_case, repository, transitions, cars, reservation) = setup(CarState::Reserved);
+
+        use_case.execute(reservation.id).await.unwrap();
+
+        let trip = repository.get(reservation.id).unwrap();
+        assert_eq!(trip.status, TripStatus::Active);
+        assert!(trip.started_at.is_some());
+        assert_eq!(cars.state(reservation.car_id), CarState::InUse);
+        assert!(transitions.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_only_reserved_trip_can_be_activated() {
+        let (use_case, repository, _, cars, reservation) = setup(CarState::Reserved);
+        let mut expired = reservation.clone();
+        expired.status = TripStatus::Expired;
+        repository.insert(expired);
+
+        let result = use_case.execute(reservation.id).await;
+
+        assert!(matches!(result, Err(TripError::InvalidStatusTransition { .. })));
+        assert_eq!(cars.state(reservation.car_id), CarState::Reserved);
+    }
+
+    #[tokio::test]
+    async fn test_cars_failure_keeps_activation_and_retries_transition() {
+        let (use_case, repository, transitions, cars, reservation) = setup(CarState::Reserved);
+        cars.set_unavailable(true);
+
+        use_case.execute(reservation.id).await.unwrap();
+
+        assert_eq!(repository.get(reservation.id).unwrap().status, TripStatus::Active);
+        assert_eq!(cars.state(reservation.car_id), CarState::Reserved);
+        assert_eq!(transitions.pending()[0].transition.to, CarState::InUse);
+
+        cars.set_unavailable(false);
+        transitions.make_due();
+        let worker = ApplyPendingCarTransitionsUseCase::new(repository.clone(), transitions.clone(), cars.clone());
+        assert_eq!(worker.execute().await.unwrap(), 1);
+        assert_eq!(cars.state(reservation.car_id), CarState::InUse);
+    }
+
+    #[tokio::test]
+    async fn test_car_taken_out_of_reserve_reverts_activation() {
+        // Машину освободили в обход поездки, пока она стояла в резерве
+        let (use_case, repository, transitions, cars, reservation) = setup(CarState::Available);
+
+        let result = use_case.execute(reservation.id).await;
+
+        assert!(matches!(result, Err(TripError::CarStateConflict { .. })));
+        let trip = repository.get(reservation.id).unwrap();
+        assert_eq!(trip.status, TripStatus::Reserved);
+        assert!(trip.started_at.is_none());
+        assert_eq!(cars.state(reservation.car_id), CarState::Available);
+        assert!(transitions.pending().is_empty());
+    }
+}
@@ -2,23 +2,34 @@ use uuid::Uuid;
 use chrono::Utc;
 use crate::domain::{
     errors::TripError,
-    interfaces::TripRepository,
-    models::TripStatus,
+    interfaces::{TripRepository, CarTransitionRepository, CarsClient, TelematicsClient},
+    models::{TripStatus, CarState},
 };
+use super::end_trip::close_trip;
+use super::trip_routes::record_route_or_leave_pending;
 
-pub struct CancelTripUseCase<R> 
+pub struct CancelTripUseCase<R, P, C, T> 
 where
     R: TripRepository,
+    P: CarTransitionRepository,
+    C: CarsClient,
+    T: TelematicsClient,
 {
     repository: R,
+    transitions: P,
+    cars_client: C,
+    telematics_client: T,
 }
 
-impl<R> CancelTripUseCase<R>
+impl<R, P, C, T> CancelTripUseCase<R, P, C, T>
 where
     R: TripRepository,
+    P: CarTransitionRepository,
+    C: CarsClient,
+    T: TelematicsClient,
 {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+    pub fn new(repository: R, transitions: P, cars_client: C, telematics_client: T) -> Self {
+        Self { repository, transitions, cars_client, telematics_client }
     }
 
     pub async fn execute(&self, trip_id: Uuid) -> Result<(), TripError> {
@@ -26,19 +37,95 @@ where
             .ok_or(TripError::TripNotFound)?;
 
         // Можно отменять только зарезервированные или активные поездки
-        if trip.status != TripStatus::Reserved && trip.status != TripStatus::Active {
-            return Err(TripError::InvalidStatusTransition {
-                from: trip.status.as_str().to_string(),
-                to: TripStatus::Cancelled.as_str().to_string(),
-            });
-        }
+        let car_state = match trip.status {
+            TripStatus::Reserved => CarState::Reserved,
+            TripStatus::Active => CarState::InUse,
+            _ => {
+                return Err(TripError::InvalidStatusTransition {
+                    from: trip.status.as_str().to_string(),
+                    to: TripStatus::Cancelled.as_str().to_string(),
+                });
+            }
+        };
+
+        let previous_status = trip.status;
 
         // Обновляем статус и время отмены
         trip.status = TripStatus::Cancelled;
         trip.cancelled_at = Some(Utc::now());
 
-        self.repository.update(trip_id, &trip).await?;
+        close_trip(&self.repository, &self.transitions, &self.cars_client, &trip, previous_status, car_state).await?;
+
+        // Отмененная активная поездка тоже успела проехать часть маршрута
+        if previous_status == TripStatus::Active {
+            record_route_or_leave_pending(&self.repository, &self.cars_client, &self.telematics_client, &trip).await;
+        }
         Ok(())
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::use_cases::car_transitions::tests::{
+        trip, InMemoryCarTransitionRepository, InMemoryTripRepository, MockCarsClient,
+    };
+    use crate::application::use_cases::end_trip::tests::UnavailableTelematicsClient;
+    use crate::domain::models::Trip;
+
+    type TestUseCase = CancelTripUseCase<InMemoryTripRepository, InMemoryCarTransitionRepository, MockCarsClient, UnavailableTelematicsClient>;
+
+    fn setup(status: TripStatus, car_state: CarState) -> (TestUseCase, InMemoryTripRepository, InMemoryCarTransitionRepository, MockCarsClient, Trip) {
+        let open = trip(status);
+        let repository = InMemoryTripRepository::default();
+        repository.insert(open.clone());
+        let transitions = InMemoryCarTransitionRepository::default();
+        let cars = MockCarsClient::with_car(open.car_id, car_state);
+        let use_case = CancelTripUseCase::new(repository.clone(), transitions.clone(), cars.clone(), UnavailableTelematicsClient);
+        (use_case, repository, transitions, cars, open)
+    }
+
+    #[tokio::test]
+    async fn test_reservation_is_cancelled_and_car_released() {
+        let (use_case, repository, transitions, cars, reservation) = setup(TripStatus::Reserved, CarState::Reserved);
+
+        use_case.execute(reservation.id).await.unwrap();
+
+        let trip = repository.get(reservation.id).unwrap();
+        assert_eq!(trip.status, TripStatus::Cancelled);
+        assert!(trip.cancelled_at.is_some());
+        // Неактивированная поездка не ездила, маршрут не нужен
+        assert!(!trip.route_pending());
+        assert_eq!(cars.state(reservation.car_id), CarState::Available);
+        assert!(transitions.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_active_trip_waits_for_route_and_retries_release() {
+        let (use_case, repository, transitions, cars, active) = setup(TripStatus::Active, CarState::InUse);
+        cars.set_unavailable(true);
+
+        use_case.execute(active.id).await.unwrap();
+
+        let trip = repository.get(active.id).unwrap();
+        assert_eq!(trip.status, TripStatus::Cancelled);
+        assert!(trip.route_pending());
+        assert_eq!(cars.state(active.car_id), CarState::InUse);
+
+        let pending = transitions.pending();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].transition.from, CarState::InUse);
+        assert_eq!(pending[0].transition.to, CarState::Available);
+    }
+
+    #[tokio::test]
+    async fn test_closed_trip_cannot_be_cancelled() {
+        let (use_case, _, transitions, cars, completed) = setup(TripStatus::Completed, CarState::Available);
+
+        let result = use_case.execute(completed.id).await;
+
+        assert!(matches!(result, Err(TripError::InvalidStatusTransition { .. })));
+        assert_eq!(cars.state(completed.car_id), CarState::Available);
+        assert!(transitions.pending().is_empty());
+    }
+}
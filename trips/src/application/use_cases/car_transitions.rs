@@ -0,0 +1,479 @@
+use std::time::Duration;
+use uuid::Uuid;
+use chrono::Utc;
+use tracing::{info, warn, error};
+use crate::domain::{
+    errors::TripError,
+    interfaces::{CarTransitionRepository, CarsClient, TripRepository},
+    models::{CarState, PendingCarTransition, TripStatus},
+};
+use super::charge_no_show_fees::to_chrono;
+
+const CAR_TRANSITION_BATCH_SIZE: i64 = 50;
+// Сценарий сам выполняет перевод сразу после записи; фоновый обработчик берет его только после этой паузы
+const CAR_TRANSITION_GRACE: Duration = Duration::from_secs(60);
+const CAR_TRANSITION_LEASE: Duration = Duration::from_secs(60);
+const CAR_TRANSITION_RETRY_BASE_DELAY: Duration = Duration::from_secs(5);
+const CAR_TRANSITION_MAX_RETRY_DELAY: Duration = Duration::from_secs(600);
+
+#[derive(Debug)]
+pub(crate) enum CarTransitionOutcome {
+    Applied,
+    // Машина не в ожидаемом состоянии или удалена: повторять бессмысленно
+    Conflict(TripError),
+    // Cars недоступен, перевод остался в очереди
+    Deferred,
+}
+
+pub(crate) async fn enqueue_car_transition<P>(
+    transitions: &P,
+    trip_id: Uuid,
+    car_id: Uuid,
+    from: CarState,
+    to: CarState,
+) -> Result<PendingCarTransition, TripError>
+where
+    P: CarTransitionRepository,
+{
+    let transition = PendingCarTransition::new(trip_id, car_id, from, to);
+    transitions.enqueue(&transition, Utc::now() + to_chrono(CAR_TRANSITION_GRACE)).await?;
+    Ok(transition)
+}
+
+pub(crate) async fn apply_car_transition<P, C>(
+    transitions: &P,
+    cars_client: &C,
+    transition: &PendingCarTransition,
+) -> CarTransitionOutcome
+where
+    P: CarTransitionRepository,
+    C: CarsClient,
+{
+    let outcome = match cars_client.transition_car_state(transition.car_id, transition.from, transition.to).await {
+        Ok(()) => CarTransitionOutcome::Applied,
+        Err(e @ TripError::CarStateConflict { .. }) | Err(e @ TripError::CarNotFound) => {
+            warn!(
+                "Car {} was not in state {} for trip {}, skipping transition to {}",
+                transition.car_id, transition.from.as_str(), transition.trip_id, transition.to.as_str()
+            );
+            CarTransitionOutcome::Conflict(e)
+        }
+        Err(e) => {
+            let delay = CAR_TRANSITION_RETRY_BASE_DELAY
+                .saturating_mul(1 << transition.attempts.min(16))
+                .min(CAR_TRANSITION_MAX_RETRY_DELAY);
+            warn!(
+                "Car {} transition {} -> {} for trip {} attempt {} failed, retry in {:?}: {}",
+                transition.car_id, transition.from.as_str(), transition.to.as_str(),
+                transition.trip_id, transition.attempts + 1, delay, e
+            );
+            if let Err(mark_error) = transitions
+                .mark_failed(transition.id, &e.to_string(), Utc::now() + to_chrono(delay))
+                .await
+            {
+                error!("Failed to record car transition {} failure: {:?}", transition.id, mark_error);
+            }
+            return CarTransitionOutcome::Deferred;
+        }
+    };
+
+    // Если отметка не записалась, повтор получит конфликт состояния и закроет перевод
+    if let Err(e) = transitions.mark_done(transition.id, Utc::now()).await {
+        error!("Failed to mark car transition {} done: {:?}", transition.id, e);
+    }
+    outcome
+}
+
+// Доводит переводы машин, которые сценарии не смогли выполнить сами
+pub struct ApplyPendingCarTransitionsUseCase<R, P, C>
+where
+    R: TripRepository,
+    P: CarTransitionRepository,
+    C: CarsClient,
+{
+    repository: R,
+    transitions: P,
+    cars_client: C,
+}
+
+impl<R, P, C> ApplyPendingCarTransitionsUseCase<R, P, C>
+where
+    R: TripRepository,
+    P: CarTransitionRepository,
+    C: CarsClient,
+{
+    pub fn new(repository: R, transitions: P, cars_client: C) -> Self {
+        Self { repository, transitions, cars_client }
+    }
+
+    pub async fn execute(&self) -> Result<usize, TripError> {
+        let now = Utc::now();
+        let pending = self.transitions
+            .claim_due(now, now + to_chrono(CAR_TRANSITION_LEASE), CAR_TRANSITION_BATCH_SIZE)
+            .await?;
+
+        let mut applied = 0;
+        for transition in pending {
+            match self.is_still_needed(&transition).await {
+                Ok(true) => {
+                    if let CarTransitionOutcome::Applied = apply_car_transition(&self.transitions, &self.cars_client, &transition).await {
+                        info!(
+                            "Car {} moved to {} for trip {}",
+                            transition.car_id, transition.to.as_str(), transition.trip_id
+                        );
+                        applied += 1;
+                    }
+                }
+                Ok(false) => {
+                    info!("Car transition {} for trip {} is no longer needed", transition.id, transition.trip_id);
+                    self.transitions.mark_done(transition.id, Utc::now()).await?;
+                }
+                // Перевод вернется в работу, когда истечет аренда
+                Err(e) => error!("Failed to check car transition {}: {:?}", transition.id, e),
+            }
+        }
+
+        Ok(applied)
+    }
+
+    // Перевод устарел, если поездка с тех пор сменила статус (или так и не была создана),
+    // а свободной машину нельзя делать, пока ее держит другая поездка
+    async fn is_still_needed(&self, transition: &PendingCarTransition) -> Result<bool, TripError> {
+        let trip = self.repository.find_by_id(transition.trip_id).await?;
+        let expected = match trip.map(|t| t.status) {
+            Some(TripStatus::Reserved) => CarState::Reserved,
+            Some(TripStatus::Active) => CarState::InUse,
+            _ => CarState::Available,
+        };
+        if transition.to != expected {
+            return Ok(false);
+        }
+
+        if transition.to == CarState::Available
+            && self.repository.find_active_by_car_id(transition.car_id).await?.is_some()
+        {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration as ChronoDuration};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use crate::domain::models::{Currency, Money, ReservationPolicy, Trip, TripRoute};
+
+    // Повторяет семантику PostgresTripRepository: условные обновления и штраф в транзакции истечения
+    #[derive(Clone, Default)]
+    pub(crate) struct InMemoryTripRepository {
+        pub trips: Arc<Mutex<HashMap<Uuid, Trip>>>,
+        pub no_show_fees: Arc<Mutex<Vec<Uuid>>>,
+        pub fail_writes: Arc<Mutex<bool>>,
+    }
+
+    impl InMemoryTripRepository {
+        pub fn insert(&self, trip: Trip) {
+            self.trips.lock().unwrap().insert(trip.id, trip);
+        }
+
+        pub fn get(&self, id: Uuid) -> Option<Trip> {
+            self.trips.lock().unwrap().get(&id).cloned()
+        }
+
+        fn check_writes(&self) -> Result<(), TripError> {
+            if *self.fail_writes.lock().unwrap() {
+                return Err(TripError::Database(sqlx::Error::PoolTimedOut));
+            }
+            Ok(())
+        }
+
+        fn find_open(&self, filter: impl Fn(&Trip) -> bool) -> Option<Trip> {
+            self.trips.lock().unwrap().values()
+                .find(|t| matches!(t.status, TripStatus::Reserved | TripStatus::Active) && filter(t))
+                .cloned()
+        }
+    }
+
+    #[async_trait]
+    impl TripRepository for InMemoryTripRepository {
+        async fn create(&self, trip: &Trip) -> Result<(), TripError> {
+            self.check_writes()?;
+            self.insert(trip.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Trip>, TripError> {
+            Ok(self.get(id))
+        }
+
+        async fn find_by_user_id(&self, _user_id: Uuid) -> Result<Vec<Trip>, TripError> { unimplemented!() }
+        async fn find_all(&self) -> Result<Vec<Trip>, TripError> { unimplemented!() }
+
+        async fn find_active_by_user_id(&self, user_id: Uuid) -> Result<Option<Trip>, TripError> {
+            Ok(self.find_open(|t| t.user_id == user_id))
+        }
+
+        async fn find_active_by_car_id(&self, car_id: Uuid) -> Result<Option<Trip>, TripError> {
+            Ok(self.find_open(|t| t.car_id == car_id))
+        }
+
+        async fn update(&self, id: Uuid, trip: &Trip) -> Result<(), TripError> {
+            self.check_writes()?;
+            self.trips.lock().unwrap().insert(id, trip.clone());
+            Ok(())
+        }
+
+        async fn update_if_status(&self, id: Uuid, expected: TripStatus, trip: &Trip) -> Result<bool, TripError> {
+            self.check_writes()?;
+            let mut trips = self.trips.lock().unwrap();
+            match trips.get(&id) {
+                Some(current) if current.status == expected => {
+                    trips.insert(id, trip.clone());
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+
+        async fn expire_reservation(&self, trip: &Trip) -> Result<bool, TripError> {
+            if !self.update_if_status(trip.id, TripStatus::Reserved, trip).await? {
+                return Ok(false);
+            }
+            if trip.no_show_fee.is_positive() {
+                self.no_show_fees.lock().unwrap().push(trip.id);
+            }
+            Ok(true)
+        }
+
+        async fn find_expired_reservations(&self, now: DateTime<Utc>, _limit: i64) -> Result<Vec<Trip>, TripError> {
+            Ok(self.trips.lock().unwrap().values()
+                .filter(|t| t.status == TripStatus::Reserved && t.expires_at.is_some_and(|e| e <= now))
+                .cloned()
+                .collect())
+        }
+
+        async fn save_route(&self, _route: &TripRoute) -> Result<(), TripError> { unimplemented!() }
+        async fn find_route(&self, _trip_id: Uuid) -> Result<Option<TripRoute>, TripError> { unimplemented!() }
+        async fn find_pending_routes(&self, _limit: i64) -> Result<Vec<Trip>, TripError> { unimplemented!() }
+    }
+
+    #[derive(Clone)]
+    pub(crate) struct StoredTransition {
+        pub transition: PendingCarTransition,
+        pub done: bool,
+        pub next_attempt_at: DateTime<Utc>,
+    }
+
+    #[derive(Clone, Default)]
+    pub(crate) struct InMemoryCarTransitionRepository {
+        pub transitions: Arc<Mutex<HashMap<Uuid, StoredTransition>>>,
+    }
+
+    impl InMemoryCarTransitionRepository {
+        pub fn pending(&self) -> Vec<StoredTransition> {
+            self.transitions.lock().unwrap().values().filter(|t| !t.done).cloned().collect()
+        }
+
+        // Сдвигает срок повтора, как будто пауза уже прошла
+        pub fn make_due(&self) {
+            for stored in self.transitions.lock().unwrap().values_mut() {
+                stored.next_attempt_at = Utc::now() - ChronoDuration::seconds(1);
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CarTransitionRepository for InMemoryCarTransitionRepository {
+        async fn enqueue(&self, transition: &PendingCarTransition, not_before: DateTime<Utc>) -> Result<(), TripError> {
+            self.transitions.lock().unwrap().insert(transition.id, StoredTransition {
+                transition: transition.clone(),
+                done: false,
+                next_attempt_at: not_before,
+            });
+            Ok(())
+        }
+
+        async fn claim_due(&self, now: DateTime<Utc>, _lease_until: DateTime<Utc>, _limit: i64) -> Result<Vec<PendingCarTransition>, TripError> {
+            Ok(self.transitions.lock().unwrap().values()
+                .filter(|t| !t.done && t.next_attempt_at <= now)
+                .map(|t| t.transition.clone())
+                .collect())
+        }
+
+        async fn mark_done(&self, id: Uuid, _done_at: DateTime<Utc>) -> Result<(), TripError> {
+            self.transitions.lock().unwrap().get_mut(&id).unwrap().done = true;
+            Ok(())
+        }
+
+        async fn mark_failed(&self, id: Uuid, _error: &str, next_attempt_at: DateTime<Utc>) -> Result<(), TripError> {
+            let mut transitions = self.transitions.lock().unwrap();
+            let stored = transitions.get_mut(&id).unwrap();
+            stored.transition.attempts += 1;
+            stored.next_attempt_at = next_attempt_at;
+            Ok(())
+        }
+    }
+
+    // Переход атомарен, как в сервисе cars: CarStateConflict, если машина не в состоянии from
+    #[derive(Clone, Default)]
+    pub(crate) struct MockCarsClient {
+        pub states: Arc<Mutex<HashMap<Uuid, CarState>>>,
+        pub unavailable: Arc<Mutex<bool>>,
+        // Cars отвечает ошибкой только на переводы в это состояние
+        pub failing_target: Arc<Mutex<Option<CarState>>>,
+    }
+
+    impl MockCarsClient {
+        pub fn with_car(car_id: Uuid, state: CarState) -> Self {
+            let cars = Self::default();
+            cars.states.lock().unwrap().insert(car_id, state);
+            cars
+        }
+
+        pub fn state(&self, car_id: Uuid) -> CarState {
+            self.states.lock().unwrap()[&car_id]
+        }
+
+        pub fn set_unavailable(&self, unavailable: bool) {
+            *self.unavailable.lock().unwrap() = unavailable;
+        }
+
+        pub fn fail_transitions_to(&self, state: Option<CarState>) {
+            *self.failing_target.lock().unwrap() = state;
+        }
+    }
+
+    #[async_trait]
+    impl CarsClient for MockCarsClient {
+        async fn transition_car_state(&self, car_id: Uuid, from: CarState, to: CarState) -> Result<(), TripError> {
+            if *self.unavailable.lock().unwrap() || *self.failing_target.lock().unwrap() == Some(to) {
+                return Err(TripError::CarsService("503: unavailable".to_string()));
+            }
+            let mut states = self.states.lock().unwrap();
+            match states.get(&car_id) {
+                None => Err(TripError::CarNotFound),
+                Some(state) if *state != from => Err(TripError::CarStateConflict { expected: from.as_str().to_string() }),
+                Some(_) => {
+                    states.insert(car_id, to);
+                    Ok(())
+                }
+            }
+        }
+
+        async fn get_reservation_policy(&self, _car_id: Uuid) -> Result<ReservationPolicy, TripError> {
+            Ok(ReservationPolicy { reservation_window_minutes: 15, no_show_fee: Money::new(15000, Currency::Rub) })
+        }
+
+        async fn get_iot_serial_number(&self, _car_id: Uuid) -> Result<String, TripError> {
+            Ok("VIN0000000000001".to_string())
+        }
+    }
+
+    pub(crate) fn trip(status: TripStatus) -> Trip {
+        let now = Utc::now();
+        Trip {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            car_id: Uuid::new_v4(),
+            status,
+            started_at: (status != TripStatus::Reserved).then_some(now),
+            ended_at: None,
+            cancelled_at: None,
+            created_at: now,
+            expires_at: Some(now + ChronoDuration::minutes(15)),
+            no_show_fee: Money::new(15000, Currency::Rub),
+            distance_km: None,
+            parking_minutes: None,
+        }
+    }
+
+    type TestApplyUseCase = ApplyPendingCarTransitionsUseCase<InMemoryTripRepository, InMemoryCarTransitionRepository, MockCarsClient>;
+
+    fn setup(trip: &Trip, car_state: CarState) -> (TestApplyUseCase, InMemoryTripRepository, InMemoryCarTransitionRepository, MockCarsClient) {
+        let repository = InMemoryTripRepository::default();
+        repository.insert(trip.clone());
+        let transitions = InMemoryCarTransitionRepository::default();
+        let cars = MockCarsClient::with_car(trip.car_id, car_state);
+        let use_case = ApplyPendingCarTransitionsUseCase::new(repository.clone(), transitions.clone(), cars.clone());
+        (use_case, repository, transitions, cars)
+    }
+
+    #[tokio::test]
+    async fn test_deferred_release_is_applied_when_cars_recovers() {
+        let expired = trip(TripStatus::Expired);
+        let (use_case, _, transitions, cars) = setup(&expired, CarState::Reserved);
+        cars.set_unavailable(true);
+
+        let release = enqueue_car_transition(&transitions, expired.id, expired.car_id, CarState::Reserved, CarState::Available).await.unwrap();
+        assert!(matches!(apply_car_transition(&transitions, &cars, &release).await, CarTransitionOutcome::Deferred));
+        assert_eq!(transitions.pending()[0].transition.attempts, 1);
+
+        // До конца паузы обработчик перевод не трогает
+        assert_eq!(use_case.execute().await.unwrap(), 0);
+
+        cars.set_unavailable(false);
+        transitions.make_due();
+        assert_eq!(use_case.execute().await.unwrap(), 1);
+        assert_eq!(cars.state(expired.car_id), CarState::Available);
+        assert!(transitions.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retry_backoff_grows_with_attempts() {
+        let expired = trip(TripStatus::Expired);
+        let (_, _, transitions, cars) = setup(&expired, CarState::Reserved);
+        cars.set_unavailable(true);
+
+        let mut release = enqueue_car_transition(&transitions, expired.id, expired.car_id, CarState::Reserved, CarState::Available).await.unwrap();
+        release.attempts = 20;
+        apply_car_transition(&transitions, &cars, &release).await;
+
+        let delay = transitions.pending()[0].next_attempt_at - Utc::now();
+        assert!(delay <= ChronoDuration::seconds(600));
+        assert!(delay > ChronoDuration::seconds(590));
+    }
+
+    #[tokio::test]
+    async fn test_release_is_dropped_when_another_trip_holds_the_car() {
+        let expired = trip(TripStatus::Expired);
+        let (use_case, repository, transitions, cars) = setup(&expired, CarState::Reserved);
+        let mut next = trip(TripStatus::Reserved);
+        next.car_id = expired.car_id;
+        repository.insert(next);
+
+        transitions.enqueue(&PendingCarTransition::new(expired.id, expired.car_id, CarState::Reserved, CarState::Available), Utc::now()).await.unwrap();
+
+        assert_eq!(use_case.execute().await.unwrap(), 0);
+        assert_eq!(cars.state(expired.car_id), CarState::Reserved);
+        assert!(transitions.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transition_is_dropped_when_trip_status_moved_on() {
+        // Активация, которую затем отменили: машину в использование переводить уже нельзя
+        let cancelled = trip(TripStatus::Cancelled);
+        let (use_case, _, transitions, cars) = setup(&cancelled, CarState::Available);
+
+        transitions.enqueue(&PendingCarTransition::new(cancelled.id, cancelled.car_id, CarState::Reserved, CarState::InUse), Utc::now()).await.unwrap();
+
+        assert_eq!(use_case.execute().await.unwrap(), 0);
+        assert_eq!(cars.state(cancelled.car_id), CarState::Available);
+        assert!(transitions.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_conflict_closes_transition_without_retry() {
+        let expired = trip(TripStatus::Expired);
+        let (use_case, _, transitions, cars) = setup(&expired, CarState::Available);
+
+        transitions.enqueue(&PendingCarTransition::new(expired.id, expired.car_id, CarState::Reserved, CarState::Available), Utc::now()).await.unwrap();
+
+        assert_eq!(use_case.execute().await.unwrap(), 0);
+        assert_eq!(cars.state(expired.car_id), CarState::Available);
+        assert!(transitions.pending().is_empty());
+    }
+}
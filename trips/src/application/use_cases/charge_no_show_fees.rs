@@ -0,0 +1,194 @@
+use std::time::Duration;
+use chrono::Utc;
+use tracing::{info, warn, error};
+use crate::domain::{
+    errors::TripError,
+    interfaces::{BillingClient, NoShowFeeRepository},
+};
+
+const FEE_BATCH_SIZE: i64 = 50;
+// После падения процесса штраф подхватит другой экземпляр, когда истечет аренда
+const FEE_LEASE: Duration = Duration::from_secs(60);
+const FEE_RETRY_BASE_DELAY: Duration = Duration::from_secs(5);
+const FEE_MAX_RETRY_DELAY: Duration = Duration::from_secs(1800);
+
+// Отправляет в billing штрафы за неявку, записанные при истечении резервов
+pub struct ChargeNoShowFeesUseCase<F, B>
+where
+    F: NoShowFeeRepository,
+    B: BillingClient,
+{
+    repository: F,
+    billing_client: B,
+}
+
+impl<F, B> ChargeNoShowFeesUseCase<F, B>
+where
+    F: NoShowFeeRepository,
+    B: BillingClient,
+{
+    pub fn new(repository: F, billing_client: B) -> Self {
+        Self { repository, billing_client }
+    }
+
+    pub async fn execute(&self) -> Result<usize, TripError> {
+        let now = Utc::now();
+        let fees = self.repository
+            .claim_due(now, now + to_chrono(FEE_LEASE), FEE_BATCH_SIZE)
+            .await?;
+
+        let mut charged = 0;
+        for fee in fees {
+            match self.billing_client.charge_no_show_fee(fee.trip_id, fee.user_id, fee.amount).await {
+                Ok(()) => {
+                    self.repository.mark_charged(fee.trip_id, Utc::now()).await?;
+                    info!("No-show fee {} charged for trip {}", fee.amount, fee.trip_id);
+                    charged += 1;
+                }
+                Err(e) => {
+                    let delay = FEE_RETRY_BASE_DELAY
+                        .saturating_mul(1 << fee.attempts.min(16))
+                        .min(FEE_MAX_RETRY_DELAY);
+                    warn!(
+                        "No-show fee for trip {} attempt {} failed, retry in {:?}: {}",
+                        fee.trip_id, fee.attempts + 1, delay, e
+                    );
+                    if let Err(mark_error) = self.repository
+                        .mark_failed(fee.trip_id, &e.to_string(), Utc::now() + to_chrono(delay))
+                        .await
+                    {
+                        error!("Failed to record no-show fee failure for trip {}: {:?}", fee.trip_id, mark_error);
+                    }
+                }
+            }
+        }
+
+        Ok(charged)
+    }
+}
+
+pub(crate) fn to_chrono(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::days(365))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::DateTime;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+    use crate::domain::models::{Currency, Money, NoShowFee};
+
+    #[derive(Clone)]
+    struct StoredFee {
+        fee: NoShowFee,
+        charged: bool,
+        next_attempt_at: DateTime<Utc>,
+    }
+
+    #[derive(Clone, Default)]
+    struct MockFeeRepository {
+        fees: Arc<Mutex<HashMap<Uuid, StoredFee>>>,
+    }
+
+    impl MockFeeRepository {
+        fn add(&self, fee: NoShowFee) {
+            self.fees.lock().unwrap().insert(fee.trip_id, StoredFee { fee, charged: false, next_attempt_at: Utc::now() });
+        }
+
+        fn get(&self, trip_id: Uuid) -> StoredFee {
+            self.fees.lock().unwrap().get(&trip_id).cloned().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl NoShowFeeRepository for MockFeeRepository {
+        async fn claim_due(&self, now: DateTime<Utc>, _lease_until: DateTime<Utc>, _limit: i64) -> Result<Vec<NoShowFee>, TripError> {
+            Ok(self.fees.lock().unwrap().values()
+                .filter(|f| !f.charged && f.next_attempt_at <= now)
+                .map(|f| f.fee.clone())
+                .collect())
+        }
+
+        async fn mark_charged(&self, trip_id: Uuid, _charged_at: DateTime<Utc>) -> Result<(), TripError> {
+            self.fees.lock().unwrap().get_mut(&trip_id).unwrap().charged = true;
+            Ok(())
+        }
+
+        async fn mark_failed(&self, trip_id: Uuid, _error: &str, next_attempt_at: DateTime<Utc>) -> Result<(), TripError> {
+            let mut fees = self.fees.lock().unwrap();
+            let stored = fees.get_mut(&trip_id).unwrap();
+            stored.fee.attempts += 1;
+            stored.next_attempt_at = next_attempt_at;
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockBillingClient {
+        failing: Arc<Mutex<bool>>,
+        calls: Arc<Mutex<Vec<Uuid>>>,
+    }
+
+    #[async_trait]
+    impl BillingClient for MockBillingClient {
+        async fn charge_no_show_fee(&self, trip_id: Uuid, _user_id: Uuid, _amount: Money) -> Result<(), TripError> {
+            self.calls.lock().unwrap().push(trip_id);
+            if *self.failing.lock().unwrap() {
+                return Err(TripError::BillingService("503: unavailable".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    fn fee() -> NoShowFee {
+        NoShowFee {
+            trip_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            amount: Money::new(15000, Currency::Rub),
+            attempts: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fee_is_charged_and_not_sent_again() {
+        let repository = MockFeeRepository::default();
+        let billing = MockBillingClient::default();
+        let fee = fee();
+        repository.add(fee.clone());
+        let use_case = ChargeNoShowFeesUseCase::new(repository.clone(), billing.clone());
+
+        assert_eq!(use_case.execute().await.unwrap(), 1);
+        assert!(repository.get(fee.trip_id).charged);
+        assert_eq!(use_case.execute().await.unwrap(), 0);
+        assert_eq!(billing.calls.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fee_is_kept_and_backed_off_when_billing_is_down() {
+        let repository = MockFeeRepository::default();
+        let billing = MockBillingClient::default();
+        *billing.failing.lock().unwrap() = true;
+        let fee = fee();
+        repository.add(fee.clone());
+        let use_case = ChargeNoShowFeesUseCase::new(repository.clone(), billing.clone());
+
+        assert_eq!(use_case.execute().await.unwrap(), 0);
+        let stored = repository.get(fee.trip_id);
+        assert!(!stored.charged);
+        assert_eq!(stored.fee.attempts, 1);
+        assert!(stored.next_attempt_at > Utc::now());
+
+        // До следующей попытки штраф не отправляется повторно
+        assert_eq!(use_case.execute().await.unwrap(), 0);
+        assert_eq!(billing.calls.lock().unwrap().len(), 1);
+
+        // Billing поднялся и срок повтора наступил - штраф доходит
+        *billing.failing.lock().unwrap() = false;
+        repository.fees.lock().unwrap().get_mut(&fee.trip_id).unwrap().next_attempt_at = Utc::now();
+        assert_eq!(use_case.execute().await.unwrap(), 1);
+        assert!(repository.get(fee.trip_id).charged);
+    }
+}
@@ -1,24 +1,36 @@
 use uuid::Uuid;
 use chrono::Utc;
+use tracing::error;
 use crate::domain::{
     errors::TripError,
-    interfaces::TripRepository,
-    models::TripStatus,
+    interfaces::{TripRepository, CarTransitionRepository, CarsClient, TelematicsClient},
+    models::{Trip, TripStatus, CarState},
 };
+use super::car_transitions::{apply_car_transition, enqueue_car_transition};
+use super::trip_routes::record_route_or_leave_pending;
 
-pub struct EndTripUseCase<R> 
+pub struct EndTripUseCase<R, P, C, T> 
 where
     R: TripRepository,
+    P: CarTransitionRepository,
+    C: CarsClient,
+    T: TelematicsClient,
 {
     repository: R,
+    transitions: P,
+    cars_client: C,
+    telematics_client: T,
 }
 
-impl<R> EndTripUseCase<R>
+impl<R, P, C, T> EndTripUseCase<R, P, C, T>
 where
     R: TripRepository,
+    P: CarTransitionRepository,
+    C: CarsClient,
+    T: TelematicsClient,
 {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+    pub fn new(repository: R, transitions: P, cars_client: C, telematics_client: T) -> Self {
+        Self { repository, transitions, cars_client, telematics_client }
     }
 
     pub async fn execute(&self, trip_id: Uuid) -> Result<(), TripError> {
@@ -37,8 +49,130 @@ where
         trip.status = TripStatus::Completed;
         trip.ended_at = Some(Utc::now());
 
-        self.repository.update(trip_id, &trip).await?;
+        close_trip(&self.repository, &self.transitions, &self.cars_client, &trip, TripStatus::Active, CarState::InUse).await?;
+
+        // Маршрут и пройденное расстояние нужны для чека, поэтому записываем их до ответа
+        record_route_or_leave_pending(&self.repository, &self.cars_client, &self.telematics_client, &trip).await;
         Ok(())
     }
 }
 
+// Поездка закрывается до освобождения машины, чтобы машину не зарезервировали, пока поездка открыта.
+// Закрытие не откатывается: машину, которую cars не принял, освободит фоновый обработчик,
+// а если машину уже перевели в другое состояние, освобождение пропускается
+pub(crate) async fn close_trip<R, P, C>(
+    repository: &R,
+    transitions: &P,
+    cars_client: &C,
+    trip: &Trip,
+    expected: TripStatus,
+    car_state: CarState,
+) -> Result<(), TripError>
+where
+    R: TripRepository,
+    P: CarTransitionRepository,
+    C: CarsClient,
+{
+    let release = enqueue_car_transition(transitions, trip.id, trip.car_id, car_state, CarState::Available).await?;
+
+    if !repository.update_if_status(trip.id, expected, trip).await? {
+        // Если отметка не записалась, устаревший перевод отбросит фоновый обработчик
+        if let Err(e) = transitions.mark_done(release.id, Utc::now()).await {
+            error!("Failed to drop car transition {}: {:?}", release.id, e);
+        }
+        return Err(TripError::InvalidTripState);
+    }
+
+    apply_car_transition(transitions, cars_client, &release).await;
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::DateTime;
+    use crate::application::use_cases::ApplyPendingCarTransitionsUseCase;
+    use crate::application::use_cases::car_transitions::tests::{
+        trip, InMemoryCarTransitionRepository, InMemoryTripRepository, MockCarsClient,
+    };
+    use crate::domain::models::RoutePoint;
+
+    // Маршрут остается незаписанным: его достраивает RecordPendingRoutesUseCase
+    pub(crate) struct UnavailableTelematicsClient;
+
+    #[async_trait]
+    impl TelematicsClient for UnavailableTelematicsClient {
+        async fn get_route_points(&self, _vin: &str, _from: DateTime<Utc>, _to: DateTime<Utc>) -> Result<Vec<RoutePoint>, TripError> {
+            Err(TripError::TelematicsService("503: unavailable".to_string()))
+        }
+    }
+
+    type TestUseCase = EndTripUseCase<InMemoryTripRepository, InMemoryCarTransitionRepository, MockCarsClient, UnavailableTelematicsClient>;
+
+    fn setup(car_state: CarState) -> (TestUseCase, InMemoryTripRepository, InMemoryCarTransitionRepository, MockCarsClient, Trip) {
+        let active = trip(TripStatus::Active);
+        let repository = InMemoryTripRepository::default();
+        repository.insert(active.clone());
+        let transitions = InMemoryCarTransitionRepository::default();
+        let cars = MockCarsClient::with_car(active.car_id, car_state);
+        let use_case = EndTripUseCase::new(repository.clone(), transitions.clone(), cars.clone(), UnavailableTelematicsClient);
+        (use_case, repository, transitions, cars, active)
+    }
+
+    #[tokio::test]
+    async fn test_trip_is_completed_and_car_released() {
+        let (use_case, repository, transitions, cars, active) = setup(CarState::InUse);
+
+        use_case.execute(active.id).await.unwrap();
+
+        let trip = repository.get(active.id).unwrap();
+        assert_eq!(trip.status, TripStatus::Completed);
+        assert!(trip.ended_at.is_some());
+        assert!(trip.route_pending());
+        assert_eq!(cars.state(active.car_id), CarState::Available);
+        assert!(transitions.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_only_active_trip_can_be_ended() {
+        let (use_case, repository, _, cars, active) = setup(CarState::InUse);
+        let mut reserved = active.clone();
+        reserved.status = TripStatus::Reserved;
+        repository.insert(reserved);
+
+        let result = use_case.execute(active.id).await;
+
+        assert!(matches!(result, Err(TripError::InvalidStatusTransition { .. })));
+        assert_eq!(cars.state(active.car_id), CarState::InUse);
+    }
+
+    #[tokio::test]
+    async fn test_cars_failure_keeps_trip_completed_and_retries_release() {
+        let (use_case, repository, transitions, cars, active) = setup(CarState::InUse);
+        cars.set_unavailable(true);
+
+        use_case.execute(active.id).await.unwrap();
+
+        assert_eq!(repository.get(active.id).unwrap().status, TripStatus::Completed);
+        assert_eq!(cars.state(active.car_id), CarState::InUse);
+        assert_eq!(transitions.pending().len(), 1);
+
+        cars.set_unavailable(false);
+        transitions.make_due();
+        let worker = ApplyPendingCarTransitionsUseCase::new(repository.clone(), transitions.clone(), cars.clone());
+        assert_eq!(worker.execute().await.unwrap(), 1);
+        assert_eq!(cars.state(active.car_id), CarState::Available);
+    }
+
+    #[tokio::test]
+    async fn test_car_in_other_state_does_not_block_closing() {
+        let (use_case, repository, transitions, cars, active) = setup(CarState::Available);
+
+        use_case.execute(active.id).await.unwrap();
+
+        assert_eq!(repository.get(active.id).unwrap().status, TripStatus::Completed);
+        assert_eq!(cars.state(active.car_id), CarState::Available);
+        assert!(transitions.pending().is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+use chrono::Utc;
+use tracing::{info, warn, error};
+use crate::domain::{
+    errors::TripError,
+    interfaces::{TripRepository, CarTransitionRepository, CarsClient},
+    models::{Trip, TripStatus, CarState},
+};
+use super::car_transitions::{apply_car_transition, enqueue_car_transition, CarTransitionOutcome};
+
+const EXPIRY_BATCH_SIZE: i64 = 100;
+
+pub struct ExpireReservationsUseCase<R, P, C> 
+where
+    R: TripRepository,
+    P: CarTransitionRepository,
+    C: CarsClient,
+{
+    repository: R,
+    transitions: P,
+    cars_client: C,
+}
+
+impl<R, P, C> ExpireReservationsUseCase<R, P, C>
+where
+    R: TripRepository,
+    P: CarTransitionRepository,
+    C: CarsClient,
+{
+    pub fn new(repository: R, transitions: P, cars_client: C) -> Self {
+        Self { repository, transitions, cars_client }
+    }
+
+    pub async fn execute(&self) -> Result<usize, TripError> {
+        let trips = self.repository.find_expired_reservations(Utc::now(), EXPIRY_BATCH_SIZE).await?;
+
+        let mut expired = 0;
+        for trip in trips {
+            let trip_id = trip.id;
+            match self.expire(trip).await {
+                Ok(true) => expired += 1,
+                Ok(false) => {}
+                // Резерв остался в статусе reserved и будет обработан на следующем проходе
+                Err(e) => error!("Failed to expire reservation {}: {:?}", trip_id, e),
+            }
+        }
+
+        Ok(expired)
+    }
+
+    async fn expire(&self, mut trip: Trip) -> Result<bool, TripError> {
+        trip.status = TripStatus::Expired;
+        trip.cancelled_at = Some(Utc::now());
+
+        let release = enqueue_car_transition(
+            &self.transitions, trip.id, trip.car_id, CarState::Reserved, CarState::Available,
+        ).await?;
+
+        // Пользователь мог успеть активировать или отменить резерв.
+        // Штраф за неявку записывается вместе с истечением и отправляется в billing отдельно
+        if !self.repository.expire_reservation(&trip).await? {
+            warn!("Reservation {} changed status before expiry, skipping", trip.id);
+            // Иначе устаревший перевод отбросит фоновый обработчик
+            if let Err(e) = self.transitions.mark_done(release.id, Utc::now()).await {
+                error!("Failed to drop car transition {}: {:?}", release.id, e);
+            }
+            return Ok(false);
+        }
+
+        // Истечение уже зафиксировано вместе со штрафом и не откатывается:
+        // машину, которую cars не принял, освободит фоновый обработчик
+        match apply_car_transition(&self.transitions, &self.cars_client, &release).await {
+            CarTransitionOutcome::Deferred => info!("Reservation {} expired, car {} release pending", trip.id, trip.car_id),
+            _ => info!("Reservation {} expired, car {} released", trip.id, trip.car_id),
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::application::use_cases::car_transitions::tests::{
+        trip, InMemoryCarTransitionRepository, InMemoryTripRepository, MockCarsClient,
+    };
+
+    type TestUseCase = ExpireReservationsUseCase<InMemoryTripRepository, InMemoryCarTransitionRepository, MockCarsClient>;
+
+    fn stale_reservation() -> Trip {
+        let mut reservation = trip(TripStatus::Reserved);
+        reservation.expires_at = Some(Utc::now() - Duration::minutes(1));
+        reservation
+    }
+
+    fn setup(reservation: &Trip) -> (TestUseCase, InMemoryTripRepository, InMemoryCarTransitionRepository, MockCarsClient) {
+        let repository = InMemoryTripRepository::default();
+        repository.insert(reservation.clone());
+        let transitions = InMemoryCarTransitionRepository::default();
+        let cars = MockCarsClient::with_car(reservation.car_id, CarState::Reserved);
+        let use_case = ExpireReservationsUseCase::new(repository.clone(), transitions.clone(), cars.clone());
+        (use_case, repository, transitions, cars)
+    }
+
+    #[tokio::test]
+    async fn test_reservation_expires_with_fee_and_car_release() {
+        let reservation = stale_reservation();
+        let (use_case, repository, transitions, cars) = setup(&reservation);
+
+        assert_eq!(use_case.execute().await.unwrap(), 1);
+
+        let stored = repository.get(reservation.id).unwrap();
+        assert_eq!(stored.status, TripStatus::Expired);
+        assert!(stored.cancelled_at.is_some());
+        assert_eq!(*repository.no_show_fees.lock().unwrap(), vec![reservation.id]);
+        assert_eq!(cars.state(reservation.car_id), CarState::Available);
+        assert!(transitions.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_lost_race_leaves_trip_car_and_fee_untouched() {
+        let reservation = stale_reservation();
+        let (use_case, repository, transitions, cars) = setup(&reservation);
+
+        // Пользователь активировал резерв между выборкой и истечением
+        let mut activated = reservation.clone();
+        activated.status = TripStatus::Active;
+        repository.insert(activated);
+
+        assert!(!use_case.expire(reservation.clone()).await.unwrap());
+
+        assert_eq!(repository.get(reservation.id).unwrap().status, TripStatus::Active);
+        assert!(repository.no_show_fees.lock().unwrap().is_empty());
+        assert_eq!(cars.state(reservation.car_id), CarState::Reserved);
+        assert!(transitions.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cars_failure_keeps_expiry_and_defers_release() {
+        let reservation = stale_reservation();
+        let (use_case, repository, transitions, cars) = setup(&reservation);
+        cars.set_unavailable(true);
+
+        assert_eq!(use_case.execute().await.unwrap(), 1);
+
+        // Резерв не возвращается в reserved: иначе штраф занял бы платеж поездки
+        assert_eq!(repository.get(reservation.id).unwrap().status, TripStatus::Expired);
+        assert_eq!(*repository.no_show_fees.lock().unwrap(), vec![reservation.id]);
+        assert_eq!(cars.state(reservation.car_id), CarState::Reserved);
+
+        let pending = transitions.pending();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].transition.to, CarState::Available);
+        assert_eq!(pending[0].transition.attempts, 1);
+        assert!(pending[0].next_attempt_at > Utc::now());
+
+        // Следующий проход не выбирает истекший резерв повторно
+        assert_eq!(use_case.execute().await.unwrap(), 0);
+    }
+}
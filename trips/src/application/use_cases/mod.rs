@@ -5,6 +5,11 @@ mod cancel_trip;
 mod get_trip;
 mod get_user_trips;
 mod get_all_trips;
+mod expire_reservations;
+mod charge_no_show_fees;
+mod trip_routes;
+mod car_transitions;
+mod relay_outbox_events;
 
 pub use start_trip::*;
 pub use activate_trip::*;
@@ -13,4 +18,9 @@ pub use cancel_trip::*;
 pub use get_trip::*;
 pub use get_user_trips::*;
 pub use get_all_trips::*;
+pub use expire_reservations::*;
+pub use charge_no_show_fees::*;
+pub use trip_routes::*;
+pub use car_transitions::*;
+pub use relay_outbox_events::*;
 
@@ -1,42 +1,76 @@
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{Duration, Utc};
+use tracing::error;
 use crate::domain::{
     errors::TripError,
-    interfaces::TripRepository,
-    models::{Trip, StartTripRequest, TripStatus},
+    interfaces::{TripRepository, CarTransitionRepository, CarsClient},
+    models::{Trip, StartTripRequest, TripStatus, CarState},
 };
+use super::car_transitions::{apply_car_transition, enqueue_car_transition};
 
-pub struct StartTripUseCase<R> 
+pub struct StartTripUseCase<R, P, C> 
 where
     R: TripRepository,
+    P: CarTransitionRepository,
+    C: CarsClient,
 {
     repository: R,
+    transitions: P,
+    cars_client: C,
 }
 
-impl<R> StartTripUseCase<R>
+impl<R, P, C> StartTripUseCase<R, P, C>
 where
     R: TripRepository,
+    P: CarTransitionRepository,
+    C: CarsClient,
 {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+    pub fn new(repository: R, transitions: P, cars_client: C) -> Self {
+        Self { repository, transitions, cars_client }
     }
 
     pub async fn execute(&self, request: StartTripRequest) -> Result<Uuid, TripError> {
         // Проверяем, нет ли у пользователя активной поездки
-        if let Some(_) = self.repository.find_active_by_user_id(request.user_id).await? {
+        if self.repository.find_active_by_user_id(request.user_id).await?.is_some() {
             return Err(TripError::UserHasActiveTrip);
         }
 
         // Проверяем, не занята ли машина
-        if let Some(_) = self.repository.find_active_by_car_id(request.car_id).await? {
+        if self.repository.find_active_by_car_id(request.car_id).await?.is_some() {
             return Err(TripError::CarAlreadyInUse);
         }
 
+        // Условия резерва берем из тарифа машины
+        let policy = self.cars_client.get_reservation_policy(request.car_id).await?;
+
+        // Освобождение машины записывается до резерва: если поездка не создастся
+        // (или процесс упадет), машину вернет в доступные фоновый обработчик
+        let trip_id = Uuid::new_v4();
+        let release = enqueue_car_transition(
+            &self.transitions, trip_id, request.car_id, CarState::Reserved, CarState::Available,
+        ).await?;
+
+        // Резервируем машину: переход available -> reserved атомарен в сервисе cars,
+        // поэтому из конкурентных запросов на одну машину пройдет только один.
+        // При сбое cars машина могла успеть зарезервироваться, поэтому освобождение остается в очереди
+        if let Err(e) = self.cars_client
+            .transition_car_state(request.car_id, CarState::Available, CarState::Reserved)
+            .await
+        {
+            if let TripError::CarStateConflict { .. } | TripError::CarNotFound = e {
+                self.drop_release(release.id).await;
+            }
+            return Err(match e {
+                TripError::CarStateConflict { .. } => TripError::CarAlreadyInUse,
+                e => e,
+            });
+        }
+
         // Создаем новую поездку со статусом Reserved
         // Поездка будет активирована отдельным действием (когда пользователь реально начал поездку)
         let now = Utc::now();
         let trip = Trip {
-            id: Uuid::new_v4(),
+            id: trip_id,
             user_id: request.user_id,
             car_id: request.car_id,
             status: TripStatus::Reserved,
@@ -44,10 +78,138 @@ where
             ended_at: None,
             cancelled_at: None,
             created_at: now,
+            expires_at: Some(now + Duration::minutes(policy.reservation_window_minutes as i64)),
+            no_show_fee: policy.no_show_fee,
+            distance_km: None,
+            parking_minutes: None,
         };
 
-        self.repository.create(&trip).await?;
+        if let Err(e) = self.repository.create(&trip).await {
+            // Компенсация: поездка не создана, возвращаем машину в доступные
+            apply_car_transition(&self.transitions, &self.cars_client, &release).await;
+            return Err(e);
+        }
+
+        self.drop_release(release.id).await;
         Ok(trip.id)
     }
+
+    // Если отметка не записалась, обработчик сам отбросит перевод: поездка держит машину
+    async fn drop_release(&self, release_id: Uuid) {
+        if let Err(e) = self.transitions.mark_done(release_id, Utc::now()).await {
+            error!("Failed to drop car transition {}: {:?}", release_id, e);
+        }
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::use_cases::ApplyPendingCarTransitionsUseCase;
+    use crate::application::use_cases::car_transitions::tests::{
+        trip, InMemoryCarTransitionRepository, InMemoryTripRepository, MockCarsClient,
+    };
+    use crate::domain::models::{Currency, Money};
+
+    type TestUseCase = StartTripUseCase<InMemoryTripRepository, InMemoryCarTransitionRepository, MockCarsClient>;
+
+    fn setup(car_state: CarState) -> (TestUseCase, InMemoryTripRepository, InMemoryCarTransitionRepository, MockCarsClient, StartTripRequest) {
+        let request = StartTripRequest { user_id: Uuid::new_v4(), car_id: Uuid::new_v4() };
+        let repository = InMemoryTripRepository::default();
+        let transitions = InMemoryCarTransitionRepository::default();
+        let cars = MockCarsClient::with_car(request.car_id, car_state);
+        let use_case = StartTripUseCase::new(repository.clone(), transitions.clone(), cars.clone());
+        (use_case, repository, transitions, cars, request)
+    }
+
+    fn request_copy(request: &StartTripRequest) -> StartTripRequest {
+        StartTripRequest { user_id: request.user_id, car_id: request.car_id }
+    }
+
+    #[tokio::test]
+    async fn test_trip_is_reserved_on_tariff_terms() {
+        let (use_case, repository, transitions, cars, request) = setup(CarState::Available);
+
+        let trip_id = use_case.execute(request_copy(&request)).await.unwrap();
+
+        let trip = repository.get(trip_id).unwrap();
+        assert_eq!(trip.status, TripStatus::Reserved);
+        assert_eq!(trip.no_show_fee, Money::new(15000, Currency::Rub));
+        assert_eq!(trip.expires_at.unwrap() - trip.created_at, Duration::minutes(15));
+        assert_eq!(cars.state(request.car_id), CarState::Reserved);
+        assert!(transitions.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_busy_car_is_rejected() {
+        let (use_case, repository, transitions, _, request) = setup(CarState::InUse);
+
+        let result = use_case.execute(request_copy(&request)).await;
+
+        assert!(matches!(result, Err(TripError::CarAlreadyInUse)));
+        assert!(repository.trips.lock().unwrap().is_empty());
+        assert!(transitions.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_user_with_open_trip_is_rejected() {
+        let (use_case, repository, _, cars, request) = setup(CarState::Available);
+        let mut open = trip(TripStatus::Active);
+        open.user_id = request.user_id;
+        repository.insert(open);
+
+        let result = use_case.execute(request_copy(&request)).await;
+
+        assert!(matches!(result, Err(TripError::UserHasActiveTrip)));
+        assert_eq!(cars.state(request.car_id), CarState::Available);
+    }
+
+    #[tokio::test]
+    async fn test_failed_trip_creation_releases_car() {
+        let (use_case, repository, transitions, cars, request) = setup(CarState::Available);
+        *repository.fail_writes.lock().unwrap() = true;
+
+        let result = use_case.execute(request_copy(&request)).await;
+
+        assert!(matches!(result, Err(TripError::Database(_))));
+        assert_eq!(cars.state(request.car_id), CarState::Available);
+        assert!(transitions.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_release_after_creation_error_is_retried() {
+        let (use_case, repository, transitions, cars, request) = setup(CarState::Available);
+        *repository.fail_writes.lock().unwrap() = true;
+        // Cars принимает резерв, но не отвечает на освобождение
+        cars.fail_transitions_to(Some(CarState::Available));
+
+        let result = use_case.execute(request_copy(&request)).await;
+
+        assert!(matches!(result, Err(TripError::Database(_))));
+        assert_eq!(cars.state(request.car_id), CarState::Reserved);
+        assert_eq!(transitions.pending()[0].transition.attempts, 1);
+
+        cars.fail_transitions_to(None);
+        transitions.make_due();
+        let worker = ApplyPendingCarTransitionsUseCase::new(repository.clone(), transitions.clone(), cars.clone());
+        assert_eq!(worker.execute().await.unwrap(), 1);
+        assert_eq!(cars.state(request.car_id), CarState::Available);
+        assert!(transitions.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cars_failure_on_reserve_keeps_release_queued() {
+        let (use_case, repository, transitions, cars, request) = setup(CarState::Available);
+        cars.set_unavailable(true);
+
+        let result = use_case.execute(request_copy(&request)).await;
+
+        // Ответ cars потерян, машина могла успеть зарезервироваться
+        assert!(matches!(result, Err(TripError::CarsService(_))));
+        assert!(repository.trips.lock().unwrap().is_empty());
+        let pending = transitions.pending();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].transition.car_id, request.car_id);
+        assert_eq!(pending[0].transition.to, CarState::Available);
+    }
+}
@@ -0,0 +1,147 @@
+use uuid::Uuid;
+use chrono::Utc;
+use tracing::{info, warn};
+
+// Сколько поездок с незаписанным маршрутом достраивается за один проход
+const PENDING_ROUTES_BATCH_SIZE: i64 = 50;
+use crate::domain::{
+    errors::TripError,
+    interfaces::{TripRepository, CarsClient, TelematicsClient},
+    models::{Trip, TripRoute, TripStatus},
+};
+
+// Маршрут от активации до завершения или отмены (для активной поездки - до текущего момента).
+// У поездки, которая не активировалась, маршрут пустой
+pub(crate) async fn build_route<C, T>(
+    cars_client: &C,
+    telematics_client: &T,
+    trip: &Trip,
+) -> Result<TripRoute, TripError>
+where
+    C: CarsClient,
+    T: TelematicsClient,
+{
+    let Some(from) = trip.started_at else {
+        return Ok(TripRoute::from_points(trip.id, Vec::new()));
+    };
+    let to = trip.ended_at.or(trip.cancelled_at).unwrap_or_else(Utc::now);
+
+    let vin = cars_client.get_iot_serial_number(trip.car_id).await?;
+    let points = telematics_client.get_route_points(&vin, from, to).await?;
+    Ok(TripRoute::from_points(trip.id, points))
+}
+
+pub(crate) async fn record_route<R, C, T>(
+    repository: &R,
+    cars_client: &C,
+    telematics_client: &T,
+    trip: &Trip,
+) -> Result<TripRoute, TripError>
+where
+    R: TripRepository,
+    C: CarsClient,
+    T: TelematicsClient,
+{
+    let route = build_route(cars_client, telematics_client, trip).await?;
+    repository.save_route(&route).await?;
+
+    info!(
+        "Route recorded for trip {}: {} points, {:.3} km",
+        trip.id, route.points.len(), route.distance_km
+    );
+    Ok(route)
+}
+
+// Закрытие поездки не откатывается из-за телеметрии: поездка остается с route_pending,
+// маршрут достраивает RecordPendingRoutesUseCase, а расчет стоимости ждет его
+pub(crate) async fn record_route_or_leave_pending<R, C, T>(
+    repository: &R,
+    cars_client: &C,
+    telematics_client: &T,
+    trip: &Trip,
+)
+where
+    R: TripRepository,
+    C: CarsClient,
+    T: TelematicsClient,
+{
+    if let Err(e) = record_route(repository, cars_client, telematics_client, trip).await {
+        warn!("Failed to record route for trip {}, left pending: {:?}", trip.id, e);
+    }
+}
+
+pub struct RecordPendingRoutesUseCase<R, C, T>
+where
+    R: TripRepository,
+    C: CarsClient,
+    T: TelematicsClient,
+{
+    repository: R,
+    cars_client: C,
+    telematics_client: T,
+}
+
+impl<R, C, T> RecordPendingRoutesUseCase<R, C, T>
+where
+    R: TripRepository,
+    C: CarsClient,
+    T: TelematicsClient,
+{
+    pub fn new(repository: R, cars_client: C, telematics_client: T) -> Self {
+        Self { repository, cars_client, telematics_client }
+    }
+
+    pub async fn execute(&self) -> Result<usize, TripError> {
+        let trips = self.repository.find_pending_routes(PENDING_ROUTES_BATCH_SIZE).await?;
+
+        let mut recorded = 0;
+        for trip in &trips {
+            match record_route(&self.repository, &self.cars_client, &self.telematics_client, trip).await {
+                Ok(_) => recorded += 1,
+                Err(e) => warn!("Route for trip {} is still pending: {:?}", trip.id, e),
+            }
+        }
+        Ok(recorded)
+    }
+}
+
+pub struct GetTripRouteUseCase<R, C, T> 
+where
+    R: TripRepository,
+    C: CarsClient,
+    T: TelematicsClient,
+{
+    repository: R,
+    cars_client: C,
+    telematics_client: T,
+}
+
+impl<R, C, T> GetTripRouteUseCase<R, C, T>
+where
+    R: TripRepository,
+    C: CarsClient,
+    T: TelematicsClient,
+{
+    pub fn new(repository: R, cars_client: C, telematics_client: T) -> Self {
+        Self { repository, cars_client, telematics_client }
+    }
+
+    pub async fn execute(&self, trip_id: Uuid) -> Result<TripRoute, TripError> {
+        let trip = self.repository.find_by_id(trip_id).await?
+            .ok_or(TripError::TripNotFound)?;
+
+        if let Some(route) = self.repository.find_route(trip_id).await? {
+            return Ok(route);
+        }
+
+        match trip.status {
+            // Маршрут активной поездки еще растет: строим на лету, не сохраняя
+            TripStatus::Active => build_route(&self.cars_client, &self.telematics_client, &trip).await,
+            // Маршрут не удалось записать при закрытии поездки - достраиваем сейчас
+            _ if trip.route_pending() => {
+                record_route(&self.repository, &self.cars_client, &self.telematics_client, &trip).await
+            }
+            _ => Ok(TripRoute::from_points(trip.id, Vec::new())),
+        }
+    }
+}
@@ -23,6 +23,21 @@ pub enum TripError {
     #[error("trip is not in a valid state for this operation")]
     InvalidTripState,
     
+    #[error("car is not in the expected state: {expected}")]
+    CarStateConflict { expected: String },
+    
+    #[error("cars service error: {0}")]
+    CarsService(String),
+    
+    #[error("billing service error: {0}")]
+    BillingService(String),
+    
+    #[error("telematics service error: {0}")]
+    TelematicsService(String),
+    
+    #[error("event publish error: {0}")]
+    EventPublish(String),
+    
     #[error("database error: {0}")]
     Database(#[from] sqlx::Error),
     
@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::domain::{errors::TripError, models::Money};
+
+#[async_trait]
+pub trait BillingClient {
+    // Выставляет штраф за неявку; повтор для той же поездки считается успехом
+    async fn charge_no_show_fee(&self, trip_id: Uuid, user_id: Uuid, amount: Money) -> Result<(), TripError>;
+}
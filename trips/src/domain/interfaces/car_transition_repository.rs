@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::domain::{
+    errors::TripError,
+    models::PendingCarTransition,
+};
+
+// Перевод записывается до вызова cars; not_before дает сценарию время выполнить его самому
+#[async_trait]
+pub trait CarTransitionRepository {
+    async fn enqueue(&self, transition: &PendingCarTransition, not_before: DateTime<Utc>) -> Result<(), TripError>;
+    async fn claim_due(&self, now: DateTime<Utc>, lease_until: DateTime<Utc>, limit: i64) -> Result<Vec<PendingCarTransition>, TripError>;
+    async fn mark_done(&self, id: Uuid, done_at: DateTime<Utc>) -> Result<(), TripError>;
+    async fn mark_failed(&self, id: Uuid, error: &str, next_attempt_at: DateTime<Utc>) -> Result<(), TripError>;
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::domain::{
+    errors::TripError,
+    models::{CarState, ReservationPolicy},
+};
+
+#[async_trait]
+pub trait CarsClient {
+    // CarStateConflict, если машина не находилась в состоянии from
+    async fn transition_car_state(&self, car_id: Uuid, from: CarState, to: CarState) -> Result<(), TripError>;
+    async fn get_reservation_policy(&self, car_id: Uuid) -> Result<ReservationPolicy, TripError>;
+    // VIN, под которым машина шлет телеметрию
+    async fn get_iot_serial_number(&self, car_id: Uuid) -> Result<String, TripError>;
+}
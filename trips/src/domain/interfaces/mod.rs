@@ -1,4 +1,17 @@
 mod trip_repository;
+mod cars_client;
+mod billing_client;
+mod telematics_client;
+mod outbox_repository;
+mod event_publisher;
+mod no_show_fee_repository;
+mod car_transition_repository;
 
 pub use trip_repository::*;
-
+pub use cars_client::*;
+pub use billing_client::*;
+pub use telematics_client::*;
+pub use outbox_repository::*;
+pub use event_publisher::*;
+pub use no_show_fee_repository::*;
+pub use car_transition_repository::*;
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::domain::{
+    errors::TripError,
+    models::NoShowFee,
+};
+
+// Штрафы записывает TripRepository::expire_reservation в транзакции истечения резерва
+#[async_trait]
+pub trait NoShowFeeRepository {
+    async fn claim_due(&self, now: DateTime<Utc>, lease_until: DateTime<Utc>, limit: i64) -> Result<Vec<NoShowFee>, TripError>;
+    async fn mark_charged(&self, trip_id: Uuid, charged_at: DateTime<Utc>) -> Result<(), TripError>;
+    async fn mark_failed(&self, trip_id: Uuid, error: &str, next_attempt_at: DateTime<Utc>) -> Result<(), TripError>;
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::{
+    errors::TripError,
+    models::RoutePoint,
+};
+
+#[async_trait]
+pub trait TelematicsClient {
+    async fn get_route_points(&self, vin: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<RoutePoint>, TripError>;
+}
@@ -1,8 +1,9 @@
 use async_trait::async_trait;
 use uuid::Uuid;
+use chrono::{DateTime, Utc};
 use crate::domain::{
     errors::TripError,
-    models::Trip,
+    models::{Trip, TripStatus, TripRoute},
 };
 
 #[async_trait]
@@ -14,5 +15,14 @@ pub trait TripRepository {
     async fn find_active_by_user_id(&self, user_id: Uuid) -> Result<Option<Trip>, TripError>;
     async fn find_active_by_car_id(&self, car_id: Uuid) -> Result<Option<Trip>, TripError>;
     async fn update(&self, id: Uuid, trip: &Trip) -> Result<(), TripError>;
+    // false, если статус уже изменился (например, конкурентным запросом)
+    async fn update_if_status(&self, id: Uuid, expected: TripStatus, trip: &Trip) -> Result<bool, TripError>;
+    // Переводит резерв в expired и в той же транзакции записывает штраф за неявку к отправке в billing
+    async fn expire_reservation(&self, trip: &Trip) -> Result<bool, TripError>;
+    async fn find_expired_reservations(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Trip>, TripError>;
+    // Заменяет ранее записанный маршрут и проставляет пройденное расстояние
+    async fn save_route(&self, route: &TripRoute) -> Result<(), TripError>;
+    async fn find_route(&self, trip_id: Uuid) -> Result<Option<TripRoute>, TripError>;
+    async fn find_pending_routes(&self, limit: i64) -> Result<Vec<Trip>, TripError>;
 }
 
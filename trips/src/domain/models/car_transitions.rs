@@ -0,0 +1,19 @@
+use uuid::Uuid;
+use crate::domain::models::CarState;
+
+// Перевод машины в cars, который поездка должна довести до конца
+#[derive(Debug, Clone)]
+pub struct PendingCarTransition {
+    pub id: Uuid,
+    pub trip_id: Uuid,
+    pub car_id: Uuid,
+    pub from: CarState,
+    pub to: CarState,
+    pub attempts: u32,
+}
+
+impl PendingCarTransition {
+    pub fn new(trip_id: Uuid, car_id: Uuid, from: CarState, to: CarState) -> Self {
+        Self { id: Uuid::new_v4(), trip_id, car_id, from, to, attempts: 0 }
+    }
+}
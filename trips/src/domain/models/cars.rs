@@ -0,0 +1,37 @@
+use crate::domain::models::Money;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CarState {
+    Available,
+    Reserved,
+    InUse,
+}
+
+impl CarState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CarState::Available => "available",
+            CarState::Reserved => "reserved",
+            CarState::InUse => "in_use",
+        }
+    }
+}
+
+impl std::str::FromStr for CarState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "available" => Ok(CarState::Available),
+            "reserved" => Ok(CarState::Reserved),
+            "in_use" => Ok(CarState::InUse),
+            _ => Err(format!("Invalid car state: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReservationPolicy {
+    pub reservation_window_minutes: u32,
+    pub no_show_fee: Money,
+}
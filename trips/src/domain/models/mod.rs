@@ -1,4 +1,17 @@
 mod trips;
+mod cars;
+mod routes;
+mod money;
+mod outbox;
+mod trip_events;
+mod no_show_fees;
+mod car_transitions;
 
 pub use trips::*;
-
+pub use cars::*;
+pub use routes::*;
+pub use money::*;
+pub use outbox::*;
+pub use trip_events::*;
+pub use no_show_fees::*;
+pub use car_transitions::*;
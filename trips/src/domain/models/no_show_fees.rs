@@ -0,0 +1,11 @@
+use uuid::Uuid;
+use crate::domain::models::Money;
+
+// Штраф за неявку, ожидающий отправки в billing
+#[derive(Debug, Clone)]
+pub struct NoShowFee {
+    pub trip_id: Uuid,
+    pub user_id: Uuid,
+    pub amount: Money,
+    pub attempts: u32,
+}
@@ -0,0 +1,213 @@
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Duration, Utc};
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+// Смещения меньше погрешности GPS считаем дрожанием координат стоящей машины
+const GPS_JITTER_KM: f64 = 0.01;
+// Стоянкой считается время, которое машина провела в радиусе PARKING_RADIUS_KM не меньше MIN_PARKING_DURATION
+const PARKING_RADIUS_KM: f64 = 0.03;
+const MIN_PARKING_DURATION: Duration = Duration::minutes(2);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RoutePoint {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TripRoute {
+    pub trip_id: Uuid,
+    pub points: Vec<RoutePoint>,
+    pub distance_km: f64,
+    pub parking_minutes: u32,
+}
+
+impl TripRoute {
+    // Точка остается в маршруте, только если отошла от предыдущей оставленной дальше GPS_JITTER_KM:
+    // так медленное движение накапливается, а дрожание на стоянке не добавляет километров
+    pub fn from_points(trip_id: Uuid, mut points: Vec<RoutePoint>) -> Self {
+        points.sort_by_key(|point| point.recorded_at);
+        let parking_minutes = (parked_time(&points).num_seconds() / 60) as u32;
+        points.dedup_by(|next, previous| haversine_distance_km(previous, next) < GPS_JITTER_KM);
+
+        let distance_km = points
+            .windows(2)
+            .map(|pair| haversine_distance_km(&pair[0], &pair[1]))
+            .sum();
+
+        Self { trip_id, points, distance_km, parking_minutes }
+    }
+}
+
+fn parked_time(points: &[RoutePoint]) -> Duration {
+    let mut parked = Duration::zero();
+    let mut start = 0;
+
+    while start < points.len() {
+        let anchor = &points[start];
+        let end = points[start..]
+            .iter()
+            .position(|point| haversine_distance_km(anchor, point) >= PARKING_RADIUS_KM)
+            .map_or(points.len(), |offset| start + offset);
+
+        let stay = points[end - 1].recorded_at - anchor.recorded_at;
+        if stay >= MIN_PARKING_DURATION {
+            parked += stay;
+        }
+        start = end.max(start + 1);
+    }
+
+    parked
+}
+
+pub fn haversine_distance_km(from: &RoutePoint, to: &RoutePoint) -> f64 {
+    let lat1 = from.latitude.to_radians();
+    let lat2 = to.latitude.to_radians();
+    let delta_lat = (to.latitude - from.latitude).to_radians();
+    let delta_lon = (to.longitude - from.longitude).to_radians();
+
+    let a = (delta_lat / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * (delta_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn point(latitude: f64, longitude: f64, seconds: i64) -> RoutePoint {
+        RoutePoint {
+            latitude,
+            longitude,
+            recorded_at: Utc.with_ymd_and_hms(2024, 5, 10, 12, 0, 0).unwrap() + Duration::seconds(seconds),
+        }
+    }
+
+    fn assert_km(actual: f64, expected: f64, tolerance: f64) {
+        assert!((actual - expected).abs() <= tolerance, "expected {} km, got {} km", expected, actual);
+    }
+
+    #[test]
+    fn test_haversine_one_degree_of_meridian() {
+        // 2 * pi * 6371 / 360
+        assert_km(haversine_distance_km(&point(0.0, 0.0, 0), &point(1.0, 0.0, 0)), 111.195, 0.001);
+    }
+
+    #[test]
+    fn test_haversine_one_degree_of_equator() {
+        assert_km(haversine_distance_km(&point(0.0, 10.0, 0), &point(0.0, 11.0, 0)), 111.195, 0.001);
+    }
+
+    #[test]
+    fn test_haversine_paris_to_london() {
+        let paris = point(48.8566, 2.3522, 0);
+        let london = point(51.5074, -0.1278, 0);
+        assert_km(haversine_distance_km(&paris, &london), 343.56, 0.1);
+        assert_km(haversine_distance_km(&london, &paris), 343.56, 0.1);
+    }
+
+    #[test]
+    fn test_haversine_same_point_is_zero() {
+        assert_eq!(haversine_distance_km(&point(55.75, 37.61, 0), &point(55.75, 37.61, 0)), 0.0);
+    }
+
+    #[test]
+    fn test_route_sums_segments_in_time_order() {
+        // Точки пришли не по порядку: 0 -> 0.01 -> 0.02 градуса по меридиану
+        let route = TripRoute::from_points(
+            Uuid::new_v4(),
+            vec![point(0.02, 0.0, 20), point(0.0, 0.0, 0), point(0.01, 0.0, 10)],
+        );
+
+        assert_eq!(route.points.len(), 3);
+        assert_eq!(route.points[0].latitude, 0.0);
+        assert_eq!(route.points[2].latitude, 0.02);
+        assert_km(route.distance_km, 2.224, 0.001);
+    }
+
+    #[test]
+    fn test_route_ignores_jitter_of_parked_car() {
+        // Машина стоит: координаты дрожат в пределах нескольких метров (0.00003 градуса ~ 3.3 м)
+        let points = (0..100)
+            .map(|i| {
+                let offset = if i % 2 == 0 { 0.00003 } else { -0.00003 };
+                point(55.75 + offset, 37.61 - offset, i)
+            })
+            .collect();
+
+        let route = TripRoute::from_points(Uuid::new_v4(), points);
+
+        assert_eq!(route.points.len(), 1);
+        assert_eq!(route.distance_km, 0.0);
+    }
+
+    #[test]
+    fn test_route_accumulates_slow_movement() {
+        // Шаг 0.00005 градуса (~5.6 м) меньше порога дрожания, но 200 шагов - это 1.112 км
+        let points = (0..=200).map(|i| point(i as f64 * 0.00005, 0.0, i)).collect();
+
+        let route = TripRoute::from_points(Uuid::new_v4(), points);
+
+        assert_km(route.distance_km, 1.112, 0.01);
+    }
+
+    #[test]
+    fn test_route_without_points_is_empty() {
+        let route = TripRoute::from_points(Uuid::new_v4(), Vec::new());
+
+        assert!(route.points.is_empty());
+        assert_eq!(route.distance_km, 0.0);
+        assert_eq!(route.parking_minutes, 0);
+    }
+
+    #[test]
+    fn test_parking_counts_stay_with_jitter() {
+        // 3 минуты езды, 10 минут стоянки с дрожанием координат, еще 3 минуты езды
+        let mut points: Vec<RoutePoint> = (0..=18).map(|i| point(0.0, i as f64 * 0.001, i * 10)).collect();
+        points.extend((1..=20).map(|i| {
+            let offset = if i % 2 == 0 { 0.00003 } else { -0.00003 };
+            point(offset, 0.018, 180 + i * 30)
+        }));
+        points.extend((1..=18).map(|i| point(0.0, 0.018 + i as f64 * 0.001, 780 + i * 10)));
+
+        let route = TripRoute::from_points(Uuid::new_v4(), points);
+
+        assert_eq!(route.parking_minutes, 10);
+        assert_km(route.distance_km, 36.0 * 0.111195, 0.01);
+    }
+
+    #[test]
+    fn test_parking_counts_gap_without_telemetry() {
+        // Машина уснула на стоянке: между показаниями час без движения
+        let route = TripRoute::from_points(
+            Uuid::new_v4(),
+            vec![point(0.0, 0.0, 0), point(0.0, 0.01, 60), point(0.0, 0.01, 3660), point(0.0, 0.02, 3720)],
+        );
+
+        assert_eq!(route.parking_minutes, 60);
+    }
+
+    #[test]
+    fn test_short_stop_is_not_parking() {
+        // Остановка на светофоре меньше MIN_PARKING_DURATION
+        let route = TripRoute::from_points(
+            Uuid::new_v4(),
+            vec![point(0.0, 0.0, 0), point(0.0, 0.01, 60), point(0.0, 0.01, 150), point(0.0, 0.02, 210)],
+        );
+
+        assert_eq!(route.parking_minutes, 0);
+    }
+
+    #[test]
+    fn test_driving_is_not_parking() {
+        // Равномерное движение ~40 км/ч: показание каждые 10 секунд, шаг ~111 м
+        let points = (0..=60).map(|i| point(0.0, i as f64 * 0.001, i * 10)).collect();
+
+        let route = TripRoute::from_points(Uuid::new_v4(), points);
+
+        assert_eq!(route.parking_minutes, 0);
+    }
+}
@@ -0,0 +1,35 @@
+use serde::Serialize;
+use crate::domain::models::{OutboxEvent, Trip, TripStatus};
+
+pub const TRIP_AGGREGATE: &str = "trip";
+
+// Полный снимок поездки и прежний статус
+#[derive(Debug, Serialize)]
+pub struct TripStatusChanged<'a> {
+    #[serde(flatten)]
+    pub trip: &'a Trip,
+    pub previous_status: Option<TripStatus>,
+}
+
+// Откат перехода при компенсации - TripStatusRestored
+pub fn trip_event_type(previous: Option<TripStatus>, current: TripStatus) -> &'static str {
+    match (previous, current) {
+        (None, TripStatus::Reserved) => "TripReserved",
+        (Some(TripStatus::Reserved), TripStatus::Active) => "TripStarted",
+        (Some(TripStatus::Active), TripStatus::Completed) => "TripEnded",
+        (Some(_), TripStatus::Cancelled) => "TripCancelled",
+        (Some(TripStatus::Reserved), TripStatus::Expired) => "TripExpired",
+        _ => "TripStatusRestored",
+    }
+}
+
+impl Trip {
+    pub fn status_event(&self, previous: Option<TripStatus>) -> Result<OutboxEvent, serde_json::Error> {
+        OutboxEvent::new(
+            TRIP_AGGREGATE,
+            self.id,
+            trip_event_type(previous, self.status),
+            &TripStatusChanged { trip: self, previous_status: previous },
+        )
+    }
+}
@@ -1,6 +1,7 @@
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::domain::models::Money;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Trip {
@@ -12,6 +13,19 @@ pub struct Trip {
     pub ended_at: Option<DateTime<Utc>>,
     pub cancelled_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>, // до какого момента резерв ждет активации
+    pub no_show_fee: Money, // штраф за неявку, зафиксированный по тарифу на момент резерва
+    pub distance_km: Option<f64>, // пройденное расстояние, известно после записи маршрута
+    pub parking_minutes: Option<u32>, // время стоянки в поездке, известно после записи маршрута
+}
+
+impl Trip {
+    // Поездка закрыта после активации, а маршрут и пройденное расстояние еще не записаны
+    pub fn route_pending(&self) -> bool {
+        matches!(self.status, TripStatus::Completed | TripStatus::Cancelled)
+            && self.started_at.is_some()
+            && (self.distance_km.is_none() || self.parking_minutes.is_none())
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -21,6 +35,7 @@ pub enum TripStatus {
     Active,     // Активная поездка
     Completed, // Завершена
     Cancelled,  // Отменена
+    Expired,    // Резерв истек без активации
 }
 
 impl TripStatus {
@@ -30,6 +45,7 @@ impl TripStatus {
             TripStatus::Active => "active",
             TripStatus::Completed => "completed",
             TripStatus::Cancelled => "cancelled",
+            TripStatus::Expired => "expired",
         }
     }
 }
@@ -43,6 +59,7 @@ impl std::str::FromStr for TripStatus {
             "active" => Ok(TripStatus::Active),
             "completed" => Ok(TripStatus::Completed),
             "cancelled" => Ok(TripStatus::Cancelled),
+            "expired" => Ok(TripStatus::Expired),
             _ => Err(format!("Invalid trip status: {}", s)),
         }
     }
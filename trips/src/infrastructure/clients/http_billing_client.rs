@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Serialize;
+use uuid::Uuid;
+use tracing::{info, error};
+use crate::domain::{
+    errors::TripError,
+    interfaces::BillingClient,
+    models::Money,
+};
+
+#[derive(Serialize)]
+struct CreatePaymentRequest {
+    trip_id: Uuid,
+    user_id: Uuid,
+    amount: Money,
+}
+
+pub struct HttpBillingClient {
+    client: Client,
+    base_url: String,
+}
+
+impl HttpBillingClient {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url,
+        }
+    }
+}
+
+impl Clone for HttpBillingClient {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            base_url: self.base_url.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl BillingClient for HttpBillingClient {
+    async fn charge_no_show_fee(&self, trip_id: Uuid, user_id: Uuid, amount: Money) -> Result<(), TripError> {
+        let url = format!("{}/payments", self.base_url);
+        info!("Calling billing service: POST {} (no-show fee for trip {})", url, trip_id);
+
+        let response = self.client
+            .post(&url)
+            .json(&CreatePaymentRequest { trip_id, user_id, amount })
+            .send()
+            .await
+            .map_err(|e| TripError::BillingService(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else if status == reqwest::StatusCode::CONFLICT {
+            // Платеж по поездке уже создан: прошлая попытка дошла, но ответ потерялся
+            info!("No-show fee for trip {} was already charged", trip_id);
+            Ok(())
+        } else {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Billing service error: {} - {}", status, error_text);
+            Err(TripError::BillingService(format!("{}: {}", status, error_text)))
+        }
+    }
+}
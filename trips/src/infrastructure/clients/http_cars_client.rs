@@ -0,0 +1,155 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use tracing::{info, error};
+use crate::domain::{
+    errors::TripError,
+    interfaces::CarsClient,
+    models::{CarState, Money, ReservationPolicy},
+};
+
+#[derive(Serialize)]
+struct TransitionCarStateRequest<'a> {
+    from: &'a str,
+    to: &'a str,
+}
+
+#[derive(Deserialize)]
+struct CarInfo {
+    tariff_id: Uuid,
+    iot_serial_number: String,
+}
+
+#[derive(Deserialize)]
+struct TariffInfo {
+    reservation_window_minutes: u32,
+    no_show_fee: Money,
+}
+
+pub struct HttpCarsClient {
+    client: Client,
+    base_url: String,
+}
+
+impl HttpCarsClient {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url,
+        }
+    }
+}
+
+impl Clone for HttpCarsClient {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            base_url: self.base_url.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl CarsClient for HttpCarsClient {
+    async fn transition_car_state(&self, car_id: Uuid, from: CarState, to: CarState) -> Result<(), TripError> {
+        let url = format!("{}/cars/{}/state", self.base_url, car_id);
+        info!("Calling cars service: PUT {} ({} -> {})", url, from.as_str(), to.as_str());
+
+        let response = self.client
+            .put(&url)
+            .json(&TransitionCarStateRequest {
+                from: from.as_str(),
+                to: to.as_str(),
+            })
+            .send()
+            .await
+            .map_err(|e| TripError::CarsService(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else if status == reqwest::StatusCode::NOT_FOUND {
+            Err(TripError::CarNotFound)
+        } else if status == reqwest::StatusCode::CONFLICT {
+            Err(TripError::CarStateConflict {
+                expected: from.as_str().to_string(),
+            })
+        } else {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Cars service error: {} - {}", status, error_text);
+            Err(TripError::CarsService(format!("{}: {}", status, error_text)))
+        }
+    }
+
+    async fn get_reservation_policy(&self, car_id: Uuid) -> Result<ReservationPolicy, TripError> {
+        let url = format!("{}/cars/{}", self.base_url, car_id);
+        info!("Calling cars service: GET {}", url);
+
+        let response = self.client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| TripError::CarsService(e.to_string()))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(TripError::CarNotFound);
+        }
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Cars service error: {} - {}", status, error_text);
+            return Err(TripError::CarsService(format!("{}: {}", status, error_text)));
+        }
+        let car: CarInfo = response.json().await
+            .map_err(|e| TripError::CarsService(e.to_string()))?;
+
+        let url = format!("{}/tariffs/{}", self.base_url, car.tariff_id);
+        info!("Calling cars service: GET {}", url);
+
+        let response = self.client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| TripError::CarsService(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Cars service error: {} - {}", status, error_text);
+            return Err(TripError::CarsService(format!("{}: {}", status, error_text)));
+        }
+        let tariff: TariffInfo = response.json().await
+            .map_err(|e| TripError::CarsService(e.to_string()))?;
+
+        Ok(ReservationPolicy {
+            reservation_window_minutes: tariff.reservation_window_minutes,
+            no_show_fee: tariff.no_show_fee,
+        })
+    }
+
+    async fn get_iot_serial_number(&self, car_id: Uuid) -> Result<String, TripError> {
+        let url = format!("{}/cars/{}", self.base_url, car_id);
+        info!("Calling cars service: GET {}", url);
+
+        let response = self.client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| TripError::CarsService(e.to_string()))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(TripError::CarNotFound);
+        }
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Cars service error: {} - {}", status, error_text);
+            return Err(TripError::CarsService(format!("{}: {}", status, error_text)));
+        }
+        let car: CarInfo = response.json().await
+            .map_err(|e| TripError::CarsService(e.to_string()))?;
+
+        Ok(car.iot_serial_number)
+    }
+}
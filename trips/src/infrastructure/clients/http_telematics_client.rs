@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use tracing::{info, error};
+use crate::domain::{
+    errors::TripError,
+    interfaces::TelematicsClient,
+    models::RoutePoint,
+};
+
+#[derive(Deserialize)]
+struct LocationInfo {
+    latitude: f64,
+    longitude: f64,
+}
+
+#[derive(Deserialize)]
+struct SensorHistoryPointInfo {
+    timestamp: DateTime<Utc>,
+    location: LocationInfo,
+}
+
+pub struct HttpTelematicsClient {
+    client: Client,
+    base_url: String,
+}
+
+impl HttpTelematicsClient {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url,
+        }
+    }
+}
+
+impl Clone for HttpTelematicsClient {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            base_url: self.base_url.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl TelematicsClient for HttpTelematicsClient {
+    async fn get_route_points(&self, vin: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<RoutePoint>, TripError> {
+        let url = format!("{}/sensors/{}/history", self.base_url, vin);
+        info!("Calling telematics service: GET {} ({} - {})", url, from, to);
+
+        // Без resolution telematics возвращает все показания за интервал
+        let response = self.client
+            .get(&url)
+            .query(&[("from", from.to_rfc3339()), ("to", to.to_rfc3339())])
+            .send()
+            .await
+            .map_err(|e| TripError::TelematicsService(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Telematics service error: {} - {}", status, error_text);
+            return Err(TripError::TelematicsService(format!("{}: {}", status, error_text)));
+        }
+        let history: Vec<SensorHistoryPointInfo> = response.json().await
+            .map_err(|e| TripError::TelematicsService(e.to_string()))?;
+
+        Ok(history
+            .into_iter()
+            .map(|point| RoutePoint {
+                latitude: point.location.latitude,
+                longitude: point.location.longitude,
+                recorded_at: point.timestamp,
+            })
+            .collect())
+    }
+}
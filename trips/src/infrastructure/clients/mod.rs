@@ -0,0 +1,7 @@
+mod http_cars_client;
+mod http_billing_client;
+mod http_telematics_client;
+
+pub use http_cars_client::*;
+pub use http_billing_client::*;
+pub use http_telematics_client::*;
@@ -1,4 +1,7 @@
 pub mod repositories;
+pub mod clients;
+pub mod messaging;
 
 pub use repositories::*;
-
+pub use clients::*;
+pub use messaging::*;
@@ -1,4 +1,10 @@
 mod postgres_trip_repository;
+mod postgres_outbox_repository;
+mod postgres_no_show_fee_repository;
+mod postgres_car_transition_repository;
 
 pub use postgres_trip_repository::*;
+pub use postgres_outbox_repository::*;
+pub use postgres_no_show_fee_repository::*;
+pub use postgres_car_transition_repository::*;
 
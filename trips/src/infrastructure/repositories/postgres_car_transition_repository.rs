@@ -0,0 +1,122 @@
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::domain::{
+    errors::TripError,
+    interfaces::CarTransitionRepository,
+    models::{CarState, PendingCarTransition},
+};
+
+fn car_state_from_str(s: &str) -> Result<CarState, TripError> {
+    s.parse().map_err(|e: String| TripError::Internal(anyhow::anyhow!(e)))
+}
+
+pub struct PostgresCarTransitionRepository {
+    pool: PgPool,
+}
+
+impl PostgresCarTransitionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl Clone for PostgresCarTransitionRepository {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl CarTransitionRepository for PostgresCarTransitionRepository {
+    async fn enqueue(&self, transition: &PendingCarTransition, not_before: DateTime<Utc>) -> Result<(), TripError> {
+        sqlx::query(
+            r#"
+            INSERT INTO car_transitions (id, trip_id, car_id, from_state, to_state, next_attempt_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(transition.id)
+        .bind(transition.trip_id)
+        .bind(transition.car_id)
+        .bind(transition.from.as_str())
+        .bind(transition.to.as_str())
+        .bind(not_before)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn claim_due(&self, now: DateTime<Utc>, lease_until: DateTime<Utc>, limit: i64) -> Result<Vec<PendingCarTransition>, TripError> {
+        let rows = sqlx::query(
+            r#"
+            UPDATE car_transitions
+            SET locked_until = $2
+            WHERE id IN (
+                SELECT id
+                FROM car_transitions
+                WHERE done_at IS NULL
+                  AND next_attempt_at <= $1
+                  AND (locked_until IS NULL OR locked_until < $1)
+                ORDER BY next_attempt_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, trip_id, car_id, from_state, to_state, attempts
+            "#,
+        )
+        .bind(now)
+        .bind(lease_until)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| {
+            Ok(PendingCarTransition {
+                id: r.get("id"),
+                trip_id: r.get("trip_id"),
+                car_id: r.get("car_id"),
+                from: car_state_from_str(r.get("from_state"))?,
+                to: car_state_from_str(r.get("to_state"))?,
+                attempts: r.get::<i32, _>("attempts") as u32,
+            })
+        }).collect()
+    }
+
+    async fn mark_done(&self, id: Uuid, done_at: DateTime<Utc>) -> Result<(), TripError> {
+        sqlx::query(
+            r#"
+            UPDATE car_transitions
+            SET done_at = $2, locked_until = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(done_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: Uuid, error: &str, next_attempt_at: DateTime<Utc>) -> Result<(), TripError> {
+        sqlx::query(
+            r#"
+            UPDATE car_transitions
+            SET attempts = attempts + 1, last_error = $2, next_attempt_at = $3, locked_until = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .bind(next_attempt_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
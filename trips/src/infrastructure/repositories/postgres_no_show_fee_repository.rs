@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use sqlx::{PgConnection, PgPool, Row};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::domain::{
+    errors::TripError,
+    interfaces::NoShowFeeRepository,
+    models::{Money, NoShowFee},
+};
+
+// Вызывается в транзакции истечения резерва: штраф не теряется, если billing недоступен
+pub(crate) async fn enqueue_no_show_fee(conn: &mut PgConnection, fee: &NoShowFee) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO no_show_fees (trip_id, user_id, amount_minor, currency)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (trip_id) DO NOTHING
+        "#,
+    )
+    .bind(fee.trip_id)
+    .bind(fee.user_id)
+    .bind(fee.amount.minor_units)
+    .bind(fee.amount.currency.as_str())
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+pub struct PostgresNoShowFeeRepository {
+    pool: PgPool,
+}
+
+impl PostgresNoShowFeeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl Clone for PostgresNoShowFeeRepository {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl NoShowFeeRepository for PostgresNoShowFeeRepository {
+    async fn claim_due(&self, now: DateTime<Utc>, lease_until: DateTime<Utc>, limit: i64) -> Result<Vec<NoShowFee>, TripError> {
+        let rows = sqlx::query(
+            r#"
+            UPDATE no_show_fees
+            SET locked_until = $2
+            WHERE trip_id IN (
+                SELECT trip_id
+                FROM no_show_fees
+                WHERE charged_at IS NULL
+                  AND next_attempt_at <= $1
+                  AND (locked_until IS NULL OR locked_until < $1)
+                ORDER BY next_attempt_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING trip_id, user_id, amount_minor, currency, attempts
+            "#,
+        )
+        .bind(now)
+        .bind(lease_until)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| {
+            Ok(NoShowFee {
+                trip_id: r.get("trip_id"),
+                user_id: r.get("user_id"),
+                amount: Money::new(
+                    r.get("amount_minor"),
+                    r.get::<String, _>("currency")
+                        .parse()
+                        .map_err(|e: String| TripError::Internal(anyhow::anyhow!(e)))?,
+                ),
+                attempts: r.get::<i32, _>("attempts") as u32,
+            })
+        }).collect()
+    }
+
+    async fn mark_charged(&self, trip_id: Uuid, charged_at: DateTime<Utc>) -> Result<(), TripError> {
+        sqlx::query(
+            r#"
+            UPDATE no_show_fees
+            SET charged_at = $2, locked_until = NULL, last_error = NULL
+            WHERE trip_id = $1
+            "#,
+        )
+        .bind(trip_id)
+        .bind(charged_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_failed(&self, trip_id: Uuid, error: &str, next_attempt_at: DateTime<Utc>) -> Result<(), TripError> {
+        sqlx::query(
+            r#"
+            UPDATE no_show_fees
+            SET attempts = attempts + 1, last_error = $2, next_attempt_at = $3, locked_until = NULL
+            WHERE trip_id = $1
+            "#,
+        )
+        .bind(trip_id)
+        .bind(error)
+        .bind(next_attempt_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use async_trait::async_trait;
+use sqlx::{PgConnection, PgPool, Row};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::domain::{
+    errors::TripError,
+    interfaces::OutboxRepository,
+    models::OutboxEvent,
+};
+
+pub(crate) async fn enqueue_event(conn: &mut PgConnection, event: &OutboxEvent) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO outbox_events (id, aggregate_type, aggregate_id, event_type, payload, occurred_at, next_attempt_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $6)
+        "#,
+    )
+    .bind(event.id)
+    .bind(&event.aggregate_type)
+    .bind(event.aggregate_id)
+    .bind(&event.event_type)
+    .bind(&event.payload)
+    .bind(event.occurred_at)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+pub struct PostgresOutboxRepository {
+    pool: PgPool,
+}
+
+impl PostgresOutboxRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl Clone for PostgresOutboxRepository {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for PostgresOutboxRepository {
+    async fn claim_pending(&self, now: DateTime<Utc>, lease_until: DateTime<Utc>, limit: i64) -> Result<Vec<OutboxEvent>, TripError> {
+        let rows = sqlx::query(
+            r#"
+            UPDATE outbox_events
+            SET locked_until = $2
+            WHERE id IN (
+                SELECT e.id
+                FROM outbox_events e
+                WHERE e.published_at IS NULL
+                  AND e.next_attempt_at <= $1
+                  AND (e.locked_until IS NULL OR e.locked_until < $1)
+                  AND NOT EXISTS (
+                      SELECT 1
+                      FROM outbox_events earlier
+                      WHERE earlier.aggregate_id = e.aggregate_id
+                        AND earlier.published_at IS NULL
+                        AND earlier.occurred_at < e.occurred_at
+                  )
+                ORDER BY e.occurred_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, aggregate_type, aggregate_id, event_type, payload, occurred_at, attempts
+            "#,
+        )
+        .bind(now)
+        .bind(lease_until)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut events: Vec<OutboxEvent> = rows.into_iter().map(|r| OutboxEvent {
+            id: r.get("id"),
+            aggregate_type: r.get("aggregate_type"),
+            aggregate_id: r.get("aggregate_id"),
+            event_type: r.get("event_type"),
+            payload: r.get("payload"),
+            occurred_at: r.get("occurred_at"),
+            attempts: r.get::<i32, _>("attempts") as u32,
+        }).collect();
+        events.sort_by_key(|e| e.occurred_at);
+
+        Ok(events)
+    }
+
+    async fn mark_published(&self, id: Uuid, published_at: DateTime<Utc>) -> Result<(), TripError> {
+        sqlx::query(
+            r#"
+            UPDATE outbox_events
+            SET published_at = $2, locked_until = NULL, last_error = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(published_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: Uuid, error: &str, next_attempt_at: DateTime<Utc>) -> Result<(), TripError> {
+        sqlx::query(
+            r#"
+            UPDATE outbox_events
+            SET attempts = attempts + 1, last_error = $2, next_attempt_at = $3, locked_until = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .bind(next_attempt_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_published_before(&self, cutoff: DateTime<Utc>) -> Result<u64, TripError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM outbox_events
+            WHERE published_at IS NOT NULL AND published_at < $1
+            "#,
+        )
+        .bind(cutoff)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -1,11 +1,13 @@
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{postgres::PgRow, PgConnection, PgPool, Row};
 use uuid::Uuid;
+use chrono::{DateTime, Utc};
 use crate::domain::{
     errors::TripError,
     interfaces::TripRepository,
-    models::{Trip, TripStatus},
+    models::{Money, NoShowFee, OutboxEvent, Trip, TripStatus, TripRoute, RoutePoint, TRIP_AGGREGATE},
 };
+use super::{enqueue_event, enqueue_no_show_fee};
 
 pub struct PostgresTripRepository {
     pool: PgPool,
@@ -31,17 +33,71 @@ fn trip_status_from_str(s: &str) -> TripStatus {
         "active" => TripStatus::Active,
         "completed" => TripStatus::Completed,
         "cancelled" => TripStatus::Cancelled,
+        "expired" => TripStatus::Expired,
         _ => TripStatus::Reserved, // default
     }
 }
 
+fn trip_from_row(r: &PgRow) -> Result<Trip, TripError> {
+    let currency = r
+        .get::<String, _>("currency")
+        .parse()
+        .map_err(|e: String| TripError::Internal(anyhow::anyhow!(e)))?;
+    Ok(Trip {
+        id: r.get("id"),
+        user_id: r.get("user_id"),
+        car_id: r.get("car_id"),
+        status: trip_status_from_str(r.get::<String, _>("status").as_str()),
+        started_at: r.get("started_at"),
+        ended_at: r.get("ended_at"),
+        cancelled_at: r.get("cancelled_at"),
+        created_at: r.get("created_at"),
+        expires_at: r.get("expires_at"),
+        no_show_fee: Money::new(r.get("no_show_fee_minor"), currency),
+        distance_km: r.get("distance_km"),
+        parking_minutes: r.get::<Option<i32>, _>("parking_minutes").map(|minutes| minutes as u32),
+    })
+}
+
+// Меняет статус, только если текущий равен `expected`, и пишет событие смены статуса в outbox
+async fn update_status_guarded(conn: &mut PgConnection, id: Uuid, expected: TripStatus, trip: &Trip) -> Result<bool, TripError> {
+    let result = sqlx::query(
+        r#"
+        UPDATE trips
+        SET status = $3, started_at = $4, ended_at = $5, cancelled_at = $6
+        WHERE id = $1 AND status = $2
+        "#,
+    )
+    .bind(id)
+    .bind(expected.as_str())
+    .bind(trip.status.as_str())
+    .bind(trip.started_at)
+    .bind(trip.ended_at)
+    .bind(trip.cancelled_at)
+    .execute(&mut *conn)
+    .await?;
+
+    if result.rows_affected() != 1 {
+        return Ok(false);
+    }
+    if expected != trip.status {
+        let event = trip.status_event(Some(expected)).map_err(anyhow::Error::from)?;
+        enqueue_event(conn, &event).await?;
+    }
+
+    Ok(true)
+}
+
 #[async_trait]
 impl TripRepository for PostgresTripRepository {
     async fn create(&self, trip: &Trip) -> Result<(), TripError> {
+        let event = trip.status_event(None).map_err(anyhow::Error::from)?;
+        let mut tx = self.pool.begin().await?;
+
         sqlx::query(
             r#"
-            INSERT INTO trips (id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO trips (id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at, expires_at, no_show_fee_minor, currency)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             "#,
         )
         .bind(trip.id)
@@ -52,16 +108,32 @@ impl TripRepository for PostgresTripRepository {
         .bind(trip.ended_at)
         .bind(trip.cancelled_at)
         .bind(trip.created_at)
-        .execute(&self.pool)
-        .await?;
-        
+        .bind(trip.expires_at)
+        .bind(trip.no_show_fee.minor_units)
+        .bind(trip.no_show_fee.currency.as_str())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            // Нарушение уникальных индексов на открытые поездки (см. миграцию 002)
+            if let sqlx::Error::Database(db_err) = &e {
+                match db_err.constraint() {
+                    Some("uq_trips_car_open") => return TripError::CarAlreadyInUse,
+                    Some("uq_trips_user_open") => return TripError::UserHasActiveTrip,
+                    _ => {}
+                }
+            }
+            TripError::Database(e)
+        })?;
+
+        enqueue_event(&mut tx, &event).await?;
+        tx.commit().await?;
         Ok(())
     }
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Trip>, TripError> {
         let row = sqlx::query(
             r#"
-            SELECT id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at
+            SELECT id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at, expires_at, no_show_fee_minor, currency, distance_km, parking_minutes
             FROM trips
             WHERE id = $1
             "#,
@@ -70,22 +142,13 @@ impl TripRepository for PostgresTripRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.map(|r| Trip {
-            id: r.get("id"),
-            user_id: r.get("user_id"),
-            car_id: r.get("car_id"),
-            status: trip_status_from_str(r.get::<String, _>("status").as_str()),
-            started_at: r.get("started_at"),
-            ended_at: r.get("ended_at"),
-            cancelled_at: r.get("cancelled_at"),
-            created_at: r.get("created_at"),
-        }))
+        row.as_ref().map(trip_from_row).transpose()
     }
 
     async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Trip>, TripError> {
         let rows = sqlx::query(
             r#"
-            SELECT id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at
+            SELECT id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at, expires_at, no_show_fee_minor, currency, distance_km, parking_minutes
             FROM trips
             WHERE user_id = $1
             ORDER BY created_at DESC
@@ -95,22 +158,13 @@ impl TripRepository for PostgresTripRepository {
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().map(|r| Trip {
-            id: r.get("id"),
-            user_id: r.get("user_id"),
-            car_id: r.get("car_id"),
-            status: trip_status_from_str(r.get::<String, _>("status").as_str()),
-            started_at: r.get("started_at"),
-            ended_at: r.get("ended_at"),
-            cancelled_at: r.get("cancelled_at"),
-            created_at: r.get("created_at"),
-        }).collect())
+        rows.iter().map(trip_from_row).collect()
     }
 
     async fn find_all(&self) -> Result<Vec<Trip>, TripError> {
         let rows = sqlx::query(
             r#"
-            SELECT id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at
+            SELECT id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at, expires_at, no_show_fee_minor, currency, distance_km, parking_minutes
             FROM trips
             ORDER BY created_at DESC
             "#,
@@ -118,22 +172,13 @@ impl TripRepository for PostgresTripRepository {
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().map(|r| Trip {
-            id: r.get("id"),
-            user_id: r.get("user_id"),
-            car_id: r.get("car_id"),
-            status: trip_status_from_str(r.get::<String, _>("status").as_str()),
-            started_at: r.get("started_at"),
-            ended_at: r.get("ended_at"),
-            cancelled_at: r.get("cancelled_at"),
-            created_at: r.get("created_at"),
-        }).collect())
+        rows.iter().map(trip_from_row).collect()
     }
 
     async fn find_active_by_user_id(&self, user_id: Uuid) -> Result<Option<Trip>, TripError> {
         let row = sqlx::query(
             r#"
-            SELECT id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at
+            SELECT id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at, expires_at, no_show_fee_minor, currency, distance_km, parking_minutes
             FROM trips
             WHERE user_id = $1 AND status IN ('reserved', 'active')
             ORDER BY created_at DESC
@@ -144,22 +189,13 @@ impl TripRepository for PostgresTripRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.map(|r| Trip {
-            id: r.get("id"),
-            user_id: r.get("user_id"),
-            car_id: r.get("car_id"),
-            status: trip_status_from_str(r.get::<String, _>("status").as_str()),
-            started_at: r.get("started_at"),
-            ended_at: r.get("ended_at"),
-            cancelled_at: r.get("cancelled_at"),
-            created_at: r.get("created_at"),
-        }))
+        row.as_ref().map(trip_from_row).transpose()
     }
 
     async fn find_active_by_car_id(&self, car_id: Uuid) -> Result<Option<Trip>, TripError> {
         let row = sqlx::query(
             r#"
-            SELECT id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at
+            SELECT id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at, expires_at, no_show_fee_minor, currency, distance_km, parking_minutes
             FROM trips
             WHERE car_id = $1 AND status IN ('reserved', 'active')
             ORDER BY created_at DESC
@@ -170,19 +206,18 @@ impl TripRepository for PostgresTripRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.map(|r| Trip {
-            id: r.get("id"),
-            user_id: r.get("user_id"),
-            car_id: r.get("car_id"),
-            status: trip_status_from_str(r.get::<String, _>("status").as_str()),
-            started_at: r.get("started_at"),
-            ended_at: r.get("ended_at"),
-            cancelled_at: r.get("cancelled_at"),
-            created_at: r.get("created_at"),
-        }))
+        row.as_ref().map(trip_from_row).transpose()
     }
 
     async fn update(&self, id: Uuid, trip: &Trip) -> Result<(), TripError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Прежний статус нужен, чтобы опубликовать событие только при его смене
+        let previous: Option<String> = sqlx::query_scalar("SELECT status FROM trips WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
         sqlx::query(
             r#"
             UPDATE trips
@@ -197,10 +232,171 @@ impl TripRepository for PostgresTripRepository {
         .bind(trip.started_at)
         .bind(trip.ended_at)
         .bind(trip.cancelled_at)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
-        
+
+        if let Some(previous) = previous.map(|s| trip_status_from_str(&s)) {
+            if previous != trip.status {
+                let event = trip.status_event(Some(previous)).map_err(anyhow::Error::from)?;
+                enqueue_event(&mut tx, &event).await?;
+            }
+        }
+
+        tx.commit().await?;
         Ok(())
     }
-}
 
+    async fn update_if_status(&self, id: Uuid, expected: TripStatus, trip: &Trip) -> Result<bool, TripError> {
+        let mut tx = self.pool.begin().await?;
+        if !update_status_guarded(&mut tx, id, expected, trip).await? {
+            return Ok(false);
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn expire_reservation(&self, trip: &Trip) -> Result<bool, TripError> {
+        let mut tx = self.pool.begin().await?;
+        if !update_status_guarded(&mut tx, trip.id, TripStatus::Reserved, trip).await? {
+            return Ok(false);
+        }
+        if trip.no_show_fee.is_positive() {
+            enqueue_no_show_fee(&mut tx, &NoShowFee {
+                trip_id: trip.id,
+                user_id: trip.user_id,
+                amount: trip.no_show_fee,
+                attempts: 0,
+            }).await?;
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn find_expired_reservations(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Trip>, TripError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at, expires_at, no_show_fee_minor, currency, distance_km, parking_minutes
+            FROM trips
+            WHERE status = 'reserved' AND expires_at IS NOT NULL AND expires_at <= $1
+            ORDER BY expires_at
+            LIMIT $2
+            "#,
+        )
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(trip_from_row).collect()
+    }
+
+    async fn save_route(&self, route: &TripRoute) -> Result<(), TripError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM trip_route_points WHERE trip_id = $1")
+            .bind(route.trip_id)
+            .execute(&mut *tx)
+            .await?;
+
+        if !route.points.is_empty() {
+            let sequence: Vec<i32> = (0..route.points.len() as i32).collect();
+            let latitudes: Vec<f64> = route.points.iter().map(|p| p.latitude).collect();
+            let longitudes: Vec<f64> = route.points.iter().map(|p| p.longitude).collect();
+            let recorded_at: Vec<DateTime<Utc>> = route.points.iter().map(|p| p.recorded_at).collect();
+
+            sqlx::query(
+                r#"
+                INSERT INTO trip_route_points (trip_id, seq, latitude, longitude, recorded_at)
+                SELECT $1, * FROM UNNEST($2::INTEGER[], $3::DOUBLE PRECISION[], $4::DOUBLE PRECISION[], $5::TIMESTAMPTZ[])
+                "#,
+            )
+            .bind(route.trip_id)
+            .bind(&sequence)
+            .bind(&latitudes)
+            .bind(&longitudes)
+            .bind(&recorded_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query("UPDATE trips SET distance_km = $2, parking_minutes = $3 WHERE id = $1")
+            .bind(route.trip_id)
+            .bind(route.distance_km)
+            .bind(route.parking_minutes as i32)
+            .execute(&mut *tx)
+            .await?;
+
+        let event = OutboxEvent::new(
+            TRIP_AGGREGATE,
+            route.trip_id,
+            "TripRouteRecorded",
+            &serde_json::json!({
+                "trip_id": route.trip_id,
+                "distance_km": route.distance_km,
+                "parking_minutes": route.parking_minutes,
+                "points": route.points.len(),
+            }),
+        )
+        .map_err(anyhow::Error::from)?;
+        enqueue_event(&mut tx, &event).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_route(&self, trip_id: Uuid) -> Result<Option<TripRoute>, TripError> {
+        // Маршрут считается записанным, когда у поездки заполнены расстояние и время стоянки
+        let recorded: Option<(Option<f64>, Option<i32>)> =
+            sqlx::query_as("SELECT distance_km, parking_minutes FROM trips WHERE id = $1")
+                .bind(trip_id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        let Some((Some(distance_km), Some(parking_minutes))) = recorded else {
+            return Ok(None);
+        };
+
+        let rows = sqlx::query(
+            r#"
+            SELECT latitude, longitude, recorded_at
+            FROM trip_route_points
+            WHERE trip_id = $1
+            ORDER BY seq
+            "#,
+        )
+        .bind(trip_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Some(TripRoute {
+            trip_id,
+            points: rows.into_iter().map(|r| RoutePoint {
+                latitude: r.get("latitude"),
+                longitude: r.get("longitude"),
+                recorded_at: r.get("recorded_at"),
+            }).collect(),
+            distance_km,
+            parking_minutes: parking_minutes as u32,
+        }))
+    }
+
+    async fn find_pending_routes(&self, limit: i64) -> Result<Vec<Trip>, TripError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, user_id, car_id, status, started_at, ended_at, cancelled_at, created_at, expires_at, no_show_fee_minor, currency, distance_km, parking_minutes
+            FROM trips
+            WHERE status IN ('completed', 'cancelled') AND started_at IS NOT NULL
+              AND (distance_km IS NULL OR parking_minutes IS NULL)
+            ORDER BY COALESCE(ended_at, cancelled_at)
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(trip_from_row).collect()
+    }
+}
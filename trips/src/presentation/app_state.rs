@@ -2,26 +2,34 @@ use std::sync::Arc;
 use crate::{
     application::use_cases::{
         StartTripUseCase, ActivateTripUseCase, EndTripUseCase, CancelTripUseCase, GetTripUseCase, GetUserTripsUseCase, GetAllTripsUseCase,
+        GetTripRouteUseCase,
     },
-    domain::interfaces::TripRepository,
+    domain::interfaces::{TripRepository, CarTransitionRepository, CarsClient, TelematicsClient},
 };
 
-pub struct AppState<R>
+pub struct AppState<R, P, C, T>
 where
     R: TripRepository + Send + Sync + 'static,
+    P: CarTransitionRepository + Send + Sync + 'static,
+    C: CarsClient + Send + Sync + 'static,
+    T: TelematicsClient + Send + Sync + 'static,
 {
-    pub start_trip_use_case: Arc<StartTripUseCase<R>>,
-    pub activate_trip_use_case: Arc<ActivateTripUseCase<R>>,
-    pub end_trip_use_case: Arc<EndTripUseCase<R>>,
-    pub cancel_trip_use_case: Arc<CancelTripUseCase<R>>,
+    pub start_trip_use_case: Arc<StartTripUseCase<R, P, C>>,
+    pub activate_trip_use_case: Arc<ActivateTripUseCase<R, P, C>>,
+    pub end_trip_use_case: Arc<EndTripUseCase<R, P, C, T>>,
+    pub cancel_trip_use_case: Arc<CancelTripUseCase<R, P, C, T>>,
     pub get_trip_use_case: Arc<GetTripUseCase<R>>,
     pub get_user_trips_use_case: Arc<GetUserTripsUseCase<R>>,
     pub get_all_trips_use_case: Arc<GetAllTripsUseCase<R>>,
+    pub get_trip_route_use_case: Arc<GetTripRouteUseCase<R, C, T>>,
 }
 
-impl<R> Clone for AppState<R>
+impl<R, P, C, T> Clone for AppState<R, P, C, T>
 where
     R: TripRepository + Send + Sync + 'static,
+    P: CarTransitionRepository + Send + Sync + 'static,
+    C: CarsClient + Send + Sync + 'static,
+    T: TelematicsClient + Send + Sync + 'static,
 {
     fn clone(&self) -> Self {
         Self {
@@ -32,6 +40,7 @@ where
             get_trip_use_case: Arc::clone(&self.get_trip_use_case),
             get_user_trips_use_case: Arc::clone(&self.get_user_trips_use_case),
             get_all_trips_use_case: Arc::clone(&self.get_all_trips_use_case),
+            get_trip_route_use_case: Arc::clone(&self.get_trip_route_use_case),
         }
     }
 }
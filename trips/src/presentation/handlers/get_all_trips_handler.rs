@@ -7,11 +7,14 @@ use tracing::{info, error};
 use crate::presentation::app_state::AppState;
 use crate::presentation::handlers::trip_handlers::TripResponse;
 
-pub async fn get_all_trips_handler<R>(
-    State(state): State<AppState<R>>,
+pub async fn get_all_trips_handler<R, P, C, T>(
+    State(state): State<AppState<R, P, C, T>>,
 ) -> Result<Json<Vec<TripResponse>>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::TripRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::CarTransitionRepository + Send + Sync + 'static,
+    C: crate::domain::interfaces::CarsClient + Send + Sync + 'static,
+    T: crate::domain::interfaces::TelematicsClient + Send + Sync + 'static,
 {
     info!("Getting all trips");
     match state.get_all_trips_use_case.execute().await {
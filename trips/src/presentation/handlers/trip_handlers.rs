@@ -30,6 +30,10 @@ pub struct TripResponse {
     pub ended_at: Option<chrono::DateTime<chrono::Utc>>,
     pub cancelled_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub distance_km: Option<f64>,
+    pub parking_minutes: Option<u32>,
+    pub route_pending: bool,
 }
 
 impl From<crate::domain::models::Trip> for TripResponse {
@@ -43,6 +47,10 @@ impl From<crate::domain::models::Trip> for TripResponse {
             ended_at: trip.ended_at,
             cancelled_at: trip.cancelled_at,
             created_at: trip.created_at,
+            expires_at: trip.expires_at,
+            route_pending: trip.route_pending(),
+            distance_km: trip.distance_km,
+            parking_minutes: trip.parking_minutes,
         }
     }
 }
@@ -52,12 +60,15 @@ pub struct MessageResponse {
     pub message: String,
 }
 
-pub async fn start_trip_handler<R>(
-    State(state): State<AppState<R>>,
+pub async fn start_trip_handler<R, P, C, T>(
+    State(state): State<AppState<R, P, C, T>>,
     Json(request): Json<StartTripRequest>,
 ) -> Result<Json<StartTripResponse>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::TripRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::CarTransitionRepository + Send + Sync + 'static,
+    C: crate::domain::interfaces::CarsClient + Send + Sync + 'static,
+    T: crate::domain::interfaces::TelematicsClient + Send + Sync + 'static,
 {
     info!("Starting trip for user {} with car {}", request.user_id, request.car_id);
     let start_request = crate::domain::models::StartTripRequest {
@@ -84,6 +95,20 @@ where
                 Json(serde_json::json!({"error": "Car is already in use"})),
             ))
         }
+        Err(TripError::CarNotFound) => {
+            warn!("Start trip failed: car {} not found", request.car_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Car not found"})),
+            ))
+        }
+        Err(TripError::CarsService(message)) => {
+            error!("Cars service error while starting trip: {}", message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": "Cars service unavailable"})),
+            ))
+        }
         Err(e) => {
             error!("Error starting trip: {:?}", e);
             Err((
@@ -94,12 +119,15 @@ where
     }
 }
 
-pub async fn activate_trip_handler<R>(
-    State(state): State<AppState<R>>,
+pub async fn activate_trip_handler<R, P, C, T>(
+    State(state): State<AppState<R, P, C, T>>,
     Path(trip_id): Path<Uuid>,
 ) -> Result<Json<MessageResponse>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::TripRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::CarTransitionRepository + Send + Sync + 'static,
+    C: crate::domain::interfaces::CarsClient + Send + Sync + 'static,
+    T: crate::domain::interfaces::TelematicsClient + Send + Sync + 'static,
 {
     info!("Activating trip: {}", trip_id);
     match state.activate_trip_use_case.execute(trip_id).await {
@@ -123,6 +151,27 @@ where
                 Json(serde_json::json!({"error": format!("Cannot activate trip: invalid status transition from {} to {}", from, to)})),
             ))
         }
+        Err(TripError::InvalidTripState) => {
+            warn!("Trip {} status changed concurrently", trip_id);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": "Trip status was changed by another request"})),
+            ))
+        }
+        Err(TripError::CarStateConflict { expected }) => {
+            warn!("Car for trip {} is not in state {}", trip_id, expected);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": format!("Car is not in the expected state: {}", expected)})),
+            ))
+        }
+        Err(TripError::CarsService(message)) => {
+            error!("Cars service error while activating trip {}: {}", trip_id, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": "Cars service unavailable"})),
+            ))
+        }
         Err(e) => {
             error!("Error activating trip {}: {:?}", trip_id, e);
             Err((
@@ -133,12 +182,15 @@ where
     }
 }
 
-pub async fn end_trip_handler<R>(
-    State(state): State<AppState<R>>,
+pub async fn end_trip_handler<R, P, C, T>(
+    State(state): State<AppState<R, P, C, T>>,
     Path(trip_id): Path<Uuid>,
 ) -> Result<Json<MessageResponse>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::TripRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::CarTransitionRepository + Send + Sync + 'static,
+    C: crate::domain::interfaces::CarsClient + Send + Sync + 'static,
+    T: crate::domain::interfaces::TelematicsClient + Send + Sync + 'static,
 {
     info!("Ending trip: {}", trip_id);
     match state.end_trip_use_case.execute(trip_id).await {
@@ -162,6 +214,20 @@ where
                 Json(serde_json::json!({"error": format!("Cannot end trip: invalid status transition from {} to {}", from, to)})),
             ))
         }
+        Err(TripError::InvalidTripState) => {
+            warn!("Trip {} status changed concurrently", trip_id);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": "Trip status was changed by another request"})),
+            ))
+        }
+        Err(TripError::CarsService(message)) => {
+            error!("Cars service error while ending trip {}: {}", trip_id, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": "Cars service unavailable"})),
+            ))
+        }
         Err(e) => {
             error!("Error ending trip {}: {:?}", trip_id, e);
             Err((
@@ -172,12 +238,15 @@ where
     }
 }
 
-pub async fn cancel_trip_handler<R>(
-    State(state): State<AppState<R>>,
+pub async fn cancel_trip_handler<R, P, C, T>(
+    State(state): State<AppState<R, P, C, T>>,
     Path(trip_id): Path<Uuid>,
 ) -> Result<Json<MessageResponse>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::TripRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::CarTransitionRepository + Send + Sync + 'static,
+    C: crate::domain::interfaces::CarsClient + Send + Sync + 'static,
+    T: crate::domain::interfaces::TelematicsClient + Send + Sync + 'static,
 {
     info!("Cancelling trip: {}", trip_id);
     match state.cancel_trip_use_case.execute(trip_id).await {
@@ -201,6 +270,20 @@ where
                 Json(serde_json::json!({"error": format!("Cannot cancel trip: invalid status transition from {} to {}", from, to)})),
             ))
         }
+        Err(TripError::InvalidTripState) => {
+            warn!("Trip {} status changed concurrently", trip_id);
+            Err((
+                StatusCode::CONFLICT,
+                Json(serde_json::json!({"error": "Trip status was changed by another request"})),
+            ))
+        }
+        Err(TripError::CarsService(message)) => {
+            error!("Cars service error while cancelling trip {}: {}", trip_id, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": "Cars service unavailable"})),
+            ))
+        }
         Err(e) => {
             error!("Error cancelling trip {}: {:?}", trip_id, e);
             Err((
@@ -211,12 +294,15 @@ where
     }
 }
 
-pub async fn get_trip_handler<R>(
-    State(state): State<AppState<R>>,
+pub async fn get_trip_handler<R, P, C, T>(
+    State(state): State<AppState<R, P, C, T>>,
     Path(trip_id): Path<Uuid>,
 ) -> Result<Json<TripResponse>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::TripRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::CarTransitionRepository + Send + Sync + 'static,
+    C: crate::domain::interfaces::CarsClient + Send + Sync + 'static,
+    T: crate::domain::interfaces::TelematicsClient + Send + Sync + 'static,
 {
     info!("Getting trip: {}", trip_id);
     match state.get_trip_use_case.execute(trip_id).await {
@@ -241,12 +327,15 @@ where
     }
 }
 
-pub async fn get_user_trips_handler<R>(
-    State(state): State<AppState<R>>,
+pub async fn get_user_trips_handler<R, P, C, T>(
+    State(state): State<AppState<R, P, C, T>>,
     Path(user_id): Path<Uuid>,
 ) -> Result<Json<Vec<TripResponse>>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::TripRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::CarTransitionRepository + Send + Sync + 'static,
+    C: crate::domain::interfaces::CarsClient + Send + Sync + 'static,
+    T: crate::domain::interfaces::TelematicsClient + Send + Sync + 'static,
 {
     info!("Getting trips for user: {}", user_id);
     match state.get_user_trips_use_case.execute(user_id).await {
@@ -264,3 +353,97 @@ where
     }
 }
 
+
+// Геометрия null, если точек меньше двух: LineString требует минимум две позиции
+#[derive(Serialize)]
+pub struct TripRouteResponse {
+    #[serde(rename = "type")]
+    pub feature_type: &'static str,
+    pub geometry: Option<LineStringGeometry>,
+    pub properties: TripRouteProperties,
+}
+
+#[derive(Serialize)]
+pub struct LineStringGeometry {
+    #[serde(rename = "type")]
+    pub geometry_type: &'static str,
+    // Порядок GeoJSON: [долгота, широта]
+    pub coordinates: Vec<[f64; 2]>,
+}
+
+#[derive(Serialize)]
+pub struct TripRouteProperties {
+    pub trip_id: Uuid,
+    pub distance_km: f64,
+    pub parking_minutes: u32,
+    pub point_count: usize,
+    // Время каждой позиции из coordinates
+    pub timestamps: Vec<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<crate::domain::models::TripRoute> for TripRouteResponse {
+    fn from(route: crate::domain::models::TripRoute) -> Self {
+        let geometry = (route.points.len() >= 2).then(|| LineStringGeometry {
+            geometry_type: "LineString",
+            coordinates: route.points.iter().map(|p| [p.longitude, p.latitude]).collect(),
+        });
+        Self {
+            feature_type: "Feature",
+            geometry,
+            properties: TripRouteProperties {
+                trip_id: route.trip_id,
+                distance_km: route.distance_km,
+                parking_minutes: route.parking_minutes,
+                point_count: route.points.len(),
+                timestamps: route.points.iter().map(|p| p.recorded_at).collect(),
+            },
+        }
+    }
+}
+
+pub async fn get_trip_route_handler<R, P, C, T>(
+    State(state): State<AppState<R, P, C, T>>,
+    Path(trip_id): Path<Uuid>,
+) -> Result<Json<TripRouteResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::TripRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::CarTransitionRepository + Send + Sync + 'static,
+    C: crate::domain::interfaces::CarsClient + Send + Sync + 'static,
+    T: crate::domain::interfaces::TelematicsClient + Send + Sync + 'static,
+{
+    info!("Getting route for trip: {}", trip_id);
+    match state.get_trip_route_use_case.execute(trip_id).await {
+        Ok(route) => {
+            info!("Route retrieved for trip {}: {} points, {:.3} km", trip_id, route.points.len(), route.distance_km);
+            Ok(Json(route.into()))
+        }
+        Err(TripError::TripNotFound) => {
+            warn!("Trip not found: {}", trip_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Trip not found"})),
+            ))
+        }
+        Err(TripError::CarsService(message)) => {
+            error!("Cars service error while building route for trip {}: {}", trip_id, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": "Cars service unavailable"})),
+            ))
+        }
+        Err(TripError::TelematicsService(message)) => {
+            error!("Telematics service error while building route for trip {}: {}", trip_id, message);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": "Telematics service unavailable"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error getting route for trip {}: {:?}", trip_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
@@ -6,9 +6,12 @@ use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing::info;
 use crate::presentation::{handlers::*, app_state::AppState};
 
-pub fn create_router<R>(app_state: AppState<R>) -> Router
+pub fn create_router<R, P, C, T>(app_state: AppState<R, P, C, T>) -> Router
 where
     R: crate::domain::interfaces::TripRepository + Send + Sync + 'static,
+    P: crate::domain::interfaces::CarTransitionRepository + Send + Sync + 'static,
+    C: crate::domain::interfaces::CarsClient + Send + Sync + 'static,
+    T: crate::domain::interfaces::TelematicsClient + Send + Sync + 'static,
 {
     info!("Setting up routes...");
     Router::new()
@@ -18,6 +21,7 @@ where
         .route("/trips/:id/activate", put(activate_trip_handler))
         .route("/trips/:id/end", put(end_trip_handler))
         .route("/trips/:id/cancel", put(cancel_trip_handler))
+        .route("/trips/:id/route", get(get_trip_route_handler))
         .route("/users/:user_id/trips", get(get_user_trips_handler))
         .with_state(app_state)
         .layer(CorsLayer::permissive())
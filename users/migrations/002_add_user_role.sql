@@ -0,0 +1,8 @@
+-- Migration: Add role to users
+-- Created: 2024-02-01
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS role VARCHAR(32) NOT NULL DEFAULT 'client';
+
+-- Допустимые роли: client, operator, admin
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_role_check;
+ALTER TABLE users ADD CONSTRAINT users_role_check CHECK (role IN ('client', 'operator', 'admin'));
@@ -1,46 +1,56 @@
+use uuid::Uuid;
 use crate::domain::{
     errors::UserError,
-    interfaces::{UserRepository, PasswordHasher, TokenGenerator},
-    models::AuthRequest,
+    interfaces::{UserRepository, PasswordHasher, TokenGenerator, RefreshTokenRepository},
+    models::{AuthRequest, AuthTokens},
 };
 use std::time::Duration;
+use super::issue_refresh_token;
 
-pub struct AuthenticateUserUseCase<R, H, T> 
+pub struct AuthenticateUserUseCase<R, H, T, RT> 
 where
     R: UserRepository,
     H: PasswordHasher,
     T: TokenGenerator,
+    RT: RefreshTokenRepository,
 {
     repository: R,
     password_hasher: H,
     token_generator: T,
+    refresh_token_repository: RT,
     jwt_secret: String,
-    token_ttl: Duration,
+    access_token_ttl: Duration,
+    refresh_token_ttl: Duration,
 }
 
-impl<R, H, T> AuthenticateUserUseCase<R, H, T>
+impl<R, H, T, RT> AuthenticateUserUseCase<R, H, T, RT>
 where
     R: UserRepository,
     H: PasswordHasher,
     T: TokenGenerator,
+    RT: RefreshTokenRepository,
 {
     pub fn new(
         repository: R,
         password_hasher: H,
         token_generator: T,
+        refresh_token_repository: RT,
         jwt_secret: String,
-        token_ttl: Duration,
+        access_token_ttl: Duration,
+        refresh_token_ttl: Duration,
     ) -> Self {
         Self {
             repository,
             password_hasher,
             token_generator,
+            refresh_token_repository,
             jwt_secret,
-            token_ttl,
+            access_token_ttl,
+            refresh_token_ttl,
         }
     }
 
-    pub async fn execute(&self, request: AuthRequest) -> Result<(String, uuid::Uuid), UserError> {
+    pub async fn execute(&self, request: AuthRequest) -> Result<AuthTokens, UserError> {
         // Находим пользователя по email
         let user = self.repository.find_by_email(&request.email).await?
             .ok_or(UserError::InvalidCredentials)?;
@@ -53,11 +63,24 @@ where
             return Err(UserError::InvalidCredentials);
         }
 
-        // Генерируем токен
-        let token = self.token_generator.generate_token(user.id, &self.jwt_secret, self.token_ttl)
+        // Генерируем короткоживущий access токен
+        let access_token = self.token_generator.generate_token(user.id, user.role, &self.jwt_secret, self.access_token_ttl)
             .map_err(|e| UserError::Internal(anyhow::anyhow!("Failed to generate token: {}", e)))?;
 
-        Ok((token, user.id))
+        // Каждый логин начинает новое семейство refresh токенов
+        let refresh_token = issue_refresh_token(
+            &self.refresh_token_repository,
+            &self.token_generator,
+            user.id,
+            Uuid::new_v4(),
+            self.refresh_token_ttl,
+        ).await?;
+
+        Ok(AuthTokens {
+            access_token,
+            refresh_token,
+            user_id: user.id,
+        })
     }
 }
 
@@ -65,8 +88,7 @@ where
 mod tests {
     use super::*;
     use async_trait::async_trait;
-    use uuid::Uuid;
-    use crate::domain::models::User;
+    use crate::domain::models::{User, UserRole, RefreshToken};
     use std::collections::HashMap;
     use std::sync::Arc;
     use tokio::sync::Mutex;
@@ -103,6 +125,11 @@ mod tests {
             Ok(users.get(email).cloned())
         }
 
+        async fn find_all(&self) -> Result<Vec<User>, UserError> {
+            let users = self.users.lock().await;
+            Ok(users.values().cloned().collect())
+        }
+
         async fn update(&self, _id: Uuid, _user: &User) -> Result<(), UserError> {
             Ok(())
         }
@@ -112,6 +139,27 @@ mod tests {
         }
     }
 
+    struct MockRefreshTokenRepository;
+
+    #[async_trait]
+    impl RefreshTokenRepository for MockRefreshTokenRepository {
+        async fn create(&self, _token: &RefreshToken) -> Result<(), UserError> {
+            Ok(())
+        }
+
+        async fn find_by_hash(&self, _token_hash: &str) -> Result<Option<RefreshToken>, UserError> {
+            Ok(None)
+        }
+
+        async fn revoke(&self, _id: Uuid) -> Result<bool, UserError> {
+            Ok(true)
+        }
+
+        async fn revoke_family(&self, _family_id: Uuid) -> Result<(), UserError> {
+            Ok(())
+        }
+    }
+
     struct MockPasswordHasher;
 
     impl PasswordHasher for MockPasswordHasher {
@@ -127,12 +175,20 @@ mod tests {
     struct MockTokenGenerator;
 
     impl TokenGenerator for MockTokenGenerator {
-        fn generate_token(&self, user_id: Uuid, _secret: &str, _ttl: Duration) -> Result<String, anyhow::Error> {
+        fn generate_token(&self, user_id: Uuid, _role: UserRole, _secret: &str, _ttl: Duration) -> Result<String, anyhow::Error> {
             Ok(format!("token_{}", user_id))
         }
 
-        fn validate_token(&self, _token: &str, _secret: &str) -> Result<Uuid, anyhow::Error> {
-            Ok(Uuid::new_v4())
+        fn validate_token(&self, _token: &str, _secret: &str) -> Result<(Uuid, UserRole), anyhow::Error> {
+            Ok((Uuid::new_v4(), UserRole::Client))
+        }
+
+        fn generate_refresh_token(&self) -> Result<String, anyhow::Error> {
+            Ok("refresh_token".to_string())
+        }
+
+        fn hash_refresh_token(&self, token: &str) -> String {
+            format!("hashed_{}", token)
         }
     }
 
@@ -150,6 +206,7 @@ mod tests {
             rating: 4.5,
             email: "test@example.com".to_string(),
             password_hash: "hashed_password123".to_string(),
+            role: UserRole::Client,
         };
         repository.add_user(user).await;
 
@@ -157,7 +214,9 @@ mod tests {
             repository,
             password_hasher,
             token_generator,
+            MockRefreshTokenRepository,
             "secret".to_string(),
+            Duration::from_secs(900),
             Duration::from_secs(3600),
         );
 
@@ -168,9 +227,10 @@ mod tests {
 
         let result = use_case.execute(request).await;
         assert!(result.is_ok());
-        let (token, returned_user_id) = result.unwrap();
-        assert_eq!(token, format!("token_{}", user_id));
-        assert_eq!(returned_user_id, user_id);
+        let tokens = result.unwrap();
+        assert_eq!(tokens.access_token, format!("token_{}", user_id));
+        assert_eq!(tokens.refresh_token, "refresh_token");
+        assert_eq!(tokens.user_id, user_id);
     }
 
     #[tokio::test]
@@ -183,7 +243,9 @@ mod tests {
             repository,
             password_hasher,
             token_generator,
+            MockRefreshTokenRepository,
             "secret".to_string(),
+            Duration::from_secs(900),
             Duration::from_secs(3600),
         );
 
@@ -213,6 +275,7 @@ mod tests {
             rating: 4.5,
             email: "test@example.com".to_string(),
             password_hash: "hashed_correct_password".to_string(),
+            role: UserRole::Client,
         };
         repository.add_user(user).await;
 
@@ -220,7 +283,9 @@ mod tests {
             repository,
             password_hasher,
             token_generator,
+            MockRefreshTokenRepository,
             "secret".to_string(),
+            Duration::from_secs(900),
             Duration::from_secs(3600),
         );
 
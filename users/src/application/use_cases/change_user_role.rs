@@ -0,0 +1,191 @@
+use uuid::Uuid;
+use tracing::{info, warn};
+use crate::domain::{
+    errors::UserError,
+    interfaces::{TokenGenerator, UserRepository},
+    models::UserRole,
+};
+
+pub struct ChangeUserRoleUseCase<R, T>
+where
+    R: UserRepository,
+    T: TokenGenerator,
+{
+    repository: R,
+    token_generator: T,
+    jwt_secret: String,
+}
+
+impl<R, T> ChangeUserRoleUseCase<R, T>
+where
+    R: UserRepository,
+    T: TokenGenerator,
+{
+    pub fn new(repository: R, token_generator: T, jwt_secret: String) -> Self {
+        Self {
+            repository,
+            token_generator,
+            jwt_secret,
+        }
+    }
+
+    pub async fn execute(&self, access_token: &str, user_id: Uuid, role: UserRole) -> Result<(), UserError> {
+        let (caller_id, _) = self.token_generator.validate_token(access_token, &self.jwt_secret)
+            .map_err(|e| UserError::InvalidToken { reason: e.to_string() })?;
+
+        // Роль берем из базы, а не из токена: снятого админа не пропустит его еще живой токен
+        let caller = self.repository.find_by_id(caller_id).await?
+            .ok_or_else(|| UserError::InvalidToken { reason: "unknown user".to_string() })?;
+        if caller.role != UserRole::Admin {
+            warn!("User {} ({}) tried to change role of user {}", caller_id, caller.role.as_str(), user_id);
+            return Err(UserError::Forbidden);
+        }
+
+        let mut user = self.repository.find_by_id(user_id).await?
+            .ok_or(UserError::NotFound)?;
+        let previous_role = user.role;
+        user.role = role;
+        self.repository.update(user_id, &user).await?;
+
+        info!("Admin {} changed role of user {}: {} -> {}", caller_id, user_id, previous_role.as_str(), role.as_str());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::domain::models::User;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Mutex;
+
+    struct MockUserRepository {
+        users: Arc<Mutex<HashMap<Uuid, User>>>,
+    }
+
+    impl MockUserRepository {
+        fn new() -> Self {
+            Self {
+                users: Arc::new(Mutex::new(HashMap::new())),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl UserRepository for MockUserRepository {
+        async fn create(&self, user: &User) -> Result<(), UserError> {
+            self.users.lock().await.insert(user.id, user.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, UserError> {
+            Ok(self.users.lock().await.get(&id).cloned())
+        }
+
+        async fn find_by_email(&self, email: &str) -> Result<Option<User>, UserError> {
+            Ok(self.users.lock().await.values().find(|u| u.email == email).cloned())
+        }
+
+        async fn find_all(&self) -> Result<Vec<User>, UserError> {
+            Ok(self.users.lock().await.values().cloned().collect())
+        }
+
+        async fn update(&self, id: Uuid, user: &User) -> Result<(), UserError> {
+            self.users.lock().await.insert(id, user.clone());
+            Ok(())
+        }
+
+        async fn delete(&self, _id: Uuid) -> Result<(), UserError> {
+            Ok(())
+        }
+    }
+
+    // Токен - это просто id пользователя
+    struct MockTokenGenerator;
+
+    impl TokenGenerator for MockTokenGenerator {
+        fn generate_token(&self, user_id: Uuid, _role: UserRole, _secret: &str, _ttl: Duration) -> Result<String, anyhow::Error> {
+            Ok(user_id.to_string())
+        }
+
+        fn validate_token(&self, token: &str, _secret: &str) -> Result<(Uuid, UserRole), anyhow::Error> {
+            Ok((Uuid::parse_str(token)?, UserRole::Admin))
+        }
+
+        fn generate_refresh_token(&self) -> Result<String, anyhow::Error> {
+            Ok("refresh_token".to_string())
+        }
+
+        fn hash_refresh_token(&self, token: &str) -> String {
+            token.to_string()
+        }
+    }
+
+    fn user(role: UserRole) -> User {
+        let id = Uuid::new_v4();
+        User {
+            id,
+            license_id: "DL123456".to_string(),
+            driving_experience: 5,
+            rating: 4.5,
+            email: format!("{}@example.com", id),
+            password_hash: "hash".to_string(),
+            role,
+        }
+    }
+
+    async fn setup(caller_role: UserRole) -> (ChangeUserRoleUseCase<MockUserRepository, MockTokenGenerator>, Arc<Mutex<HashMap<Uuid, User>>>, User, User) {
+        let repository = MockUserRepository::new();
+        let users = repository.users.clone();
+        let caller = user(caller_role);
+        let target = user(UserRole::Client);
+        repository.create(&caller).await.unwrap();
+        repository.create(&target).await.unwrap();
+        let use_case = ChangeUserRoleUseCase::new(repository, MockTokenGenerator, "secret".to_string());
+        (use_case, users, caller, target)
+    }
+
+    #[tokio::test]
+    async fn test_admin_changes_role() {
+        let (use_case, users, admin, target) = setup(UserRole::Admin).await;
+
+        use_case.execute(&admin.id.to_string(), target.id, UserRole::Operator).await.unwrap();
+
+        let updated = users.lock().await.get(&target.id).cloned().unwrap();
+        assert_eq!(updated.role, UserRole::Operator);
+    }
+
+    #[tokio::test]
+    async fn test_non_admin_cannot_change_role() {
+        for caller_role in [UserRole::Client, UserRole::Operator] {
+            let (use_case, users, caller, target) = setup(caller_role).await;
+
+            // Токен заявляет admin, но в базе у вызывающего другая роль
+            let result = use_case.execute(&caller.id.to_string(), target.id, UserRole::Admin).await;
+            assert!(matches!(result, Err(UserError::Forbidden)));
+
+            let self_promotion = use_case.execute(&caller.id.to_string(), caller.id, UserRole::Admin).await;
+            assert!(matches!(self_promotion, Err(UserError::Forbidden)));
+            assert_eq!(users.lock().await.get(&caller.id).unwrap().role, caller_role);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_token_rejected() {
+        let (use_case, _, _, target) = setup(UserRole::Admin).await;
+
+        let result = use_case.execute("not-a-token", target.id, UserRole::Admin).await;
+        assert!(matches!(result, Err(UserError::InvalidToken { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_change_role_user_not_found() {
+        let (use_case, _, admin, _) = setup(UserRole::Admin).await;
+
+        let result = use_case.execute(&admin.id.to_string(), Uuid::new_v4(), UserRole::Operator).await;
+        assert!(matches!(result, Err(UserError::NotFound)));
+    }
+}
@@ -30,7 +30,7 @@ where
 mod tests {
     use super::*;
     use async_trait::async_trait;
-    use crate::domain::models::User;
+    use crate::domain::models::{User, UserRole};
     use std::collections::HashMap;
     use std::sync::Arc;
     use tokio::sync::Mutex;
@@ -64,6 +64,11 @@ mod tests {
             Ok(None)
         }
 
+        async fn find_all(&self) -> Result<Vec<User>, UserError> {
+            let users = self.users.lock().await;
+            Ok(users.values().cloned().collect())
+        }
+
         async fn update(&self, _id: Uuid, _user: &User) -> Result<(), UserError> {
             Ok(())
         }
@@ -83,6 +88,7 @@ mod tests {
             rating: 4.5,
             email: "test@example.com".to_string(),
             password_hash: "hash".to_string(),
+            role: UserRole::Client,
         };
         repository.create(&user).await.unwrap();
 
@@ -0,0 +1,38 @@
+use tracing::info;
+use crate::domain::{
+    errors::UserError,
+    interfaces::{TokenGenerator, RefreshTokenRepository},
+};
+
+pub struct LogoutUseCase<T, RT>
+where
+    T: TokenGenerator,
+    RT: RefreshTokenRepository,
+{
+    token_generator: T,
+    refresh_token_repository: RT,
+}
+
+impl<T, RT> LogoutUseCase<T, RT>
+where
+    T: TokenGenerator,
+    RT: RefreshTokenRepository,
+{
+    pub fn new(token_generator: T, refresh_token_repository: RT) -> Self {
+        Self {
+            token_generator,
+            refresh_token_repository,
+        }
+    }
+
+    // Отзывает все семейство refresh токена (сессию, начатую одним логином)
+    pub async fn execute(&self, refresh_token: &str) -> Result<(), UserError> {
+        let token_hash = self.token_generator.hash_refresh_token(refresh_token);
+        let stored = self.refresh_token_repository.find_by_hash(&token_hash).await?
+            .ok_or_else(|| UserError::InvalidToken { reason: "unknown refresh token".to_string() })?;
+
+        self.refresh_token_repository.revoke_family(stored.family_id).await?;
+        info!("Revoked refresh token family {} for user {}", stored.family_id, stored.user_id);
+        Ok(())
+    }
+}
@@ -1,12 +1,19 @@
 mod register_user;
 mod authenticate_user;
+mod refresh_token;
+mod logout;
 mod update_user;
+mod change_user_role;
 mod get_user;
 mod get_all_users;
+mod relay_outbox_events;
 
 pub use register_user::*;
 pub use authenticate_user::*;
+pub use refresh_token::*;
+pub use logout::*;
 pub use update_user::*;
+pub use change_user_role::*;
 pub use get_user::*;
 pub use get_all_users::*;
-
+pub use relay_outbox_events::*;
@@ -0,0 +1,324 @@
+use uuid::Uuid;
+use chrono::Utc;
+use std::time::Duration;
+use tracing::warn;
+use crate::domain::{
+    errors::UserError,
+    interfaces::{UserRepository, TokenGenerator, RefreshTokenRepository},
+    models::{RefreshToken, AuthTokens},
+};
+
+// Выпускает новый refresh токен в заданном семействе и сохраняет его хеш
+pub(crate) async fn issue_refresh_token<RT, T>(
+    refresh_token_repository: &RT,
+    token_generator: &T,
+    user_id: Uuid,
+    family_id: Uuid,
+    ttl: Duration,
+) -> Result<String, UserError>
+where
+    RT: RefreshTokenRepository,
+    T: TokenGenerator,
+{
+    let token = token_generator.generate_refresh_token()
+        .map_err(|e| UserError::Internal(anyhow::anyhow!("Failed to generate refresh token: {}", e)))?;
+    let ttl = chrono::Duration::from_std(ttl)
+        .map_err(|e| UserError::Internal(anyhow::anyhow!("Invalid refresh token ttl: {}", e)))?;
+
+    let now = Utc::now();
+    let refresh_token = RefreshToken {
+        id: Uuid::new_v4(),
+        user_id,
+        family_id,
+        token_hash: token_generator.hash_refresh_token(&token),
+        expires_at: now + ttl,
+        revoked_at: None,
+        created_at: now,
+    };
+    refresh_token_repository.create(&refresh_token).await?;
+
+    Ok(token)
+}
+
+pub struct RefreshTokenUseCase<R, T, RT>
+where
+    R: UserRepository,
+    T: TokenGenerator,
+    RT: RefreshTokenRepository,
+{
+    repository: R,
+    token_generator: T,
+    refresh_token_repository: RT,
+    jwt_secret: String,
+    access_token_ttl: Duration,
+    refresh_token_ttl: Duration,
+}
+
+impl<R, T, RT> RefreshTokenUseCase<R, T, RT>
+where
+    R: UserRepository,
+    T: TokenGenerator,
+    RT: RefreshTokenRepository,
+{
+    pub fn new(
+        repository: R,
+        token_generator: T,
+        refresh_token_repository: RT,
+        jwt_secret: String,
+        access_token_ttl: Duration,
+        refresh_token_ttl: Duration,
+    ) -> Self {
+        Self {
+            repository,
+            token_generator,
+            refresh_token_repository,
+            jwt_secret,
+            access_token_ttl,
+            refresh_token_ttl,
+        }
+    }
+
+    pub async fn execute(&self, refresh_token: &str) -> Result<AuthTokens, UserError> {
+        let token_hash = self.token_generator.hash_refresh_token(refresh_token);
+        let stored = self.refresh_token_repository.find_by_hash(&token_hash).await?
+            .ok_or_else(|| UserError::InvalidToken { reason: "unknown refresh token".to_string() })?;
+
+        // Повторное использование уже ротированного токена - признак кражи,
+        // отзываем все семейство, чтобы выбить и злоумышленника, и владельца
+        if stored.revoked_at.is_some() {
+            warn!("Refresh token reuse detected for user {}, revoking family {}", stored.user_id, stored.family_id);
+            self.refresh_token_repository.revoke_family(stored.family_id).await?;
+            return Err(UserError::RefreshTokenReused);
+        }
+
+        if stored.expires_at <= Utc::now() {
+            return Err(UserError::ExpiredToken);
+        }
+
+        // Помечаем токен использованным; если его успел использовать параллельный запрос - это тоже reuse
+        if !self.refresh_token_repository.revoke(stored.id).await? {
+            warn!("Concurrent refresh token reuse for user {}, revoking family {}", stored.user_id, stored.family_id);
+            self.refresh_token_repository.revoke_family(stored.family_id).await?;
+            return Err(UserError::RefreshTokenReused);
+        }
+
+        // Роль берем актуальную, а не из старого токена
+        let user = match self.repository.find_by_id(stored.user_id).await? {
+            Some(user) => user,
+            None => {
+                self.refresh_token_repository.revoke_family(stored.family_id).await?;
+                return Err(UserError::InvalidToken { reason: "user no longer exists".to_string() });
+            }
+        };
+
+        let access_token = self.token_generator.generate_token(user.id, user.role, &self.jwt_secret, self.access_token_ttl)
+            .map_err(|e| UserError::Internal(anyhow::anyhow!("Failed to generate token: {}", e)))?;
+        let refresh_token = issue_refresh_token(
+            &self.refresh_token_repository,
+            &self.token_generator,
+            user.id,
+            stored.family_id,
+            self.refresh_token_ttl,
+        ).await?;
+
+        Ok(AuthTokens {
+            access_token,
+            refresh_token,
+            user_id: user.id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::domain::models::{User, UserRole};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    struct MockUserRepository {
+        users: Arc<Mutex<HashMap<Uuid, User>>>,
+    }
+
+    impl MockUserRepository {
+        fn new() -> Self {
+            Self {
+                users: Arc::new(Mutex::new(HashMap::new())),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl UserRepository for MockUserRepository {
+        async fn create(&self, user: &User) -> Result<(), UserError> {
+            let mut users = self.users.lock().await;
+            users.insert(user.id, user.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, UserError> {
+            let users = self.users.lock().await;
+            Ok(users.get(&id).cloned())
+        }
+
+        async fn find_by_email(&self, _email: &str) -> Result<Option<User>, UserError> {
+            Ok(None)
+        }
+
+        async fn find_all(&self) -> Result<Vec<User>, UserError> {
+            let users = self.users.lock().await;
+            Ok(users.values().cloned().collect())
+        }
+
+        async fn update(&self, _id: Uuid, _user: &User) -> Result<(), UserError> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: Uuid) -> Result<(), UserError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockRefreshTokenRepository {
+        tokens: Arc<Mutex<HashMap<String, RefreshToken>>>,
+    }
+
+    impl MockRefreshTokenRepository {
+        fn new() -> Self {
+            Self {
+                tokens: Arc::new(Mutex::new(HashMap::new())),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RefreshTokenRepository for MockRefreshTokenRepository {
+        async fn create(&self, token: &RefreshToken) -> Result<(), UserError> {
+            let mut tokens = self.tokens.lock().await;
+            tokens.insert(token.token_hash.clone(), token.clone());
+            Ok(())
+        }
+
+        async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, UserError> {
+            let tokens = self.tokens.lock().await;
+            Ok(tokens.get(token_hash).cloned())
+        }
+
+        async fn revoke(&self, id: Uuid) -> Result<bool, UserError> {
+            let mut tokens = self.tokens.lock().await;
+            match tokens.values_mut().find(|t| t.id == id && t.revoked_at.is_none()) {
+                Some(token) => {
+                    token.revoked_at = Some(Utc::now());
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+
+        async fn revoke_family(&self, family_id: Uuid) -> Result<(), UserError> {
+            let mut tokens = self.tokens.lock().await;
+            for token in tokens.values_mut().filter(|t| t.family_id == family_id && t.revoked_at.is_none()) {
+                token.revoked_at = Some(Utc::now());
+            }
+            Ok(())
+        }
+    }
+
+    struct MockTokenGenerator;
+
+    impl TokenGenerator for MockTokenGenerator {
+        fn generate_token(&self, user_id: Uuid, _role: UserRole, _secret: &str, _ttl: Duration) -> Result<String, anyhow::Error> {
+            Ok(format!("token_{}", user_id))
+        }
+
+        fn validate_token(&self, _token: &str, _secret: &str) -> Result<(Uuid, UserRole), anyhow::Error> {
+            Ok((Uuid::new_v4(), UserRole::Client))
+        }
+
+        fn generate_refresh_token(&self) -> Result<String, anyhow::Error> {
+            Ok(format!("refresh_{}", Uuid::new_v4()))
+        }
+
+        fn hash_refresh_token(&self, token: &str) -> String {
+            format!("hashed_{}", token)
+        }
+    }
+
+    async fn setup() -> (RefreshTokenUseCase<MockUserRepository, MockTokenGenerator, MockRefreshTokenRepository>, MockRefreshTokenRepository, String) {
+        let repository = MockUserRepository::new();
+        let refresh_token_repository = MockRefreshTokenRepository::new();
+
+        let user = User {
+            id: Uuid::new_v4(),
+            license_id: "DL123456".to_string(),
+            driving_experience: 5,
+            rating: 4.5,
+            email: "test@example.com".to_string(),
+            password_hash: "hash".to_string(),
+            role: UserRole::Client,
+        };
+        repository.create(&user).await.unwrap();
+
+        let refresh_token = issue_refresh_token(
+            &refresh_token_repository,
+            &MockTokenGenerator,
+            user.id,
+            Uuid::new_v4(),
+            Duration::from_secs(3600),
+        ).await.unwrap();
+
+        let use_case = RefreshTokenUseCase::new(
+            repository,
+            MockTokenGenerator,
+            refresh_token_repository.clone(),
+            "secret".to_string(),
+            Duration::from_secs(900),
+            Duration::from_secs(3600),
+        );
+
+        (use_case, refresh_token_repository, refresh_token)
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_rotates() {
+        let (use_case, _, refresh_token) = setup().await;
+
+        let tokens = use_case.execute(&refresh_token).await.unwrap();
+        assert_eq!(tokens.access_token, format!("token_{}", tokens.user_id));
+        assert_ne!(tokens.refresh_token, refresh_token);
+
+        // Новый токен из того же семейства тоже работает
+        assert!(use_case.execute(&tokens.refresh_token).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_reuse_revokes_family() {
+        let (use_case, refresh_token_repository, refresh_token) = setup().await;
+
+        let tokens = use_case.execute(&refresh_token).await.unwrap();
+
+        // Повторное использование старого токена
+        match use_case.execute(&refresh_token).await {
+            Err(UserError::RefreshTokenReused) => {}
+            _ => panic!("Expected RefreshTokenReused error"),
+        }
+
+        // Текущий токен семейства тоже отозван
+        assert!(use_case.execute(&tokens.refresh_token).await.is_err());
+        let tokens = refresh_token_repository.tokens.lock().await;
+        assert!(tokens.values().all(|t| t.revoked_at.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_unknown() {
+        let (use_case, _, _) = setup().await;
+
+        match use_case.execute("unknown").await {
+            Err(UserError::InvalidToken { .. }) => {}
+            _ => panic!("Expected InvalidToken error"),
+        }
+    }
+}
@@ -2,7 +2,7 @@ use uuid::Uuid;
 use crate::domain::{
     errors::UserError,
     interfaces::{UserRepository, PasswordHasher},
-    models::{User, CreateUserRequest},
+    models::{User, UserRole, CreateUserRequest},
 };
 
 pub struct RegisterUserUseCase<R, H> 
@@ -28,7 +28,7 @@ where
 
     pub async fn execute(&self, request: CreateUserRequest) -> Result<Uuid, UserError> {
         // Проверяем, существует ли пользователь с таким email
-        if let Some(_) = self.repository.find_by_email(&request.email).await? {
+        if self.repository.find_by_email(&request.email).await?.is_some() {
             return Err(UserError::AlreadyExists {
                 email: request.email,
             });
@@ -46,6 +46,8 @@ where
             rating: request.rating,
             email: request.email,
             password_hash,
+            // Самостоятельная регистрация всегда создает клиента
+            role: UserRole::Client,
         };
 
         self.repository.create(&user).await?;
@@ -96,6 +98,11 @@ mod tests {
             Ok(emails.get(email).cloned())
         }
 
+        async fn find_all(&self) -> Result<Vec<User>, UserError> {
+            let users = self.users.lock().await;
+            Ok(users.values().cloned().collect())
+        }
+
         async fn update(&self, _id: Uuid, _user: &User) -> Result<(), UserError> {
             Ok(())
         }
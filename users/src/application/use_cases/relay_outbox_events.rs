@@ -0,0 +1,209 @@
+use std::time::Duration;
+use chrono::Utc;
+use tracing::{warn, error};
+use crate::domain::{
+    errors::UserError,
+    interfaces::{OutboxRepository, EventPublisher},
+};
+
+const RELAY_BATCH_SIZE: i64 = 100;
+// После падения процесса события подхватит другой экземпляр, когда аренда истечет
+const RELAY_LEASE: Duration = Duration::from_secs(30);
+// Удваивается с каждой неудачей до RELAY_MAX_RETRY_DELAY
+const RELAY_RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+const RELAY_MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+pub struct RelayOutboxEventsUseCase<R, P>
+where
+    R: OutboxRepository,
+    P: EventPublisher,
+{
+    repository: R,
+    publisher: P,
+    retention: Duration,
+}
+
+impl<R, P> RelayOutboxEventsUseCase<R, P>
+where
+    R: OutboxRepository,
+    P: EventPublisher,
+{
+    pub fn new(repository: R, publisher: P, retention: Duration) -> Self {
+        Self { repository, publisher, retention }
+    }
+
+    pub async fn execute(&self) -> Result<usize, UserError> {
+        let now = Utc::now();
+        let events = self.repository
+            .claim_pending(now, now + to_chrono(RELAY_LEASE), RELAY_BATCH_SIZE)
+            .await?;
+
+        let mut published = 0;
+        for event in events {
+            match self.publisher.publish(&event).await {
+                Ok(()) => {
+                    self.repository.mark_published(event.id, Utc::now()).await?;
+                    published += 1;
+                }
+                Err(e) => {
+                    let delay = RELAY_RETRY_BASE_DELAY
+                        .saturating_mul(1 << event.attempts.min(16))
+                        .min(RELAY_MAX_RETRY_DELAY);
+                    warn!(
+                        "Event {} ({}) publish attempt {} failed, retry in {:?}: {}",
+                        event.id, event.event_type, event.attempts + 1, delay, e
+                    );
+                    if let Err(mark_error) = self.repository
+                        .mark_failed(event.id, &e.to_string(), Utc::now() + to_chrono(delay))
+                        .await
+                    {
+                        error!("Failed to record publish failure of event {}: {:?}", event.id, mark_error);
+                    }
+                    // Брокер, скорее всего, недоступен: остальные события пакета вернутся
+                    // в работу, когда истечет аренда
+                    break;
+                }
+            }
+        }
+
+        Ok(published)
+    }
+
+    pub async fn purge_published(&self) -> Result<u64, UserError> {
+        self.repository
+            .delete_published_before(Utc::now() - to_chrono(self.retention))
+            .await
+    }
+}
+
+fn to_chrono(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::days(365))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::DateTime;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+    use crate::domain::models::OutboxEvent;
+
+    #[derive(Default)]
+    struct MockOutboxRepository {
+        pending: Mutex<Vec<OutboxEvent>>,
+        published: Mutex<Vec<Uuid>>,
+        failed: Mutex<Vec<(Uuid, DateTime<Utc>)>>,
+        purge_cutoff: Mutex<Option<DateTime<Utc>>>,
+    }
+
+    #[async_trait]
+    impl OutboxRepository for MockOutboxRepository {
+        async fn claim_pending(&self, _now: DateTime<Utc>, _lease_until: DateTime<Utc>, limit: i64) -> Result<Vec<OutboxEvent>, UserError> {
+            Ok(self.pending.lock().unwrap().iter().take(limit as usize).cloned().collect())
+        }
+
+        async fn mark_published(&self, id: Uuid, _published_at: DateTime<Utc>) -> Result<(), UserError> {
+            self.published.lock().unwrap().push(id);
+            Ok(())
+        }
+
+        async fn mark_failed(&self, id: Uuid, _error: &str, next_attempt_at: DateTime<Utc>) -> Result<(), UserError> {
+            self.failed.lock().unwrap().push((id, next_attempt_at));
+            Ok(())
+        }
+
+        async fn delete_published_before(&self, cutoff: DateTime<Utc>) -> Result<u64, UserError> {
+            *self.purge_cutoff.lock().unwrap() = Some(cutoff);
+            Ok(3)
+        }
+    }
+
+    // Брокер отклоняет события из списка
+    #[derive(Default)]
+    struct MockEventPublisher {
+        rejected: Vec<Uuid>,
+        published: Mutex<Vec<Uuid>>,
+    }
+
+    #[async_trait]
+    impl EventPublisher for MockEventPublisher {
+        async fn publish(&self, event: &OutboxEvent) -> Result<(), UserError> {
+            if self.rejected.contains(&event.id) {
+                return Err(UserError::EventPublish("broker unavailable".to_string()));
+            }
+            self.published.lock().unwrap().push(event.id);
+            Ok(())
+        }
+    }
+
+    fn event(attempts: u32) -> OutboxEvent {
+        let mut event = OutboxEvent::new("user", Uuid::new_v4(), "UserRegistered", &serde_json::json!({})).unwrap();
+        event.attempts = attempts;
+        event
+    }
+
+    fn relay(events: Vec<OutboxEvent>, rejected: Vec<Uuid>) -> RelayOutboxEventsUseCase<MockOutboxRepository, MockEventPublisher> {
+        let repository = MockOutboxRepository { pending: Mutex::new(events), ..Default::default() };
+        let publisher = MockEventPublisher { rejected, ..Default::default() };
+        RelayOutboxEventsUseCase::new(repository, publisher, Duration::from_secs(3600))
+    }
+
+    #[tokio::test]
+    async fn test_publishes_claimed_events_in_order() {
+        let events = vec![event(0), event(0), event(0)];
+        let ids: Vec<Uuid> = events.iter().map(|e| e.id).collect();
+        let relay = relay(events, vec![]);
+
+        assert_eq!(relay.execute().await.unwrap(), 3);
+        assert_eq!(*relay.publisher.published.lock().unwrap(), ids);
+        assert_eq!(*relay.repository.published.lock().unwrap(), ids);
+        assert!(relay.repository.failed.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_publish_is_rescheduled_and_stops_batch() {
+        let events = vec![event(0), event(2), event(0)];
+        let ids: Vec<Uuid> = events.iter().map(|e| e.id).collect();
+        let relay = relay(events, vec![ids[1]]);
+
+        let before = Utc::now();
+        assert_eq!(relay.execute().await.unwrap(), 1);
+
+        assert_eq!(*relay.repository.published.lock().unwrap(), vec![ids[0]]);
+        let failed = relay.repository.failed.lock().unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].0, ids[1]);
+        // Третья попытка: 1s * 2^2
+        let delay = failed[0].1 - before;
+        assert!(delay >= chrono::Duration::seconds(4) && delay < chrono::Duration::seconds(5));
+        // Последнее событие пакета не публикуется и не помечается
+        assert!(!relay.publisher.published.lock().unwrap().contains(&ids[2]));
+    }
+
+    #[tokio::test]
+    async fn test_retry_delay_is_capped() {
+        let event = event(30);
+        let id = event.id;
+        let relay = relay(vec![event], vec![id]);
+
+        let before = Utc::now();
+        relay.execute().await.unwrap();
+
+        let next_attempt_at = relay.repository.failed.lock().unwrap()[0].1;
+        assert!(next_attempt_at - before <= chrono::Duration::seconds(301));
+        assert!(next_attempt_at - before >= chrono::Duration::seconds(300));
+    }
+
+    #[tokio::test]
+    async fn test_purge_removes_events_older_than_retention() {
+        let relay = relay(vec![], vec![]);
+
+        let before = Utc::now();
+        assert_eq!(relay.purge_published().await.unwrap(), 3);
+
+        let cutoff = relay.repository.purge_cutoff.lock().unwrap().unwrap();
+        let age = before - cutoff;
+        assert!(age >= chrono::Duration::seconds(3599) && age <= chrono::Duration::seconds(3600));
+    }
+}
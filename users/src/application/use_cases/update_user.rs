@@ -54,7 +54,7 @@ where
 mod tests {
     use super::*;
     use async_trait::async_trait;
-    use crate::domain::models::User;
+    use crate::domain::models::{User, UserRole};
     use std::collections::HashMap;
     use std::sync::Arc;
     use tokio::sync::Mutex;
@@ -98,6 +98,11 @@ mod tests {
             }
         }
 
+        async fn find_all(&self) -> Result<Vec<User>, UserError> {
+            let users = self.users.lock().await;
+            Ok(users.values().cloned().collect())
+        }
+
         async fn update(&self, id: Uuid, user: &User) -> Result<(), UserError> {
             let mut users = self.users.lock().await;
             let mut emails = self.emails.lock().await;
@@ -124,6 +129,7 @@ mod tests {
             rating: 4.5,
             email: "test@example.com".to_string(),
             password_hash: "hash".to_string(),
+            role: UserRole::Client,
         };
         repository.create(&user).await.unwrap();
 
@@ -168,6 +174,7 @@ mod tests {
             rating: 4.5,
             email: "user1@example.com".to_string(),
             password_hash: "hash".to_string(),
+            role: UserRole::Client,
         };
         let user2 = User {
             id: Uuid::new_v4(),
@@ -176,6 +183,7 @@ mod tests {
             rating: 4.0,
             email: "user2@example.com".to_string(),
             password_hash: "hash".to_string(),
+            role: UserRole::Client,
         };
         repository.create(&user1).await.unwrap();
         repository.create(&user2).await.unwrap();
@@ -17,6 +17,15 @@ pub enum UserError {
     #[error("user's token has expired")]
     ExpiredToken,
     
+    #[error("operation requires admin role")]
+    Forbidden,
+    
+    #[error("refresh token reuse detected, token family revoked")]
+    RefreshTokenReused,
+    
+    #[error("event publish error: {0}")]
+    EventPublish(String),
+    
     #[error("database error: {0}")]
     Database(#[from] sqlx::Error),
     
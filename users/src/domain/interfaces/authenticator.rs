@@ -1,9 +1,13 @@
 use uuid::Uuid;
 use std::time::Duration;
+use crate::domain::models::UserRole;
 
 pub trait TokenGenerator {
-    fn generate_token(&self, user_id: Uuid, secret: &str, ttl: Duration) -> Result<String, anyhow::Error>;
-    fn validate_token(&self, token: &str, secret: &str) -> Result<Uuid, anyhow::Error>;
+    fn generate_token(&self, user_id: Uuid, role: UserRole, secret: &str, ttl: Duration) -> Result<String, anyhow::Error>;
+    fn validate_token(&self, token: &str, secret: &str) -> Result<(Uuid, UserRole), anyhow::Error>;
+    // Непрозрачный случайный refresh токен и его хеш для хранения в БД
+    fn generate_refresh_token(&self) -> Result<String, anyhow::Error>;
+    fn hash_refresh_token(&self, token: &str) -> String;
 }
 
 pub trait PasswordHasher {
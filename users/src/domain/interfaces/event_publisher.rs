@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use crate::domain::{
+    errors::UserError,
+    models::OutboxEvent,
+};
+
+#[async_trait]
+pub trait EventPublisher {
+    // Возвращается после подтверждения брокером
+    async fn publish(&self, event: &OutboxEvent) -> Result<(), UserError>;
+}
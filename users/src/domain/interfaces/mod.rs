@@ -1,5 +1,11 @@
 mod authenticator;
 mod user_repository;
+mod refresh_token_repository;
+mod outbox_repository;
+mod event_publisher;
 
 pub use authenticator::*;
-pub use user_repository::*;
\ No newline at end of file
+pub use user_repository::*;
+pub use refresh_token_repository::*;
+pub use outbox_repository::*;
+pub use event_publisher::*;
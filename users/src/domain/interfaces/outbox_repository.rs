@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::domain::{
+    errors::UserError,
+    models::OutboxEvent,
+};
+
+#[async_trait]
+pub trait OutboxRepository {
+    // В порядке возникновения; следующее событие агрегата - только после публикации предыдущего
+    async fn claim_pending(&self, now: DateTime<Utc>, lease_until: DateTime<Utc>, limit: i64) -> Result<Vec<OutboxEvent>, UserError>;
+    async fn mark_published(&self, id: Uuid, published_at: DateTime<Utc>) -> Result<(), UserError>;
+    async fn mark_failed(&self, id: Uuid, error: &str, next_attempt_at: DateTime<Utc>) -> Result<(), UserError>;
+    async fn delete_published_before(&self, cutoff: DateTime<Utc>) -> Result<u64, UserError>;
+}
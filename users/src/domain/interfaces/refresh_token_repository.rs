@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::domain::{
+    errors::UserError,
+    models::RefreshToken,
+};
+
+#[async_trait]
+pub trait RefreshTokenRepository {
+    async fn create(&self, token: &RefreshToken) -> Result<(), UserError>;
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, UserError>;
+    // Возвращает false, если токен уже был отозван (например, параллельной ротацией)
+    async fn revoke(&self, id: Uuid) -> Result<bool, UserError>;
+    async fn revoke_family(&self, family_id: Uuid) -> Result<(), UserError>;
+}
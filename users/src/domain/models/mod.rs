@@ -1,3 +1,9 @@
 mod users;
+mod refresh_tokens;
+mod outbox;
+mod user_events;
 
-pub use users::*;
\ No newline at end of file
+pub use users::*;
+pub use refresh_tokens::*;
+pub use outbox::*;
+pub use user_events::*;
@@ -0,0 +1,22 @@
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+// Refresh токен хранится только в виде хеша. Все токены, полученные
+// ротацией из одного логина, принадлежат одному семейству (family_id)
+#[derive(Debug, Clone)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct AuthTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub user_id: Uuid,
+}
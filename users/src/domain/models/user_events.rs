@@ -0,0 +1,37 @@
+use serde::Serialize;
+use uuid::Uuid;
+use crate::domain::models::{OutboxEvent, User};
+
+pub const USER_AGGREGATE: &str = "user";
+
+// Хеш пароля за пределы сервиса не уходит
+#[derive(Debug, Serialize)]
+pub struct UserSnapshot<'a> {
+    pub id: Uuid,
+    pub license_id: &'a str,
+    pub driving_experience: u32,
+    pub rating: f64,
+    pub email: &'a str,
+    pub role: &'static str,
+}
+
+impl User {
+    fn snapshot(&self) -> UserSnapshot<'_> {
+        UserSnapshot {
+            id: self.id,
+            license_id: &self.license_id,
+            driving_experience: self.driving_experience,
+            rating: self.rating,
+            email: &self.email,
+            role: self.role.as_str(),
+        }
+    }
+
+    pub fn registered_event(&self) -> Result<OutboxEvent, serde_json::Error> {
+        OutboxEvent::new(USER_AGGREGATE, self.id, "UserRegistered", &self.snapshot())
+    }
+
+    pub fn updated_event(&self) -> Result<OutboxEvent, serde_json::Error> {
+        OutboxEvent::new(USER_AGGREGATE, self.id, "UserUpdated", &self.snapshot())
+    }
+}
@@ -10,6 +10,38 @@ pub struct User {
     pub rating: f64,
     pub email: String,
     pub password_hash: String,
+    pub role: UserRole,
+}
+
+// Роль пользователя: клиенты пользуются каршерингом, операторы (поддержка) и админы управляют им
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserRole {
+    Client,
+    Operator,
+    Admin,
+}
+
+impl UserRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::Client => "client",
+            UserRole::Operator => "operator",
+            UserRole::Admin => "admin",
+        }
+    }
+}
+
+impl std::str::FromStr for UserRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "client" => Ok(UserRole::Client),
+            "operator" => Ok(UserRole::Operator),
+            "admin" => Ok(UserRole::Admin),
+            _ => Err(format!("Invalid user role: {}", s)),
+        }
+    }
 }
 
 pub struct CreateUserRequest {
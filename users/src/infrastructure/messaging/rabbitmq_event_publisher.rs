@@ -0,0 +1,135 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use lapin::{
+    options::{BasicPublishOptions, ConfirmSelectOptions, ExchangeDeclareOptions},
+    types::FieldTable,
+    BasicProperties, Channel, Connection, ConnectionProperties, ExchangeKind,
+};
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+use uuid::Uuid;
+use crate::domain::{
+    errors::UserError,
+    interfaces::EventPublisher,
+    models::OutboxEvent,
+};
+
+pub const EVENTS_EXCHANGE: &str = "zdrive_events";
+// Сервис-источник: первая часть ключа маршрутизации `users.<EventType>`
+const EVENT_SOURCE: &str = "users";
+// Сообщение переживает перезапуск брокера
+const PERSISTENT_DELIVERY_MODE: u8 = 2;
+
+#[derive(Serialize)]
+struct EventEnvelope<'a> {
+    event_id: Uuid,
+    event_type: &'a str,
+    source: &'a str,
+    aggregate_type: &'a str,
+    aggregate_id: Uuid,
+    occurred_at: DateTime<Utc>,
+    payload: &'a serde_json::Value,
+}
+
+// Соединение устанавливается при первой публикации и пересоздается после ошибки
+pub struct RabbitMQEventPublisher {
+    amqp_url: String,
+    channel: Arc<Mutex<Option<(Connection, Channel)>>>,
+}
+
+impl RabbitMQEventPublisher {
+    pub fn new(amqp_url: &str) -> Self {
+        Self {
+            amqp_url: amqp_url.to_string(),
+            channel: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    async fn connect(&self) -> Result<(Connection, Channel), lapin::Error> {
+        let connection = Connection::connect(&self.amqp_url, ConnectionProperties::default()).await?;
+        let channel = connection.create_channel().await?;
+        channel.confirm_select(ConfirmSelectOptions::default()).await?;
+        channel
+            .exchange_declare(
+                EVENTS_EXCHANGE,
+                ExchangeKind::Topic,
+                ExchangeDeclareOptions {
+                    durable: true,
+                    ..Default::default()
+                },
+                FieldTable::default(),
+            )
+            .await?;
+
+        info!("Connected to RabbitMQ, publishing events to exchange {}", EVENTS_EXCHANGE);
+        Ok((connection, channel))
+    }
+
+    async fn publish_on(&self, channel: &Channel, event: &OutboxEvent) -> Result<(), UserError> {
+        let envelope = EventEnvelope {
+            event_id: event.id,
+            event_type: &event.event_type,
+            source: EVENT_SOURCE,
+            aggregate_type: &event.aggregate_type,
+            aggregate_id: event.aggregate_id,
+            occurred_at: event.occurred_at,
+            payload: &event.payload,
+        };
+        let body = serde_json::to_vec(&envelope).map_err(anyhow::Error::from)?;
+        let routing_key = format!("{}.{}", EVENT_SOURCE, event.event_type);
+        // message_id - ключ дедупликации у получателей
+        let properties = BasicProperties::default()
+            .with_message_id(event.id.to_string().into())
+            .with_type(event.event_type.clone().into())
+            .with_app_id(EVENT_SOURCE.into())
+            .with_content_type("application/json".into())
+            .with_delivery_mode(PERSISTENT_DELIVERY_MODE)
+            .with_timestamp(event.occurred_at.timestamp().max(0) as u64);
+
+        let confirmation = channel
+            .basic_publish(EVENTS_EXCHANGE, &routing_key, BasicPublishOptions::default(), &body, properties)
+            .await
+            .map_err(|e| UserError::EventPublish(e.to_string()))?
+            .await
+            .map_err(|e| UserError::EventPublish(e.to_string()))?;
+
+        if !confirmation.is_ack() {
+            return Err(UserError::EventPublish(format!("broker rejected event {}", event.id)));
+        }
+        Ok(())
+    }
+}
+
+impl Clone for RabbitMQEventPublisher {
+    fn clone(&self) -> Self {
+        Self {
+            amqp_url: self.amqp_url.clone(),
+            channel: Arc::clone(&self.channel),
+        }
+    }
+}
+
+#[async_trait]
+impl EventPublisher for RabbitMQEventPublisher {
+    async fn publish(&self, event: &OutboxEvent) -> Result<(), UserError> {
+        let mut guard = self.channel.lock().await;
+        if !guard.as_ref().is_some_and(|(_, channel)| channel.status().connected()) {
+            let connected = self.connect().await
+                .map_err(|e| UserError::EventPublish(format!("failed to connect to RabbitMQ: {}", e)))?;
+            *guard = Some(connected);
+        }
+        let Some((_, channel)) = guard.as_ref() else {
+            return Err(UserError::EventPublish("RabbitMQ channel is not available".to_string()));
+        };
+
+        let result = self.publish_on(channel, event).await;
+        if let Err(e) = &result {
+            // Канал после ошибки может быть закрыт брокером - переподключимся при следующей публикации
+            warn!("Failed to publish event {} ({}): {}", event.id, event.event_type, e);
+            *guard = None;
+        }
+        result
+    }
+}
@@ -1,4 +1,7 @@
 mod postgres_user_repository;
+mod postgres_refresh_token_repository;
+mod postgres_outbox_repository;
 
 pub use postgres_user_repository::*;
-
+pub use postgres_refresh_token_repository::*;
+pub use postgres_outbox_repository::*;
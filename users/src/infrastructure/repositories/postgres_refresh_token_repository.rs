@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+use crate::domain::{
+    errors::UserError,
+    interfaces::RefreshTokenRepository,
+    models::RefreshToken,
+};
+
+pub struct PostgresRefreshTokenRepository {
+    pool: PgPool,
+}
+
+impl PostgresRefreshTokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl Clone for PostgresRefreshTokenRepository {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for PostgresRefreshTokenRepository {
+    async fn create(&self, token: &RefreshToken) -> Result<(), UserError> {
+        sqlx::query(
+            r#"
+            INSERT INTO refresh_tokens (id, user_id, family_id, token_hash, expires_at, revoked_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(token.id)
+        .bind(token.user_id)
+        .bind(token.family_id)
+        .bind(&token.token_hash)
+        .bind(token.expires_at)
+        .bind(token.revoked_at)
+        .bind(token.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, UserError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, user_id, family_id, token_hash, expires_at, revoked_at, created_at
+            FROM refresh_tokens
+            WHERE token_hash = $1
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| RefreshToken {
+            id: r.get("id"),
+            user_id: r.get("user_id"),
+            family_id: r.get("family_id"),
+            token_hash: r.get("token_hash"),
+            expires_at: r.get("expires_at"),
+            revoked_at: r.get("revoked_at"),
+            created_at: r.get("created_at"),
+        }))
+    }
+
+    async fn revoke(&self, id: Uuid) -> Result<bool, UserError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE id = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn revoke_family(&self, family_id: Uuid) -> Result<(), UserError> {
+        sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE family_id = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(family_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -1,10 +1,11 @@
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{postgres::PgRow, PgPool, Row};
+use super::enqueue_event;
 use uuid::Uuid;
 use crate::domain::{
     errors::UserError,
     interfaces::UserRepository,
-    models::User,
+    models::{OutboxEvent, User, UserRole, USER_AGGREGATE},
 };
 
 pub struct PostgresUserRepository {
@@ -17,6 +18,20 @@ impl PostgresUserRepository {
     }
 }
 
+fn user_from_row(r: &PgRow) -> Result<User, UserError> {
+    Ok(User {
+        id: r.get("id"),
+        license_id: r.get("license_id"),
+        driving_experience: r.get::<i32, _>("driving_experience") as u32,
+        rating: r.get("rating"),
+        email: r.get("email"),
+        password_hash: r.get("password_hash"),
+        role: r.get::<String, _>("role")
+            .parse::<UserRole>()
+            .map_err(|e| UserError::Internal(anyhow::anyhow!(e)))?,
+    })
+}
+
 impl Clone for PostgresUserRepository {
     fn clone(&self) -> Self {
         Self {
@@ -28,10 +43,13 @@ impl Clone for PostgresUserRepository {
 #[async_trait]
 impl UserRepository for PostgresUserRepository {
     async fn create(&self, user: &User) -> Result<(), UserError> {
+        let event = user.registered_event().map_err(anyhow::Error::from)?;
+        let mut tx = self.pool.begin().await?;
+
         sqlx::query(
             r#"
-            INSERT INTO users (id, license_id, driving_experience, rating, email, password_hash)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO users (id, license_id, driving_experience, rating, email, password_hash, role)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             "#,
         )
         .bind(user.id)
@@ -40,16 +58,19 @@ impl UserRepository for PostgresUserRepository {
         .bind(user.rating)
         .bind(&user.email)
         .bind(&user.password_hash)
-        .execute(&self.pool)
+        .bind(user.role.as_str())
+        .execute(&mut *tx)
         .await?;
-        
+
+        enqueue_event(&mut tx, &event).await?;
+        tx.commit().await?;
         Ok(())
     }
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, UserError> {
         let row = sqlx::query(
             r#"
-            SELECT id, license_id, driving_experience, rating, email, password_hash
+            SELECT id, license_id, driving_experience, rating, email, password_hash, role
             FROM users
             WHERE id = $1
             "#,
@@ -58,20 +79,13 @@ impl UserRepository for PostgresUserRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.map(|r| User {
-            id: r.get("id"),
-            license_id: r.get("license_id"),
-            driving_experience: r.get::<i32, _>("driving_experience") as u32,
-            rating: r.get("rating"),
-            email: r.get("email"),
-            password_hash: r.get("password_hash"),
-        }))
+        row.as_ref().map(user_from_row).transpose()
     }
 
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, UserError> {
         let row = sqlx::query(
             r#"
-            SELECT id, license_id, driving_experience, rating, email, password_hash
+            SELECT id, license_id, driving_experience, rating, email, password_hash, role
             FROM users
             WHERE email = $1
             "#,
@@ -80,20 +94,13 @@ impl UserRepository for PostgresUserRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.map(|r| User {
-            id: r.get("id"),
-            license_id: r.get("license_id"),
-            driving_experience: r.get::<i32, _>("driving_experience") as u32,
-            rating: r.get("rating"),
-            email: r.get("email"),
-            password_hash: r.get("password_hash"),
-        }))
+        row.as_ref().map(user_from_row).transpose()
     }
 
     async fn find_all(&self) -> Result<Vec<User>, UserError> {
         let rows = sqlx::query(
             r#"
-            SELECT id, license_id, driving_experience, rating, email, password_hash
+            SELECT id, license_id, driving_experience, rating, email, password_hash, role
             FROM users
             ORDER BY created_at DESC
             "#,
@@ -101,21 +108,16 @@ impl UserRepository for PostgresUserRepository {
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().map(|r| User {
-            id: r.get("id"),
-            license_id: r.get("license_id"),
-            driving_experience: r.get::<i32, _>("driving_experience") as u32,
-            rating: r.get("rating"),
-            email: r.get("email"),
-            password_hash: r.get("password_hash"),
-        }).collect())
+        rows.iter().map(user_from_row).collect()
     }
 
     async fn update(&self, id: Uuid, user: &User) -> Result<(), UserError> {
-        sqlx::query(
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
             r#"
             UPDATE users
-            SET license_id = $2, driving_experience = $3, rating = $4, email = $5, password_hash = $6
+            SET license_id = $2, driving_experience = $3, rating = $4, email = $5, password_hash = $6, role = $7
             WHERE id = $1
             "#,
         )
@@ -125,23 +127,37 @@ impl UserRepository for PostgresUserRepository {
         .bind(user.rating)
         .bind(&user.email)
         .bind(&user.password_hash)
-        .execute(&self.pool)
+        .bind(user.role.as_str())
+        .execute(&mut *tx)
         .await?;
-        
+
+        if result.rows_affected() > 0 {
+            let event = User { id, ..user.clone() }.updated_event().map_err(anyhow::Error::from)?;
+            enqueue_event(&mut tx, &event).await?;
+        }
+        tx.commit().await?;
         Ok(())
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), UserError> {
-        sqlx::query(
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
             r#"
             DELETE FROM users
             WHERE id = $1
             "#,
         )
         .bind(id)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
-        
+
+        if result.rows_affected() > 0 {
+            let event = OutboxEvent::new(USER_AGGREGATE, id, "UserDeleted", &serde_json::json!({ "id": id }))
+                .map_err(anyhow::Error::from)?;
+            enqueue_event(&mut tx, &event).await?;
+        }
+        tx.commit().await?;
         Ok(())
     }
 }
@@ -10,6 +10,12 @@ impl BcryptPasswordHasher {
     }
 }
 
+impl Default for BcryptPasswordHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PasswordHasher for BcryptPasswordHasher {
     fn hash(&self, password: &str) -> Result<String, anyhow::Error> {
         let hashed = hash(password, DEFAULT_COST)?;
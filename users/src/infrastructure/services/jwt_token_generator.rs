@@ -2,11 +2,14 @@ use uuid::Uuid;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use jsonwebtoken::{encode, decode, Header, Algorithm, Validation, EncodingKey, DecodingKey};
 use serde::{Deserialize, Serialize};
-use crate::domain::interfaces::TokenGenerator;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use crate::domain::{interfaces::TokenGenerator, models::UserRole};
 
 #[derive(Debug, Serialize, Deserialize)]
 struct Claims {
     sub: String, // user_id
+    role: String,
     exp: usize,
     iat: usize,
 }
@@ -20,8 +23,14 @@ impl JwtTokenGenerator {
     }
 }
 
+impl Default for JwtTokenGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TokenGenerator for JwtTokenGenerator {
-    fn generate_token(&self, user_id: Uuid, secret: &str, ttl: Duration) -> Result<String, anyhow::Error> {
+    fn generate_token(&self, user_id: Uuid, role: UserRole, secret: &str, ttl: Duration) -> Result<String, anyhow::Error> {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)?
             .as_secs() as usize;
@@ -30,6 +39,7 @@ impl TokenGenerator for JwtTokenGenerator {
         
         let claims = Claims {
             sub: user_id.to_string(),
+            role: role.as_str().to_string(),
             exp,
             iat: now,
         };
@@ -43,7 +53,7 @@ impl TokenGenerator for JwtTokenGenerator {
         Ok(token)
     }
 
-    fn validate_token(&self, token: &str, secret: &str) -> Result<Uuid, anyhow::Error> {
+    fn validate_token(&self, token: &str, secret: &str) -> Result<(Uuid, UserRole), anyhow::Error> {
         let mut validation = Validation::new(Algorithm::HS256);
         validation.validate_exp = true;
 
@@ -54,7 +64,19 @@ impl TokenGenerator for JwtTokenGenerator {
         )?;
 
         let user_id = Uuid::parse_str(&token_data.claims.sub)?;
-        Ok(user_id)
+        let role = token_data.claims.role.parse::<UserRole>()
+            .map_err(|e| anyhow::anyhow!(e))?;
+        Ok((user_id, role))
+    }
+
+    fn generate_refresh_token(&self) -> Result<String, anyhow::Error> {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().try_fill_bytes(&mut bytes)?;
+        Ok(hex::encode(bytes))
+    }
+
+    fn hash_refresh_token(&self, token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
     }
 }
 
@@ -1,36 +1,46 @@
 use std::sync::Arc;
 use crate::{
-    application::use_cases::{RegisterUserUseCase, AuthenticateUserUseCase, UpdateUserUseCase, GetUserUseCase, GetAllUsersUseCase},
-    domain::interfaces::{UserRepository, PasswordHasher, TokenGenerator},
+    application::use_cases::{
+        RegisterUserUseCase, AuthenticateUserUseCase, RefreshTokenUseCase, LogoutUseCase,
+        UpdateUserUseCase, ChangeUserRoleUseCase, GetUserUseCase, GetAllUsersUseCase,
+    },
+    domain::interfaces::{UserRepository, PasswordHasher, TokenGenerator, RefreshTokenRepository},
 };
 
-pub struct AppState<R, H, T>
+pub struct AppState<R, H, T, RT>
 where
     R: UserRepository + Send + Sync + 'static,
     H: PasswordHasher + Send + Sync + 'static,
     T: TokenGenerator + Send + Sync + 'static,
+    RT: RefreshTokenRepository + Send + Sync + 'static,
 {
     pub register_use_case: Arc<RegisterUserUseCase<R, H>>,
-    pub auth_use_case: Arc<AuthenticateUserUseCase<R, H, T>>,
+    pub auth_use_case: Arc<AuthenticateUserUseCase<R, H, T, RT>>,
+    pub refresh_token_use_case: Arc<RefreshTokenUseCase<R, T, RT>>,
+    pub logout_use_case: Arc<LogoutUseCase<T, RT>>,
     pub update_use_case: Arc<UpdateUserUseCase<R>>,
+    pub change_role_use_case: Arc<ChangeUserRoleUseCase<R, T>>,
     pub get_use_case: Arc<GetUserUseCase<R>>,
     pub get_all_users_use_case: Arc<GetAllUsersUseCase<R>>,
 }
 
-impl<R, H, T> Clone for AppState<R, H, T>
+impl<R, H, T, RT> Clone for AppState<R, H, T, RT>
 where
     R: UserRepository + Send + Sync + 'static,
     H: PasswordHasher + Send + Sync + 'static,
     T: TokenGenerator + Send + Sync + 'static,
+    RT: RefreshTokenRepository + Send + Sync + 'static,
 {
     fn clone(&self) -> Self {
         Self {
             register_use_case: Arc::clone(&self.register_use_case),
             auth_use_case: Arc::clone(&self.auth_use_case),
+            refresh_token_use_case: Arc::clone(&self.refresh_token_use_case),
+            logout_use_case: Arc::clone(&self.logout_use_case),
             update_use_case: Arc::clone(&self.update_use_case),
+            change_role_use_case: Arc::clone(&self.change_role_use_case),
             get_use_case: Arc::clone(&self.get_use_case),
             get_all_users_use_case: Arc::clone(&self.get_all_users_use_case),
         }
     }
 }
-
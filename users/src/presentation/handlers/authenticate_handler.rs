@@ -19,17 +19,19 @@ pub struct AuthRequest {
 #[derive(Serialize)]
 pub struct AuthResponse {
     pub token: String,
+    pub refresh_token: String,
     pub user_id: uuid::Uuid,
 }
 
-pub async fn authenticate_handler<R, H, T>(
-    State(state): State<AppState<R, H, T>>,
+pub async fn authenticate_handler<R, H, T, RT>(
+    State(state): State<AppState<R, H, T, RT>>,
     Json(request): Json<AuthRequest>,
 ) -> Result<Json<AuthResponse>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::UserRepository + Send + Sync + 'static,
     H: crate::domain::interfaces::PasswordHasher + Send + Sync + 'static,
     T: crate::domain::interfaces::TokenGenerator + Send + Sync + 'static,
+    RT: crate::domain::interfaces::RefreshTokenRepository + Send + Sync + 'static,
 {
     let email = request.email.clone();
     info!("Authenticating user with email: {}", email);
@@ -39,9 +41,13 @@ where
         password: request.password,
     };
     match state.auth_use_case.execute(auth_request).await {
-        Ok((token, user_id)) => {
+        Ok(tokens) => {
             info!("User authenticated successfully: {}", email);
-            Ok(Json(AuthResponse { token, user_id }))
+            Ok(Json(AuthResponse {
+                token: tokens.access_token,
+                refresh_token: tokens.refresh_token,
+                user_id: tokens.user_id,
+            }))
         }
         Err(UserError::InvalidCredentials) => {
             warn!("Authentication failed: invalid credentials for email {}", email);
@@ -0,0 +1,91 @@
+use axum::{
+    extract::{State, Path},
+    http::{header, HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use tracing::{info, warn, error};
+use crate::{
+    presentation::app_state::AppState,
+    domain::{errors::UserError, models::UserRole},
+};
+
+#[derive(Deserialize)]
+pub struct ChangeUserRoleRequest {
+    pub role: String,
+}
+
+#[derive(Serialize)]
+pub struct ChangeUserRoleResponse {
+    pub message: String,
+}
+
+// Смена роли доступна только администратору: нужен его access токен в Authorization
+pub async fn change_user_role_handler<R, H, T, RT>(
+    State(state): State<AppState<R, H, T, RT>>,
+    Path(user_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(request): Json<ChangeUserRoleRequest>,
+) -> Result<Json<ChangeUserRoleResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::UserRepository + Send + Sync + 'static,
+    H: crate::domain::interfaces::PasswordHasher + Send + Sync + 'static,
+    T: crate::domain::interfaces::TokenGenerator + Send + Sync + 'static,
+    RT: crate::domain::interfaces::RefreshTokenRepository + Send + Sync + 'static,
+{
+    let Some(access_token) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        warn!("Role change for user {} without access token", user_id);
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Missing access token"})),
+        ));
+    };
+
+    let role = match request.role.parse::<UserRole>() {
+        Ok(role) => role,
+        Err(e) => {
+            warn!("Role change failed for user {}: {}", user_id, e);
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": e})),
+            ));
+        }
+    };
+
+    info!("Changing role of user {} to {}", user_id, role.as_str());
+    match state.change_role_use_case.execute(access_token, user_id, role).await {
+        Ok(_) => Ok(Json(ChangeUserRoleResponse {
+            message: "User role updated successfully".to_string(),
+        })),
+        Err(UserError::InvalidToken { reason }) => {
+            warn!("Role change rejected: {}", reason);
+            Err((
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Invalid access token"})),
+            ))
+        }
+        Err(UserError::Forbidden) => Err((
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({"error": "Only admins can change user roles"})),
+        )),
+        Err(UserError::NotFound) => {
+            warn!("User not found for role change: {}", user_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "User not found"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error changing role of user {}: {:?}", user_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
@@ -7,13 +7,14 @@ use tracing::{info, error};
 use crate::presentation::app_state::AppState;
 use crate::presentation::handlers::types::UserResponse;
 
-pub async fn get_all_users_handler<R, H, T>(
-    State(state): State<AppState<R, H, T>>,
+pub async fn get_all_users_handler<R, H, T, RT>(
+    State(state): State<AppState<R, H, T, RT>>,
 ) -> Result<Json<Vec<UserResponse>>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::UserRepository + Send + Sync + 'static,
     H: crate::domain::interfaces::PasswordHasher + Send + Sync + 'static,
     T: crate::domain::interfaces::TokenGenerator + Send + Sync + 'static,
+    RT: crate::domain::interfaces::RefreshTokenRepository + Send + Sync + 'static,
 {
     info!("Getting all users");
     match state.get_all_users_use_case.execute().await {
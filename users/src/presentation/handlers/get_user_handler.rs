@@ -11,14 +11,15 @@ use crate::{
     presentation::handlers::types::UserResponse,
 };
 
-pub async fn get_user_handler<R, H, T>(
-    State(state): State<AppState<R, H, T>>,
+pub async fn get_user_handler<R, H, T, RT>(
+    State(state): State<AppState<R, H, T, RT>>,
     Path(user_id): Path<Uuid>,
 ) -> Result<Json<UserResponse>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::UserRepository + Send + Sync + 'static,
     H: crate::domain::interfaces::PasswordHasher + Send + Sync + 'static,
     T: crate::domain::interfaces::TokenGenerator + Send + Sync + 'static,
+    RT: crate::domain::interfaces::RefreshTokenRepository + Send + Sync + 'static,
 {
     info!("Getting user: {}", user_id);
     match state.get_use_case.execute(user_id).await {
@@ -0,0 +1,53 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn, error};
+use crate::{
+    presentation::app_state::AppState,
+    domain::errors::UserError,
+};
+
+#[derive(Deserialize)]
+pub struct LogoutRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Serialize)]
+pub struct LogoutResponse {
+    pub message: String,
+}
+
+pub async fn logout_handler<R, H, T, RT>(
+    State(state): State<AppState<R, H, T, RT>>,
+    Json(request): Json<LogoutRequest>,
+) -> Result<Json<LogoutResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::UserRepository + Send + Sync + 'static,
+    H: crate::domain::interfaces::PasswordHasher + Send + Sync + 'static,
+    T: crate::domain::interfaces::TokenGenerator + Send + Sync + 'static,
+    RT: crate::domain::interfaces::RefreshTokenRepository + Send + Sync + 'static,
+{
+    info!("Logging out");
+    match state.logout_use_case.execute(&request.refresh_token).await {
+        Ok(_) => Ok(Json(LogoutResponse {
+            message: "Logged out successfully".to_string(),
+        })),
+        Err(UserError::InvalidToken { reason }) => {
+            warn!("Logout failed: {}", reason);
+            Err((
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Invalid refresh token"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error logging out: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
@@ -1,14 +1,19 @@
 mod types;
 mod register_handler;
 mod authenticate_handler;
+mod refresh_token_handler;
+mod logout_handler;
 mod update_user_handler;
+mod change_user_role_handler;
 mod get_user_handler;
 mod get_all_users_handler;
 
 pub use types::*;
 pub use register_handler::*;
 pub use authenticate_handler::*;
+pub use refresh_token_handler::*;
+pub use logout_handler::*;
 pub use update_user_handler::*;
+pub use change_user_role_handler::*;
 pub use get_user_handler::*;
 pub use get_all_users_handler::*;
-
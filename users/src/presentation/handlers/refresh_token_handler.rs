@@ -0,0 +1,67 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+};
+use serde::Deserialize;
+use tracing::{info, warn, error};
+use crate::{
+    presentation::{app_state::AppState, handlers::AuthResponse},
+    domain::errors::UserError,
+};
+
+#[derive(Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+pub async fn refresh_token_handler<R, H, T, RT>(
+    State(state): State<AppState<R, H, T, RT>>,
+    Json(request): Json<RefreshTokenRequest>,
+) -> Result<Json<AuthResponse>, (StatusCode, Json<serde_json::Value>)>
+where
+    R: crate::domain::interfaces::UserRepository + Send + Sync + 'static,
+    H: crate::domain::interfaces::PasswordHasher + Send + Sync + 'static,
+    T: crate::domain::interfaces::TokenGenerator + Send + Sync + 'static,
+    RT: crate::domain::interfaces::RefreshTokenRepository + Send + Sync + 'static,
+{
+    info!("Refreshing access token");
+    match state.refresh_token_use_case.execute(&request.refresh_token).await {
+        Ok(tokens) => {
+            info!("Access token refreshed for user: {}", tokens.user_id);
+            Ok(Json(AuthResponse {
+                token: tokens.access_token,
+                refresh_token: tokens.refresh_token,
+                user_id: tokens.user_id,
+            }))
+        }
+        Err(UserError::RefreshTokenReused) => {
+            warn!("Refresh failed: token reuse detected");
+            Err((
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Refresh token reuse detected, please sign in again"})),
+            ))
+        }
+        Err(UserError::ExpiredToken) => {
+            warn!("Refresh failed: token expired");
+            Err((
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Refresh token expired"})),
+            ))
+        }
+        Err(UserError::InvalidToken { reason }) => {
+            warn!("Refresh failed: {}", reason);
+            Err((
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Invalid refresh token"})),
+            ))
+        }
+        Err(e) => {
+            error!("Error refreshing token: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Internal server error"})),
+            ))
+        }
+    }
+}
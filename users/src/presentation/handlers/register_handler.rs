@@ -25,14 +25,15 @@ pub struct RegisterResponse {
     pub user_id: Uuid,
 }
 
-pub async fn register_handler<R, H, T>(
-    State(state): State<AppState<R, H, T>>,
+pub async fn register_handler<R, H, T, RT>(
+    State(state): State<AppState<R, H, T, RT>>,
     Json(request): Json<RegisterRequest>,
 ) -> Result<Json<RegisterResponse>, (StatusCode, Json<serde_json::Value>)>
 where
     R: crate::domain::interfaces::UserRepository + Send + Sync + 'static,
     H: crate::domain::interfaces::PasswordHasher + Send + Sync + 'static,
     T: crate::domain::interfaces::TokenGenerator + Send + Sync + 'static,
+    RT: crate::domain::interfaces::RefreshTokenRepository + Send + Sync + 'static,
 {
     let email = request.email.clone();
     info!("Registering new user with email: {}", email);
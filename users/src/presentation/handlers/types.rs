@@ -8,6 +8,7 @@ pub struct UserResponse {
     pub driving_experience: u32,
     pub rating: f64,
     pub email: String,
+    pub role: String,
 }
 
 impl From<crate::domain::models::User> for UserResponse {
@@ -18,6 +19,7 @@ impl From<crate::domain::models::User> for UserResponse {
             driving_experience: user.driving_experience,
             rating: user.rating,
             email: user.email,
+            role: user.role.as_str().to_string(),
         }
     }
 }
@@ -24,8 +24,8 @@ pub struct UpdateUserResponse {
     pub message: String,
 }
 
-pub async fn update_user_handler<R, H, T>(
-    State(state): State<AppState<R, H, T>>,
+pub async fn update_user_handler<R, H, T, RT>(
+    State(state): State<AppState<R, H, T, RT>>,
     Path(user_id): Path<Uuid>,
     Json(request): Json<UpdateUserRequest>,
 ) -> Result<Json<UpdateUserResponse>, (StatusCode, Json<serde_json::Value>)>
@@ -33,6 +33,7 @@ where
     R: crate::domain::interfaces::UserRepository + Send + Sync + 'static,
     H: crate::domain::interfaces::PasswordHasher + Send + Sync + 'static,
     T: crate::domain::interfaces::TokenGenerator + Send + Sync + 'static,
+    RT: crate::domain::interfaces::RefreshTokenRepository + Send + Sync + 'static,
 {
     let update_request = crate::domain::models::UpdateUserRequest {
         license_id: request.license_id,